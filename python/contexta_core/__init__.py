@@ -10,29 +10,32 @@ Example usage:
 
 Public API:
     - analyze(): Analyze source code and extract symbols/dependencies
-    - capabilities(): List available analyzer features
+    - capabilities_v2(): Describe languages, features and version as a dict
+    - capabilities(): List available analyzer features (deprecated)
     - check_compatibility(): Check version compatibility
     - AnalysisConfig: Configuration dataclass
     - AnalysisResult: Result dataclass with symbols and dependencies
 """
 
 from pathlib import Path
-from typing import Optional, List, Union
+from typing import Any, Dict, Optional, List, Union
 import ast
 import os
+import warnings
 
 # Import configuration and result types
 from .config import AnalysisConfig
 from .result import AnalysisResult, Symbol, Dependency, SymbolKind
 
-# Version constants
-__version__ = "0.1.0"
+# Version constants (__version__ comes from the Rust bindings below)
 __api_version__ = "1.0.0"  # Semantic versioning for API compatibility
 
 # Import Rust bindings for capabilities and version checks.
 try:
     from ._bindings import (
+        __version__,
         capabilities as _rust_capabilities,
+        capabilities_v2 as _rust_capabilities_v2,
         check_compatibility as _rust_check_compatibility,
     )
 except ImportError as e:
@@ -207,6 +210,9 @@ def _collect_symbols_from_ast(
 def capabilities() -> List[str]:
     """Return list of available analyzer capabilities.
 
+    .. deprecated::
+        Use :func:`capabilities_v2`, which returns a structured dict.
+
     Returns:
         List of capability strings
 
@@ -215,7 +221,29 @@ def capabilities() -> List[str]:
         >>> if 'deep-mode' in caps:
         ...     print("Deep Mode available")
     """
-    return _rust_capabilities()
+    warnings.warn(
+        "capabilities() is deprecated, use capabilities_v2()",
+        DeprecationWarning,
+        stacklevel=2,
+    )
+    with warnings.catch_warnings():
+        warnings.simplefilter("ignore", DeprecationWarning)
+        return _rust_capabilities()
+
+
+def capabilities_v2() -> Dict[str, Any]:
+    """Describe what this build of the analyzer supports.
+
+    Returns:
+        Dict with keys ``languages`` (list of str), ``features`` (list of str),
+        ``version`` (str) and ``deep_mode`` (bool)
+
+    Example:
+        >>> caps = capabilities_v2()
+        >>> if caps["deep_mode"]:
+        ...     print("Deep Mode available")
+    """
+    return _rust_capabilities_v2()
 
 
 def check_compatibility(client_version: str) -> bool:
@@ -267,6 +295,7 @@ __all__ = [
     # Main functions
     "analyze",
     "capabilities",
+    "capabilities_v2",
     "check_compatibility",
     # Configuration
     "AnalysisConfig",
@@ -4,10 +4,12 @@ This file provides type hints for functions implemented in Rust and exposed via
 """
 
 from pathlib import Path
-from typing import Optional, List
+from typing import Any, Dict, Optional, List
 from .config import AnalysisConfig
 from .result import AnalysisResult
 
+__version__: str
+
 def analyze(
     source: str | Path, config: Optional[AnalysisConfig] = None
 ) -> AnalysisResult:
@@ -42,6 +44,8 @@ def analyze(
 def capabilities() -> List[str]:
     """Return list of available analyzer capabilities.
 
+    Deprecated: use ``capabilities_v2()``.
+
     This function queries the Rust core for supported features. Capabilities
     may vary based on compile-time features (e.g., deep-mode).
 
@@ -57,6 +61,23 @@ def capabilities() -> List[str]:
     """
     ...
 
+def capabilities_v2() -> Dict[str, Any]:
+    """Return analyzer capabilities as a structured dict.
+
+    Returns:
+        Dict with keys:
+        - ``languages``: languages with a bundled analyzer
+        - ``features``: enabled capabilities, including optional Cargo features
+        - ``version``: core version string, same as ``contexta_core.__version__``
+        - ``deep_mode``: whether the ``deep-mode`` feature was compiled in
+
+    Example:
+        >>> from contexta_core import capabilities_v2
+        >>> capabilities_v2()["languages"]
+        ['python', 'typescript', 'javascript', 'rust']
+    """
+    ...
+
 def check_compatibility(client_version: str) -> bool:
     """Check if a client version is compatible with this core version.
 
@@ -3,6 +3,7 @@
 import pytest
 from contexta_core import (
     capabilities,
+    capabilities_v2,
     check_compatibility,
     __version__,
     __api_version__,
@@ -28,6 +29,11 @@ class TestCapabilities:
         caps = capabilities()
         assert "analyze" in caps
 
+    def test_capabilities_has_languages(self):
+        """Test that capabilities lists every bundled language."""
+        caps = capabilities()
+        assert {"python", "typescript", "rust", "go", "java", "c", "cpp"} <= set(caps)
+
     def test_capabilities_is_not_empty(self):
         """Test that capabilities list is not empty."""
         caps = capabilities()
@@ -48,6 +54,43 @@ class TestCapabilities:
             assert isinstance("deep-mode", str)
 
 
+class TestCapabilitiesV2:
+    """Test capabilities_v2() function."""
+
+    def test_returns_expected_keys(self):
+        """Test that the dict has exactly the documented keys."""
+        caps = capabilities_v2()
+        assert set(caps) == {"languages", "features", "version", "deep_mode"}
+
+    def test_value_types(self):
+        """Test the type of each entry."""
+        caps = capabilities_v2()
+        assert all(isinstance(lang, str) for lang in caps["languages"])
+        assert all(isinstance(feature, str) for feature in caps["features"])
+        assert isinstance(caps["deep_mode"], bool)
+
+    def test_languages_and_version(self):
+        """Test that bundled languages and the package version are reported."""
+        caps = capabilities_v2()
+        assert {"python", "typescript", "rust", "c", "cpp"} <= set(caps["languages"])
+        assert caps["version"] == __version__
+        assert "incremental" in caps["features"]
+        assert "constant_kind" in caps["features"]
+
+    def test_deep_mode_matches_build(self):
+        """Test that deep_mode reflects the build-time feature flag."""
+        caps = capabilities_v2()
+        assert caps["deep_mode"] == ("deep-mode" in caps["features"])
+        with pytest.warns(DeprecationWarning):
+            assert caps["deep_mode"] == ("deep-mode" in capabilities())
+
+    def test_capabilities_is_deprecated(self):
+        """Test that the list form warns and still lists every language."""
+        with pytest.warns(DeprecationWarning):
+            caps = capabilities()
+        assert set(capabilities_v2()["languages"]) <= set(caps)
+
+
 class TestCheckCompatibility:
     """Test check_compatibility() function."""
 
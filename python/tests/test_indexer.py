@@ -0,0 +1,1379 @@
+"""Unit tests for the PyIndexer bindings."""
+
+import asyncio
+import json
+import os
+import shutil
+import sqlite3
+import tempfile
+import time
+from datetime import datetime, timezone
+
+import pytest
+
+from contexta_core._bindings import PyIndexer, PyIndexerConfig, PyQueryBuilder, PySymbol, PySymbolKind
+
+
+@pytest.fixture
+def temp_project():
+    """Create a temporary directory with Python and Rust sources."""
+    temp_dir = tempfile.mkdtemp()
+
+    with open(os.path.join(temp_dir, "main.py"), "w") as f:
+        f.write(
+            """
+def main():
+    print("Hello from main")
+
+class App:
+    def run(self):
+        pass
+"""
+        )
+
+    with open(os.path.join(temp_dir, "lib.rs"), "w") as f:
+        f.write(
+            """
+fn helper() -> i32 {
+    42
+}
+"""
+        )
+
+    yield temp_dir
+
+    if os.path.exists(temp_dir):
+        shutil.rmtree(temp_dir)
+
+
+@pytest.fixture
+def indexer(temp_project):
+    """Create an indexer whose database path lives next to the project."""
+    return PyIndexer(os.path.join(temp_project, "index.db"))
+
+
+class TestIndexerConfigValidate:
+    """Test PyIndexerConfig.validate()."""
+
+    def test_valid_config(self, temp_project):
+        """Test that the default config for an existing directory is valid."""
+        PyIndexerConfig(temp_project).validate()
+
+    def test_reports_all_errors(self, temp_project):
+        """Test that every invalid field is listed in one ValueError."""
+        config = PyIndexerConfig(os.path.join(temp_project, "missing"))
+        config.extensions = [".py", "*"]
+        config.exclude_dirs = ["src/generated"]
+        config.max_file_size = 0
+
+        with pytest.raises(ValueError) as excinfo:
+            config.validate()
+        message = str(excinfo.value)
+        for field in ["root_dir", "max_file_size", "extensions", "exclude_dirs"]:
+            assert field in message
+        assert message.count("extensions:") == 2
+
+
+class TestIndexerConfigRootDirs:
+    """Test multi-root PyIndexerConfig."""
+
+    def test_single_root_constructor(self, temp_project):
+        """Test that a single path becomes a one-element root_dirs."""
+        config = PyIndexerConfig(temp_project)
+        assert config.root_dirs == [temp_project]
+        assert config.root_dir == temp_project
+
+    def test_discovers_files_from_every_root(self, temp_project):
+        """Test that files under each root are analyzed."""
+        other_root = tempfile.mkdtemp()
+        try:
+            with open(os.path.join(other_root, "extra.py"), "w") as f:
+                f.write("def extra():\n    pass\n")
+
+            config = PyIndexerConfig([temp_project, other_root])
+            indexer = PyIndexer(os.path.join(other_root, "index.db"))
+
+            async def run():
+                return await indexer.analyze_directory_no_persist(config)
+
+            results = asyncio.run(run())
+
+            assert any(path.startswith(temp_project) for path in results)
+            assert any(path.endswith("extra.py") for path in results)
+        finally:
+            shutil.rmtree(other_root)
+
+
+class TestAnalyzeDirectoryNoPersist:
+    """Test PyIndexer.analyze_directory_no_persist()."""
+
+    def test_returns_dict_keyed_by_path(self, indexer, temp_project):
+        """Test that results are keyed by discovered file path."""
+        config = PyIndexerConfig(temp_project)
+
+        async def run():
+            return await indexer.analyze_directory_no_persist(config)
+
+        results = asyncio.run(run())
+        assert isinstance(results, dict)
+        assert os.path.join(temp_project, "main.py") in results
+        assert os.path.join(temp_project, "lib.rs") in results
+
+    def test_extracts_symbols(self, indexer, temp_project):
+        """Test that symbols are extracted for each file."""
+        config = PyIndexerConfig(temp_project)
+
+        async def run():
+            return await indexer.analyze_directory_no_persist(config)
+
+        results = asyncio.run(run())
+        names = {s.name for s in results[os.path.join(temp_project, "main.py")]}
+        assert {"main", "App", "run"} <= names
+
+    def test_does_not_create_database(self, indexer, temp_project):
+        """Test that no database file is written."""
+        config = PyIndexerConfig(temp_project)
+
+        async def run():
+            return await indexer.analyze_directory_no_persist(config)
+
+        asyncio.run(run())
+        assert not os.path.exists(os.path.join(temp_project, "index.db"))
+
+    def test_respects_gitignore(self, indexer, temp_project):
+        """Test that .gitignore matches are skipped unless gitignore is disabled."""
+        with open(os.path.join(temp_project, ".gitignore"), "w") as f:
+            f.write("*.rs\n")
+        config = PyIndexerConfig(temp_project)
+        assert config.gitignore
+
+        async def run():
+            return await indexer.analyze_directory_no_persist(config)
+
+        assert set(asyncio.run(run())) == {os.path.join(temp_project, "main.py")}
+
+        config.gitignore = False
+        assert os.path.join(temp_project, "lib.rs") in asyncio.run(run())
+
+    def test_respects_contextaignore(self, indexer, temp_project):
+        """Test that .contextaignore globs are skipped unless use_contextaignore is disabled."""
+        with open(os.path.join(temp_project, ".contextaignore"), "w") as f:
+            f.write("# not indexed\n*.rs\n")
+        config = PyIndexerConfig(temp_project)
+        assert config.use_contextaignore
+
+        async def run():
+            return await indexer.analyze_directory_no_persist(config)
+
+        assert set(asyncio.run(run())) == {os.path.join(temp_project, "main.py")}
+
+        config.use_contextaignore = False
+        assert os.path.join(temp_project, "lib.rs") in asyncio.run(run())
+
+
+class TestAnalyzeTextBatch:
+    """Test PyIndexer.analyze_text_batch()."""
+
+    def test_batch_preserves_order(self, indexer):
+        """Test that one symbol list is returned per input, in order."""
+        results = indexer.analyze_text_batch(
+            [
+                ("python", "def py_func():\n    pass\n"),
+                ("rust", "fn rs_func() {}\n"),
+                ("typescript", "function tsFunc() {}\n"),
+            ]
+        )
+        assert len(results) == 3
+        assert [s.name for s in results[0]] == ["py_func"]
+        assert [s.name for s in results[1]] == ["rs_func"]
+        assert [s.name for s in results[2]] == ["tsFunc"]
+
+    def test_empty_batch(self, indexer):
+        """Test that an empty batch returns an empty list."""
+        assert indexer.analyze_text_batch([]) == []
+
+    def test_unsupported_language(self, indexer):
+        """Test that an unknown language raises ValueError."""
+        with pytest.raises(ValueError):
+            indexer.analyze_text_batch([("cobol", "DISPLAY 'HELLO'.")])
+
+
+class TestUpdateSymbolPositions:
+    """Test PyIndexer.update_symbol_positions()."""
+
+    def test_shifts_symbols_below_edit(self, indexer, temp_project):
+        """Test that symbols after the edited line are moved."""
+        config = PyIndexerConfig(temp_project)
+        main_py = os.path.join(temp_project, "main.py")
+
+        async def run():
+            return await indexer.index_files(config, None)
+
+        asyncio.run(run())
+        before = {s.name: s.line_start for s in indexer.list_symbols_in_file(main_py)}
+
+        updated = indexer.update_symbol_positions(main_py, before["main"], 2)
+        assert updated == 2
+
+        after = {s.name: s.line_start for s in indexer.list_symbols_in_file(main_py)}
+        assert after["main"] == before["main"]
+        assert after["App"] == before["App"] + 2
+        assert after["run"] == before["run"] + 2
+
+    def test_unindexed_file(self, indexer):
+        """Test that an unknown file raises ValueError."""
+        indexer.init_database()
+        with pytest.raises(ValueError):
+            indexer.update_symbol_positions("missing.py", 0, 1)
+
+
+class TestSubscribeFileChanges:
+    """Test PyIndexer.subscribe_file_changes()."""
+
+    def test_receives_created_file(self, indexer, temp_project):
+        """Test that creating a file yields a change event dict."""
+
+        async def run():
+            stream = indexer.subscribe_file_changes(temp_project)
+            with open(os.path.join(temp_project, "new.py"), "w") as f:
+                f.write("x = 1\n")
+            return await asyncio.wait_for(stream.__anext__(), timeout=5)
+
+        event = asyncio.run(run())
+        assert event["kind"] in ("create", "modify")
+        assert any(p.endswith("new.py") for p in event["paths"])
+
+
+class TestReindexChangedFiles:
+    """Test PyIndexer.reindex_changed_files()."""
+
+    def test_only_modified_file_is_updated(self, indexer, temp_project):
+        """Test that only the modified file is re-analyzed."""
+        config = PyIndexerConfig(temp_project)
+
+        async def index():
+            return await indexer.index_files(config, None)
+
+        async def reindex():
+            return await indexer.reindex_changed_files(config)
+
+        main_py = os.path.join(temp_project, "main.py")
+        lib_rs = os.path.join(temp_project, "lib.rs")
+
+        # SQLite timestamps have second resolution
+        time.sleep(1.1)
+        asyncio.run(index())
+        time.sleep(1.1)
+
+        with open(main_py, "w") as f:
+            f.write("def renamed():\n    pass\n")
+
+        stats = asyncio.run(reindex())
+        assert stats == {"checked": 2, "reindexed": 1, "deleted": 0}
+
+        assert [s.name for s in indexer.list_symbols_in_file(main_py)] == ["renamed"]
+        assert [s.name for s in indexer.list_symbols_in_file(lib_rs)] == ["helper"]
+
+    def test_content_hash_detects_same_second_edit(self, indexer, temp_project):
+        """Test that edits are found by content hash, not just timestamps."""
+        config = PyIndexerConfig(temp_project)
+
+        async def index():
+            return await indexer.index_files(config, None)
+
+        async def reindex():
+            return await indexer.reindex_changed_files(config)
+
+        asyncio.run(index())
+        assert asyncio.run(reindex()) == {"checked": 2, "reindexed": 0, "deleted": 0}
+
+        main_py = os.path.join(temp_project, "main.py")
+        with open(main_py) as f:
+            source = f.read()
+        with open(main_py, "w") as f:
+            f.write(source.replace("main", "mian"))
+
+        assert asyncio.run(reindex()) == {"checked": 2, "reindexed": 1, "deleted": 0}
+        assert "mian" in [s.name for s in indexer.list_symbols_in_file(main_py)]
+
+    def test_deleted_file_is_removed(self, indexer, temp_project):
+        """Test that files missing on disk are dropped from the index."""
+        config = PyIndexerConfig(temp_project)
+
+        async def index():
+            return await indexer.index_files(config, None)
+
+        async def reindex():
+            return await indexer.reindex_changed_files(config)
+
+        asyncio.run(index())
+        os.remove(os.path.join(temp_project, "lib.rs"))
+
+        stats = asyncio.run(reindex())
+        assert stats["deleted"] == 1
+        with pytest.raises(RuntimeError):
+            indexer.list_symbols_in_file(os.path.join(temp_project, "lib.rs"))
+
+
+class TestFindSymbolsRegex:
+    """Test PyIndexer.find_symbols_regex()."""
+
+    def test_matches_uppercase_names(self, indexer, temp_project):
+        """Test that a regex selects class-like names."""
+        config = PyIndexerConfig(temp_project)
+
+        async def run():
+            return await indexer.index_files(config, None)
+
+        asyncio.run(run())
+        results = indexer.find_symbols_regex("^[A-Z]", 10)
+        assert [s.name for s in results] == ["App"]
+
+    def test_invalid_pattern(self, indexer):
+        """Test that an invalid regex raises ValueError."""
+        indexer.init_database()
+        with pytest.raises(ValueError):
+            indexer.find_symbols_regex("(unclosed", 10)
+
+
+class TestFindSymbolsPrefix:
+    """Test PyIndexer.find_symbols_prefix()."""
+
+    def test_prefix_and_kind(self, indexer, temp_project):
+        """Test prefix matching with and without a kind filter."""
+        config = PyIndexerConfig(temp_project)
+
+        async def run():
+            return await indexer.index_files(config, None)
+
+        asyncio.run(run())
+        assert [s.name for s in indexer.find_symbols_prefix("", 10)] == ["App", "helper", "main", "run"]
+        assert [s.name for s in indexer.find_symbols_prefix("", 2)] == ["App", "helper"]
+        assert [s.name for s in indexer.find_symbols_prefix("ma", 10)] == ["main"]
+        assert indexer.find_symbols_prefix("Ma", 10) == []
+        assert [s.name for s in indexer.find_symbols_prefix("", 10, kind="class")] == ["App"]
+        assert [s.name for s in indexer.find_symbols_prefix("", 10, PySymbolKind.Function)] == [
+            "helper",
+            "main",
+            "run",
+        ]
+
+    def test_invalid_kind(self, indexer):
+        """Test that an unknown kind raises ValueError."""
+        indexer.init_database()
+        with pytest.raises(ValueError):
+            indexer.find_symbols_prefix("a", 10, kind="widget")
+
+
+class TestPagedQueries:
+    """Test PyIndexer.find_symbols_paged() and list_symbols_in_file_paged()."""
+
+    def test_pages(self, indexer, temp_project):
+        """Test that consecutive pages split the results without overlap."""
+        with open(os.path.join(temp_project, "other.py"), "w") as f:
+            f.write("def main():\n    pass\n")
+        config = PyIndexerConfig(temp_project)
+
+        async def run():
+            return await indexer.index_files(config, None)
+
+        asyncio.run(run())
+        first = indexer.find_symbols_paged("main", 1)
+        second = indexer.find_symbols_paged("main", 1, offset=1)
+        assert len(first) == len(second) == 1
+        assert first[0].file_id < second[0].file_id
+        assert indexer.find_symbols_paged("main", 10, 2) == []
+
+        main_py = os.path.join(temp_project, "main.py")
+        assert [s.name for s in indexer.list_symbols_in_file_paged(main_py, 2, 1)] == ["App", "run"]
+        with pytest.raises(RuntimeError):
+            indexer.list_symbols_in_file_paged(os.path.join(temp_project, "missing.py"), 10)
+
+
+class TestSymbolCounts:
+    """Test PyIndexer.get_symbol_counts() and get_file_symbol_count()."""
+
+    def test_counts_match_indexed_symbols(self, indexer, temp_project):
+        """Test that per-kind and per-file counts agree with the stored symbols."""
+        main_py = os.path.join(temp_project, "main.py")
+        indexer.index_file(main_py)
+
+        assert indexer.get_symbol_counts() == {"function": 2, "class": 1}
+        assert indexer.get_file_symbol_count(main_py) == 3
+
+        with pytest.raises(RuntimeError):
+            indexer.get_file_symbol_count(os.path.join(temp_project, "missing.py"))
+
+
+class TestLanguageStatsExtended:
+    """Test PyIndexer.get_language_stats_extended()."""
+
+    def test_reports_symbol_counts(self, indexer, temp_project):
+        """Test that symbol totals and averages are reported per language."""
+        indexer.index_file(os.path.join(temp_project, "main.py"))
+
+        stats = json.loads(indexer.get_language_stats_extended())
+        assert stats == [
+            {
+                "language": "python",
+                "file_count": 1,
+                "total_size": stats[0]["total_size"],
+                "symbol_count": 3,
+                "avg_symbols_per_file": 3.0,
+                "files_with_errors": 0,
+            }
+        ]
+        assert "symbol_count" not in json.loads(indexer.get_language_stats())[0]
+
+
+class TestDuplicateSymbols:
+    """Test PyIndexer.find_duplicate_symbol_names() and find_duplicate_symbols_in_file()."""
+
+    def test_reports_repeated_names(self, indexer, temp_project):
+        """Test that names declared more than once are counted across and within files."""
+        main_py = os.path.join(temp_project, "main.py")
+        other_py = os.path.join(temp_project, "other.py")
+        with open(other_py, "w") as f:
+            f.write("def main():\n    pass\n\ndef main():\n    pass\n")
+        indexer.index_file(main_py)
+        indexer.index_file(other_py)
+
+        assert indexer.find_duplicate_symbol_names("function") == [("main", 3)]
+        assert indexer.find_duplicate_symbol_names("class") == []
+        assert indexer.find_duplicate_symbols_in_file(other_py) == [("main", 2)]
+        assert indexer.find_duplicate_symbols_in_file(main_py) == []
+
+
+class TestFindSymbolsGrouped:
+    """Test PyIndexer.find_symbols_grouped()."""
+
+    def test_groups_by_path(self, indexer, temp_project):
+        """Test that matches are keyed by file path and ordered by line."""
+        main_py = os.path.join(temp_project, "main.py")
+        other_py = os.path.join(temp_project, "other.py")
+        with open(other_py, "w") as f:
+            f.write("def main():\n    pass\n\ndef main():\n    pass\n")
+        indexer.index_file(main_py)
+        indexer.index_file(other_py)
+
+        grouped = indexer.find_symbols_grouped("main")
+        assert set(grouped) == {main_py, other_py}
+        assert len(grouped[main_py]) == 1
+        assert [s.line_start for s in grouped[other_py]] == [0, 3]
+        assert indexer.find_symbols_grouped("missing") == {}
+
+
+class TestReindexFile:
+    """Test PyIndexer.reindex_file()."""
+
+    def test_reports_symbol_diff(self, indexer, temp_project):
+        """Test that re-indexing reports added and removed symbols."""
+        main_py = os.path.join(temp_project, "main.py")
+        first = indexer.reindex_file(main_py)
+        assert sorted(s.name for s in first["added"]) == ["App", "main", "run"]
+        assert first["removed"] == []
+        assert first["unchanged_count"] == 0
+
+        with open(main_py, "a") as f:
+            f.write("\ndef extra():\n    pass\n")
+        second = indexer.reindex_file(main_py)
+        assert [s.name for s in second["added"]] == ["extra"]
+        assert second["removed"] == []
+        assert second["unchanged_count"] == 3
+
+        with open(main_py, "w") as f:
+            f.write("def extra():\n    pass\n")
+        third = indexer.reindex_file(main_py)
+        # Moving `extra` to the first line counts as removing and re-adding it
+        assert [s.name for s in third["added"]] == ["extra"]
+        assert sorted(s.name for s in third["removed"]) == ["App", "extra", "main", "run"]
+        assert third["unchanged_count"] == 0
+
+    def test_unsupported_file(self, indexer, temp_project):
+        """Test that files without an analyzer raise ValueError."""
+        notes = os.path.join(temp_project, "notes.txt")
+        with open(notes, "w") as f:
+            f.write("hello")
+
+        with pytest.raises(ValueError):
+            indexer.reindex_file(notes)
+
+
+class TestIndexFile:
+    """Test PyIndexer.index_file()."""
+
+    def test_indexes_single_file(self, indexer, temp_project):
+        """Test that one file is indexed synchronously and re-indexing replaces its symbols."""
+        main_py = os.path.join(temp_project, "main.py")
+
+        indexed = indexer.index_file(main_py)
+        assert indexed.path == main_py
+        assert indexed.language == "python"
+        assert indexed.last_indexed is not None
+        assert indexed.parse_errors == []
+        assert len(indexer.list_symbols_in_file(main_py)) == 3
+        assert [f.path for f in indexer.list_files()] == [main_py]
+
+        with open(main_py, "a") as f:
+            f.write("\ndef extra():\n    pass\n")
+        indexer.index_file(main_py)
+        assert len(indexer.list_symbols_in_file(main_py)) == 4
+
+    def test_failed_store_keeps_previous_index(self, indexer, temp_project):
+        """Test that a failed symbol insert leaves the previously indexed symbols in place."""
+        main_py = os.path.join(temp_project, "main.py")
+        indexer.index_file(main_py)
+
+        conn = sqlite3.connect(os.path.join(temp_project, "index.db"))
+        conn.execute(
+            "CREATE TRIGGER block_symbols BEFORE INSERT ON symbols "
+            "BEGIN SELECT RAISE(ABORT, 'blocked'); END"
+        )
+        conn.commit()
+        conn.close()
+
+        with open(main_py, "a") as f:
+            f.write("\ndef extra():\n    pass\n")
+        with pytest.raises(RuntimeError):
+            indexer.index_file(main_py)
+        assert len(indexer.list_symbols_in_file(main_py)) == 3
+
+    def test_rejects_unsupported_file(self, indexer, temp_project):
+        """Test that files without an analyzer raise ValueError."""
+        notes = os.path.join(temp_project, "notes.txt")
+        with open(notes, "w") as f:
+            f.write("hello")
+
+        with pytest.raises(ValueError):
+            indexer.index_file(notes)
+
+    def test_indexes_config_file_without_symbols(self, indexer, temp_project):
+        """Test that config files are recorded with their language but no symbols."""
+        package_json = os.path.join(temp_project, "package.json")
+        with open(package_json, "w") as f:
+            f.write('{"name": "demo"}')
+
+        indexed = indexer.index_file(package_json)
+        assert indexed.language == "json"
+        assert indexer.list_symbols_in_file(package_json) == []
+
+    def test_indexes_c_and_cpp_files(self, indexer, temp_project):
+        """Test that C and C++ sources are indexed with their own analyzers."""
+        util_h = os.path.join(temp_project, "util.h")
+        with open(util_h, "w") as f:
+            f.write("struct point { int x, y; };\nint distance(struct point a, struct point b);\n")
+        shape_cpp = os.path.join(temp_project, "shape.cpp")
+        with open(shape_cpp, "w") as f:
+            f.write("namespace geo {\nclass Shape {\n    double area() const { return 0; }\n};\n}\n")
+
+        assert indexer.index_file(util_h).language == "c"
+        assert {s.name for s in indexer.list_symbols_in_file(util_h)} == {"point", "distance"}
+        assert indexer.index_file(shape_cpp).language == "cpp"
+        assert {s.name for s in indexer.list_symbols_in_file(shape_cpp)} == {"geo", "Shape", "area"}
+
+    def test_missing_file(self, indexer, temp_project):
+        """Test that unreadable files raise RuntimeError."""
+        with pytest.raises(RuntimeError):
+            indexer.index_file(os.path.join(temp_project, "missing.py"))
+
+
+class TestRemoveFile:
+    """Test PyIndexer.remove_file()."""
+
+    def test_round_trip(self, indexer, temp_project):
+        """Test that removing an indexed file drops all of its symbols."""
+        config = PyIndexerConfig(temp_project)
+
+        async def run():
+            return await indexer.index_files(config, None)
+
+        asyncio.run(run())
+        main_py = os.path.join(temp_project, "main.py")
+        assert len(indexer.list_symbols_in_file(main_py)) == 3
+
+        assert indexer.remove_file(main_py) is True
+        assert indexer.find_symbols("main") == []
+        assert [s.name for s in indexer.find_symbols_regex(".", 100)] == ["helper"]
+        with pytest.raises(RuntimeError):
+            indexer.list_symbols_in_file(main_py)
+
+        assert indexer.remove_file(main_py) is False
+
+
+class TestGetSymbol:
+    """Test PyIndexer.get_symbol()."""
+
+    def test_lookup_by_id(self, indexer, temp_project):
+        """Test that a symbol id round-trips and unknown ids give None."""
+        config = PyIndexerConfig(temp_project)
+
+        async def run():
+            return await indexer.index_files(config, None)
+
+        asyncio.run(run())
+        helper = indexer.find_symbols("helper")[0]
+        found = indexer.get_symbol(helper.id)
+        assert found == helper
+        assert found.name == "helper"
+        assert indexer.get_symbol(helper.id + 1000) is None
+
+
+class TestPySymbolComparison:
+    """Test PySymbol hashing, equality and ordering."""
+
+    def _symbols(self, indexer, temp_project):
+        config = PyIndexerConfig(temp_project)
+
+        async def run():
+            return await indexer.index_files(config, None)
+
+        asyncio.run(run())
+        return indexer.list_symbols_in_file(os.path.join(temp_project, "main.py"))
+
+    def test_set_deduplicates(self, indexer, temp_project):
+        """Test that duplicate symbols collapse in a set."""
+        symbols = self._symbols(indexer, temp_project)
+        duplicated = symbols + self._symbols(indexer, temp_project)
+
+        assert len(set(duplicated)) < len(duplicated)
+        assert len(set(duplicated)) == len(symbols)
+        assert symbols[0] == duplicated[len(symbols)]
+        assert symbols[0] != symbols[1]
+
+    def test_sort_by_position(self, indexer, temp_project):
+        """Test that sorting follows line order."""
+        symbols = self._symbols(indexer, temp_project)
+        shuffled = list(reversed(symbols))
+
+        shuffled.sort()
+        assert [s.line_start for s in shuffled] == sorted(s.line_start for s in symbols)
+        assert [s.name for s in shuffled] == ["main", "App", "run"]
+
+    def test_compare_with_other_type(self, indexer, temp_project):
+        """Test that comparing against a non-symbol is not equal."""
+        symbols = self._symbols(indexer, temp_project)
+        assert symbols[0] != "main"
+
+    def test_from_dict_roundtrip(self, indexer, temp_project):
+        """Test that from_dict(to_dict()) rebuilds an equal symbol."""
+        symbols = self._symbols(indexer, temp_project)
+
+        rebuilt = [PySymbol.from_dict(s.to_dict()) for s in symbols]
+        assert rebuilt == symbols
+        assert set(rebuilt + symbols) == set(symbols)
+        assert rebuilt[0].metadata == symbols[0].metadata
+
+    def test_kind_affects_equality(self, indexer, temp_project):
+        """Test that symbols differing only in kind are distinct."""
+        symbol = self._symbols(indexer, temp_project)[0]
+        data = symbol.to_dict()
+        data["kind"] = "class" if data["kind"] != "class" else "function"
+
+        other = PySymbol.from_dict(data)
+        assert other != symbol
+        assert len({symbol, other}) == 2
+
+    def test_from_dict_rejects_invalid_kind(self, indexer, temp_project):
+        """Test that from_dict validates the symbol kind."""
+        data = self._symbols(indexer, temp_project)[0].to_dict()
+        data["kind"] = "NotAKind"
+
+        with pytest.raises(ValueError):
+            PySymbol.from_dict(data)
+
+
+class TestContextManager:
+    """Test PyIndexer as a context manager and PyIndexer.close()."""
+
+    def test_with_block_closes(self, temp_project):
+        """Test that leaving a with block closes the indexer and truncates the WAL."""
+        db_path = os.path.join(temp_project, "index.db")
+        with PyIndexer(db_path) as indexer:
+
+            async def run():
+                return await indexer.index_files(PyIndexerConfig(temp_project), None)
+
+            asyncio.run(run())
+            assert "(closed)" not in repr(indexer)
+
+        assert repr(indexer).endswith("(closed)")
+        wal_path = db_path + "-wal"
+        assert not os.path.exists(wal_path) or os.path.getsize(wal_path) == 0
+
+    def test_exception_propagates(self, temp_project):
+        """Test that exceptions inside the block propagate after closing."""
+        with pytest.raises(KeyError):
+            with PyIndexer(os.path.join(temp_project, "index.db")) as indexer:
+                raise KeyError("boom")
+
+        assert repr(indexer).endswith("(closed)")
+
+    def test_close_is_idempotent(self, indexer, temp_project):
+        """Test that close() can be called twice and stops incremental indexing."""
+        indexer.start_incremental(PyIndexerConfig(temp_project))
+        indexer.close()
+        with pytest.raises(RuntimeError):
+            indexer.incremental_stats()
+
+        indexer.close()
+        assert repr(indexer).endswith("(closed)")
+
+
+class TestIncrementalIndexing:
+    """Test PyIndexer.start_incremental() / stop_incremental()."""
+
+    def test_indexes_new_file(self, indexer, temp_project):
+        """Test that a file written while watching gets indexed."""
+        config = PyIndexerConfig(temp_project)
+        indexer.start_incremental(config)
+
+        new_file = os.path.join(temp_project, "watched.py")
+        with open(new_file, "w") as f:
+            f.write("def watched():\n    pass\n")
+
+        deadline = time.time() + 5
+        while indexer.incremental_stats()["reindexed"] == 0 and time.time() < deadline:
+            time.sleep(0.05)
+
+        stats = indexer.stop_incremental()
+        assert stats["reindexed"] >= 1
+        assert [s.name for s in indexer.list_symbols_in_file(new_file)] == ["watched"]
+
+    def test_double_start(self, indexer, temp_project):
+        """Test that starting twice raises RuntimeError."""
+        config = PyIndexerConfig(temp_project)
+        indexer.start_incremental(config)
+        try:
+            with pytest.raises(RuntimeError):
+                indexer.start_incremental(config)
+        finally:
+            indexer.stop_incremental()
+
+    def test_stop_without_start(self, indexer):
+        """Test that stopping an idle indexer raises RuntimeError."""
+        with pytest.raises(RuntimeError):
+            indexer.stop_incremental()
+
+
+class TestPyQueryBuilder:
+    """Test the PyQueryBuilder class."""
+
+    def test_combined_filters(self, indexer, temp_project):
+        """Test chaining filters, ordering and limits."""
+        config = PyIndexerConfig(temp_project)
+
+        async def run():
+            return await indexer.index_files(config, None)
+
+        asyncio.run(run())
+        main_py = os.path.join(temp_project, "main.py")
+
+        results = (
+            PyQueryBuilder()
+            .filter_file(main_py)
+            .filter_kind("function")
+            .order_by("line_start", desc=True)
+            .limit(1)
+            .execute(indexer)
+        )
+        assert [s.name for s in results] == ["run"]
+
+        scoped = PyQueryBuilder().filter_scope("App").execute(indexer)
+        assert [s.name for s in scoped] == ["run"]
+
+    def test_invalid_arguments(self):
+        """Test that unknown kinds and fields raise ValueError."""
+        with pytest.raises(ValueError):
+            PyQueryBuilder().filter_kind("widget")
+        with pytest.raises(ValueError):
+            PyQueryBuilder().order_by("name; DROP TABLE symbols")
+
+    def test_sql_uses_placeholders(self):
+        """Test that filter values are not spliced into the SQL."""
+        sql = PyQueryBuilder().filter_name("x' OR '1'='1").sql()
+        assert "OR '1'='1" not in sql
+        assert "name = ?" in sql
+
+
+class TestLspWorkspaceSymbols:
+    """Test PyIndexer.export_lsp_workspace_symbols() and import_lsp_workspace_symbols()."""
+
+    def test_round_trip_preserves_symbol_count(self, indexer, temp_project):
+        """Test that an export re-imported into a fresh index keeps every symbol."""
+        config = PyIndexerConfig(temp_project)
+
+        async def run():
+            return await indexer.index_files(config, None)
+
+        asyncio.run(run())
+        output = os.path.join(temp_project, "symbols.ndjson")
+        indexer.export_lsp_workspace_symbols(output)
+
+        with open(output) as f:
+            lines = [json.loads(line) for line in f]
+        assert {"name", "kind", "location"} <= set(lines[0])
+        assert all(line["location"]["uri"].startswith("file://") for line in lines)
+
+        fresh = PyIndexer(os.path.join(temp_project, "fresh.db"))
+        fresh.init_database()
+        stats = fresh.import_lsp_workspace_symbols(output)
+        assert stats == {"files": 2, "symbols": len(lines)}
+        assert sorted(s.name for s in fresh.find_symbols_regex(".", 100)) == sorted(
+            line["name"] for line in lines
+        )
+
+
+class TestSnapshot:
+    """Test PyIndexer.export_snapshot() and import_snapshot()."""
+
+    def test_round_trip_replaces_index(self, indexer, temp_project):
+        """Test that importing a snapshot restores every file and symbol."""
+        config = PyIndexerConfig(temp_project)
+
+        async def run():
+            return await indexer.index_files(config, None)
+
+        asyncio.run(run())
+        output = os.path.join(temp_project, "index.snapshot")
+        indexer.export_snapshot(output)
+
+        with open(output) as f:
+            records = [json.loads(line) for line in f]
+        symbol_count = sum(1 for r in records if r["type"] == "symbol")
+
+        fresh = PyIndexer(os.path.join(temp_project, "fresh.db"))
+        fresh.init_database()
+        counts = fresh.import_snapshot(output)
+        assert counts == {"files": 2, "symbols": symbol_count}
+        assert fresh.find_symbols("run")[0].line_start == indexer.find_symbols("run")[0].line_start
+
+    def test_malformed_snapshot_raises(self, indexer, temp_project):
+        """Test that a malformed snapshot is rejected."""
+        bad = os.path.join(temp_project, "bad.snapshot")
+        with open(bad, "w") as f:
+            f.write("not json\n")
+
+        with pytest.raises(RuntimeError, match="Import failed"):
+            indexer.import_snapshot(bad)
+
+
+class TestSymbolColumns:
+    """Test that column offsets survive indexing."""
+
+    def test_method_column_offsets(self, indexer, temp_project):
+        """Test that a method reports its indentation as column_start."""
+        config = PyIndexerConfig(temp_project)
+
+        async def run():
+            return await indexer.index_files(config, None)
+
+        asyncio.run(run())
+
+        run_method = indexer.find_symbols("run")[0]
+        assert run_method.column_start == 4
+        assert run_method.column_end > run_method.column_start
+        assert indexer.find_symbols("App")[0].column_start == 0
+        assert run_method.to_dict()["column_start"] == "4"
+
+
+class TestExportJsonAsync:
+    """Test PyIndexer.export_json_async()."""
+
+    def test_export_counts_symbols(self, indexer, temp_project):
+        """Test that the streamed export is valid JSON holding every symbol."""
+        config = PyIndexerConfig(temp_project)
+        output = os.path.join(temp_project, "index.json")
+
+        async def run():
+            await indexer.index_files(config, None)
+            return await indexer.export_json_async(output)
+
+        written = asyncio.run(run())
+
+        with open(output) as f:
+            exported = json.load(f)
+        assert written == len(exported["symbols"]) == len(indexer.find_symbols_regex(".", 100))
+        assert len(exported["files"]) == 2
+
+
+class TestMaxSymbolsPerFile:
+    """Test PyIndexerConfig.max_symbols_per_file."""
+
+    def test_truncates_generated_file(self, indexer, temp_project):
+        """Test that a file over the limit stores only the cap and is flagged."""
+        generated = os.path.join(temp_project, "generated.py")
+        with open(generated, "w") as f:
+            f.write("".join(f"VALUE_{i} = {i}\n" for i in range(10000)))
+
+        config = PyIndexerConfig(temp_project)
+        assert config.max_symbols_per_file is None
+        config.max_symbols_per_file = 100
+
+        async def run():
+            return await indexer.index_files(config, None)
+
+        returned = {f.path: f for f in asyncio.run(run())}
+        [truncated] = returned[generated].parse_errors
+        assert truncated["file_path"] == generated
+        assert "truncated" in truncated["message"]
+
+        assert len(indexer.list_symbols_in_file(generated)) == 100
+        stored = {f.path: f for f in indexer.list_files()}
+        assert stored[generated].parse_errors == returned[generated].parse_errors
+        assert stored[os.path.join(temp_project, "main.py")].parse_errors == []
+
+
+class TestParseErrors:
+    """Test PyFileMetadata.parse_errors and PyIndexer.find_files_with_errors()."""
+
+    def test_reports_syntax_errors(self, indexer, temp_project):
+        """Test that syntax errors are stored per file with their position."""
+        broken = os.path.join(temp_project, "broken.py")
+        with open(broken, "w") as f:
+            f.write("def ok():\n    pass\n\ndef broken(:\n    pass\n")
+
+        async def run():
+            return await indexer.index_files(PyIndexerConfig(temp_project), None)
+
+        returned = {f.path: f for f in asyncio.run(run())}
+        errors = returned[broken].parse_errors
+        assert errors
+        assert all(e["file_path"] == broken and e["line"] == 3 for e in errors)
+        assert all(isinstance(e["column"], int) and e["message"] for e in errors)
+
+        [stored] = indexer.find_files_with_errors()
+        assert stored.path == broken
+        assert stored.parse_errors == errors
+        assert json.loads(stored.to_dict()["parse_errors"]) == errors
+
+
+class TestGetSymbolContext:
+    """Test PyIndexer.get_symbol_context()."""
+
+    def test_middle_method_has_neighbours(self, indexer, temp_project):
+        """Test that a method between two others returns both as context."""
+        with open(os.path.join(temp_project, "service.py"), "w") as f:
+            f.write(
+                """class Service:
+    def load(self):
+        pass
+
+    def handle(self):
+        pass
+
+    def save(self):
+        pass
+"""
+            )
+        config = PyIndexerConfig(temp_project)
+
+        async def run():
+            return await indexer.index_files(config, None)
+
+        asyncio.run(run())
+        handle = next(s for s in indexer.find_symbols("handle") if s.scope == "Service")
+
+        context = indexer.get_symbol_context(handle.id, 3)
+        assert context.symbol.name == "handle"
+        assert context.file_path.endswith("service.py")
+        assert [s.name for s in context.preceding_symbols] == ["load"]
+        assert [s.name for s in context.following_symbols] == ["save"]
+
+    def test_unknown_symbol(self, indexer):
+        """Test that a missing symbol id raises RuntimeError."""
+        indexer.init_database()
+        with pytest.raises(RuntimeError):
+            indexer.get_symbol_context(12345, 3)
+
+
+class TestChangeLog:
+    """Test PyIndexer.get_change_log() and prune_change_log()."""
+
+    def test_incremental_changes_are_logged_and_pruned(self, indexer, temp_project):
+        """Test that watched edits create log entries that pruning removes."""
+        config = PyIndexerConfig(temp_project)
+        indexer.start_incremental(config)
+        try:
+            path = os.path.join(temp_project, "logged.py")
+            with open(path, "w") as f:
+                f.write("def logged():\n    pass\n")
+
+            deadline = time.time() + 5
+            while indexer.incremental_stats()["reindexed"] == 0 and time.time() < deadline:
+                time.sleep(0.05)
+        finally:
+            indexer.stop_incremental()
+
+        entries = [e for e in indexer.get_change_log("1970-01-01T00:00:00Z") if e.file_path == path]
+        assert entries
+        assert entries[-1].symbols_added == 1
+        assert entries[0].event_kind == "create"
+
+        assert indexer.get_change_log("2999-01-01T00:00:00Z") == []
+        assert indexer.prune_change_log("2999-01-01T00:00:00Z") >= len(entries)
+        assert indexer.get_change_log("1970-01-01T00:00:00Z") == []
+
+    def test_invalid_timestamp(self, indexer):
+        """Test that an unparseable timestamp raises ValueError."""
+        indexer.init_database()
+        with pytest.raises(ValueError):
+            indexer.get_change_log("last tuesday")
+        with pytest.raises(ValueError):
+            indexer.prune_change_log("last tuesday")
+
+
+class TestFindSymbolsByKind:
+    """Test PyIndexer.find_symbols_by_kind()."""
+
+    def _index(self, indexer, temp_project):
+        with open(os.path.join(temp_project, "imports.py"), "w") as f:
+            f.write("import os\nfrom pathlib import Path\n\ndef uses_path():\n    return Path(os.sep)\n")
+        config = PyIndexerConfig(temp_project)
+
+        async def run():
+            return await indexer.index_files(config, None)
+
+        asyncio.run(run())
+
+    def test_all_functions_in_project(self, indexer, temp_project):
+        """Test that functions from every language are returned for a string kind."""
+        self._index(indexer, temp_project)
+        names = {s.name for s in indexer.find_symbols_by_kind("function")}
+        assert {"main", "run", "helper", "uses_path"} <= names
+        assert names == {s.name for s in indexer.find_symbols_by_kind(PySymbolKind.Function)}
+
+    def test_imports_in_file(self, indexer, temp_project):
+        """Test that a file path restricts the results to that file."""
+        self._index(indexer, temp_project)
+        path = os.path.join(temp_project, "imports.py")
+        imports = indexer.find_symbols_by_kind(PySymbolKind.Import, path)
+        assert len(imports) == 2
+        assert all(s.kind == "import" for s in imports)
+        assert indexer.find_symbols_by_kind("import", os.path.join(temp_project, "main.py")) == []
+
+    def test_invalid_kind(self, indexer):
+        """Test that unknown kinds and wrong types raise ValueError."""
+        indexer.init_database()
+        with pytest.raises(ValueError):
+            indexer.find_symbols_by_kind("widget")
+        with pytest.raises(ValueError):
+            indexer.find_symbols_by_kind(42)
+
+    def test_constants(self, indexer, temp_project):
+        """Test that Rust consts and ALL_CAPS Python assignments are constants."""
+        with open(os.path.join(temp_project, "settings.py"), "w") as f:
+            f.write("MAX_RETRIES = 3\nretries = 0\n")
+        with open(os.path.join(temp_project, "limits.rs"), "w") as f:
+            f.write("const LIMIT: u32 = 10;\n")
+        self._index(indexer, temp_project)
+        names = {s.name for s in indexer.find_symbols_by_kind(PySymbolKind.Constant)}
+        assert names == {"MAX_RETRIES", "LIMIT"}
+        assert all(s.kind == "constant" for s in indexer.find_symbols_by_kind("constant"))
+
+    def test_symbol_kind_str(self):
+        """Test that PySymbolKind renders as the stored kind name."""
+        assert str(PySymbolKind.Interface) == "interface"
+
+
+class TestGoIndexing:
+    """Test that Go sources are indexed with the Go analyzer."""
+
+    def test_go_symbols(self, indexer, temp_project):
+        """Test that functions, methods, types and imports are extracted."""
+        path = os.path.join(temp_project, "server.go")
+        with open(path, "w") as f:
+            f.write(
+                'package main\n\nimport "net/http"\n\n'
+                "type Server struct{}\n\n"
+                "func (s *Server) Serve() {}\n\n"
+                "func main() {}\n"
+            )
+        config = PyIndexerConfig(temp_project)
+
+        async def run():
+            return await indexer.index_files(config, None)
+
+        files = asyncio.run(run())
+        assert any(f.path == path and f.language == "go" for f in files)
+
+        symbols = {s.name: s for s in indexer.list_symbols_in_file(path)}
+        assert set(symbols) == {"net/http", "Server", "Serve", "main"}
+        assert symbols["Server"].kind == "struct"
+        assert symbols["Serve"].scope == "Server"
+        assert symbols["net/http"].kind == "import"
+
+
+class TestJavaIndexing:
+    """Test that Java sources are indexed with the Java analyzer."""
+
+    def test_java_symbols(self, indexer, temp_project):
+        """Test that inner classes and members are scoped to their enclosing class."""
+        path = os.path.join(temp_project, "Outer.java")
+        with open(path, "w") as f:
+            f.write(
+                "import java.util.List;\n\n"
+                "public class Outer {\n"
+                "    private List<String> items;\n"
+                "    static class Inner {\n"
+                "        void work() {}\n"
+                "    }\n"
+                "}\n"
+            )
+        config = PyIndexerConfig(temp_project)
+
+        async def run():
+            return await indexer.index_files(config, None)
+
+        asyncio.run(run())
+
+        symbols = {s.name: s for s in indexer.list_symbols_in_file(path)}
+        assert set(symbols) == {"java.util.List", "Outer", "items", "Inner", "work"}
+        assert symbols["items"].kind == "field"
+        assert symbols["Inner"].scope == "Outer"
+        assert symbols["work"].scope == "Inner"
+
+
+class TestFindTestFunctions:
+    """Test PyIndexer.find_test_functions()."""
+
+    def test_finds_rust_tests(self, indexer, temp_project):
+        """Test that #[test] functions are found and production code is not."""
+        tests_rs = os.path.join(temp_project, "tests.rs")
+        with open(tests_rs, "w") as f:
+            f.write("#[test]\nfn simple_test() {}\n\n#[ignore]\n#[test]\nfn slow_test() {}\n")
+        config = PyIndexerConfig(temp_project)
+
+        async def run():
+            return await indexer.index_files(config, None)
+
+        asyncio.run(run())
+        assert sorted(s.name for s in indexer.find_test_functions()) == ["simple_test", "slow_test"]
+        assert len(indexer.find_test_functions(tests_rs)) == 2
+        assert indexer.find_test_functions(os.path.join(temp_project, "lib.rs")) == []
+
+
+class TestSearchAll:
+    """Test PyIndexer.search_all() across languages."""
+
+    def test_rust_and_python_share_concept(self, indexer, temp_project):
+        """Test that a Rust type and its Python wrapper are both found."""
+        with open(os.path.join(temp_project, "config.rs"), "w") as f:
+            f.write("pub struct Config {}\n\nimpl Config {\n    pub fn new() -> Self { Config {} }\n}\n")
+        with open(os.path.join(temp_project, "config.py"), "w") as f:
+            f.write("class Config:\n    def load(self):\n        pass\n\ndef parse_config():\n    pass\n")
+        config = PyIndexerConfig(temp_project)
+
+        async def run():
+            return await indexer.index_files(config, None)
+
+        asyncio.run(run())
+
+        results = indexer.search_all("Config")
+        assert [symbol.name for symbol, _ in results[:2]] == ["Config", "Config"]
+        assert sorted(path.rsplit(".", 1)[1] for _, path in results[:2]) == ["py", "rs"]
+        assert results[-1][0].name == "parse_config"
+
+        rust_only = indexer.search_all("Config", ["rust"])
+        assert [symbol.name for symbol, _ in rust_only] == ["Config"]
+        assert rust_only[0][1].endswith("config.rs")
+
+        assert indexer.search_all("Config", ["typescript"]) == []
+
+
+class TestFindSymbolsInDirectory:
+    """Test PyIndexer.find_symbols_in_directory() prefix matching."""
+
+    def test_subtree_only(self, indexer, temp_project):
+        """Test that only symbols under the prefix are returned."""
+        os.makedirs(os.path.join(temp_project, "api", "v2"))
+        with open(os.path.join(temp_project, "api", "users.py"), "w") as f:
+            f.write("def get_user():\n    pass\n")
+        with open(os.path.join(temp_project, "api", "v2", "orders.py"), "w") as f:
+            f.write("def get_order():\n    pass\n")
+        with open(os.path.join(temp_project, "apiary.py"), "w") as f:
+            f.write("def bees():\n    pass\n")
+        config = PyIndexerConfig(temp_project)
+
+        async def run():
+            return await indexer.index_files(config, None)
+
+        asyncio.run(run())
+
+        api_dir = os.path.join(temp_project, "api")
+        names = sorted(symbol.name for symbol in indexer.find_symbols_in_directory(api_dir))
+        assert names == ["get_order", "get_user"]
+        assert indexer.find_symbols_in_directory(api_dir + "/") == indexer.find_symbols_in_directory(api_dir)
+        assert [s.name for s in indexer.find_symbols_in_directory(os.path.join(api_dir, "v2"))] == ["get_order"]
+        assert indexer.find_symbols_in_directory(os.path.join(temp_project, "missing")) == []
+
+
+class TestPackageEntryPoints:
+    """Test PyIndexer.find_package_init_files() and package metadata."""
+
+    def test_init_and_main_detected(self, indexer, temp_project):
+        """Test that __init__.py and __main__.py are flagged and dunder metadata is marked."""
+        package = os.path.join(temp_project, "pkg")
+        os.makedirs(package)
+        with open(os.path.join(package, "__init__.py"), "w") as f:
+            f.write('__version__ = "1.2.0"\n__author__ = "Jane"\nDEFAULT = 1\n')
+        with open(os.path.join(package, "__main__.py"), "w") as f:
+            f.write('__version__ = "0.0"\n')
+        config = PyIndexerConfig(temp_project)
+
+        async def run():
+            return await indexer.index_files(config, None)
+
+        asyncio.run(run())
+
+        entry_points = indexer.find_package_init_files()
+        assert [os.path.basename(f.path) for f in entry_points] == ["__init__.py", "__main__.py"]
+        assert json.loads(entry_points[0].metadata) == {"package_init": True}
+        assert json.loads(entry_points[1].metadata) == {"package_main": True}
+        assert all(f.metadata is None for f in indexer.list_files() if "pkg" not in f.path)
+
+        def is_package_metadata(symbol):
+            return bool(symbol.metadata) and json.loads(symbol.metadata).get("package_metadata") is True
+
+        # Only the __init__.py assignment is package metadata, not the one in __main__.py
+        versions = indexer.find_symbols("__version__")
+        assert len(versions) == 2
+        assert sum(is_package_metadata(s) for s in versions) == 1
+        assert is_package_metadata(indexer.find_symbols("__author__")[0])
+        assert indexer.find_symbols("DEFAULT")[0].metadata is None
+
+
+class TestLastIndexedDatetime:
+    """Test PyFileMetadata.last_indexed_datetime and last_indexed_timestamp."""
+
+    def test_datetime_and_timestamp(self, indexer, temp_project):
+        """Test that last_indexed converts to an aware datetime and epoch seconds."""
+        before = datetime.now(timezone.utc).replace(microsecond=0)
+        config = PyIndexerConfig(temp_project)
+
+        async def run():
+            return await indexer.index_files(config, None)
+
+        asyncio.run(run())
+
+        files = indexer.list_files()
+        assert len(files) == 2
+        for metadata in files:
+            indexed = metadata.last_indexed_datetime
+            assert isinstance(indexed, datetime)
+            assert indexed.tzinfo is not None
+            assert indexed.year == before.year
+            assert before <= indexed <= datetime.now(timezone.utc)
+            assert metadata.last_indexed_timestamp == int(indexed.timestamp())
+
+    def test_compare_metadata_timestamps(self, indexer, temp_project):
+        """Test that a file indexed later compares as newer."""
+        later_project = tempfile.mkdtemp()
+        with open(os.path.join(later_project, "later.py"), "w") as f:
+            f.write("def later():\n    pass\n")
+
+        async def run(root):
+            return await indexer.index_files(PyIndexerConfig(root), None)
+
+        try:
+            asyncio.run(run(temp_project))
+            # CURRENT_TIMESTAMP has one-second resolution
+            time.sleep(1.1)
+            asyncio.run(run(later_project))
+
+            stored = {os.path.basename(f.path): f for f in indexer.list_files()}
+            assert stored["main.py"].last_indexed_datetime < stored["later.py"].last_indexed_datetime
+            assert stored["main.py"].last_indexed_timestamp < stored["later.py"].last_indexed_timestamp
+        finally:
+            shutil.rmtree(later_project)
+
+
+class TestDatabaseSize:
+    """Test database size monitoring."""
+
+    def test_sizes_positive_and_grow(self, indexer, temp_project):
+        """Test that sizes are positive and increase after indexing."""
+        initial = indexer.get_database_size_bytes()
+        assert initial > 0
+
+        with open(os.path.join(temp_project, "many.py"), "w") as f:
+            for i in range(500):
+                f.write(f"def function_number_{i}():\n    pass\n\n")
+        config = PyIndexerConfig(temp_project)
+
+        async def run():
+            return await indexer.index_files(config, None)
+
+        asyncio.run(run())
+
+        assert indexer.get_database_size_bytes() > initial
+        sizes = indexer.get_table_sizes()
+        assert sizes["symbols"] > 0
+        assert all(isinstance(size, int) for size in sizes.values())
+
+
+class TestSlowestFiles:
+    """Test per-file indexing durations."""
+
+    def test_duration_recorded(self, indexer, temp_project):
+        """Test that durations are captured and the largest file is slowest."""
+        with open(os.path.join(temp_project, "big.py"), "w") as f:
+            for i in range(3000):
+                f.write(f"def function_number_{i}(value):\n    return value + {i}\n\n")
+        config = PyIndexerConfig(temp_project)
+
+        async def run():
+            return await indexer.index_files(config, None)
+
+        files = asyncio.run(run())
+        assert all(f.index_duration_ms is not None for f in files)
+
+        slowest = indexer.get_slowest_files(1)
+        assert len(slowest) == 1
+        file, duration_ms = slowest[0]
+        assert file.path.endswith("big.py")
+        assert duration_ms > 0
+        assert file.index_duration_ms == duration_ms
+        assert len(indexer.get_slowest_files(10)) == 3
+
+
+class TestPythonVersion:
+    """Test PyIndexerConfig.python_version."""
+
+    def test_python2_print_flagged_for_python3(self, indexer, temp_project):
+        """Test that a Python 2 print statement is flagged when targeting Python 3."""
+        with open(os.path.join(temp_project, "legacy.py"), "w") as f:
+            f.write('def greet(name):\n    print "Hello, %s" % name\n')
+        config = PyIndexerConfig(temp_project)
+        assert config.python_version is None
+        config.python_version = (3, 12)
+
+        async def run():
+            return await indexer.index_files(config, None)
+
+        asyncio.run(run())
+
+        greet = indexer.find_symbols("greet")[0]
+        metadata = json.loads(greet.metadata)
+        assert metadata["python2"] is True
+        assert metadata["version_mismatch"] is True
+
+
+class TestMetadataMaintenance:
+    """Test PyIndexer.compact_metadata() and validate_metadata()."""
+
+    def test_compact_and_validate(self, indexer, temp_project):
+        """Test that indexed metadata is valid and compaction is idempotent."""
+        with open(os.path.join(temp_project, "tests.rs"), "w") as f:
+            f.write("#[test]\nfn check() {}\n\nasync fn fetch() {}\n")
+        config = PyIndexerConfig(temp_project)
+
+        async def run():
+            return await indexer.index_files(config, None)
+
+        asyncio.run(run())
+
+        assert indexer.validate_metadata() == []
+        saved = indexer.compact_metadata()
+        assert saved >= 0
+        assert indexer.compact_metadata() == 0
+        assert json.loads(indexer.find_symbols("check")[0].metadata)["test"] is True
@@ -0,0 +1,55 @@
+//! C code analyzer using Tree-sitter
+//!
+//! Provides parsing and symbol extraction for C source and header files.
+
+pub mod parser;
+pub mod symbol_extract;
+
+pub use parser::{CParser, CParserPool};
+pub use symbol_extract::extract_symbols;
+
+use analyzer_core::{IndexingError, ParseError, Symbol};
+
+/// Analyze a C source file and extract symbols
+///
+/// Syntax errors are reported as parse errors; symbols outside the broken
+/// regions are still extracted.
+pub fn analyze_c(source: &str) -> Result<(Vec<Symbol>, Vec<ParseError>), IndexingError> {
+    let mut parser = CParser::new().map_err(|e| IndexingError::parse_failed("c", e))?;
+    let tree = parser
+        .parse(source)
+        .map_err(|e| IndexingError::parse_failed("c", e))?;
+    let (symbols, mut errors) = extract_symbols(&tree, source, None).map_err(IndexingError::extraction_failed)?;
+    errors.splice(0..0, ParseError::from_tree(&tree, source));
+    Ok((symbols, errors))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use analyzer_core::SymbolKind;
+
+    #[test]
+    fn test_analyze_simple_function() {
+        let source = r#"
+#include <stdio.h>
+
+int main(void) {
+    printf("Hello, world!\n");
+    return 0;
+}
+"#;
+        let (symbols, errors) = analyze_c(source).unwrap();
+        assert!(symbols.iter().any(|s| s.name == "main" && matches!(s.kind, SymbolKind::Function)));
+        assert!(symbols.iter().any(|s| s.name == "stdio.h" && matches!(s.kind, SymbolKind::Import)));
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_analyze_reports_parse_errors() {
+        let source = "int broken(int a {\n    return a;\n\nstruct after { int x; };\n";
+        let (symbols, errors) = analyze_c(source).unwrap();
+        assert!(!errors.is_empty());
+        assert!(symbols.iter().any(|s| s.name == "after" && matches!(s.kind, SymbolKind::Struct)));
+    }
+}
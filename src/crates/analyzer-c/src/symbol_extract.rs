@@ -0,0 +1,405 @@
+//! C symbol extraction
+//!
+//! Extracts functions, prototypes, structs, unions, enums, global variables, and includes from C AST.
+
+use analyzer_core::{ExtractConfig, ParseError, Symbol, SymbolKind, SymbolLocation};
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+use tree_sitter::{Node, Tree, TreeCursor};
+
+/// Extract symbols from a C parse tree
+///
+/// Subtrees nested deeper than `config.max_depth` (default
+/// [`ExtractConfig::default`]) are skipped and reported as parse errors.
+pub fn extract_symbols(
+    tree: &Tree,
+    source: &str,
+    config: Option<ExtractConfig>,
+) -> Result<(Vec<Symbol>, Vec<ParseError>)> {
+    let config = config.unwrap_or_default();
+    let mut symbols = Vec::new();
+    let mut errors = Vec::new();
+    let root = tree.root_node();
+    let mut cursor = root.walk();
+
+    extract_from_node(&mut cursor, source, &mut symbols, None, 0, &config, &mut errors)?;
+
+    Ok((symbols, errors))
+}
+
+/// Recursively extract file-level symbols from a node
+///
+/// Types nested in a struct or union are scoped to it. Function bodies are
+/// not entered, so local types and variables are skipped. Nodes without a
+/// name (anonymous structs, declarators broken by syntax errors) produce no
+/// symbol, but their children are still searched.
+fn extract_from_node(
+    cursor: &mut TreeCursor,
+    source: &str,
+    symbols: &mut Vec<Symbol>,
+    parent_scope: Option<String>,
+    depth: usize,
+    config: &ExtractConfig,
+    errors: &mut Vec<ParseError>,
+) -> Result<()> {
+    let node = cursor.node();
+    if depth >= config.max_depth {
+        // Leaf tokens cannot hold symbols, so only skipped subtrees are reported
+        if node.named_child_count() > 0 {
+            errors.push(ParseError::nesting_too_deep(&node, config.max_depth));
+        }
+        return Ok(());
+    }
+
+    match node.kind() {
+        "function_definition" => {
+            if let Some(symbol) = extract_function(node, source)? {
+                symbols.push(symbol);
+            }
+            return Ok(());
+        }
+        "declaration" => {
+            symbols.extend(extract_declaration(node, source)?);
+            // Initializers hold no file-level symbols, but the type may define a struct
+            return recurse_into_type(cursor, source, symbols, parent_scope, depth, config, errors);
+        }
+        "struct_specifier" | "union_specifier" | "enum_specifier" => {
+            if let Some(symbol) = extract_type(node, source, parent_scope.as_deref())? {
+                let type_scope = Some(symbol.name.clone());
+                symbols.push(symbol);
+                recurse(cursor, source, symbols, type_scope, depth, config, errors)?;
+                return Ok(());
+            }
+        }
+        "enumerator" => {
+            if parent_scope.is_some() {
+                symbols.push(Symbol::try_from((node, source, symbol_kind(node)?, parent_scope.as_deref()))?);
+            }
+            return Ok(());
+        }
+        "preproc_include" => {
+            if let Some(symbol) = extract_include(node, source)? {
+                symbols.push(symbol);
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    recurse(cursor, source, symbols, parent_scope, depth, config, errors)
+}
+
+/// Extract symbols from every child of the cursor's node
+fn recurse(
+    cursor: &mut TreeCursor,
+    source: &str,
+    symbols: &mut Vec<Symbol>,
+    parent_scope: Option<String>,
+    depth: usize,
+    config: &ExtractConfig,
+    errors: &mut Vec<ParseError>,
+) -> Result<()> {
+    if cursor.goto_first_child() {
+        loop {
+            extract_from_node(cursor, source, symbols, parent_scope.clone(), depth + 1, config, errors)?;
+            if !cursor.goto_next_sibling() {
+                break;
+            }
+        }
+        cursor.goto_parent();
+    }
+    Ok(())
+}
+
+/// Extract symbols from the `type` child of the cursor's node
+fn recurse_into_type(
+    cursor: &mut TreeCursor,
+    source: &str,
+    symbols: &mut Vec<Symbol>,
+    parent_scope: Option<String>,
+    depth: usize,
+    config: &ExtractConfig,
+    errors: &mut Vec<ParseError>,
+) -> Result<()> {
+    if cursor.goto_first_child() {
+        loop {
+            if cursor.field_name() == Some("type") {
+                extract_from_node(cursor, source, symbols, parent_scope.clone(), depth + 1, config, errors)?;
+            }
+            if !cursor.goto_next_sibling() {
+                break;
+            }
+        }
+        cursor.goto_parent();
+    }
+    Ok(())
+}
+
+/// Extract a function definition, named after its declarator
+///
+/// Storage classes such as `static` are stored as `metadata["storage"]`.
+fn extract_function(node: Node, source: &str) -> Result<Option<Symbol>> {
+    let Some((name, _)) = node.child_by_field_name("declarator").and_then(declarator_name) else {
+        return Ok(None);
+    };
+
+    let location = SymbolLocation::from_node(&node);
+    let mut symbol = Symbol::with_location(node_text(name, source), symbol_kind(node)?, location, None);
+    symbol.metadata = storage_metadata(node, source).map(|m| Value::Object(m).to_string());
+    Ok(Some(symbol))
+}
+
+/// Extract every name declared at file level by a declaration (`int a, *b;`)
+///
+/// Function prototypes are `Function`s with `metadata["prototype"] = true`;
+/// everything else is a `Variable`. Storage classes such as `extern` are
+/// stored as `metadata["storage"]`.
+fn extract_declaration(node: Node, source: &str) -> Result<Vec<Symbol>> {
+    let storage = storage_metadata(node, source);
+
+    let mut cursor = node.walk();
+    let mut symbols = Vec::new();
+    for declarator in node.children_by_field_name("declarator", &mut cursor) {
+        let Some((name, is_function)) = declarator_name(declarator) else {
+            continue;
+        };
+
+        let mut metadata = storage.clone().unwrap_or_default();
+        let kind = if is_function {
+            metadata.insert("prototype".to_string(), true.into());
+            SymbolKind::Function
+        } else {
+            symbol_kind(node)?
+        };
+
+        let mut symbol = Symbol::with_location(node_text(name, source), kind, SymbolLocation::from_node(&node), None);
+        symbol.metadata = (!metadata.is_empty()).then(|| Value::Object(metadata).to_string());
+        symbols.push(symbol);
+    }
+    Ok(symbols)
+}
+
+/// Extract a struct, union, or enum that has a name and a body
+///
+/// Bare references such as `struct point p;` define nothing. Unions are
+/// `Struct`s with `metadata["union"] = true`.
+fn extract_type(node: Node, source: &str, scope: Option<&str>) -> Result<Option<Symbol>> {
+    if node.child_by_field_name("name").is_none() || node.child_by_field_name("body").is_none() {
+        return Ok(None);
+    }
+
+    let mut symbol = Symbol::try_from((node, source, symbol_kind(node)?, scope))?;
+    if node.kind() == "union_specifier" {
+        symbol.metadata = Some(json!({ "union": true }).to_string());
+    }
+    Ok(Some(symbol))
+}
+
+/// Extract an `#include` as a symbol named after the included path
+///
+/// `<...>` includes get `metadata["system"] = true`.
+fn extract_include(node: Node, source: &str) -> Result<Option<Symbol>> {
+    let Some(path) = node.child_by_field_name("path") else {
+        return Ok(None);
+    };
+
+    let name = node_text(path, source).trim_matches(['"', '<', '>']).to_string();
+    let mut symbol = Symbol::with_location(name, symbol_kind(node)?, SymbolLocation::from_node(&node), None);
+    if path.kind() == "system_lib_string" {
+        symbol.metadata = Some(json!({ "system": true }).to_string());
+    }
+    Ok(Some(symbol))
+}
+
+/// Follow a declarator (`*name`, `name[4]`, `name(void)`, `name = 1`) down to
+/// the declared identifier
+///
+/// Also returns whether the identifier is declared as a function, i.e. sits
+/// directly in a `function_declarator`; `int (*fp)(int)` declares a pointer.
+fn declarator_name(declarator: Node) -> Option<(Node, bool)> {
+    let mut node = declarator;
+    let mut is_function = false;
+    loop {
+        match node.kind() {
+            "identifier" | "field_identifier" | "type_identifier" => return Some((node, is_function)),
+            _ => {
+                is_function = node.kind() == "function_declarator";
+                node = node.child_by_field_name("declarator").or_else(|| node.named_child(0))?;
+            }
+        }
+    }
+}
+
+/// Storage class specifiers of a declaration, e.g. `{"storage": "static"}`
+fn storage_metadata(node: Node, source: &str) -> Option<Map<String, Value>> {
+    let mut cursor = node.walk();
+    let storage: Vec<String> = node
+        .children(&mut cursor)
+        .filter(|child| child.kind() == "storage_class_specifier")
+        .map(|child| node_text(child, source))
+        .collect();
+    if storage.is_empty() {
+        return None;
+    }
+
+    let mut metadata = Map::new();
+    metadata.insert("storage".to_string(), json!(storage.join(" ")));
+    Some(metadata)
+}
+
+/// Look up the symbol kind for a node in the shared mapping table
+fn symbol_kind(node: Node) -> Result<SymbolKind> {
+    SymbolKind::from_file_node_kind("c", node.kind())
+        .ok_or_else(|| anyhow::anyhow!("Node kind `{}` does not define a symbol", node.kind()))
+}
+
+/// Get text content of a node
+fn node_text(node: Node, source: &str) -> String {
+    source[node.byte_range()].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::CParser;
+
+    fn extract(source: &str) -> Vec<Symbol> {
+        let mut parser = CParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        extract_symbols(&tree, source, None).unwrap().0
+    }
+
+    fn metadata(symbol: &Symbol) -> Option<Value> {
+        symbol.metadata.as_deref().map(|m| serde_json::from_str(m).unwrap())
+    }
+
+    #[test]
+    fn test_extract_functions() {
+        let source = r#"
+static int add(int a, int b) {
+    int local = a + b;
+    return local;
+}
+
+char *dup(const char *s);
+int (*handler)(int);
+"#;
+        let symbols = extract(source);
+
+        let names: Vec<(&str, SymbolKind)> = symbols.iter().map(|s| (s.name.as_str(), s.kind)).collect();
+        assert_eq!(
+            names,
+            [
+                ("add", SymbolKind::Function),
+                ("dup", SymbolKind::Function),
+                ("handler", SymbolKind::Variable),
+            ]
+        );
+        assert_eq!(symbols[0].line_start, 1);
+        assert_eq!(metadata(&symbols[0]), Some(json!({"storage": "static"})));
+        assert_eq!(metadata(&symbols[1]), Some(json!({"prototype": true})));
+        assert_eq!(metadata(&symbols[2]), None);
+    }
+
+    #[test]
+    fn test_extract_types() {
+        let source = r#"
+struct point {
+    int x, y;
+    struct inner { int z; } in;
+};
+
+union value { int i; float f; };
+
+enum color { RED, GREEN = 2 };
+
+typedef struct { int w; } anon_t;
+struct point origin;
+"#;
+        let symbols = extract(source);
+
+        let names: Vec<(&str, SymbolKind, Option<&str>)> = symbols
+            .iter()
+            .map(|s| (s.name.as_str(), s.kind, s.scope.as_deref()))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                ("point", SymbolKind::Struct, None),
+                ("inner", SymbolKind::Struct, Some("point")),
+                ("value", SymbolKind::Struct, None),
+                ("color", SymbolKind::Enum, None),
+                ("RED", SymbolKind::EnumVariant, Some("color")),
+                ("GREEN", SymbolKind::EnumVariant, Some("color")),
+                ("origin", SymbolKind::Variable, None),
+            ]
+        );
+        assert_eq!(metadata(&symbols[2]), Some(json!({"union": true})));
+    }
+
+    #[test]
+    fn test_extract_globals_and_includes() {
+        let source = r#"
+#include <stdio.h>
+#include "config.h"
+
+extern int counter, *cursor;
+static const char names[4][8] = {0};
+
+#ifdef DEBUG
+int debug_level = 1;
+#endif
+"#;
+        let symbols = extract(source);
+
+        let names: Vec<(&str, SymbolKind)> = symbols.iter().map(|s| (s.name.as_str(), s.kind)).collect();
+        assert_eq!(
+            names,
+            [
+                ("stdio.h", SymbolKind::Import),
+                ("config.h", SymbolKind::Import),
+                ("counter", SymbolKind::Variable),
+                ("cursor", SymbolKind::Variable),
+                ("names", SymbolKind::Variable),
+                ("debug_level", SymbolKind::Variable),
+            ]
+        );
+        assert_eq!(metadata(&symbols[0]), Some(json!({"system": true})));
+        assert_eq!(metadata(&symbols[1]), None);
+        assert_eq!(metadata(&symbols[2]), Some(json!({"storage": "extern"})));
+        assert_eq!(metadata(&symbols[4]), Some(json!({"storage": "static"})));
+    }
+
+    #[test]
+    fn test_extract_with_syntax_errors() {
+        let source = r#"
+int ok(void) { return 0; }
+
+int broken(int a {
+    return a;
+
+struct after { int x; };
+"#;
+        let symbols = extract(source);
+
+        assert!(symbols.iter().any(|s| s.name == "ok"));
+        assert!(symbols.iter().any(|s| s.name == "after" && s.kind == SymbolKind::Struct));
+    }
+
+    #[test]
+    fn test_max_depth_skips_deep_nesting() {
+        let source = "struct a { struct b { struct c { int x; } y; } z; };\nint top;\n";
+        let mut parser = CParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+
+        let (symbols, errors) = extract_symbols(&tree, source, None).unwrap();
+        assert!(symbols.iter().any(|s| s.name == "c"));
+        assert!(errors.is_empty());
+
+        let (symbols, errors) = extract_symbols(&tree, source, Some(ExtractConfig { max_depth: 6 })).unwrap();
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["a", "b", "top"]);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line, 0);
+    }
+}
@@ -1,20 +1,30 @@
 // Parsing performance benchmarks using Criterion
-// Measures parsing speed for Python, TypeScript, and Rust files
+// Measures parsing speed for Python, TypeScript, Rust, and Java files,
+// and the cost of storing the extracted symbols
 // Run with: cargo bench --bench parsing
 
-use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion, BenchmarkId};
 use std::fs;
-use tempfile::TempDir;
+use tempfile::{NamedTempFile, TempDir};
 
+use analyzer_python::analyze_python;
 use analyzer_python::parser::PythonParser;
 use analyzer_typescript::parser::TypeScriptParser;
-use analyzer_rust::parser::RustParser;
+use analyzer_rust::parser::{RustParser, RustParserPool};
+use analyzer_java::parser::JavaParser;
 use analyzer_core::indexer::{
     IndexerConfig,
     discover_files,
     index_files_with_progress,
     index_files_with_progress_parallel,
 };
+use analyzer_core::storage::{
+    delete_file_symbols,
+    get_or_create_file,
+    init_schema,
+    insert_symbol,
+    insert_symbols_batch,
+};
 
 // Sample Python code for benchmarking
 const PYTHON_CODE: &str = r#"
@@ -238,6 +248,77 @@ impl UserRepository {
 }
 "#;
 
+// Sample Java code for benchmarking
+const JAVA_CODE: &str = r#"
+package com.example.users;
+
+import java.util.HashMap;
+import java.util.Map;
+import java.util.Optional;
+
+public class UserRepository {
+    private final Map<Long, User> users = new HashMap<>();
+    private long nextId = 1;
+
+    public static class User {
+        private final long id;
+        private String name;
+        private String email;
+
+        public User(long id, String name, String email) {
+            this.id = id;
+            this.name = name;
+            this.email = email;
+        }
+
+        public long getId() { return id; }
+        public String getName() { return name; }
+        public String getEmail() { return email; }
+    }
+
+    public static class UserException extends RuntimeException {
+        public UserException(String message) {
+            super(message);
+        }
+    }
+
+    public User create(String name, String email) {
+        if (name == null || name.isEmpty()) {
+            throw new UserException("Name cannot be empty");
+        }
+        if (email == null || !email.contains("@")) {
+            throw new UserException("Invalid email address");
+        }
+
+        User user = new User(nextId, name, email);
+        users.put(nextId, user);
+        nextId++;
+        return user;
+    }
+
+    public Optional<User> findById(long id) {
+        return Optional.ofNullable(users.get(id));
+    }
+
+    public User update(long id, String name, String email) {
+        User user = findById(id).orElseThrow(() -> new UserException("User not found"));
+        if (name != null && !name.isEmpty()) {
+            user.name = name;
+        }
+        if (email != null && email.contains("@")) {
+            user.email = email;
+        }
+        return user;
+    }
+
+    public void delete(long id) {
+        if (users.remove(id) == null) {
+            throw new UserException("User not found");
+        }
+    }
+}
+"#;
+
 /// Benchmark Python parser
 fn bench_python_parsing(c: &mut Criterion) {
     let mut parser = PythonParser::new().expect("Failed to create Python parser");
@@ -274,25 +355,71 @@ fn bench_rust_parsing(c: &mut Criterion) {
     });
 }
 
+/// Benchmark Java parser
+fn bench_java_parsing(c: &mut Criterion) {
+    let mut parser = JavaParser::new().expect("Failed to create Java parser");
+
+    c.bench_function("parse_java_file", |b| {
+        b.iter(|| {
+            let tree = parser.parse(black_box(JAVA_CODE)).expect("Parse failed");
+            black_box(tree);
+        })
+    });
+}
+
+/// Benchmark a fresh parser per file vs a pooled parser
+fn bench_parser_pool(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rust_parser_reuse");
+
+    group.bench_function("new_parser_per_file", |b| {
+        b.iter(|| {
+            let mut parser = RustParser::new().expect("Failed to create Rust parser");
+            let tree = parser.parse(black_box(RUST_CODE)).expect("Parse failed");
+            black_box(tree);
+        })
+    });
+
+    let pool = RustParserPool::new(4);
+    group.bench_function("pooled_parser", |b| {
+        b.iter(|| {
+            let tree = pool.acquire().parse(black_box(RUST_CODE)).expect("Parse failed");
+            black_box(tree);
+        })
+    });
+
+    group.finish();
+}
+
 /// Benchmark sequential vs parallel indexing
 fn bench_indexing_modes(c: &mut Criterion) {
-    let temp_dir = TempDir::new().expect("Failed to create temp dir");
-    let temp_path = temp_dir.path();
+    // Split the test files across two roots of a multi-root workspace
+    let roots = [
+        TempDir::new().expect("Failed to create temp dir"),
+        TempDir::new().expect("Failed to create temp dir"),
+    ];
 
     // Create test files
     for i in 0..50 {
         fs::write(
-            temp_path.join(format!("test{}.py", i)),
+            roots[i % 2].path().join(format!("test{}.py", i)),
             PYTHON_CODE
         ).expect("Failed to write test file");
     }
 
     let config = IndexerConfig {
-        root_dir: temp_path.to_path_buf(),
+        root_dirs: roots.iter().map(|root| root.path().to_path_buf()).collect(),
         ..Default::default()
     };
 
     let files = discover_files(&config).expect("Failed to discover files");
+    assert_eq!(files.len(), 50);
+    for root in &roots {
+        assert!(
+            files.iter().any(|f| f.starts_with(root.path())),
+            "no files discovered under {}",
+            root.path().display()
+        );
+    }
 
     let mut group = c.benchmark_group("indexing_modes");
 
@@ -323,11 +450,58 @@ fn bench_indexing_modes(c: &mut Criterion) {
     group.finish();
 }
 
+/// Benchmark storing a 500-function file one symbol at a time vs in one batch
+fn bench_symbol_insertion(c: &mut Criterion) {
+    let source: String = (0..500)
+        .map(|i| format!("def function_{i}(x):\n    return x + {i}\n\n"))
+        .collect();
+    let (symbols, _) = analyze_python(&source).expect("Analysis failed");
+
+    let temp_file = NamedTempFile::new().expect("Failed to create temp file");
+    let conn = init_schema(temp_file.path()).expect("Failed to init schema");
+    let (file_id, _) = get_or_create_file(&conn, "large.py", "python", source.len() as u64)
+        .expect("Failed to create file");
+    let symbols: Vec<_> = symbols
+        .into_iter()
+        .map(|symbol| analyzer_core::Symbol { file_id, ..symbol })
+        .collect();
+    let clear = || {
+        delete_file_symbols(&conn, file_id).expect("Failed to clear symbols");
+    };
+
+    let mut group = c.benchmark_group("symbol_insertion");
+
+    group.bench_function(BenchmarkId::new("per_symbol", symbols.len()), |b| {
+        b.iter_batched(
+            clear,
+            |()| {
+                for symbol in &symbols {
+                    insert_symbol(&conn, black_box(symbol)).expect("Insert failed");
+                }
+            },
+            BatchSize::PerIteration,
+        )
+    });
+
+    group.bench_function(BenchmarkId::new("batch", symbols.len()), |b| {
+        b.iter_batched(
+            clear,
+            |()| insert_symbols_batch(&conn, black_box(&symbols)).expect("Insert failed"),
+            BatchSize::PerIteration,
+        )
+    });
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_python_parsing,
     bench_typescript_parsing,
     bench_rust_parsing,
-    bench_indexing_modes
+    bench_java_parsing,
+    bench_parser_pool,
+    bench_indexing_modes,
+    bench_symbol_insertion
 );
 criterion_main!(benches);
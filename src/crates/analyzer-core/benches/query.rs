@@ -0,0 +1,74 @@
+// Query performance benchmarks using Criterion
+// Compares REGEXP name matching against an equivalent LIKE query
+// Run with: cargo bench --bench query
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rusqlite::Connection;
+use tempfile::NamedTempFile;
+
+use analyzer_core::query::find_symbols_by_name_regex;
+use analyzer_core::storage::{get_or_create_file, init_schema, insert_symbols_batch};
+use analyzer_core::{Symbol, SymbolKind};
+
+const SYMBOL_COUNT: usize = 10_000;
+
+/// Create a database with `SYMBOL_COUNT` symbols spread over 100 files
+fn populated_db() -> (NamedTempFile, Connection) {
+    let temp_file = NamedTempFile::new().expect("Failed to create temp file");
+    let conn = init_schema(temp_file.path()).expect("Failed to init schema");
+
+    for file in 0..100 {
+        let (file_id, _) = get_or_create_file(&conn, &format!("file{}.py", file), "python", 100)
+            .expect("Failed to create file");
+        let symbols: Vec<Symbol> = (0..SYMBOL_COUNT / 100)
+            .map(|i| Symbol {
+                id: None,
+                file_id,
+                name: if i % 10 == 0 {
+                    format!("Handler{}", i)
+                } else {
+                    format!("handle_event_{}", i)
+                },
+                kind: SymbolKind::Function,
+                line_start: i,
+                line_end: i,
+                column_start: 0,
+                column_end: 0,
+                scope: None,
+                metadata: None,
+            })
+            .collect();
+        insert_symbols_batch(&conn, &symbols).expect("Failed to insert symbols");
+    }
+
+    (temp_file, conn)
+}
+
+/// Benchmark prefix matching with REGEXP vs. LIKE
+fn bench_name_matching(c: &mut Criterion) {
+    let (_temp_file, conn) = populated_db();
+    let mut group = c.benchmark_group("name_prefix_match");
+
+    group.bench_function("regexp", |b| {
+        b.iter(|| {
+            find_symbols_by_name_regex(&conn, black_box("^Handler"), SYMBOL_COUNT)
+                .expect("Regex query failed")
+        })
+    });
+
+    group.bench_function("like", |b| {
+        let mut stmt = conn
+            .prepare("SELECT id FROM symbols WHERE name LIKE ?1 LIMIT ?2")
+            .expect("Failed to prepare query");
+        b.iter(|| {
+            stmt.query_map((black_box("Handler%"), SYMBOL_COUNT as i64), |row| row.get::<_, i64>(0))
+                .expect("Like query failed")
+                .count()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_name_matching);
+criterion_main!(benches);
@@ -0,0 +1,94 @@
+// Storage performance benchmarks using Criterion
+// Compares per-file vs bulk deletion of indexed files and their symbols
+// Run with: cargo bench --bench storage
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use rusqlite::Connection;
+use tempfile::NamedTempFile;
+
+use analyzer_core::storage::{
+    bulk_delete_files, bulk_delete_symbols_by_file_ids, delete_file_symbols, get_or_create_file,
+    init_schema, insert_symbol,
+};
+use analyzer_core::{Symbol, SymbolKind};
+
+const FILE_COUNT: usize = 1000;
+
+/// Create a database with `FILE_COUNT` files holding a few symbols each
+fn populated_db() -> (NamedTempFile, Connection, Vec<i64>) {
+    let temp_file = NamedTempFile::new().expect("Failed to create temp file");
+    let conn = init_schema(temp_file.path()).expect("Failed to init schema");
+
+    let mut file_ids = Vec::with_capacity(FILE_COUNT);
+    conn.execute_batch("BEGIN").unwrap();
+    for i in 0..FILE_COUNT {
+        let (file_id, _) = get_or_create_file(&conn, &format!("file{}.py", i), "python", 100)
+            .expect("Failed to create file");
+        for line in 0..5 {
+            let symbol = Symbol {
+                id: None,
+                file_id,
+                name: format!("symbol_{}", line),
+                kind: SymbolKind::Function,
+                line_start: line,
+                line_end: line,
+                column_start: 0,
+                column_end: 0,
+                scope: None,
+                metadata: None,
+            };
+            insert_symbol(&conn, &symbol).expect("Failed to insert symbol");
+        }
+        file_ids.push(file_id);
+    }
+    conn.execute_batch("COMMIT").unwrap();
+
+    (temp_file, conn, file_ids)
+}
+
+/// Benchmark deleting files one at a time vs. a single bulk delete
+fn bench_delete_files(c: &mut Criterion) {
+    let mut group = c.benchmark_group("delete_files");
+    group.sample_size(10);
+
+    group.bench_function(BenchmarkId::new("single", FILE_COUNT), |b| {
+        b.iter_batched(
+            populated_db,
+            |(_temp_file, conn, file_ids)| {
+                for file_id in file_ids {
+                    delete_file_symbols(&conn, file_id).expect("Delete failed");
+                    conn.execute("DELETE FROM files WHERE id = ?1", [file_id])
+                        .expect("Delete failed");
+                }
+            },
+            BatchSize::PerIteration,
+        )
+    });
+
+    group.bench_function(BenchmarkId::new("bulk", FILE_COUNT), |b| {
+        b.iter_batched(
+            populated_db,
+            |(_temp_file, conn, file_ids)| {
+                bulk_delete_files(&conn, black_box(&file_ids)).expect("Bulk delete failed");
+            },
+            BatchSize::PerIteration,
+        )
+    });
+
+    // Symbols only, e.g. before re-inserting them for every file
+    group.bench_function(BenchmarkId::new("bulk_symbols", FILE_COUNT), |b| {
+        b.iter_batched(
+            populated_db,
+            |(_temp_file, conn, file_ids)| {
+                bulk_delete_symbols_by_file_ids(&conn, black_box(&file_ids))
+                    .expect("Bulk symbol delete failed");
+            },
+            BatchSize::PerIteration,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_delete_files);
+criterion_main!(benches);
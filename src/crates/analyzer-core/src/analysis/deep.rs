@@ -1,27 +1,42 @@
-/// Deep Mode: Advanced code analysis for enterprise use cases.
-///
-/// This module provides enhanced analysis capabilities that require
-/// additional computational resources and are typically used in
-/// enterprise environments with compliance requirements.
-///
-/// Features include:
-/// - Type inference across compilation boundaries
-/// - Cross-project dependency resolution
-/// - Advanced semantic analysis
-/// - Audit trail generation for compliance
-///
-/// **Note**: This is an enterprise feature and requires explicit
-/// enabling via the `deep-mode` Cargo feature flag.
+//! Deep Mode: Advanced code analysis for enterprise use cases.
+//!
+//! This module provides enhanced analysis capabilities that require
+//! additional computational resources and are typically used in
+//! enterprise environments with compliance requirements.
+//!
+//! Features include:
+//! - Type inference across compilation boundaries
+//! - Cross-project dependency resolution
+//! - Advanced semantic analysis
+//! - Audit trail generation for compliance
+//!
+//! **Note**: This is an enterprise feature and requires explicit
+//! enabling via the `deep-mode` Cargo feature flag.
+
+#[cfg(feature = "deep-mode")]
+use std::collections::{HashMap, HashSet};
+
+#[cfg(feature = "deep-mode")]
+use std::path::PathBuf;
 
 #[cfg(feature = "deep-mode")]
-use std::collections::HashMap;
+use anyhow::{Context, Result};
 
 #[cfg(feature = "deep-mode")]
-use anyhow::Result;
+use regex::Regex;
+
+#[cfg(feature = "deep-mode")]
+use rusqlite::{params, Connection};
 
 #[cfg(feature = "deep-mode")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "deep-mode")]
+use crate::scoring::compute_symbol_importance;
+
+#[cfg(feature = "deep-mode")]
+use crate::{Symbol, SymbolKind};
+
 /// Audit event types for Deep Mode compliance tracking.
 #[cfg(feature = "deep-mode")]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,6 +65,170 @@ pub enum AuditEvent {
     },
 }
 
+#[cfg(feature = "deep-mode")]
+impl AuditEvent {
+    /// `audit_events.event_kind` value for this event
+    fn kind(&self) -> &'static str {
+        match self {
+            AuditEvent::AnalysisStarted { .. } => "analysis_started",
+            AuditEvent::AnalysisCompleted { .. } => "analysis_completed",
+            AuditEvent::AnalysisFailed { .. } => "analysis_failed",
+            AuditEvent::DeepModeAccessed { .. } => "deep_mode_accessed",
+        }
+    }
+
+    /// The analyzed file or project, or the accessed feature
+    fn source(&self) -> &str {
+        match self {
+            AuditEvent::AnalysisStarted { source, .. }
+            | AuditEvent::AnalysisCompleted { source, .. }
+            | AuditEvent::AnalysisFailed { source, .. } => source,
+            AuditEvent::DeepModeAccessed { feature, .. } => feature,
+        }
+    }
+
+    /// Seconds since the Unix epoch
+    fn timestamp(&self) -> u64 {
+        match self {
+            AuditEvent::AnalysisStarted { timestamp, .. }
+            | AuditEvent::AnalysisCompleted { timestamp, .. }
+            | AuditEvent::AnalysisFailed { timestamp, .. }
+            | AuditEvent::DeepModeAccessed { timestamp, .. } => *timestamp,
+        }
+    }
+}
+
+/// Output format of a compliance report.
+#[cfg(feature = "deep-mode")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Json,
+    Csv,
+    Markdown,
+}
+
+#[cfg(feature = "deep-mode")]
+impl std::str::FromStr for ReportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(ReportFormat::Json),
+            "csv" => Ok(ReportFormat::Csv),
+            "markdown" | "md" => Ok(ReportFormat::Markdown),
+            _ => anyhow::bail!("Unknown report format: {}", s),
+        }
+    }
+}
+
+/// One audited analysis or indexing event in a compliance report.
+#[cfg(feature = "deep-mode")]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReportEvent {
+    /// `YYYY-MM-DD HH:MM:SS` (UTC)
+    pub timestamp: String,
+    /// Audit event kind or change log event kind (`create`, `modify`, ...)
+    pub kind: String,
+    pub source: String,
+    /// Symbols found by an analysis, or added by a change
+    pub symbols: usize,
+    pub error: Option<String>,
+}
+
+/// Summary of indexing activity within a time range.
+#[cfg(feature = "deep-mode")]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ComplianceReport {
+    pub from: String,
+    pub to: String,
+    /// Distinct files created or modified in the range
+    pub files_indexed: usize,
+    pub languages: Vec<String>,
+    /// Distinct symbol names in those files
+    pub unique_symbols: usize,
+    pub error_count: usize,
+    /// Audit events and change log entries, oldest first
+    pub events: Vec<ReportEvent>,
+}
+
+/// How a symbol in one file refers to a symbol in another.
+#[cfg(feature = "deep-mode")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RelationKind {
+    /// A function calls the target function or constructor
+    Calls,
+    /// A class extends the target class or struct
+    Inherits,
+    /// A class implements the target trait or interface
+    Implements,
+    /// Any other reference (type annotations, constants, ...)
+    Uses,
+}
+
+#[cfg(feature = "deep-mode")]
+impl std::fmt::Display for RelationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            RelationKind::Calls => "calls",
+            RelationKind::Inherits => "inherits",
+            RelationKind::Implements => "implements",
+            RelationKind::Uses => "uses",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[cfg(feature = "deep-mode")]
+impl std::str::FromStr for RelationKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "calls" => Ok(RelationKind::Calls),
+            "inherits" => Ok(RelationKind::Inherits),
+            "implements" => Ok(RelationKind::Implements),
+            "uses" => Ok(RelationKind::Uses),
+            _ => anyhow::bail!("Unknown relation kind: {}", s),
+        }
+    }
+}
+
+/// Symbols of every file in a project, keyed by path.
+#[cfg(feature = "deep-mode")]
+pub type SymbolTable = HashMap<PathBuf, Vec<Symbol>>;
+
+/// A reference from a symbol in `source_file` to a symbol defined in `target_file`.
+#[cfg(feature = "deep-mode")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CrossFileRelation {
+    pub source_file: String,
+    pub source_symbol: String,
+    pub target_file: String,
+    pub target_symbol: String,
+    pub relation_kind: RelationKind,
+}
+
+/// The public API of one package version, as returned by `DeepMode::detect_api_surface`.
+#[cfg(feature = "deep-mode")]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiSurface {
+    pub symbols: Vec<Symbol>,
+    pub language: String,
+    /// Package version the surface was taken from, e.g. `"1.4.0"`
+    pub version: Option<String>,
+}
+
+/// Differences between two API surfaces, each entry formatted as `"{kind} {name}"`.
+#[cfg(feature = "deep-mode")]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiDiff {
+    /// Removals, kind changes and parameter changes that can break callers
+    pub breaking_changes: Vec<String>,
+    pub additions: Vec<String>,
+    pub removals: Vec<String>,
+}
+
 /// Deep Mode configuration and state.
 #[cfg(feature = "deep-mode")]
 #[derive(Debug)]
@@ -103,6 +282,529 @@ impl DeepMode {
         // Placeholder: actual deep analysis would go here
         Ok(())
     }
+
+    /// Find references from functions and classes to symbols defined in other files.
+    ///
+    /// `symbols` is the symbol table for every file in the project (e.g. the
+    /// output of `analyze_directory`); only `files` are scanned for references.
+    /// Matching is by identifier name: names defined in the scanned file are
+    /// treated as local, and a name defined in several other files produces a
+    /// relation to each of them.
+    pub fn analyze_cross_file(
+        &mut self,
+        files: &[(PathBuf, String)],
+        symbols: &SymbolTable,
+    ) -> Result<Vec<CrossFileRelation>> {
+        self.record_event(AuditEvent::DeepModeAccessed {
+            feature: "cross_file_analysis".to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+        });
+
+        // name -> (file, kind) for every referenceable definition
+        let mut definitions: HashMap<&str, Vec<(&PathBuf, SymbolKind)>> = HashMap::new();
+        for (path, file_symbols) in symbols {
+            for symbol in file_symbols.iter().filter(|s| is_reference_target(s.kind)) {
+                definitions.entry(symbol.name.as_str()).or_default().push((path, symbol.kind));
+            }
+        }
+
+        let identifier = Regex::new(r"[A-Za-z_][A-Za-z0-9_]*")?;
+        let mut seen = HashSet::new();
+        let mut relations = Vec::new();
+
+        for (path, source) in files {
+            let lines: Vec<&str> = source.lines().collect();
+
+            // Local definitions shadow same-named symbols in other files
+            let local: HashSet<&str> = symbols
+                .get(path)
+                .into_iter()
+                .flatten()
+                .filter(|s| is_reference_target(s.kind))
+                .map(|s| s.name.as_str())
+                .collect();
+
+            for symbol in symbols.get(path).into_iter().flatten() {
+                if !matches!(symbol.kind, SymbolKind::Function | SymbolKind::Class) {
+                    continue;
+                }
+
+                let end = symbol.line_end.min(lines.len().saturating_sub(1));
+                let Some(body) = lines.get(symbol.line_start..=end) else {
+                    continue;
+                };
+
+                for (offset, line) in body.iter().enumerate() {
+                    let mut implements_clause = false;
+
+                    for token in identifier.find_iter(line) {
+                        if token.as_str() == "implements" {
+                            implements_clause = true;
+                        }
+
+                        if local.contains(token.as_str()) {
+                            continue;
+                        }
+                        let Some(targets) = definitions.get(token.as_str()) else {
+                            continue;
+                        };
+                        let is_call = line[token.end()..].trim_start().starts_with('(');
+
+                        for (target_path, target_kind) in targets {
+                            if *target_path == path {
+                                continue;
+                            }
+
+                            let relation_kind = classify_relation(
+                                symbol.kind,
+                                *target_kind,
+                                offset == 0,
+                                is_call,
+                                implements_clause,
+                            );
+                            let relation = CrossFileRelation {
+                                source_file: path.to_string_lossy().to_string(),
+                                source_symbol: symbol.name.clone(),
+                                target_file: target_path.to_string_lossy().to_string(),
+                                target_symbol: token.as_str().to_string(),
+                                relation_kind,
+                            };
+                            if seen.insert(relation.clone()) {
+                                relations.push(relation);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(relations)
+    }
+
+    /// Export a compliance report covering `from..=to` (ISO 8601 timestamps).
+    ///
+    /// The report is built from the `audit_events` table (see
+    /// `store_audit_events`) and the `change_log` table.
+    pub fn export_compliance_report(
+        &mut self,
+        conn: &Connection,
+        format: ReportFormat,
+        from: &str,
+        to: &str,
+    ) -> Result<String> {
+        self.record_event(AuditEvent::DeepModeAccessed {
+            feature: "compliance_report".to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+        });
+
+        let report = build_compliance_report(conn, from, to)?;
+        match format {
+            ReportFormat::Json => {
+                serde_json::to_string_pretty(&report).context("Failed to serialize compliance report")
+            }
+            ReportFormat::Csv => report_to_csv(&report),
+            ReportFormat::Markdown => Ok(report_to_markdown(&report)),
+        }
+    }
+
+    /// Select the top-level public API from `symbols`, most important first.
+    ///
+    /// A symbol is public when its `visibility` metadata is `pub`, `public` or
+    /// `export`. Test code (`"test": true`) and `"doc_hidden": true` symbols
+    /// are excluded; the rest are ranked by `compute_symbol_importance`.
+    pub fn detect_api_surface(&mut self, symbols: &[Symbol]) -> Vec<Symbol> {
+        self.record_event(AuditEvent::DeepModeAccessed {
+            feature: "api_surface".to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+        });
+
+        let mut ranked: Vec<(f32, &Symbol)> = symbols
+            .iter()
+            .filter(|symbol| symbol.scope.is_none() && is_public_api(symbol))
+            .map(|symbol| (compute_symbol_importance(symbol), symbol))
+            .collect();
+        // Stable sort keeps source order among equally important symbols
+        ranked.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        ranked.into_iter().map(|(_, symbol)| symbol.clone()).collect()
+    }
+}
+
+/// Compare the API of two versions, treating `v1` as the baseline.
+///
+/// Symbols are matched by name. A removed symbol, a symbol whose kind
+/// changed, or one whose `params` metadata changed is a breaking change.
+#[cfg(feature = "deep-mode")]
+pub fn compare_api_surfaces(v1: &ApiSurface, v2: &ApiSurface) -> ApiDiff {
+    let old: HashMap<&str, &Symbol> = v1.symbols.iter().map(|s| (s.name.as_str(), s)).collect();
+    let new: HashMap<&str, &Symbol> = v2.symbols.iter().map(|s| (s.name.as_str(), s)).collect();
+    let mut diff = ApiDiff::default();
+
+    for (name, before) in &old {
+        let Some(after) = new.get(name) else {
+            diff.removals.push(format!("{} {}", before.kind, name));
+            diff.breaking_changes.push(format!("{} {} was removed", before.kind, name));
+            continue;
+        };
+
+        if before.kind != after.kind {
+            diff.breaking_changes.push(format!(
+                "{} {} changed kind to {}",
+                before.kind, name, after.kind
+            ));
+        } else if api_params(before) != api_params(after) {
+            diff.breaking_changes.push(format!("{} {} changed parameters", before.kind, name));
+        }
+    }
+    for (name, after) in &new {
+        if !old.contains_key(name) {
+            diff.additions.push(format!("{} {}", after.kind, name));
+        }
+    }
+
+    diff.breaking_changes.sort();
+    diff.additions.sort();
+    diff.removals.sort();
+    diff
+}
+
+/// Whether a symbol is exported and not hidden or test-only
+#[cfg(feature = "deep-mode")]
+fn is_public_api(symbol: &Symbol) -> bool {
+    let Some(serde_json::Value::Object(metadata)) = symbol
+        .metadata
+        .as_deref()
+        .and_then(|m| serde_json::from_str(m).ok())
+    else {
+        return false;
+    };
+
+    let flag = |key: &str| metadata.get(key) == Some(&serde_json::Value::Bool(true));
+    let visibility = metadata.get("visibility").and_then(serde_json::Value::as_str);
+    matches!(visibility, Some("pub" | "public" | "export")) && !flag("test") && !flag("doc_hidden")
+}
+
+/// The `params` metadata of a symbol, if any
+#[cfg(feature = "deep-mode")]
+fn api_params(symbol: &Symbol) -> Option<serde_json::Value> {
+    let metadata: serde_json::Value = serde_json::from_str(symbol.metadata.as_deref()?).ok()?;
+    metadata.get("params").cloned()
+}
+
+/// Collect the report data for `from..=to`
+#[cfg(feature = "deep-mode")]
+fn build_compliance_report(conn: &Connection, from: &str, to: &str) -> Result<ComplianceReport> {
+    let from = crate::query::normalize_timestamp(conn, from)?;
+    let to = crate::query::normalize_timestamp(conn, to)?;
+
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT timestamp, event_kind, source, symbols_found, error
+        FROM (
+            SELECT timestamp, event_kind, source, symbols_found, error, 0 AS origin, id
+            FROM audit_events WHERE timestamp BETWEEN ?1 AND ?2
+            UNION ALL
+            SELECT timestamp, event_kind, file_path, symbols_added, NULL, 1, id
+            FROM change_log WHERE timestamp BETWEEN ?1 AND ?2
+        )
+        ORDER BY timestamp, origin, id
+        "#,
+    )?;
+    let events = stmt
+        .query_map(params![from, to], |row| {
+            Ok(ReportEvent {
+                timestamp: row.get(0)?,
+                kind: row.get(1)?,
+                source: row.get(2)?,
+                symbols: row.get(3)?,
+                error: row.get(4)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to query audit events")?;
+
+    let (files_indexed, unique_symbols): (usize, usize) = conn.query_row(
+        r#"
+        WITH indexed AS (
+            SELECT DISTINCT file_path FROM change_log
+            WHERE timestamp BETWEEN ?1 AND ?2 AND event_kind IN ('create', 'modify')
+        )
+        SELECT
+            (SELECT COUNT(*) FROM indexed),
+            (SELECT COUNT(DISTINCT s.name) FROM symbols s
+             JOIN files f ON f.id = s.file_id
+             WHERE f.path IN (SELECT file_path FROM indexed))
+        "#,
+        params![from, to],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT DISTINCT f.language FROM files f
+        WHERE f.path IN (
+            SELECT file_path FROM change_log
+            WHERE timestamp BETWEEN ?1 AND ?2 AND event_kind IN ('create', 'modify')
+        )
+        ORDER BY f.language
+        "#,
+    )?;
+    let languages = stmt
+        .query_map(params![from, to], |row| row.get(0))?
+        .collect::<Result<Vec<String>, _>>()?;
+
+    let error_count = events.iter().filter(|e| e.error.is_some()).count();
+    Ok(ComplianceReport {
+        from,
+        to,
+        files_indexed,
+        languages,
+        unique_symbols,
+        error_count,
+        events,
+    })
+}
+
+/// Summary rows followed by a blank line and the event rows
+#[cfg(feature = "deep-mode")]
+fn report_to_csv(report: &ComplianceReport) -> Result<String> {
+    let mut summary = csv::Writer::from_writer(Vec::new());
+    summary.write_record(["metric", "value"])?;
+    summary.write_record(["from", report.from.as_str()])?;
+    summary.write_record(["to", report.to.as_str()])?;
+    summary.write_record(["files_indexed", &report.files_indexed.to_string()])?;
+    summary.write_record(["languages", &report.languages.join(";")])?;
+    summary.write_record(["unique_symbols", &report.unique_symbols.to_string()])?;
+    summary.write_record(["errors", &report.error_count.to_string()])?;
+
+    let mut events = csv::Writer::from_writer(Vec::new());
+    events.write_record(["timestamp", "kind", "source", "symbols", "error"])?;
+    for event in &report.events {
+        events.write_record([
+            event.timestamp.as_str(),
+            event.kind.as_str(),
+            event.source.as_str(),
+            &event.symbols.to_string(),
+            event.error.as_deref().unwrap_or(""),
+        ])?;
+    }
+
+    let summary = summary.into_inner().context("Failed to write CSV report")?;
+    let events = events.into_inner().context("Failed to write CSV report")?;
+    Ok(format!("{}\n{}", String::from_utf8(summary)?, String::from_utf8(events)?))
+}
+
+#[cfg(feature = "deep-mode")]
+fn report_to_markdown(report: &ComplianceReport) -> String {
+    // Pipes and newlines would break the table layout
+    let cell = |s: &str| s.replace('|', "\\|").replace('\n', " ");
+
+    let mut out = format!("# Compliance Report\n\n{} to {}\n\n", report.from, report.to);
+    out.push_str("| Metric | Value |\n|---|---|\n");
+    out.push_str(&format!("| Files indexed | {} |\n", report.files_indexed));
+    out.push_str(&format!("| Languages | {} |\n", report.languages.join(", ")));
+    out.push_str(&format!("| Unique symbols | {} |\n", report.unique_symbols));
+    out.push_str(&format!("| Errors | {} |\n", report.error_count));
+
+    out.push_str("\n## Events\n\n| Timestamp | Kind | Source | Symbols | Error |\n|---|---|---|---|---|\n");
+    for event in &report.events {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} |\n",
+            event.timestamp,
+            event.kind,
+            cell(&event.source),
+            event.symbols,
+            cell(event.error.as_deref().unwrap_or("")),
+        ));
+    }
+    out
+}
+
+/// Symbol kinds another file can refer to by name
+#[cfg(feature = "deep-mode")]
+fn is_reference_target(kind: SymbolKind) -> bool {
+    !matches!(kind, SymbolKind::Import | SymbolKind::Export | SymbolKind::Module)
+}
+
+/// Decide the relation for a reference found in a function or class
+///
+/// Only references on a class's header line count as inheritance.
+#[cfg(feature = "deep-mode")]
+fn classify_relation(
+    source_kind: SymbolKind,
+    target_kind: SymbolKind,
+    header_line: bool,
+    is_call: bool,
+    implements_clause: bool,
+) -> RelationKind {
+    let is_type = matches!(target_kind, SymbolKind::Class | SymbolKind::Struct);
+    let is_contract = matches!(target_kind, SymbolKind::Trait | SymbolKind::Interface);
+
+    if source_kind == SymbolKind::Class && header_line {
+        if implements_clause || is_contract {
+            return RelationKind::Implements;
+        }
+        if is_type {
+            return RelationKind::Inherits;
+        }
+    }
+
+    if is_call && (target_kind == SymbolKind::Function || is_type) {
+        RelationKind::Calls
+    } else {
+        RelationKind::Uses
+    }
+}
+
+/// Create the `cross_file_relations` and `audit_events` tables (called by `init_schema`).
+#[cfg(feature = "deep-mode")]
+pub fn init_deep_schema(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS cross_file_relations (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            source_file TEXT NOT NULL,
+            source_symbol TEXT NOT NULL,
+            target_file TEXT NOT NULL,
+            target_symbol TEXT NOT NULL,
+            relation_kind TEXT NOT NULL,
+            UNIQUE(source_file, source_symbol, target_file, target_symbol, relation_kind)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_cross_file_relations_target
+            ON cross_file_relations(target_file, target_symbol);
+
+        CREATE TABLE IF NOT EXISTS audit_events (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            event_kind TEXT NOT NULL,
+            source TEXT NOT NULL,
+            symbols_found INTEGER NOT NULL DEFAULT 0,
+            error TEXT,
+            timestamp TIMESTAMP NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_audit_events_timestamp ON audit_events(timestamp);
+        "#,
+    )
+    .context("Failed to create Deep Mode tables")
+}
+
+/// Replace the stored relations of every source file in `relations`.
+///
+/// Returns the number of rows written.
+#[cfg(feature = "deep-mode")]
+pub fn store_cross_file_relations(conn: &Connection, relations: &[CrossFileRelation]) -> Result<usize> {
+    let tx = conn
+        .unchecked_transaction()
+        .context("Failed to start transaction")?;
+
+    let sources: HashSet<&str> = relations.iter().map(|r| r.source_file.as_str()).collect();
+    for source in sources {
+        tx.execute("DELETE FROM cross_file_relations WHERE source_file = ?1", params![source])
+            .context("Failed to clear cross-file relations")?;
+    }
+
+    let mut written = 0;
+    {
+        let mut stmt = tx.prepare(
+            r#"
+            INSERT OR IGNORE INTO cross_file_relations
+                (source_file, source_symbol, target_file, target_symbol, relation_kind)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            "#,
+        )?;
+        for relation in relations {
+            written += stmt.execute(params![
+                relation.source_file,
+                relation.source_symbol,
+                relation.target_file,
+                relation.target_symbol,
+                relation.relation_kind.to_string(),
+            ])?;
+        }
+    }
+
+    tx.commit().context("Failed to commit cross-file relations")?;
+    Ok(written)
+}
+
+/// Load every stored relation, ordered by source file and symbol.
+#[cfg(feature = "deep-mode")]
+pub fn load_cross_file_relations(conn: &Connection) -> Result<Vec<CrossFileRelation>> {
+    let mut stmt = conn.prepare(
+        "SELECT source_file, source_symbol, target_file, target_symbol, relation_kind
+         FROM cross_file_relations ORDER BY source_file, source_symbol, target_symbol",
+    )?;
+
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    rows.into_iter()
+        .map(|(source_file, source_symbol, target_file, target_symbol, kind)| {
+            Ok(CrossFileRelation {
+                source_file,
+                source_symbol,
+                target_file,
+                target_symbol,
+                relation_kind: kind.parse()?,
+            })
+        })
+        .collect()
+}
+
+/// Append audit events to the `audit_events` table.
+///
+/// Returns the number of rows written.
+#[cfg(feature = "deep-mode")]
+pub fn store_audit_events(conn: &Connection, events: &[AuditEvent]) -> Result<usize> {
+    let tx = conn
+        .unchecked_transaction()
+        .context("Failed to start transaction")?;
+
+    let mut written = 0;
+    {
+        let mut stmt = tx.prepare(
+            r#"
+            INSERT INTO audit_events (event_kind, source, symbols_found, error, timestamp)
+            VALUES (?1, ?2, ?3, ?4, datetime(?5, 'unixepoch'))
+            "#,
+        )?;
+        for event in events {
+            let (symbols_found, error) = match event {
+                AuditEvent::AnalysisCompleted { symbols_found, .. } => (*symbols_found, None),
+                AuditEvent::AnalysisFailed { error, .. } => (0, Some(error.as_str())),
+                _ => (0, None),
+            };
+            written += stmt.execute(params![
+                event.kind(),
+                event.source(),
+                symbols_found,
+                error,
+                event.timestamp() as i64,
+            ])?;
+        }
+    }
+
+    tx.commit().context("Failed to commit audit events")?;
+    Ok(written)
 }
 
 #[cfg(feature = "deep-mode")]
@@ -133,6 +835,20 @@ mod tests {
         assert!(!available, "Deep Mode should not be available when feature is disabled");
     }
 
+    #[cfg(feature = "deep-mode")]
+    #[test]
+    fn test_classify_relation() {
+        use SymbolKind::*;
+
+        assert_eq!(classify_relation(Class, Class, true, false, false), RelationKind::Inherits);
+        assert_eq!(classify_relation(Class, Interface, true, false, false), RelationKind::Implements);
+        assert_eq!(classify_relation(Class, Class, true, false, true), RelationKind::Implements);
+        assert_eq!(classify_relation(Class, Class, false, true, false), RelationKind::Calls);
+        assert_eq!(classify_relation(Function, Function, false, true, false), RelationKind::Calls);
+        assert_eq!(classify_relation(Function, Class, true, false, false), RelationKind::Uses);
+        assert_eq!(classify_relation(Function, Variable, false, true, false), RelationKind::Uses);
+    }
+
     #[cfg(feature = "deep-mode")]
     #[test]
     fn test_deep_mode_audit() {
@@ -147,4 +863,161 @@ mod tests {
 
         assert_eq!(deep.get_audit_trail().len(), 1);
     }
+
+    #[cfg(feature = "deep-mode")]
+    fn report_fixture() -> (tempfile::NamedTempFile, Connection) {
+        use crate::storage::{init_schema, insert_change_log, insert_symbols_batch, upsert_file};
+        use crate::FileMetadata;
+
+        let temp_file = tempfile::NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        for (path, language, names) in [
+            ("app.py", "python", &["main", "helper"][..]),
+            ("lib.rs", "rust", &["main", "parse"][..]),
+        ] {
+            let file = FileMetadata {
+                id: None,
+                path: path.to_string(),
+                language: language.to_string(),
+                size: 10,
+                last_indexed: None,
+                parse_errors: 0,
+                index_duration_ms: None,
+                metadata: None,
+            };
+            let file_id = upsert_file(&conn, &file).unwrap();
+            let symbols: Vec<Symbol> = names
+                .iter()
+                .enumerate()
+                .map(|(line, name)| Symbol {
+                    id: None,
+                    file_id,
+                    name: name.to_string(),
+                    kind: SymbolKind::Function,
+                    line_start: line,
+                    line_end: line,
+                    column_start: 0,
+                    column_end: 0,
+                    scope: None,
+                    metadata: None,
+                })
+                .collect();
+            insert_symbols_batch(&conn, &symbols).unwrap();
+            insert_change_log(&conn, "create", path, names.len(), 0).unwrap();
+        }
+        insert_change_log(&conn, "modify", "old.py", 1, 0).unwrap();
+        conn.execute("UPDATE change_log SET timestamp = '2024-01-01 10:00:00'", []).unwrap();
+        conn.execute(
+            "UPDATE change_log SET timestamp = '2023-06-01 00:00:00' WHERE file_path = 'old.py'",
+            [],
+        )
+        .unwrap();
+
+        // 2024-01-01 09:00:00 and 11:00:00 UTC
+        store_audit_events(
+            &conn,
+            &[
+                AuditEvent::AnalysisCompleted {
+                    source: "app.py".to_string(),
+                    symbols_found: 2,
+                    timestamp: 1704099600,
+                },
+                AuditEvent::AnalysisFailed {
+                    source: "lib.rs".to_string(),
+                    error: "unexpected token | here".to_string(),
+                    timestamp: 1704106800,
+                },
+            ],
+        )
+        .unwrap();
+
+        (temp_file, conn)
+    }
+
+    #[cfg(feature = "deep-mode")]
+    #[test]
+    fn test_compliance_report_json() {
+        let (_temp_file, conn) = report_fixture();
+        let mut deep = DeepMode::new();
+
+        let json = deep
+            .export_compliance_report(&conn, ReportFormat::Json, "2024-01-01", "2024-01-02")
+            .unwrap();
+        let report: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(report["from"], "2024-01-01 00:00:00");
+        assert_eq!(report["files_indexed"], 2);
+        assert_eq!(report["languages"], serde_json::json!(["python", "rust"]));
+        assert_eq!(report["unique_symbols"], 3);
+        assert_eq!(report["error_count"], 1);
+
+        let kinds: Vec<&str> = report["events"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["kind"].as_str().unwrap())
+            .collect();
+        assert_eq!(kinds, vec!["analysis_completed", "create", "create", "analysis_failed"]);
+        assert_eq!(report["events"][3]["error"], "unexpected token | here");
+
+        assert!(matches!(
+            deep.get_audit_trail().last(),
+            Some(AuditEvent::DeepModeAccessed { feature, .. }) if feature == "compliance_report"
+        ));
+    }
+
+    #[cfg(feature = "deep-mode")]
+    #[test]
+    fn test_compliance_report_csv() {
+        let (_temp_file, conn) = report_fixture();
+
+        let csv = DeepMode::new()
+            .export_compliance_report(&conn, ReportFormat::Csv, "2023-01-01", "2024-01-01 09:30:00")
+            .unwrap();
+        let (summary, events) = csv.split_once("\n\n").unwrap();
+
+        assert!(summary.starts_with("metric,value\n"));
+        assert!(summary.contains("files_indexed,1\n"));
+        assert!(summary.contains("languages,\n"));
+        assert!(summary.ends_with("errors,0"));
+
+        let rows: Vec<&str> = events.lines().collect();
+        assert_eq!(
+            rows,
+            vec![
+                "timestamp,kind,source,symbols,error",
+                "2023-06-01 00:00:00,modify,old.py,1,",
+                "2024-01-01 09:00:00,analysis_completed,app.py,2,",
+            ]
+        );
+    }
+
+    #[cfg(feature = "deep-mode")]
+    #[test]
+    fn test_compliance_report_markdown() {
+        let (_temp_file, conn) = report_fixture();
+
+        let markdown = DeepMode::new()
+            .export_compliance_report(&conn, ReportFormat::Markdown, "2024-01-01", "2024-12-31")
+            .unwrap();
+
+        assert!(markdown.starts_with("# Compliance Report\n"));
+        assert!(markdown.contains("| Files indexed | 2 |"));
+        assert!(markdown.contains("| Languages | python, rust |"));
+        assert!(markdown.contains("| 2024-01-01 11:00:00 | analysis_failed | lib.rs | 0 | unexpected token \\| here |"));
+    }
+
+    #[cfg(feature = "deep-mode")]
+    #[test]
+    fn test_compliance_report_rejects_invalid_range() {
+        let (_temp_file, conn) = report_fixture();
+
+        let err = DeepMode::new()
+            .export_compliance_report(&conn, ReportFormat::Json, "yesterday", "2024-01-02")
+            .unwrap_err();
+        assert!(err.downcast_ref::<crate::query::QueryError>().is_some());
+        assert!("pdf".parse::<ReportFormat>().is_err());
+        assert_eq!("Markdown".parse::<ReportFormat>().unwrap(), ReportFormat::Markdown);
+    }
 }
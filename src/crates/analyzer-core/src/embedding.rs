@@ -0,0 +1,163 @@
+// Embedding module - Natural-language symbol descriptions for AI/embedding pipelines
+// Builds short hints from symbol data, metadata and nearby doc comments
+
+use serde_json::{Map, Value};
+
+use crate::{parse_function_metadata, FunctionParam, Symbol};
+
+/// Produce a concise description of a symbol, e.g.
+/// "function `parse` at line 12 takes source: &str, returns Tree. Parse a file."
+///
+/// `source` is the full text of the file the symbol was extracted from.
+pub fn compute_embedding_hint(symbol: &Symbol, source: &str) -> String {
+    let mut hint = format!("{} `{}` at line {}", symbol.kind, symbol.name, symbol.line_start + 1);
+
+    if let Some(scope) = &symbol.scope {
+        hint.push_str(&format!(" in `{}`", scope));
+    }
+
+    if let Some(function) = parse_function_metadata(symbol) {
+        if !function.params.is_empty() {
+            hint.push_str(&format!(" takes {}", describe_parameters(&function.params)));
+        }
+        if let Some(return_type) = function.return_type {
+            hint.push_str(&format!(", returns {}", return_type));
+        }
+    }
+
+    if let Some(doc) = extract_doc_comment(symbol, source) {
+        hint.push_str(". ");
+        hint.push_str(&doc);
+    }
+
+    hint
+}
+
+/// Compute hints for all symbols and store them as `metadata["embedding_hint"]`
+pub fn populate_embedding_hints(symbols: &mut [Symbol], source: &str) {
+    for symbol in symbols.iter_mut() {
+        let hint = compute_embedding_hint(symbol, source);
+        let mut metadata = parse_metadata(symbol);
+        metadata.insert("embedding_hint".to_string(), Value::String(hint));
+        symbol.metadata = Some(Value::Object(metadata).to_string());
+    }
+}
+
+fn parse_metadata(symbol: &Symbol) -> Map<String, Value> {
+    symbol
+        .metadata
+        .as_deref()
+        .and_then(|m| serde_json::from_str::<Value>(m).ok())
+        .and_then(|v| match v {
+            Value::Object(map) => Some(map),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+/// Render `[{"name": "a", "type": null}, {"name": "b", "type": "int"}]` as "a, b: int"
+fn describe_parameters(params: &[FunctionParam]) -> String {
+    params
+        .iter()
+        .map(|param| match &param.type_name {
+            Some(ty) => format!("{}: {}", param.name, ty),
+            None => param.name.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// First line of the doc comment above the symbol, or of a Python docstring below it
+fn extract_doc_comment(symbol: &Symbol, source: &str) -> Option<String> {
+    let lines: Vec<&str> = source.lines().collect();
+
+    // Comment block directly above the definition (///, //, #, /** */)
+    let mut doc_lines = Vec::new();
+    let mut line = symbol.line_start;
+    while line > 0 {
+        line -= 1;
+        let text = lines.get(line)?.trim();
+        let stripped = ["///", "//!", "//", "/**", "*/", "*", "#"]
+            .iter()
+            .find_map(|prefix| text.strip_prefix(prefix));
+        match stripped {
+            Some(rest) if !text.starts_with("#[") => doc_lines.push(rest.trim()),
+            _ => break,
+        }
+    }
+    doc_lines.reverse();
+    if let Some(first) = doc_lines.into_iter().find(|l| !l.is_empty()) {
+        return Some(first.to_string());
+    }
+
+    // Python docstring on the first body line
+    let body = lines.get(symbol.line_start + 1)?.trim();
+    ["\"\"\"", "'''"].iter().find_map(|quote| {
+        let rest = body.strip_prefix(quote)?;
+        let text = rest.split(quote).next().unwrap_or("").trim();
+        (!text.is_empty()).then(|| text.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SymbolKind;
+
+    fn symbol(name: &str, line: usize, metadata: Option<&str>) -> Symbol {
+        Symbol {
+            id: None,
+            file_id: 1,
+            name: name.to_string(),
+            kind: SymbolKind::Function,
+            line_start: line,
+            line_end: line + 2,
+            column_start: 0,
+            column_end: 0,
+            scope: None,
+            metadata: metadata.map(|m| m.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_hint_contains_name_and_doc() {
+        let source = "/// Parse a symbol kind\nfn parse_symbol_kind(s: &str) -> SymbolKind {\n}\n";
+        let sym = symbol(
+            "parse_symbol_kind",
+            1,
+            Some(r#"{"params":[{"name":"s","type":"&str"}],"return_type":"SymbolKind"}"#),
+        );
+
+        let hint = compute_embedding_hint(&sym, source);
+        assert!(hint.contains("parse_symbol_kind"));
+        assert!(hint.contains("s: &str"));
+        assert!(hint.contains("returns SymbolKind"));
+        assert!(hint.contains("Parse a symbol kind"));
+    }
+
+    #[test]
+    fn test_hint_python_docstring() {
+        let source = "def greet(name):\n    \"\"\"Say hello.\"\"\"\n    pass\n";
+        let hint = compute_embedding_hint(&symbol("greet", 0, None), source);
+        assert!(hint.starts_with("function `greet`"));
+        assert!(hint.ends_with("Say hello."));
+    }
+
+    #[test]
+    fn test_populate_embedding_hints_preserves_metadata() {
+        let source = "fn a() {}\nfn b() {}\n";
+        let mut symbols = vec![symbol("a", 0, Some(r#"{"async":true}"#)), symbol("b", 1, None)];
+
+        populate_embedding_hints(&mut symbols, source);
+
+        for sym in &symbols {
+            let metadata: Value = serde_json::from_str(sym.metadata.as_deref().unwrap()).unwrap();
+            let hint = metadata["embedding_hint"].as_str().unwrap();
+            assert!(!hint.is_empty());
+            assert!(hint.contains(&sym.name));
+        }
+
+        let first: Value = serde_json::from_str(symbols[0].metadata.as_deref().unwrap()).unwrap();
+        assert_eq!(first["async"], true);
+    }
+}
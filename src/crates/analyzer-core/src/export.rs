@@ -0,0 +1,176 @@
+// Export module - Whole-index JSON dumps for backups and external tooling
+// Symbols are read in id-ordered batches so large indexes never sit in memory at once
+
+use anyhow::{anyhow, Context, Result};
+use rusqlite::{params, Connection};
+use std::io::Write;
+use std::path::Path;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::sync::mpsc;
+
+use crate::query::list_files;
+use crate::storage::init_schema;
+use crate::{Symbol, SymbolKind};
+
+/// Symbols read and serialized per batch
+pub const EXPORT_BATCH_SIZE: usize = 1000;
+
+/// Write the index as `{"files": [...], "symbols": [...]}` to `writer`
+///
+/// Returns the number of symbols written.
+pub fn export_index_to_json(conn: &Connection, mut writer: impl Write) -> Result<usize> {
+    let count = write_index_chunks(conn, |chunk| {
+        writer.write_all(&chunk).context("Failed to write export")
+    })?;
+    writer.flush().context("Failed to flush export")?;
+    Ok(count)
+}
+
+/// Async variant of `export_index_to_json` for sockets and slow disks
+///
+/// The database at `db_path` is read on a blocking thread; serialized batches
+/// are handed over a bounded channel and written with `write_all`, so the
+/// runtime never blocks on either side.
+pub async fn export_index_to_json_async<W: AsyncWrite + Unpin>(db_path: &Path, mut writer: W) -> Result<usize> {
+    // Two batches in flight keeps the reader ahead without buffering the whole index
+    let (tx, mut rx) = mpsc::channel::<Vec<u8>>(2);
+    let db_path = db_path.to_path_buf();
+    let producer = tokio::task::spawn_blocking(move || {
+        let conn = init_schema(&db_path)?;
+        write_index_chunks(&conn, |chunk| {
+            tx.blocking_send(chunk).map_err(|_| anyhow!("Export writer closed"))
+        })
+    });
+
+    while let Some(chunk) = rx.recv().await {
+        writer.write_all(&chunk).await.context("Failed to write export")?;
+    }
+    let count = producer.await.context("Export task panicked")??;
+    writer.flush().await.context("Failed to flush export")?;
+
+    Ok(count)
+}
+
+/// Serialize the index as a sequence of JSON fragments passed to `emit`
+fn write_index_chunks(conn: &Connection, mut emit: impl FnMut(Vec<u8>) -> Result<()>) -> Result<usize> {
+    let mut chunk = b"{\"files\":".to_vec();
+    serde_json::to_writer(&mut chunk, &list_files(conn)?)?;
+    chunk.extend_from_slice(b",\"symbols\":[");
+    emit(chunk)?;
+
+    let mut count = 0;
+    let mut after_id = 0;
+    loop {
+        let batch = query_symbol_batch(conn, after_id, EXPORT_BATCH_SIZE)?;
+        let Some(last) = batch.last() else {
+            break;
+        };
+        after_id = last.id.unwrap_or(after_id);
+
+        let mut chunk = Vec::new();
+        for symbol in &batch {
+            if count > 0 {
+                chunk.push(b',');
+            }
+            serde_json::to_writer(&mut chunk, symbol)?;
+            count += 1;
+        }
+        emit(chunk)?;
+
+        if batch.len() < EXPORT_BATCH_SIZE {
+            break;
+        }
+    }
+
+    emit(b"]}".to_vec())?;
+    Ok(count)
+}
+
+/// Up to `limit` symbols with an id above `after_id`, in id order
+pub(crate) fn query_symbol_batch(conn: &Connection, after_id: i64, limit: usize) -> Result<Vec<Symbol>> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end
+         FROM symbols WHERE id > ?1 ORDER BY id LIMIT ?2",
+    )?;
+
+    let symbols = stmt
+        .query_map(params![after_id, limit as i64], |row| {
+            Ok(Symbol {
+                id: Some(row.get(0)?),
+                file_id: row.get(1)?,
+                name: row.get(2)?,
+                kind: row.get::<_, String>(3)?.parse().unwrap_or(SymbolKind::Variable),
+                line_start: row.get(4)?,
+                line_end: row.get(5)?,
+                column_start: row.get(8)?,
+                column_end: row.get(9)?,
+                scope: row.get(6)?,
+                metadata: row.get(7)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to query symbols for export")?;
+
+    Ok(symbols)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{insert_symbols_batch, upsert_file};
+    use crate::FileMetadata;
+    use serde_json::Value;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_export_spans_multiple_batches() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+        let file_id = upsert_file(&conn, &FileMetadata {
+            id: None,
+            path: "big.py".to_string(),
+            language: "python".to_string(),
+            size: 10,
+            last_indexed: None,
+            parse_errors: 0,
+            index_duration_ms: None,
+            metadata: None,
+        })
+        .unwrap();
+
+        let symbols: Vec<Symbol> = (0..EXPORT_BATCH_SIZE + 1)
+            .map(|i| Symbol {
+                id: None,
+                file_id,
+                name: format!("f{}", i),
+                kind: SymbolKind::Function,
+                line_start: i,
+                line_end: i,
+                column_start: 0,
+                column_end: 0,
+                scope: None,
+                metadata: None,
+            })
+            .collect();
+        insert_symbols_batch(&conn, &symbols).unwrap();
+
+        let mut output = Vec::new();
+        assert_eq!(export_index_to_json(&conn, &mut output).unwrap(), EXPORT_BATCH_SIZE + 1);
+
+        let json: Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(json["files"][0]["path"], "big.py");
+        let exported = json["symbols"].as_array().unwrap();
+        assert_eq!(exported.len(), EXPORT_BATCH_SIZE + 1);
+        assert_eq!(exported[EXPORT_BATCH_SIZE]["name"], format!("f{}", EXPORT_BATCH_SIZE));
+    }
+
+    #[test]
+    fn test_export_empty_index() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let mut output = Vec::new();
+        assert_eq!(export_index_to_json(&conn, &mut output).unwrap(), 0);
+        assert_eq!(String::from_utf8(output).unwrap(), r#"{"files":[],"symbols":[]}"#);
+    }
+}
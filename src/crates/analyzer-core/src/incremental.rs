@@ -2,52 +2,94 @@
 // Implements file change detection and efficient re-indexing
 
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use notify::event::{AccessKind, AccessMode};
 use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::sync::mpsc::{channel, Receiver};
+use std::sync::mpsc::{self, channel};
+use std::sync::{Arc, Mutex};
 use std::fs;
+use std::io::Read;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, watch};
+use tokio::task::JoinHandle;
+use tree_sitter::{InputEdit, Point, Tree};
 
-use crate::FileMetadata;
+use crate::indexer::{discover_files, should_index_file, IgnoreMatcher, IndexerConfig};
+use crate::query::{find_symbols_by_file_path, list_files};
+use crate::registry::{AnalyzerOutput, AnalyzerRegistry, IncrementalAnalyzerFn};
+use crate::storage::{
+    delete_file_by_path, delete_file_symbols, get_file_content_hash, get_or_create_file,
+    init_schema, insert_change_log, insert_symbols_batch, replace_parse_errors, set_file_content_hash,
+    upsert_file,
+};
+use crate::{detect_language, package_entry_point_metadata, FileMetadata, IndexingError, Symbol, SymbolKind};
 
 /// Check if a file has been modified since last index
+///
+/// When `content_hash` (the digest recorded at index time) is known, the file
+/// is re-hashed and compared, so touched-but-unchanged files are skipped and
+/// edits that keep the old mtime are still caught.
+///
+/// Otherwise falls back to mtime: the file counts as modified when it was
+/// written after `last_indexed`. Rows stored by SQLite's `CURRENT_TIMESTAMP`
+/// only have second resolution, so edits in the same second as indexing are
+/// still caught.
 pub fn is_file_modified(
     file_path: &Path,
-    last_indexed: Option<&str>,
+    last_indexed: Option<DateTime<Utc>>,
+    content_hash: Option<&[u8; 32]>,
 ) -> Result<bool> {
+    if let Some(stored) = content_hash {
+        return Ok(calculate_file_hash(file_path)? != *stored);
+    }
+
+    let Some(last_indexed) = last_indexed else {
+        // Never indexed before
+        return Ok(true);
+    };
+
     let metadata = fs::metadata(file_path)
         .context("Failed to read file metadata")?;
 
-    let _modified_time = metadata
+    let modified_time = metadata
         .modified()
         .context("Failed to get file modified time")?;
 
-    if let Some(last_indexed_str) = last_indexed {
-        // Parse last_indexed timestamp
-        // For simplicity, we'll use SystemTime comparison
-        // In production, parse the ISO timestamp properly
-        let _ = last_indexed_str; // Suppress warning
+    Ok(DateTime::<Utc>::from(modified_time) > last_indexed)
+}
 
-        // For now, always return true for MVP
-        // TODO: Implement proper timestamp parsing
-        Ok(true)
-    } else {
-        // Never indexed before
-        Ok(true)
+/// Bytes read per chunk when hashing files
+const HASH_CHUNK_SIZE: usize = 64 * 1024;
+
+/// SHA-256 digest of a file, streamed in `HASH_CHUNK_SIZE` chunks
+pub fn calculate_file_hash(file_path: &Path) -> Result<[u8; 32]> {
+    let mut file = fs::File::open(file_path)
+        .context("Failed to open file for hashing")?;
+
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0; HASH_CHUNK_SIZE];
+    loop {
+        let read = file.read(&mut buffer).context("Failed to read file for hashing")?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
     }
-}
 
-/// Calculate file hash for change detection (simple size-based for MVP)
-pub fn calculate_file_hash(file_path: &Path) -> Result<u64> {
-    let metadata = fs::metadata(file_path)
-        .context("Failed to read file metadata")?;
+    Ok(hasher.finalize().into())
+}
 
-    // For MVP, use file size as a simple hash
-    // In production, use a proper hash algorithm (SHA256, etc.)
-    Ok(metadata.len())
+/// SHA-256 digest of content already in memory (matches `calculate_file_hash`)
+pub fn content_hash(content: &[u8]) -> [u8; 32] {
+    Sha256::digest(content).into()
 }
 
 /// Detect which files need re-indexing
 pub fn detect_changed_files(
+    conn: &rusqlite::Connection,
     files: &[FileMetadata],
 ) -> Result<Vec<String>> {
     let mut changed = Vec::new();
@@ -59,7 +101,8 @@ pub fn detect_changed_files(
             continue;
         }
 
-        if is_file_modified(path, file.last_indexed.as_deref())? {
+        let stored_hash = get_file_content_hash(conn, &file.path)?;
+        if is_file_modified(path, file.last_indexed, stored_hash.as_ref())? {
             changed.push(file.path.clone());
         }
     }
@@ -67,16 +110,557 @@ pub fn detect_changed_files(
     Ok(changed)
 }
 
+/// Summary of an incremental re-index
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReindexStats {
+    /// Files examined (indexed files still on disk + newly discovered)
+    pub checked: usize,
+    /// Files (re-)analyzed and written to the index
+    pub reindexed: usize,
+    /// Files removed from the index because they no longer exist
+    pub deleted: usize,
+}
+
+/// Re-index only files that changed since they were last indexed
+///
+/// Indexed files that no longer exist are removed, modified files are
+/// re-analyzed, and files under any of `config.root_dirs` that are not yet
+/// indexed are added.
+pub fn reindex_changed_files(
+    conn: &rusqlite::Connection,
+    config: &IndexerConfig,
+    registry: &AnalyzerRegistry,
+) -> Result<ReindexStats> {
+    let indexed = list_files(conn)?;
+    let mut stats = ReindexStats::default();
+
+    // Drop files that disappeared from disk
+    for file in &indexed {
+        if !Path::new(&file.path).exists() && delete_file_by_path(conn, &file.path)? {
+            stats.deleted += 1;
+        }
+    }
+
+    let known: HashSet<&str> = indexed.iter().map(|f| f.path.as_str()).collect();
+    let mut to_index = detect_changed_files(conn, &indexed)?;
+    let new_files: Vec<String> = discover_files(config)?
+        .into_iter()
+        .filter_map(|p| p.to_str().map(|s| s.to_string()))
+        .filter(|p| !known.contains(p.as_str()))
+        .collect();
+
+    stats.checked = indexed.len() - stats.deleted + new_files.len();
+    to_index.extend(new_files);
+
+    for path in &to_index {
+        match reindex_file(conn, path, registry, None) {
+            Ok(Some(_)) => stats.reindexed += 1,
+            Ok(None) => {}
+            Err(e) => eprintln!("Failed to re-index {}: {:#}", path, e),
+        }
+    }
+
+    Ok(stats)
+}
+
+/// How a file's symbols changed when it was re-indexed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SymbolDiff {
+    /// Symbols only in the new analysis
+    pub added: Vec<Symbol>,
+    /// Previously indexed symbols missing from the new analysis
+    pub removed: Vec<Symbol>,
+    /// Symbols present in both (as found in the new analysis)
+    pub unchanged: Vec<Symbol>,
+}
+
+/// Compare two symbol lists of the same file by `(name, kind, line_start)`
+///
+/// Repeated keys are matched pairwise, so a second `foo` on the same line
+/// still counts as added. `added` and `unchanged` follow the order of `new`,
+/// `removed` the order of `old`.
+pub fn diff_symbols(old: &[Symbol], new: &[Symbol]) -> SymbolDiff {
+    let key = |symbol: &Symbol| (symbol.name.clone(), symbol.kind, symbol.line_start);
+
+    // Old indices per key, last first so `pop` matches in source order
+    let mut unmatched: HashMap<(String, SymbolKind, usize), Vec<usize>> = HashMap::new();
+    for (i, symbol) in old.iter().enumerate().rev() {
+        unmatched.entry(key(symbol)).or_default().push(i);
+    }
+
+    let mut diff = SymbolDiff::default();
+    let mut matched = vec![false; old.len()];
+    for symbol in new {
+        match unmatched.get_mut(&key(symbol)).and_then(Vec::pop) {
+            Some(i) => {
+                matched[i] = true;
+                diff.unchanged.push(symbol.clone());
+            }
+            None => diff.added.push(symbol.clone()),
+        }
+    }
+    diff.removed = old
+        .iter()
+        .zip(matched)
+        .filter(|(_, matched)| !matched)
+        .map(|(symbol, _)| symbol.clone())
+        .collect();
+
+    diff
+}
+
+/// Re-analyze one file and replace its symbols in the index
+///
+/// Languages supported by `incremental` are re-parsed against the file's
+/// previous tree. Returns how the file's symbols changed, or `None` if no
+/// analyzer is registered for the file's language.
+pub fn reindex_file(
+    conn: &rusqlite::Connection,
+    path: &str,
+    registry: &AnalyzerRegistry,
+    incremental: Option<&mut IncrementalIndexer>,
+) -> Result<Option<SymbolDiff>> {
+    let Some(language) = detect_language(path) else {
+        return Ok(None);
+    };
+    let Some(analyzer) = registry.get(language) else {
+        return Ok(None);
+    };
+
+    let source = fs::read_to_string(path).context("Failed to read file")?;
+    let start = Instant::now();
+    let analysis = match incremental.and_then(|indexer| indexer.analyze(Path::new(path), language, &source)) {
+        Some(analysis) => analysis,
+        None => analyzer(&source),
+    };
+    let (mut symbols, errors) = analysis.context("Failed to analyze file")?;
+
+    let (file_id, _) = get_or_create_file(conn, path, language, source.len() as u64)?;
+    let previous = find_symbols_by_file_path(conn, path)?;
+
+    delete_file_symbols(conn, file_id)?;
+    for symbol in &mut symbols {
+        symbol.file_id = file_id;
+    }
+    insert_symbols_batch(conn, &symbols)?;
+
+    // Refresh size/last_indexed/parse_errors and record how long the file took
+    let metadata = FileMetadata {
+        id: None,
+        path: path.to_string(),
+        language: language.to_string(),
+        size: source.len() as u64,
+        last_indexed: None,
+        parse_errors: errors.len() as i32,
+        index_duration_ms: Some(start.elapsed().as_millis() as u64),
+        metadata: package_entry_point_metadata(path),
+    };
+    upsert_file(conn, &metadata)?;
+    replace_parse_errors(conn, file_id, &errors)?;
+    set_file_content_hash(conn, path, &content_hash(source.as_bytes()))?;
+
+    Ok(Some(diff_symbols(&previous, &symbols)))
+}
+
+/// Number of indexed symbols belonging to `path`
+fn count_file_symbols(conn: &rusqlite::Connection, path: &str) -> Result<usize> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM symbols s JOIN files f ON s.file_id = f.id WHERE f.path = ?1",
+        [path],
+        |row| row.get(0),
+    )
+    .context("Failed to count file symbols")
+}
+
+/// Smallest single edit turning `old` into `new`: the bytes between their
+/// common prefix and common suffix
+pub fn input_edit(old: &str, new: &str) -> InputEdit {
+    let (old, new) = (old.as_bytes(), new.as_bytes());
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let (old_end, new_end) = (old.len() - suffix, new.len() - suffix);
+
+    InputEdit {
+        start_byte: prefix,
+        old_end_byte: old_end,
+        new_end_byte: new_end,
+        start_position: point_at(old, prefix),
+        old_end_position: point_at(old, old_end),
+        new_end_position: point_at(new, new_end),
+    }
+}
+
+/// Row and byte column of `offset` in `text`
+fn point_at(text: &[u8], offset: usize) -> Point {
+    let before = &text[..offset];
+    Point {
+        row: before.iter().filter(|&&b| b == b'\n').count(),
+        column: before
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(offset, |newline| offset - newline - 1),
+    }
+}
+
+/// Re-analyzes files by re-parsing against their previous tree
+///
+/// The last tree and source of each analyzed file are kept; when the file is
+/// analyzed again, the old tree is edited to match the new source so
+/// tree-sitter only re-parses the changed region.
+#[derive(Default)]
+pub struct IncrementalIndexer {
+    analyzers: HashMap<String, IncrementalAnalyzerFn>,
+    trees: HashMap<PathBuf, Tree>,
+    sources: HashMap<PathBuf, String>,
+}
+
+impl IncrementalIndexer {
+    /// Create an indexer with no analyzers and no cached trees
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an incremental analyzer for a language (as returned by `detect_language`)
+    pub fn register(&mut self, language: &str, analyzer: IncrementalAnalyzerFn) -> &mut Self {
+        self.analyzers.insert(language.to_string(), analyzer);
+        self
+    }
+
+    /// Check whether a language has a registered incremental analyzer
+    pub fn supports(&self, language: &str) -> bool {
+        self.analyzers.contains_key(language)
+    }
+
+    /// Whether a tree is cached for `path`
+    pub fn has_tree(&self, path: &Path) -> bool {
+        self.trees.contains_key(path)
+    }
+
+    /// Drop the cached tree of a file (e.g. once it is deleted)
+    pub fn forget(&mut self, path: &Path) {
+        self.trees.remove(path);
+        self.sources.remove(path);
+    }
+
+    /// Analyze `source` as the new contents of `path`, or `None` if `language`
+    /// has no incremental analyzer
+    ///
+    /// A failed analysis drops the cached tree, so the next one parses from scratch.
+    pub fn analyze(
+        &mut self,
+        path: &Path,
+        language: &str,
+        source: &str,
+    ) -> Option<Result<AnalyzerOutput, IndexingError>> {
+        let analyzer = *self.analyzers.get(language)?;
+
+        let old_tree = match (self.trees.remove(path), self.sources.remove(path)) {
+            (Some(mut tree), Some(old_source)) => {
+                tree.edit(&input_edit(&old_source, source));
+                Some(tree)
+            }
+            _ => None,
+        };
+
+        Some(analyzer(source, old_tree.as_ref()).map(|(symbols, errors, tree)| {
+            self.trees.insert(path.to_path_buf(), tree);
+            self.sources.insert(path.to_path_buf(), source.to_string());
+            (symbols, errors)
+        }))
+    }
+}
+
+/// Delay used to coalesce bursts of watch events into one batch
+const DEBOUNCE_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Runtime statistics of a running `IncrementalIndex`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IndexStats {
+    /// Watch events received
+    pub events: usize,
+    /// Debounced batches processed
+    pub batches: usize,
+    /// Files (re-)analyzed and written to the index
+    pub reindexed: usize,
+    /// Files removed from the index
+    pub deleted: usize,
+    /// Files that failed to re-index
+    pub errors: usize,
+}
+
+/// Watches every root in `config.root_dirs` and keeps the index at `db_path` up to date
+pub struct IncrementalIndex {
+    config: IndexerConfig,
+    ignore: IgnoreMatcher,
+    /// Opened once and shared by every batch
+    conn: Mutex<rusqlite::Connection>,
+    registry: AnalyzerRegistry,
+    incremental: Mutex<IncrementalIndexer>,
+}
+
+impl IncrementalIndex {
+    /// Create an incremental index (opens the database and initializes its schema)
+    pub fn new(config: IndexerConfig, db_path: &Path) -> Result<Self> {
+        let conn = init_schema(db_path)?;
+
+        Ok(Self {
+            ignore: IgnoreMatcher::new(&config)?,
+            config,
+            conn: Mutex::new(conn),
+            registry: AnalyzerRegistry::new(),
+            incremental: Mutex::new(IncrementalIndexer::new()),
+        })
+    }
+
+    /// Use `registry` to analyze changed files
+    pub fn with_registry(mut self, registry: AnalyzerRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    /// Re-parse changed files incrementally for the languages `indexer` supports
+    ///
+    /// Other languages still go through the registry's analyzers.
+    pub fn with_incremental_parsing(mut self, indexer: IncrementalIndexer) -> Self {
+        self.incremental = Mutex::new(indexer);
+        self
+    }
+
+    /// Start watching in a background task; must be called inside a Tokio runtime
+    pub fn start(self) -> Result<IncrementalIndexHandle> {
+        let runtime = tokio::runtime::Handle::try_current()
+            .context("IncrementalIndex must be started inside a Tokio runtime")?;
+
+        let first_root = self
+            .config
+            .root_dirs
+            .first()
+            .context("IncrementalIndex needs at least one root directory")?;
+        let mut watcher = FileWatcher::new(first_root)?;
+        for root in &self.config.root_dirs {
+            watcher.watch(root)?;
+        }
+        let events = watcher.subscribe();
+
+        let (shutdown, shutdown_rx) = watch::channel(false);
+        let stats = Arc::new(Mutex::new(IndexStats::default()));
+        let task = runtime.spawn(run_incremental_index(
+            Arc::new(self),
+            watcher,
+            events,
+            shutdown_rx,
+            stats.clone(),
+        ));
+
+        Ok(IncrementalIndexHandle {
+            task,
+            shutdown,
+            stats,
+        })
+    }
+
+    /// Apply one debounced batch of changed paths, logging each change to `change_log`
+    fn process_batch(&self, changes: &HashMap<PathBuf, FileChangeKind>) -> Result<IndexStats> {
+        let conn = self.conn.lock().unwrap();
+        let mut stats = IndexStats::default();
+        let mut incremental = self.incremental.lock().unwrap();
+
+        for (path, kind) in changes {
+            let Some(path_str) = path.to_str() else {
+                continue;
+            };
+
+            if !path.exists() {
+                incremental.forget(path);
+                let removed = count_file_symbols(&conn, path_str)?;
+                if delete_file_by_path(&conn, path_str)? {
+                    insert_change_log(&conn, &FileChangeKind::Delete.to_string(), path_str, 0, removed)?;
+                    stats.deleted += 1;
+                }
+                continue;
+            }
+
+            if !self.is_watched_file(path) {
+                continue;
+            }
+
+            match reindex_file(&conn, path_str, &self.registry, Some(&mut incremental)) {
+                Ok(Some(diff)) => {
+                    insert_change_log(&conn, &kind.to_string(), path_str, diff.added.len(), diff.removed.len())?;
+                    stats.reindexed += 1;
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    eprintln!("Failed to re-index {}: {:#}", path_str, e);
+                    stats.errors += 1;
+                }
+            }
+        }
+
+        Ok(stats)
+    }
+
+    /// Apply the same filters as `discover_files` to a single path
+    fn is_watched_file(&self, path: &Path) -> bool {
+        let excluded = self
+            .config
+            .root_for(path)
+            .and_then(|root| path.strip_prefix(root).ok())
+            .unwrap_or(path)
+            .components()
+            .any(|c| {
+                c.as_os_str()
+                    .to_str()
+                    .is_some_and(|name| self.config.exclude_dirs.iter().any(|d| d == name))
+            });
+
+        path.is_file() && !excluded && should_index_file(path, &self.config, &self.ignore).unwrap_or(false)
+    }
+}
+
+/// Background loop: collect events, debounce, then re-index the batch
+async fn run_incremental_index(
+    index: Arc<IncrementalIndex>,
+    _watcher: FileWatcher,
+    mut events: broadcast::Receiver<FileChangeEvent>,
+    mut shutdown: watch::Receiver<bool>,
+    stats: Arc<Mutex<IndexStats>>,
+) -> Result<()> {
+    loop {
+        let mut pending = HashMap::new();
+
+        let event = tokio::select! {
+            _ = shutdown.changed() => return Ok(()),
+            event = events.recv() => event,
+        };
+        if !collect_event(event, &mut pending, &stats) {
+            return Ok(());
+        }
+
+        // Keep collecting until the burst settles
+        let debounce = tokio::time::sleep(DEBOUNCE_INTERVAL);
+        tokio::pin!(debounce);
+        loop {
+            tokio::select! {
+                _ = &mut debounce => break,
+                event = events.recv() => {
+                    if !collect_event(event, &mut pending, &stats) {
+                        break;
+                    }
+                }
+            }
+        }
+
+        let batch_index = index.clone();
+        let batch = tokio::task::spawn_blocking(move || batch_index.process_batch(&pending))
+            .await
+            .context("Incremental index batch panicked")??;
+
+        let mut stats = stats.lock().unwrap();
+        stats.batches += 1;
+        stats.reindexed += batch.reindexed;
+        stats.deleted += batch.deleted;
+        stats.errors += batch.errors;
+    }
+}
+
+/// Add an event's paths to `pending`; returns `false` once the channel is closed
+fn collect_event(
+    event: Result<FileChangeEvent, broadcast::error::RecvError>,
+    pending: &mut HashMap<PathBuf, FileChangeKind>,
+    stats: &Mutex<IndexStats>,
+) -> bool {
+    match event {
+        Ok(event) => {
+            stats.lock().unwrap().events += 1;
+            add_pending(pending, event);
+            true
+        }
+        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+            eprintln!("Incremental index lagged, skipped {} events", skipped);
+            true
+        }
+        Err(broadcast::error::RecvError::Closed) => false,
+    }
+}
+
+/// Add an event's paths to `pending`, merging with earlier events for the same path
+fn add_pending(pending: &mut HashMap<PathBuf, FileChangeKind>, event: FileChangeEvent) {
+    for path in event.paths {
+        pending
+            .entry(path)
+            .and_modify(|kind| *kind = merge_change_kind(*kind, event.kind))
+            .or_insert(event.kind);
+    }
+}
+
+/// Combine two events for the same path within one debounce window
+///
+/// `Other` (e.g. close-after-write) never hides a known kind, and a file created
+/// in this window stays `Create` through subsequent writes.
+fn merge_change_kind(current: FileChangeKind, next: FileChangeKind) -> FileChangeKind {
+    match (current, next) {
+        (current, FileChangeKind::Other) => current,
+        (FileChangeKind::Create, FileChangeKind::Modify) => FileChangeKind::Create,
+        (_, next) => next,
+    }
+}
+
+/// Handle to a running `IncrementalIndex`
+pub struct IncrementalIndexHandle {
+    task: JoinHandle<Result<()>>,
+    shutdown: watch::Sender<bool>,
+    stats: Arc<Mutex<IndexStats>>,
+}
+
+impl IncrementalIndexHandle {
+    /// Snapshot of the runtime statistics
+    pub fn stats(&self) -> IndexStats {
+        *self.stats.lock().unwrap()
+    }
+
+    /// Ask the background task to stop after the current batch
+    pub fn stop(&self) {
+        let _ = self.shutdown.send(true);
+    }
+
+    /// Wait for the background task to finish (call `stop()` first)
+    ///
+    /// Returns the final statistics, including the batch in flight when stopped.
+    pub async fn join(self) -> Result<IndexStats> {
+        self.task.await.context("Incremental index task panicked")??;
+        Ok(*self.stats.lock().unwrap())
+    }
+}
+
+/// Whether a notify event only reports a file being opened or read
+fn is_read_only_access(kind: &EventKind) -> bool {
+    matches!(kind, EventKind::Access(access) if *access != AccessKind::Close(AccessMode::Write))
+}
+
+/// Capacity of the broadcast channel; slow subscribers skip older events
+const EVENT_CHANNEL_CAPACITY: usize = 1024;
+
 /// File watcher for detecting changes in real-time
+///
+/// Events from the notify thread are forwarded to a broadcast channel so any
+/// number of consumers can `subscribe()` to them.
 pub struct FileWatcher {
     watcher: RecommendedWatcher,
-    receiver: Receiver<Result<Event, notify::Error>>,
+    sender: broadcast::Sender<FileChangeEvent>,
+    receiver: broadcast::Receiver<FileChangeEvent>,
 }
 
 impl FileWatcher {
     /// Create a new file watcher for the given root directory
     pub fn new(_root: &Path) -> Result<Self> {
-        let (tx, rx) = channel();
+        let (tx, rx) = channel::<Result<Event, notify::Error>>();
+        let (sender, receiver) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
 
         let watcher = RecommendedWatcher::new(
             move |res| {
@@ -88,9 +672,27 @@ impl FileWatcher {
         )
         .context("Failed to create file watcher")?;
 
+        // Bridge notify's std channel to the broadcast channel; exits when the
+        // watcher (and with it the std sender) is dropped
+        let bridge_sender = sender.clone();
+        std::thread::spawn(move || {
+            for res in rx {
+                match res {
+                    // Reads (including our own re-indexing) would otherwise retrigger indexing
+                    Ok(event) if is_read_only_access(&event.kind) => {}
+                    // No subscribers is not an error
+                    Ok(event) => {
+                        let _ = bridge_sender.send(FileChangeEvent::from_notify_event(event));
+                    }
+                    Err(e) => eprintln!("File watch error: {}", e),
+                }
+            }
+        });
+
         Ok(Self {
             watcher,
-            receiver: rx,
+            sender,
+            receiver,
         })
     }
 
@@ -102,27 +704,161 @@ impl FileWatcher {
         Ok(())
     }
 
+    /// Subscribe to file change events emitted after this call
+    pub fn subscribe(&self) -> broadcast::Receiver<FileChangeEvent> {
+        self.sender.subscribe()
+    }
+
     /// Get the next file change event (blocking)
-    pub fn next_event(&self) -> Option<FileChangeEvent> {
-        match self.receiver.recv() {
-            Ok(Ok(event)) => Some(FileChangeEvent::from_notify_event(event)),
-            Ok(Err(e)) => {
-                eprintln!("File watch error: {}", e);
-                None
+    ///
+    /// Must not be called from within an async runtime; use `subscribe()` there.
+    pub fn next_event(&mut self) -> Option<FileChangeEvent> {
+        loop {
+            match self.receiver.blocking_recv() {
+                Ok(event) => return Some(event),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    eprintln!("File watcher lagged, skipped {} events", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
             }
-            Err(_) => None, // Channel closed
         }
     }
 
     /// Try to get the next event without blocking
-    pub fn try_next_event(&self) -> Option<FileChangeEvent> {
-        match self.receiver.try_recv() {
-            Ok(Ok(event)) => Some(FileChangeEvent::from_notify_event(event)),
-            Ok(Err(e)) => {
-                eprintln!("File watch error: {}", e);
-                None
+    pub fn try_next_event(&mut self) -> Option<FileChangeEvent> {
+        loop {
+            match self.receiver.try_recv() {
+                Ok(event) => return Some(event),
+                Err(broadcast::error::TryRecvError::Lagged(skipped)) => {
+                    eprintln!("File watcher lagged, skipped {} events", skipped);
+                }
+                Err(_) => return None,
             }
-            Err(_) => None,
+        }
+    }
+}
+
+/// Default window over which `DebouncedFileWatcher` batches events
+pub const DEFAULT_DEBOUNCE_MS: u64 = 300;
+
+/// File watcher that delivers changes in debounced batches
+///
+/// A background thread collects events until `debounce_ms` have passed since
+/// the first event of a burst, then delivers one `FileChangeEvent` per path,
+/// with kinds combined as `IncrementalIndex` does. Use `FileWatcher` for raw
+/// events.
+pub struct DebouncedFileWatcher {
+    watcher: FileWatcher,
+    batches: mpsc::Receiver<Vec<FileChangeEvent>>,
+    debounce_ms: u64,
+}
+
+impl DebouncedFileWatcher {
+    /// Create a debounced watcher with the default `DEFAULT_DEBOUNCE_MS` window
+    pub fn new(root: &Path) -> Result<Self> {
+        Self::with_debounce(root, DEFAULT_DEBOUNCE_MS)
+    }
+
+    /// Create a debounced watcher that batches events over `debounce_ms`
+    pub fn with_debounce(root: &Path, debounce_ms: u64) -> Result<Self> {
+        let watcher = FileWatcher::new(root)?;
+        let events = watcher.subscribe();
+        let (sender, batches) = mpsc::channel();
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .context("Failed to create debounce runtime")?;
+        // Exits once the watcher is dropped (closing `events`) or nobody reads batches
+        std::thread::spawn(move || {
+            runtime.block_on(debounce_events(events, sender, Duration::from_millis(debounce_ms)))
+        });
+
+        Ok(Self {
+            watcher,
+            batches,
+            debounce_ms,
+        })
+    }
+
+    /// Start watching a directory
+    pub fn watch(&mut self, path: &Path) -> Result<()> {
+        self.watcher.watch(path)
+    }
+
+    /// Debounce window in milliseconds
+    pub fn debounce_ms(&self) -> u64 {
+        self.debounce_ms
+    }
+
+    /// Get the next batch of changes (blocking)
+    pub fn next_batch(&self) -> Option<Vec<FileChangeEvent>> {
+        self.batches.recv().ok()
+    }
+
+    /// Get the next batch of changes, waiting at most `timeout`
+    pub fn next_batch_timeout(&self, timeout: Duration) -> Option<Vec<FileChangeEvent>> {
+        self.batches.recv_timeout(timeout).ok()
+    }
+
+    /// Try to get the next batch without blocking
+    pub fn try_next_batch(&self) -> Option<Vec<FileChangeEvent>> {
+        self.batches.try_recv().ok()
+    }
+}
+
+/// Background loop of `DebouncedFileWatcher`: collect a burst, then send it as one batch
+async fn debounce_events(
+    mut events: broadcast::Receiver<FileChangeEvent>,
+    batches: mpsc::Sender<Vec<FileChangeEvent>>,
+    window: Duration,
+) {
+    loop {
+        let mut pending = HashMap::new();
+        let Some(event) = recv_event(&mut events).await else {
+            return;
+        };
+        add_pending(&mut pending, event);
+
+        let mut closed = false;
+        let debounce = tokio::time::sleep(window);
+        tokio::pin!(debounce);
+        loop {
+            tokio::select! {
+                _ = &mut debounce => break,
+                event = recv_event(&mut events) => match event {
+                    Some(event) => add_pending(&mut pending, event),
+                    None => {
+                        closed = true;
+                        break;
+                    }
+                },
+            }
+        }
+
+        let mut batch: Vec<FileChangeEvent> = pending
+            .into_iter()
+            .map(|(path, kind)| FileChangeEvent {
+                kind,
+                paths: vec![path],
+            })
+            .collect();
+        batch.sort_by(|a, b| a.paths.cmp(&b.paths));
+        if batches.send(batch).is_err() || closed {
+            return;
+        }
+    }
+}
+
+/// Next event from a watcher subscription; `None` once the watcher is gone
+async fn recv_event(events: &mut broadcast::Receiver<FileChangeEvent>) -> Option<FileChangeEvent> {
+    loop {
+        match events.recv().await {
+            Ok(event) => return Some(event),
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                eprintln!("Debounced file watcher lagged, skipped {} events", skipped);
+            }
+            Err(broadcast::error::RecvError::Closed) => return None,
         }
     }
 }
@@ -143,11 +879,25 @@ pub enum FileChangeKind {
     Other,
 }
 
+impl std::fmt::Display for FileChangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            FileChangeKind::Create => "create",
+            FileChangeKind::Modify => "modify",
+            FileChangeKind::Delete => "delete",
+            FileChangeKind::Rename => "rename",
+            FileChangeKind::Other => "other",
+        };
+        write!(f, "{}", s)
+    }
+}
+
 impl FileChangeEvent {
     fn from_notify_event(event: Event) -> Self {
         let kind = match event.kind {
             EventKind::Create(_) => FileChangeKind::Create,
             EventKind::Modify(_) => FileChangeKind::Modify,
+            EventKind::Access(AccessKind::Close(AccessMode::Write)) => FileChangeKind::Modify,
             EventKind::Remove(_) => FileChangeKind::Delete,
             EventKind::Any => FileChangeKind::Other,
             _ => FileChangeKind::Other,
@@ -174,7 +924,6 @@ impl FileChangeEvent {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs::File;
     use std::io::Write;
     use tempfile::NamedTempFile;
 
@@ -184,16 +933,186 @@ mod tests {
         writeln!(temp_file, "test content").unwrap();
 
         // Never indexed before
-        let result = is_file_modified(temp_file.path(), None).unwrap();
+        let result = is_file_modified(temp_file.path(), None, None).unwrap();
         assert!(result);
 
-        // With last_indexed (always returns true for MVP)
-        let result = is_file_modified(
-            temp_file.path(),
-            Some("2025-01-01T00:00:00Z"),
-        )
-        .unwrap();
-        assert!(result);
+        // Indexed before the file was written
+        let before: DateTime<Utc> = "2025-01-01T00:00:00Z".parse().unwrap();
+        assert!(is_file_modified(temp_file.path(), Some(before), None).unwrap());
+
+        // Indexed after the file was written, even within the same second
+        temp_file.flush().unwrap();
+        assert!(!is_file_modified(temp_file.path(), Some(Utc::now()), None).unwrap());
+
+        // A recorded hash takes precedence over timestamps
+        let future: DateTime<Utc> = "2999-01-01T00:00:00Z".parse().unwrap();
+        let hash = calculate_file_hash(temp_file.path()).unwrap();
+        assert!(!is_file_modified(temp_file.path(), None, Some(&hash)).unwrap());
+        assert!(is_file_modified(temp_file.path(), Some(future), Some(&[0; 32])).unwrap());
+    }
+
+    #[test]
+    fn test_reindex_changed_files() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let db_file = NamedTempFile::new().unwrap();
+        let conn = crate::storage::init_schema(db_file.path()).unwrap();
+
+        let kept = temp_dir.path().join("kept.py");
+        let removed = temp_dir.path().join("removed.py");
+        fs::write(&kept, "x = 1\n").unwrap();
+        fs::write(&removed, "y = 1\n").unwrap();
+
+        fn analyzer(source: &str) -> Result<crate::registry::AnalyzerOutput, crate::IndexingError> {
+            let symbols = vec![crate::Symbol {
+                id: None,
+                file_id: 0,
+                name: source.trim().to_string(),
+                kind: crate::SymbolKind::Variable,
+                line_start: 0,
+                line_end: 0,
+                column_start: 0,
+                column_end: 0,
+                scope: None,
+                metadata: None,
+            }];
+            Ok((symbols, Vec::new()))
+        }
+        let mut registry = AnalyzerRegistry::new();
+        registry.register("python", analyzer);
+        let config = IndexerConfig {
+            root_dirs: vec![temp_dir.path().to_path_buf()],
+            ..Default::default()
+        };
+
+        // First pass indexes everything as new
+        let stats = reindex_changed_files(&conn, &config, &registry).unwrap();
+        assert_eq!(stats, ReindexStats { checked: 2, reindexed: 2, deleted: 0 });
+
+        // Unchanged content is skipped even if the timestamps look stale
+        conn.execute("UPDATE files SET last_indexed = '2000-01-01 00:00:00'", []).unwrap();
+        let stats = reindex_changed_files(&conn, &config, &registry).unwrap();
+        assert_eq!(stats, ReindexStats { checked: 2, reindexed: 0, deleted: 0 });
+
+        // Same-size edit is detected; deleted files are dropped
+        fs::write(&kept, "x = 2\n").unwrap();
+        fs::remove_file(&removed).unwrap();
+
+        let stats = reindex_changed_files(&conn, &config, &registry).unwrap();
+        assert_eq!(stats, ReindexStats { checked: 1, reindexed: 1, deleted: 1 });
+        assert_eq!(list_files(&conn).unwrap().len(), 1);
+    }
+
+    fn symbol(name: &str, kind: crate::SymbolKind, line: usize) -> crate::Symbol {
+        crate::Symbol {
+            id: None,
+            file_id: 0,
+            name: name.to_string(),
+            kind,
+            line_start: line,
+            line_end: line,
+            column_start: 0,
+            column_end: 0,
+            scope: None,
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_diff_symbols() {
+        use crate::SymbolKind::{Class, Function};
+
+        let old = vec![symbol("load", Function, 0), symbol("Config", Class, 4), symbol("save", Function, 9)];
+        let new = vec![
+            symbol("load", Function, 0),
+            symbol("Config", Function, 4),
+            symbol("save", Function, 12),
+            symbol("load", Function, 0),
+        ];
+
+        let diff = diff_symbols(&old, &new);
+        assert_eq!(diff.unchanged, vec![symbol("load", Function, 0)]);
+        // Kind and line changes count as remove + add; the repeated `load` is new
+        assert_eq!(
+            diff.added,
+            vec![symbol("Config", Function, 4), symbol("save", Function, 12), symbol("load", Function, 0)]
+        );
+        assert_eq!(diff.removed, vec![symbol("Config", Class, 4), symbol("save", Function, 9)]);
+
+        assert_eq!(diff_symbols(&[], &[]), SymbolDiff::default());
+        assert_eq!(diff_symbols(&old, &[]).removed, old);
+    }
+
+    #[test]
+    fn test_reindex_file_returns_diff() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let db_file = NamedTempFile::new().unwrap();
+        let conn = crate::storage::init_schema(db_file.path()).unwrap();
+
+        // One variable per non-empty line, named after the line
+        fn analyzer(source: &str) -> Result<crate::registry::AnalyzerOutput, crate::IndexingError> {
+            let symbols = source
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| !line.is_empty())
+                .map(|(i, line)| symbol(line, crate::SymbolKind::Variable, i))
+                .collect();
+            Ok((symbols, Vec::new()))
+        }
+        let mut registry = AnalyzerRegistry::new();
+        registry.register("python", analyzer);
+
+        let file = temp_dir.path().join("module.py");
+        let path = file.to_str().unwrap();
+        fs::write(&file, "alpha\nbeta\n").unwrap();
+        let diff = reindex_file(&conn, path, &registry, None).unwrap().unwrap();
+        assert_eq!(diff.added.len(), 2);
+        assert!(diff.removed.is_empty());
+
+        fs::write(&file, "alpha\n\nbeta\ngamma\n").unwrap();
+        let diff = reindex_file(&conn, path, &registry, None).unwrap().unwrap();
+        let names = |symbols: &[crate::Symbol]| symbols.iter().map(|s| s.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&diff.unchanged), vec!["alpha"]);
+        assert_eq!(names(&diff.added), vec!["beta", "gamma"]);
+        assert_eq!(names(&diff.removed), vec!["beta"]);
+        assert!(diff.added.iter().all(|s| s.file_id == diff.removed[0].file_id));
+
+        let unsupported = temp_dir.path().join("notes.txt");
+        fs::write(&unsupported, "text").unwrap();
+        assert!(reindex_file(&conn, unsupported.to_str().unwrap(), &registry, None).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_is_read_only_access() {
+        assert!(is_read_only_access(&EventKind::Access(AccessKind::Open(AccessMode::Any))));
+        assert!(is_read_only_access(&EventKind::Access(AccessKind::Close(AccessMode::Read))));
+        assert!(!is_read_only_access(&EventKind::Access(AccessKind::Close(AccessMode::Write))));
+        assert!(!is_read_only_access(&EventKind::Modify(notify::event::ModifyKind::Any)));
+    }
+
+    #[test]
+    fn test_input_edit() {
+        let edit = input_edit("fn a() {}\nfn b() {}\n", "fn a() {}\nfn bc() {}\n");
+        assert_eq!((edit.start_byte, edit.old_end_byte, edit.new_end_byte), (14, 14, 15));
+        assert_eq!(edit.start_position, Point { row: 1, column: 4 });
+        assert_eq!(edit.new_end_position, Point { row: 1, column: 5 });
+
+        // Repeated text: the prefix and suffix must not overlap
+        let edit = input_edit("aaa", "aaaa");
+        assert_eq!((edit.start_byte, edit.old_end_byte, edit.new_end_byte), (3, 3, 4));
+
+        let edit = input_edit("x\ny\nz", "x\nz");
+        assert_eq!((edit.start_byte, edit.old_end_byte, edit.new_end_byte), (2, 4, 2));
+        assert_eq!(edit.old_end_position, Point { row: 2, column: 0 });
+    }
+
+    #[test]
+    fn test_merge_change_kind() {
+        use FileChangeKind::*;
+
+        assert_eq!(merge_change_kind(Modify, Other), Modify);
+        assert_eq!(merge_change_kind(Create, Modify), Create);
+        assert_eq!(merge_change_kind(Create, Delete), Delete);
+        assert_eq!(merge_change_kind(Other, Modify), Modify);
     }
 
     #[test]
@@ -203,6 +1122,128 @@ mod tests {
         temp_file.flush().unwrap();
 
         let hash = calculate_file_hash(temp_file.path()).unwrap();
-        assert!(hash > 0);
+        assert_eq!(hash, content_hash(b"test content\n"));
+        assert_ne!(hash, content_hash(b"test_content\n"));
+
+        // Known SHA-256 vector for the empty input
+        assert_eq!(content_hash(b"")[..4], [0xe3, 0xb0, 0xc4, 0x42]);
+    }
+
+    #[tokio::test]
+    async fn test_file_watcher_multiple_subscribers() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut watcher = FileWatcher::new(temp_dir.path()).unwrap();
+        watcher.watch(temp_dir.path()).unwrap();
+
+        let mut first = watcher.subscribe();
+        let mut second = watcher.subscribe();
+
+        let file_path = temp_dir.path().join("watched.py");
+        fs::write(&file_path, "x = 1\n").unwrap();
+
+        let timeout = std::time::Duration::from_secs(5);
+        let a = tokio::time::timeout(timeout, first.recv()).await.unwrap().unwrap();
+        let b = tokio::time::timeout(timeout, second.recv()).await.unwrap().unwrap();
+
+        assert_eq!(a.kind, b.kind);
+        assert_eq!(a.paths, b.paths);
+        assert!(a.paths.iter().any(|p| p.ends_with("watched.py")));
+    }
+
+    #[test]
+    fn test_debounced_file_watcher_batches_burst() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        assert_eq!(DebouncedFileWatcher::new(temp_dir.path()).unwrap().debounce_ms(), DEFAULT_DEBOUNCE_MS);
+
+        let mut watcher = DebouncedFileWatcher::with_debounce(temp_dir.path(), 200).unwrap();
+        watcher.watch(temp_dir.path()).unwrap();
+        assert!(watcher.try_next_batch().is_none());
+
+        let file_path = temp_dir.path().join("watched.py");
+        for i in 0..5 {
+            fs::write(&file_path, format!("x = {}\n", i)).unwrap();
+        }
+
+        let batch = watcher.next_batch_timeout(Duration::from_secs(5)).unwrap();
+        let watched: Vec<_> = batch.iter().filter(|e| e.paths[0].ends_with("watched.py")).collect();
+        assert_eq!(watched.len(), 1);
+        assert_eq!(watched[0].kind, FileChangeKind::Create);
+        assert!(batch.iter().all(|e| e.paths.len() == 1));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_incremental_index_reindexes_changes() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let db_file = NamedTempFile::new().unwrap();
+
+        fn analyzer(source: &str) -> Result<crate::registry::AnalyzerOutput, crate::IndexingError> {
+            let symbols = source
+                .lines()
+                .enumerate()
+                .map(|(line, name)| crate::Symbol {
+                    id: None,
+                    file_id: 0,
+                    name: name.to_string(),
+                    kind: crate::SymbolKind::Variable,
+                    line_start: line,
+                    line_end: line,
+                    column_start: 0,
+                    column_end: 0,
+                    scope: None,
+                    metadata: None,
+                })
+                .collect();
+            Ok((symbols, Vec::new()))
+        }
+        let mut registry = AnalyzerRegistry::new();
+        registry.register("python", analyzer);
+        let config = IndexerConfig {
+            root_dirs: vec![temp_dir.path().to_path_buf()],
+            ..Default::default()
+        };
+
+        let handle = IncrementalIndex::new(config, db_file.path())
+            .unwrap()
+            .with_registry(registry)
+            .start()
+            .unwrap();
+
+        let file = temp_dir.path().join("watched.py");
+        fs::write(&file, "alpha\nbeta\n").unwrap();
+
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while handle.stats().reindexed == 0 && std::time::Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+
+        let stats = handle.stats();
+        assert!(stats.events > 0);
+        assert!(stats.reindexed > 0);
+
+        let conn = init_schema(db_file.path()).unwrap();
+        let symbols = crate::query::find_symbols_by_file_path(&conn, file.to_str().unwrap()).unwrap();
+        let names: Vec<_> = symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["alpha", "beta"]);
+
+        let log = crate::query::query_change_log(&conn, "1970-01-01").unwrap();
+        let entry = log.iter().find(|e| e.file_path == file.to_str().unwrap()).unwrap();
+        assert_eq!(entry.symbols_added, 2);
+        assert_eq!(entry.symbols_removed, 0);
+
+        // Deleting the file is logged with the symbols it took with it
+        fs::remove_file(&file).unwrap();
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while handle.stats().deleted == 0 && std::time::Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+
+        let log = crate::query::query_change_log(&conn, "1970-01-01").unwrap();
+        let last = log.last().unwrap();
+        assert_eq!(last.event_kind, "delete");
+        assert_eq!(last.symbols_removed, 2);
+
+        handle.stop();
+        let stats = handle.join().await.unwrap();
+        assert_eq!(stats.deleted, 1);
     }
 }
@@ -2,13 +2,18 @@
 // Implements recursive directory walk, language detection, and progress reporting
 
 use anyhow::{Context, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::fs;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use rayon::prelude::*;
 
-use crate::{detect_language, FileMetadata};
+use crate::registry::AnalyzerRegistry;
+use crate::{detect_language, FileMetadata, ParseError, Symbol};
 
 /// Callback for progress reporting during indexing
 pub type ProgressCallback = Box<dyn Fn(usize, usize) + Send + Sync>;
@@ -16,8 +21,8 @@ pub type ProgressCallback = Box<dyn Fn(usize, usize) + Send + Sync>;
 /// Indexer configuration
 #[derive(Debug, Clone)]
 pub struct IndexerConfig {
-    /// Root directory to index
-    pub root_dir: PathBuf,
+    /// Root directories to index (e.g. the packages of a monorepo)
+    pub root_dirs: Vec<PathBuf>,
 
     /// File extensions to index (empty = all supported languages)
     pub extensions: Vec<String>,
@@ -25,14 +30,26 @@ pub struct IndexerConfig {
     /// Directories to exclude
     pub exclude_dirs: Vec<String>,
 
+    /// Skip files matched by `.gitignore`, `.git/info/exclude` and the global excludes file
+    pub gitignore: bool,
+
+    /// Skip files matched by the glob patterns in each root's `.contextaignore`
+    pub use_contextaignore: bool,
+
     /// Maximum file size in bytes (skip larger files)
     pub max_file_size: u64,
+
+    /// Maximum symbols stored per file (None = unlimited)
+    pub max_symbols_per_file: Option<usize>,
+
+    /// Target Python version as `(major, minor)` (None = accept any syntax)
+    pub python_version: Option<(u8, u8)>,
 }
 
 impl Default for IndexerConfig {
     fn default() -> Self {
         Self {
-            root_dir: PathBuf::from("."),
+            root_dirs: vec![PathBuf::from(".")],
             extensions: vec![],
             exclude_dirs: vec![
                 ".git".to_string(),
@@ -45,44 +62,301 @@ impl Default for IndexerConfig {
                 "build".to_string(),
                 ".next".to_string(),
             ],
+            gitignore: true,
+            use_contextaignore: true,
             max_file_size: 10 * 1024 * 1024, // 10 MB
+            max_symbols_per_file: None,
+            python_version: None,
+        }
+    }
+}
+
+/// One invalid `IndexerConfig` field
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{field}: {message}")]
+pub struct ConfigError {
+    pub field: String,
+    pub message: String,
+}
+
+impl ConfigError {
+    fn new(field: &str, message: impl Into<String>) -> Self {
+        Self {
+            field: field.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl IndexerConfig {
+    /// The first root directory
+    #[deprecated(note = "use `root_dirs`, which may list several roots")]
+    pub fn root_dir(&self) -> &Path {
+        self.root_dirs.first().map_or(Path::new("."), PathBuf::as_path)
+    }
+
+    /// The innermost root directory containing `path`
+    pub(crate) fn root_for(&self, path: &Path) -> Option<&Path> {
+        self.root_dirs
+            .iter()
+            .filter(|root| path.starts_with(root))
+            .max_by_key(|root| root.components().count())
+            .map(PathBuf::as_path)
+    }
+
+    /// Check the configuration before indexing, reporting every problem at once
+    pub fn validate(&self) -> std::result::Result<(), Vec<ConfigError>> {
+        let mut errors = Vec::new();
+
+        if self.root_dirs.is_empty() {
+            errors.push(ConfigError::new("root_dirs", "at least one root directory is required"));
+        }
+        for root in &self.root_dirs {
+            if !root.exists() {
+                errors.push(ConfigError::new(
+                    "root_dirs",
+                    format!("{} does not exist", root.display()),
+                ));
+            } else if !root.is_dir() {
+                errors.push(ConfigError::new(
+                    "root_dirs",
+                    format!("{} is not a directory", root.display()),
+                ));
+            }
+        }
+
+        if self.max_file_size == 0 {
+            errors.push(ConfigError::new("max_file_size", "must be greater than 0"));
+        }
+
+        // Extensions are compared against `Path::extension`, which has no leading dot
+        for ext in &self.extensions {
+            if ext.is_empty() {
+                errors.push(ConfigError::new("extensions", "empty extension"));
+            } else if ext.contains('.') {
+                errors.push(ConfigError::new(
+                    "extensions",
+                    format!("`{}` must not contain `.` (use `{}`)", ext, ext.trim_start_matches('.')),
+                ));
+            } else if ext.contains(['*', '?', '[', ']', '{', '}']) {
+                errors.push(ConfigError::new("extensions", format!("`{}` must not contain wildcards", ext)));
+            } else if ext.contains(['/', '\\']) {
+                errors.push(ConfigError::new("extensions", format!("`{}` must not contain path separators", ext)));
+            }
+        }
+
+        // Excluded directories are matched by name, not by path
+        for dir in &self.exclude_dirs {
+            if dir.contains(['/', '\\']) {
+                errors.push(ConfigError::new(
+                    "exclude_dirs",
+                    format!("`{}` must be a directory name without path separators", dir),
+                ));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Gitignore and `.contextaignore` rules that apply under `IndexerConfig::root_dirs`
+///
+/// Every `.gitignore` is matched relative to its own directory, and deeper
+/// files take precedence, as in git. `.git/info/exclude` and the global
+/// excludes file (`core.excludesFile`) apply below all `.gitignore` files.
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreMatcher {
+    /// Ordered from the most to the least specific
+    rules: Vec<Gitignore>,
+
+    /// `.contextaignore` patterns of each root, matched against paths relative to it
+    contextaignore: Vec<(PathBuf, GlobSet)>,
+}
+
+impl IgnoreMatcher {
+    /// Collect the ignore files for `config`
+    ///
+    /// Gitignore rules are only read if `config.gitignore` is set, and
+    /// `.contextaignore` files only if `config.use_contextaignore` is.
+    pub fn new(config: &IndexerConfig) -> Result<Self> {
+        let mut contextaignore = Vec::new();
+        if config.use_contextaignore {
+            for root in &config.root_dirs {
+                let patterns = load_contextaignore(root)?;
+                if !patterns.is_empty() {
+                    contextaignore.push((root.clone(), build_glob_set(root, &patterns)?));
+                }
+            }
+        }
+
+        if !config.gitignore {
+            return Ok(Self {
+                rules: Vec::new(),
+                contextaignore,
+            });
+        }
+
+        let mut rules = Vec::new();
+        for root in config.root_dirs.iter().filter(|root| root.is_dir()) {
+            collect_gitignores(root, config, &mut rules)?;
+        }
+
+        // Deeper directories override their parents
+        rules.sort_by_key(|rule: &Gitignore| std::cmp::Reverse(rule.path().components().count()));
+
+        // A matcher only applies below its own root, so each root gets its own
+        for root in &config.root_dirs {
+            let mut repo = GitignoreBuilder::new(root);
+            let exclude = root.join(".git").join("info").join("exclude");
+            if exclude.is_file() {
+                if let Some(err) = repo.add(&exclude) {
+                    eprintln!("Ignoring invalid patterns in {}: {}", exclude.display(), err);
+                }
+                rules.push(repo.build().context("Failed to build .git/info/exclude matcher")?);
+            }
+
+            let (global, err) = GitignoreBuilder::new(root).build_global();
+            if let Some(err) = err {
+                eprintln!("Ignoring invalid global gitignore patterns: {}", err);
+            }
+            if !global.is_empty() {
+                rules.push(global);
+            }
+        }
+
+        Ok(Self { rules, contextaignore })
+    }
+
+    /// Whether `path` (or one of its parent directories) is ignored
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        for rule in &self.rules {
+            if !path.starts_with(rule.path()) {
+                continue;
+            }
+            match rule.matched_path_or_any_parents(path, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+        false
+    }
+
+    /// Whether `path` matches a `.contextaignore` pattern of a root containing it
+    pub fn is_contextaignored(&self, path: &Path) -> bool {
+        self.contextaignore.iter().any(|(root, globs)| {
+            path.strip_prefix(root).is_ok_and(|relative| globs.is_match(relative))
+        })
+    }
+}
+
+/// Read the glob patterns listed in `root/.contextaignore`
+///
+/// Blank lines and `#` comments are skipped. A missing file yields no patterns.
+pub fn load_contextaignore(root: &Path) -> Result<Vec<String>> {
+    let path = root.join(".contextaignore");
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+
+    let contents = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(String::from)
+        .collect())
+}
+
+/// Compile `.contextaignore` patterns, skipping invalid ones
+fn build_glob_set(root: &Path, patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        match Glob::new(pattern) {
+            Ok(glob) => {
+                builder.add(glob);
+            }
+            Err(err) => eprintln!(
+                "Ignoring invalid pattern in {}: {}",
+                root.join(".contextaignore").display(),
+                err
+            ),
+        }
+    }
+    builder.build().context("Failed to build .contextaignore matcher")
+}
+
+/// Build a matcher for each `.gitignore` under `dir`, skipping excluded directories
+fn collect_gitignores(dir: &Path, config: &IndexerConfig, rules: &mut Vec<Gitignore>) -> Result<()> {
+    let gitignore = dir.join(".gitignore");
+    if gitignore.is_file() {
+        let (rule, err) = Gitignore::new(&gitignore);
+        if let Some(err) = err {
+            eprintln!("Ignoring invalid patterns in {}: {}", gitignore.display(), err);
+        }
+        rules.push(rule);
+    }
+
+    for entry in fs::read_dir(dir).context("Failed to read directory")? {
+        let path = entry.context("Failed to read directory entry")?.path();
+        if path.is_dir() && !is_excluded_dir(&path, config) {
+            collect_gitignores(&path, config, rules)?;
         }
     }
+
+    Ok(())
 }
 
-/// Discover all indexable files in a directory
+/// Whether a directory's name is listed in `config.exclude_dirs`
+fn is_excluded_dir(dir: &Path, config: &IndexerConfig) -> bool {
+    dir.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|name| config.exclude_dirs.iter().any(|d| d == name))
+}
+
+/// Discover all indexable files under every root directory
+///
+/// Missing roots are skipped, and files reachable from several (nested) roots
+/// are listed once.
 pub fn discover_files(config: &IndexerConfig) -> Result<Vec<PathBuf>> {
     let mut files = Vec::new();
-    discover_files_recursive(&config.root_dir, config, &mut files)?;
+    let ignore = IgnoreMatcher::new(config)?;
+    for root in &config.root_dirs {
+        discover_files_recursive(root, config, &ignore, &mut files)?;
+    }
+
+    let mut seen = HashSet::new();
+    files.retain(|path| seen.insert(path.clone()));
     Ok(files)
 }
 
 fn discover_files_recursive(
     dir: &Path,
     config: &IndexerConfig,
+    ignore: &IgnoreMatcher,
     files: &mut Vec<PathBuf>,
 ) -> Result<()> {
     if !dir.is_dir() {
         return Ok(());
     }
 
-    // Check if directory should be excluded
-    if let Some(dir_name) = dir.file_name().and_then(|n| n.to_str()) {
-        if config.exclude_dirs.contains(&dir_name.to_string()) {
-            return Ok(());
-        }
-    }
-
     for entry in fs::read_dir(dir).context("Failed to read directory")? {
         let entry = entry.context("Failed to read directory entry")?;
         let path = entry.path();
 
         if path.is_dir() {
-            // Recursively index subdirectories
-            discover_files_recursive(&path, config, files)?;
+            // Exclusions apply below a root, so a root named e.g. `build` is still indexed
+            if !is_excluded_dir(&path, config) && !ignore.is_ignored(&path, true) {
+                discover_files_recursive(&path, config, ignore, files)?;
+            }
         } else if path.is_file() {
             // Check if file should be indexed
-            if should_index_file(&path, config)? {
+            if should_index_file(&path, config, ignore)? {
                 files.push(path);
             }
         }
@@ -91,7 +365,11 @@ fn discover_files_recursive(
     Ok(())
 }
 
-fn should_index_file(path: &Path, config: &IndexerConfig) -> Result<bool> {
+pub(crate) fn should_index_file(path: &Path, config: &IndexerConfig, ignore: &IgnoreMatcher) -> Result<bool> {
+    if ignore.is_ignored(path, false) || ignore.is_contextaignored(path) {
+        return Ok(false);
+    }
+
     // Check file size
     let metadata = fs::metadata(path).context("Failed to read file metadata")?;
     if metadata.len() > config.max_file_size {
@@ -115,6 +393,19 @@ fn should_index_file(path: &Path, config: &IndexerConfig) -> Result<bool> {
     Ok(true)
 }
 
+/// Cap `symbols` at `limit`, keeping the first ones in source order
+///
+/// Returns `true` if symbols were dropped.
+pub fn truncate_symbols(symbols: &mut Vec<Symbol>, limit: Option<usize>) -> bool {
+    match limit {
+        Some(limit) if symbols.len() > limit => {
+            symbols.truncate(limit);
+            true
+        }
+        _ => false,
+    }
+}
+
 /// Create FileMetadata from a file path
 pub fn create_file_metadata(path: &Path) -> Result<FileMetadata> {
     let metadata = fs::metadata(path).context("Failed to read file metadata")?;
@@ -130,6 +421,8 @@ pub fn create_file_metadata(path: &Path) -> Result<FileMetadata> {
         size: metadata.len(),
         last_indexed: None,
         parse_errors: 0,
+        index_duration_ms: None,
+        metadata: None,
     })
 }
 
@@ -263,7 +556,7 @@ pub fn reindex_files_parallel(
 /// Handle a single file change event for incremental indexing
 pub fn handle_file_change(path: &Path, config: &IndexerConfig) -> Result<Option<FileMetadata>> {
     // Check if file should be indexed
-    if !should_index_file(path, config)? {
+    if !should_index_file(path, config, &IgnoreMatcher::new(config)?)? {
         return Ok(None);
     }
 
@@ -271,6 +564,88 @@ pub fn handle_file_change(path: &Path, config: &IndexerConfig) -> Result<Option<
     create_file_metadata(path).map(Some)
 }
 
+/// Symbols extracted from a single file without touching the database
+#[derive(Debug, Clone)]
+pub struct AnalysisResult {
+    pub language: String,
+    pub symbols: Vec<Symbol>,
+    /// Syntax errors, attributed to the analyzed file
+    pub parse_errors: Vec<ParseError>,
+}
+
+/// Discover, read and analyze every file under `root` (sequential).
+///
+/// Files are dispatched to the analyzer registered for their language;
+/// files without an analyzer, unreadable files and analyzer failures are skipped.
+pub fn analyze_directory(
+    root: &Path,
+    config: &IndexerConfig,
+    registry: &AnalyzerRegistry,
+) -> Result<HashMap<PathBuf, AnalysisResult>> {
+    let config = IndexerConfig {
+        root_dirs: vec![root.to_path_buf()],
+        ..config.clone()
+    };
+    let files = discover_files(&config)?;
+
+    Ok(files
+        .into_iter()
+        .filter_map(|path| {
+            let result = analyze_file(&path, registry)?;
+            Some((path, result))
+        })
+        .collect())
+}
+
+/// Discover, read and analyze every file under `root` (parallel with rayon)
+pub fn analyze_directory_parallel(
+    root: &Path,
+    config: &IndexerConfig,
+    registry: &AnalyzerRegistry,
+) -> Result<HashMap<PathBuf, AnalysisResult>> {
+    let config = IndexerConfig {
+        root_dirs: vec![root.to_path_buf()],
+        ..config.clone()
+    };
+    let files = discover_files(&config)?;
+
+    Ok(files
+        .into_par_iter()
+        .filter_map(|path| {
+            let result = analyze_file(&path, registry)?;
+            Some((path, result))
+        })
+        .collect())
+}
+
+fn analyze_file(path: &Path, registry: &AnalyzerRegistry) -> Option<AnalysisResult> {
+    let language = detect_language(path.to_str()?)?;
+    let analyzer = registry.get(language)?;
+
+    let source = match fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("Failed to read {}: {}", path.display(), e);
+            return None;
+        }
+    };
+
+    match analyzer(&source) {
+        Ok((symbols, errors)) => Some(AnalysisResult {
+            language: language.to_string(),
+            symbols,
+            parse_errors: errors
+                .into_iter()
+                .map(|error| error.with_file_path(path.to_string_lossy()))
+                .collect(),
+        }),
+        Err(e) => {
+            eprintln!("Failed to analyze {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -286,17 +661,19 @@ mod tests {
         File::create(temp_path.join("test.py")).unwrap();
         File::create(temp_path.join("test.ts")).unwrap();
         File::create(temp_path.join("test.rs")).unwrap();
+        File::create(temp_path.join("deploy.sh")).unwrap();
         File::create(temp_path.join("test.txt")).unwrap();
 
         let config = IndexerConfig {
-            root_dir: temp_path.to_path_buf(),
+            root_dirs: vec![temp_path.to_path_buf()],
             ..Default::default()
         };
 
         let files = discover_files(&config).unwrap();
 
-        // Should find 3 files (py, ts, rs) but not txt
-        assert_eq!(files.len(), 3);
+        // Should find 4 files (py, ts, rs, and sh without an analyzer) but not txt
+        assert_eq!(files.len(), 4);
+        assert!(files.iter().any(|f| f.ends_with("deploy.sh")));
     }
 
     #[test]
@@ -310,7 +687,7 @@ mod tests {
         File::create(temp_path.join("main.js")).unwrap();
 
         let config = IndexerConfig {
-            root_dir: temp_path.to_path_buf(),
+            root_dirs: vec![temp_path.to_path_buf()],
             ..Default::default()
         };
 
@@ -321,6 +698,115 @@ mod tests {
         assert!(files[0].ends_with("main.js"));
     }
 
+    #[test]
+    fn test_discover_files_multiple_roots() {
+        let temp_a = TempDir::new().unwrap();
+        let temp_b = TempDir::new().unwrap();
+        // A root whose name matches `exclude_dirs` is still indexed
+        let build_root = temp_b.path().join("build");
+        fs::create_dir_all(build_root.join("build")).unwrap();
+        File::create(temp_a.path().join("a.py")).unwrap();
+        File::create(build_root.join("b.rs")).unwrap();
+        File::create(build_root.join("build/generated.rs")).unwrap();
+
+        let config = IndexerConfig {
+            root_dirs: vec![temp_a.path().to_path_buf(), build_root.clone(), temp_a.path().to_path_buf()],
+            ..Default::default()
+        };
+
+        let files = discover_files(&config).unwrap();
+
+        assert_eq!(files.len(), 2);
+        assert!(files.iter().any(|f| f.starts_with(temp_a.path()) && f.ends_with("a.py")));
+        assert!(files.iter().any(|f| f.starts_with(&build_root) && f.ends_with("b.rs")));
+        assert_eq!(config.root_for(&build_root.join("b.rs")), Some(build_root.as_path()));
+    }
+
+    #[test]
+    fn test_gitignore_patterns() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        fs::write(root.join(".gitignore"), "**/*.min.js\nout/\n!keep.min.js\n").unwrap();
+        fs::create_dir_all(root.join("out")).unwrap();
+        fs::create_dir_all(root.join("web/vendor")).unwrap();
+        fs::create_dir_all(root.join(".git/info")).unwrap();
+        fs::write(root.join(".git/info/exclude"), "scratch.py\n").unwrap();
+        fs::write(root.join("web/.gitignore"), "vendor/\n").unwrap();
+        for file in ["app.js", "app.min.js", "keep.min.js", "out/gen.js", "web/ui.js", "web/lib.min.js", "web/vendor/x.js", "scratch.py"] {
+            File::create(root.join(file)).unwrap();
+        }
+
+        let config = IndexerConfig {
+            root_dirs: vec![root.to_path_buf()],
+            ..Default::default()
+        };
+        let mut found: Vec<String> = discover_files(&config)
+            .unwrap()
+            .iter()
+            .map(|p| p.strip_prefix(root).unwrap().to_string_lossy().replace('\\', "/"))
+            .collect();
+        found.sort();
+        assert_eq!(found, ["app.js", "keep.min.js", "web/ui.js"]);
+
+        // Disabled: only `exclude_dirs` applies
+        let config = IndexerConfig { gitignore: false, ..config };
+        assert_eq!(discover_files(&config).unwrap().len(), 8);
+    }
+
+    #[test]
+    fn test_contextaignore() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        assert!(load_contextaignore(root).unwrap().is_empty());
+
+        fs::write(root.join(".contextaignore"), "# generated code\n**/*_pb2.py\n\n  fixtures/**  \n[\n").unwrap();
+        fs::create_dir_all(root.join("api/fixtures")).unwrap();
+        fs::create_dir_all(root.join("fixtures")).unwrap();
+        for file in ["app.py", "api/user_pb2.py", "api/fixtures/data.py", "fixtures/data.py"] {
+            File::create(root.join(file)).unwrap();
+        }
+        assert_eq!(load_contextaignore(root).unwrap(), ["**/*_pb2.py", "fixtures/**", "["]);
+
+        let config = IndexerConfig {
+            root_dirs: vec![root.to_path_buf()],
+            ..Default::default()
+        };
+        let mut found: Vec<String> = discover_files(&config)
+            .unwrap()
+            .iter()
+            .map(|p| p.strip_prefix(root).unwrap().to_string_lossy().replace('\\', "/"))
+            .collect();
+        found.sort();
+        // Patterns are relative to the root, and the invalid `[` is skipped
+        assert_eq!(found, ["api/fixtures/data.py", "app.py"]);
+
+        let config = IndexerConfig { use_contextaignore: false, ..config };
+        assert_eq!(discover_files(&config).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_truncate_symbols() {
+        let symbol = |line| Symbol {
+            id: None,
+            file_id: 1,
+            name: format!("v{}", line),
+            kind: crate::SymbolKind::Variable,
+            line_start: line,
+            line_end: line,
+            column_start: 0,
+            column_end: 0,
+            scope: None,
+            metadata: None,
+        };
+        let mut symbols: Vec<Symbol> = (0..5).map(symbol).collect();
+
+        assert!(!truncate_symbols(&mut symbols, None));
+        assert!(!truncate_symbols(&mut symbols, Some(5)));
+        assert!(truncate_symbols(&mut symbols, Some(2)));
+        assert_eq!(symbols.iter().map(|s| s.line_start).collect::<Vec<_>>(), vec![0, 1]);
+    }
+
     #[test]
     fn test_create_file_metadata() {
         let temp_dir = TempDir::new().unwrap();
@@ -346,7 +832,7 @@ mod tests {
         }
 
         let config = IndexerConfig {
-            root_dir: temp_path.to_path_buf(),
+            root_dirs: vec![temp_path.to_path_buf()],
             ..Default::default()
         };
 
@@ -394,4 +880,136 @@ mod tests {
         let metadata = reindex_files_parallel(&files, None).unwrap();
         assert_eq!(metadata.len(), 5);
     }
+
+    #[test]
+    fn test_analyze_directory_uses_registry() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path();
+
+        fs::write(temp_path.join("a.py"), "x = 1").unwrap();
+        fs::write(temp_path.join("b.rs"), "fn main() {}").unwrap();
+
+        fn count_lines(source: &str) -> Result<crate::registry::AnalyzerOutput, crate::IndexingError> {
+            let symbols = source
+                .lines()
+                .enumerate()
+                .map(|(line, text)| Symbol {
+                    id: None,
+                    file_id: 0,
+                    name: text.to_string(),
+                    kind: crate::SymbolKind::Variable,
+                    line_start: line,
+                    line_end: line,
+                    column_start: 0,
+                    column_end: 0,
+                    scope: None,
+                    metadata: None,
+                })
+                .collect();
+            Ok((symbols, vec![ParseError::new(0, 0, "unexpected `x`")]))
+        }
+
+        let mut registry = AnalyzerRegistry::new();
+        registry.register("python", count_lines);
+
+        let config = IndexerConfig::default();
+        let results = analyze_directory(temp_path, &config, &registry).unwrap();
+
+        // Only the Python file has a registered analyzer
+        assert_eq!(results.len(), 1);
+        let result = &results[&temp_path.join("a.py")];
+        assert_eq!(result.language, "python");
+        assert_eq!(result.symbols[0].name, "x = 1");
+        // Errors are attributed to the analyzed file
+        assert_eq!(result.parse_errors[0].file_path, temp_path.join("a.py").to_string_lossy());
+
+        let parallel = analyze_directory_parallel(temp_path, &config, &registry).unwrap();
+        assert_eq!(parallel.len(), 1);
+    }
+
+    #[test]
+    fn test_validate_accepts_valid_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = IndexerConfig {
+            root_dirs: vec![temp_dir.path().to_path_buf()],
+            extensions: vec!["py".to_string(), "ts".to_string()],
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_root_dirs() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("main.py");
+        File::create(&file_path).unwrap();
+
+        for root_dir in [temp_dir.path().join("missing"), file_path] {
+            let config = IndexerConfig {
+                root_dirs: vec![temp_dir.path().to_path_buf(), root_dir],
+                ..Default::default()
+            };
+            let errors = config.validate().unwrap_err();
+            assert_eq!(errors.len(), 1);
+            assert_eq!(errors[0].field, "root_dirs");
+        }
+
+        let config = IndexerConfig {
+            root_dirs: vec![],
+            ..Default::default()
+        };
+        assert_eq!(config.validate().unwrap_err()[0].field, "root_dirs");
+    }
+
+    #[test]
+    fn test_validate_max_file_size() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = IndexerConfig {
+            root_dirs: vec![temp_dir.path().to_path_buf()],
+            max_file_size: 0,
+            ..Default::default()
+        };
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors, vec![ConfigError::new("max_file_size", "must be greater than 0")]);
+    }
+
+    #[test]
+    fn test_validate_extensions() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = IndexerConfig {
+            root_dirs: vec![temp_dir.path().to_path_buf()],
+            extensions: ["rs", ".py", "*.ts", "", "src/js", "t?x"].iter().map(|e| e.to_string()).collect(),
+            ..Default::default()
+        };
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 5);
+        assert!(errors.iter().all(|e| e.field == "extensions"));
+        assert_eq!(errors[0].to_string(), "extensions: `.py` must not contain `.` (use `py`)");
+    }
+
+    #[test]
+    fn test_validate_exclude_dirs() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = IndexerConfig {
+            root_dirs: vec![temp_dir.path().to_path_buf()],
+            exclude_dirs: vec!["target".to_string(), "src/generated".to_string(), "out\\tmp".to_string()],
+            ..Default::default()
+        };
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|e| e.field == "exclude_dirs"));
+    }
+
+    #[test]
+    fn test_validate_collects_all_errors() {
+        let config = IndexerConfig {
+            root_dirs: vec![PathBuf::from("/nonexistent/contexta/root")],
+            extensions: vec![".py".to_string()],
+            exclude_dirs: vec!["a/b".to_string()],
+            max_file_size: 0,
+            ..Default::default()
+        };
+        let fields: Vec<String> = config.validate().unwrap_err().into_iter().map(|e| e.field).collect();
+        assert_eq!(fields, vec!["root_dirs", "max_file_size", "extensions", "exclude_dirs"]);
+    }
 }
@@ -5,13 +5,28 @@ pub mod indexer;
 pub mod storage;
 pub mod query;
 pub mod incremental;
+pub mod embedding;
+pub mod registry;
+#[cfg(feature = "lsp")]
+pub mod lsp;
+pub mod maintenance;
+pub mod normalization;
+pub mod pool;
+pub mod scoring;
+pub mod export;
+pub mod snapshot;
 
 // Analysis modules
 pub mod analysis {
     pub mod deep;
 }
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::path::Path;
+use thiserror::Error;
+use tree_sitter::{Node, Tree};
 
 /// Represents a code symbol (function, class, variable, etc.)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -34,6 +49,14 @@ pub struct Symbol {
     /// End line number (0-indexed)
     pub line_end: usize,
 
+    /// Start column (0-indexed)
+    #[serde(default)]
+    pub column_start: usize,
+
+    /// End column (0-indexed)
+    #[serde(default)]
+    pub column_end: usize,
+
     /// Parent scope (JSON array of parent symbol IDs)
     pub scope: Option<String>,
 
@@ -41,13 +64,154 @@ pub struct Symbol {
     pub metadata: Option<String>,
 }
 
+/// Source position of a symbol, as reported by tree-sitter (0-indexed)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SymbolLocation {
+    pub line_start: usize,
+    pub line_end: usize,
+    pub byte_start: usize,
+    pub byte_end: usize,
+    pub column_start: usize,
+    pub column_end: usize,
+}
+
+impl SymbolLocation {
+    /// Capture the span of a syntax node
+    pub fn from_node(node: &Node) -> SymbolLocation {
+        let start = node.start_position();
+        let end = node.end_position();
+
+        SymbolLocation {
+            line_start: start.row,
+            line_end: end.row,
+            byte_start: node.start_byte(),
+            byte_end: node.end_byte(),
+            column_start: start.column,
+            column_end: end.column,
+        }
+    }
+}
+
+/// Build a symbol from `(node, source, kind, scope)`, naming it after the
+/// node's `name` field
+impl TryFrom<(Node<'_>, &str, SymbolKind, Option<&str>)> for Symbol {
+    type Error = anyhow::Error;
+
+    fn try_from(
+        (node, source, kind, scope): (Node<'_>, &str, SymbolKind, Option<&str>),
+    ) -> anyhow::Result<Self> {
+        let name_node = node
+            .child_by_field_name("name")
+            .ok_or_else(|| anyhow::anyhow!("{} node `{}` has no name", kind, node.kind()))?;
+        let name = source
+            .get(name_node.byte_range())
+            .ok_or_else(|| anyhow::anyhow!("Node range is outside the source text"))?;
+
+        Ok(Symbol::with_location(
+            name,
+            kind,
+            SymbolLocation::from_node(&node),
+            scope,
+        ))
+    }
+}
+
+impl Symbol {
+    /// Create an unsaved symbol (no id/file/metadata) at `loc`
+    pub fn with_location(
+        name: impl Into<String>,
+        kind: SymbolKind,
+        loc: SymbolLocation,
+        scope: Option<&str>,
+    ) -> Symbol {
+        Symbol {
+            id: None,
+            file_id: 0,
+            name: name.into(),
+            kind,
+            line_start: loc.line_start,
+            line_end: loc.line_end,
+            column_start: loc.column_start,
+            column_end: loc.column_end,
+            scope: scope.map(|s| s.to_string()),
+            metadata: None,
+        }
+    }
+
+    /// Return a copy shifted by the given line/column deltas (clamped at 0).
+    ///
+    /// Used to patch positions after lines are inserted or removed above the symbol.
+    pub fn relocate(&self, line_delta: i64, col_delta: i64) -> Symbol {
+        let shift = |value: usize, delta: i64| (value as i64 + delta).max(0) as usize;
+
+        Symbol {
+            line_start: shift(self.line_start, line_delta),
+            line_end: shift(self.line_end, line_delta),
+            column_start: shift(self.column_start, col_delta),
+            column_end: shift(self.column_end, col_delta),
+            ..self.clone()
+        }
+    }
+
+    /// Check that the symbol is safe to store
+    ///
+    /// The name must be non-empty, `line_end` must not precede `line_start`,
+    /// and `metadata`, when present, must be valid JSON.
+    pub fn validate(&self) -> Result<(), SymbolValidationError> {
+        if self.name.is_empty() {
+            return Err(SymbolValidationError::new(
+                ValidationErrorKind::EmptyName,
+                "symbol name is empty".to_string(),
+            ));
+        }
+        if self.line_end < self.line_start {
+            return Err(SymbolValidationError::new(
+                ValidationErrorKind::InvalidLineRange,
+                format!("line_end {} is before line_start {}", self.line_end, self.line_start),
+            ));
+        }
+        if let Some(ref metadata) = self.metadata {
+            if let Err(e) = serde_json::from_str::<serde::de::IgnoredAny>(metadata) {
+                return Err(SymbolValidationError::new(
+                    ValidationErrorKind::InvalidMetadata,
+                    format!("metadata is not valid JSON: {}", e),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Which `Symbol` invariant was violated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationErrorKind {
+    EmptyName,
+    InvalidLineRange,
+    InvalidMetadata,
+}
+
+/// A symbol rejected by `Symbol::validate`
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Invalid symbol: {message}")]
+pub struct SymbolValidationError {
+    pub kind: ValidationErrorKind,
+    pub message: String,
+}
+
+impl SymbolValidationError {
+    fn new(kind: ValidationErrorKind, message: String) -> Self {
+        SymbolValidationError { kind, message }
+    }
+}
+
 /// Symbol kind enumeration
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum SymbolKind {
     Function,
     Class,
     Variable,
+    Constant,
     Import,
     Export,
     Module,
@@ -56,6 +220,11 @@ pub enum SymbolKind {
     Trait,
     Interface,
     Type,
+    Field,
+    #[serde(rename = "enum_variant")]
+    EnumVariant,
+    Macro,
+    Namespace,
 }
 
 impl std::fmt::Display for SymbolKind {
@@ -64,6 +233,7 @@ impl std::fmt::Display for SymbolKind {
             SymbolKind::Function => "function",
             SymbolKind::Class => "class",
             SymbolKind::Variable => "variable",
+            SymbolKind::Constant => "constant",
             SymbolKind::Import => "import",
             SymbolKind::Export => "export",
             SymbolKind::Module => "module",
@@ -72,11 +242,142 @@ impl std::fmt::Display for SymbolKind {
             SymbolKind::Trait => "trait",
             SymbolKind::Interface => "interface",
             SymbolKind::Type => "type",
+            SymbolKind::Field => "field",
+            SymbolKind::EnumVariant => "enum_variant",
+            SymbolKind::Macro => "macro",
+            SymbolKind::Namespace => "namespace",
         };
         write!(f, "{}", s)
     }
 }
 
+impl std::str::FromStr for SymbolKind {
+    type Err = anyhow::Error;
+
+    /// Parse the lowercase name produced by `Display` (case-insensitive)
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.to_lowercase().as_str() {
+            "function" => Ok(SymbolKind::Function),
+            "class" => Ok(SymbolKind::Class),
+            "variable" => Ok(SymbolKind::Variable),
+            "constant" => Ok(SymbolKind::Constant),
+            "import" => Ok(SymbolKind::Import),
+            "export" => Ok(SymbolKind::Export),
+            "module" => Ok(SymbolKind::Module),
+            "struct" => Ok(SymbolKind::Struct),
+            "enum" => Ok(SymbolKind::Enum),
+            "trait" => Ok(SymbolKind::Trait),
+            "interface" => Ok(SymbolKind::Interface),
+            "type" => Ok(SymbolKind::Type),
+            "field" => Ok(SymbolKind::Field),
+            "enum_variant" => Ok(SymbolKind::EnumVariant),
+            "macro" => Ok(SymbolKind::Macro),
+            "namespace" => Ok(SymbolKind::Namespace),
+            other => anyhow::bail!("Unknown symbol kind: {}", other),
+        }
+    }
+}
+
+/// Tree-sitter node kinds that produce symbols, per language
+///
+/// `javascript` and `typescript_declaration` share the `typescript` grammar node names.
+const NODE_KIND_TABLE: &[(&str, &str, SymbolKind)] = &[
+    // Python
+    ("python", "function_definition", SymbolKind::Function),
+    ("python", "class_definition", SymbolKind::Class),
+    ("python", "import_statement", SymbolKind::Import),
+    ("python", "import_from_statement", SymbolKind::Import),
+    ("python", "assignment", SymbolKind::Variable),
+    // TypeScript / JavaScript
+    ("typescript", "function_declaration", SymbolKind::Function),
+    ("typescript", "function_signature", SymbolKind::Function),
+    ("typescript", "function", SymbolKind::Function),
+    ("typescript", "arrow_function", SymbolKind::Function),
+    ("typescript", "method_definition", SymbolKind::Function),
+    ("typescript", "method_signature", SymbolKind::Function),
+    ("typescript", "class_declaration", SymbolKind::Class),
+    ("typescript", "class", SymbolKind::Class),
+    ("typescript", "public_field_definition", SymbolKind::Field),
+    ("typescript", "interface_declaration", SymbolKind::Interface),
+    ("typescript", "type_alias_declaration", SymbolKind::Type),
+    ("typescript", "enum_declaration", SymbolKind::Enum),
+    ("typescript", "import_statement", SymbolKind::Import),
+    ("typescript", "export_statement", SymbolKind::Export),
+    ("typescript", "module", SymbolKind::Module),
+    ("typescript", "internal_module", SymbolKind::Module),
+    ("typescript", "lexical_declaration", SymbolKind::Variable),
+    ("typescript", "variable_declaration", SymbolKind::Variable),
+    // Rust
+    ("rust", "function_item", SymbolKind::Function),
+    ("rust", "struct_item", SymbolKind::Struct),
+    ("rust", "enum_item", SymbolKind::Enum),
+    ("rust", "enum_variant", SymbolKind::EnumVariant),
+    ("rust", "trait_item", SymbolKind::Trait),
+    ("rust", "type_item", SymbolKind::Type),
+    ("rust", "use_declaration", SymbolKind::Import),
+    ("rust", "extern_crate_declaration", SymbolKind::Import),
+    ("rust", "function_signature_item", SymbolKind::Function),
+    ("rust", "const_item", SymbolKind::Constant),
+    ("rust", "static_item", SymbolKind::Variable),
+    ("rust", "macro_definition", SymbolKind::Macro),
+    ("rust", "mod_item", SymbolKind::Namespace),
+    // Go
+    ("go", "function_declaration", SymbolKind::Function),
+    ("go", "method_declaration", SymbolKind::Function),
+    ("go", "type_spec", SymbolKind::Type),
+    ("go", "type_alias", SymbolKind::Type),
+    ("go", "const_spec", SymbolKind::Constant),
+    ("go", "var_spec", SymbolKind::Variable),
+    ("go", "import_spec", SymbolKind::Import),
+    // Java
+    ("java", "class_declaration", SymbolKind::Class),
+    ("java", "interface_declaration", SymbolKind::Interface),
+    ("java", "enum_declaration", SymbolKind::Enum),
+    ("java", "record_declaration", SymbolKind::Class),
+    ("java", "annotation_type_declaration", SymbolKind::Interface),
+    ("java", "method_declaration", SymbolKind::Function),
+    ("java", "constructor_declaration", SymbolKind::Function),
+    ("java", "field_declaration", SymbolKind::Field),
+    ("java", "import_declaration", SymbolKind::Import),
+    // C
+    ("c", "function_definition", SymbolKind::Function),
+    ("c", "declaration", SymbolKind::Variable),
+    ("c", "struct_specifier", SymbolKind::Struct),
+    ("c", "union_specifier", SymbolKind::Struct),
+    ("c", "enum_specifier", SymbolKind::Enum),
+    ("c", "enumerator", SymbolKind::EnumVariant),
+    ("c", "preproc_include", SymbolKind::Import),
+    // C++
+    ("cpp", "function_definition", SymbolKind::Function),
+    ("cpp", "declaration", SymbolKind::Variable),
+    ("cpp", "field_declaration", SymbolKind::Field),
+    ("cpp", "class_specifier", SymbolKind::Class),
+    ("cpp", "struct_specifier", SymbolKind::Struct),
+    ("cpp", "union_specifier", SymbolKind::Struct),
+    ("cpp", "enum_specifier", SymbolKind::Enum),
+    ("cpp", "enumerator", SymbolKind::EnumVariant),
+    ("cpp", "namespace_definition", SymbolKind::Namespace),
+    ("cpp", "using_declaration", SymbolKind::Import),
+    ("cpp", "preproc_include", SymbolKind::Import),
+];
+
+impl SymbolKind {
+    /// Map a tree-sitter node kind to the symbol kind it defines
+    ///
+    /// Returns `None` for node kinds that do not produce symbols.
+    pub fn from_file_node_kind(language: &str, node_kind: &str) -> Option<SymbolKind> {
+        let language = match language {
+            "javascript" | "typescript_declaration" => "typescript",
+            other => other,
+        };
+
+        NODE_KIND_TABLE
+            .iter()
+            .find(|(lang, kind, _)| *lang == language && *kind == node_kind)
+            .map(|(_, _, symbol_kind)| *symbol_kind)
+    }
+}
+
 /// File metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileMetadata {
@@ -84,12 +385,197 @@ pub struct FileMetadata {
     pub path: String,
     pub language: String,
     pub size: u64,
-    pub last_indexed: Option<String>,
+    /// When the file was last written to the index (UTC)
+    pub last_indexed: Option<DateTime<Utc>>,
+    /// Number of syntax errors; the errors themselves are in the `parse_errors` table
     pub parse_errors: i32,
+    /// Wall-clock time spent analyzing and storing the file
+    #[serde(default)]
+    pub index_duration_ms: Option<u64>,
+    /// File-level flags as a JSON object (e.g. `{"package_init": true}`)
+    #[serde(default)]
+    pub metadata: Option<String>,
+}
+
+/// A syntax error reported by tree-sitter (0-indexed position)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ParseError {
+    /// Empty until the caller attributes the error to a file
+    pub file_path: String,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+/// Longest source excerpt quoted in a `ParseError` message
+const PARSE_ERROR_EXCERPT_CHARS: usize = 40;
+
+impl ParseError {
+    /// Create an error at `line`/`column` that is not yet attributed to a file
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            file_path: String::new(),
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Collect one error per `ERROR` node and per node the parser had to insert
+    pub fn from_tree(tree: &Tree, source: &str) -> Vec<ParseError> {
+        let mut errors = Vec::new();
+        collect_parse_errors(tree.root_node(), source, &mut errors);
+        errors
+    }
+
+    /// Attribute the error to `file_path`
+    pub fn with_file_path(mut self, file_path: impl Into<String>) -> Self {
+        self.file_path = file_path.into();
+        self
+    }
+
+    /// Error for a subtree the extractor skipped because it is nested `max_depth` levels deep
+    pub fn nesting_too_deep(node: &Node, max_depth: usize) -> Self {
+        let start = node.start_position();
+        ParseError::new(
+            start.row,
+            start.column,
+            format!("nested deeper than {} levels, symbols inside are skipped", max_depth),
+        )
+    }
+}
+
+/// Limits applied by the symbol extractors while walking a parse tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtractConfig {
+    /// Nesting depth at which extraction stops descending, so deeply nested
+    /// source cannot overflow the stack
+    pub max_depth: usize,
+}
+
+impl Default for ExtractConfig {
+    fn default() -> Self {
+        Self { max_depth: 128 }
+    }
+}
+
+fn collect_parse_errors(node: Node, source: &str, errors: &mut Vec<ParseError>) {
+    let start = node.start_position();
+    if node.is_missing() {
+        errors.push(ParseError::new(start.row, start.column, format!("missing `{}`", node.kind())));
+        return;
+    }
+    if node.is_error() {
+        let excerpt: String = source
+            .get(node.byte_range())
+            .and_then(|text| text.lines().map(str::trim).find(|line| !line.is_empty()))
+            .unwrap_or_default()
+            .chars()
+            .take(PARSE_ERROR_EXCERPT_CHARS)
+            .collect();
+        let message = if excerpt.is_empty() {
+            "syntax error".to_string()
+        } else {
+            format!("unexpected `{}`", excerpt)
+        };
+        errors.push(ParseError::new(start.row, start.column, message));
+        return;
+    }
+    if !node.has_error() {
+        return;
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_parse_errors(child, source, errors);
+    }
+}
+
+/// File-level metadata marking Python package entry points
+///
+/// `__init__.py` gets `{"package_init": true}` and `__main__.py`
+/// `{"package_main": true}`; other files get `None`.
+pub fn package_entry_point_metadata(path: &str) -> Option<String> {
+    let file_name = Path::new(path).file_name()?.to_str()?;
+    let flag = match file_name {
+        "__init__.py" => "package_init",
+        "__main__.py" => "package_main",
+        _ => return None,
+    };
+    Some(serde_json::json!({ flag: true }).to_string())
+}
+
+/// A function parameter as stored in `metadata["params"]`
+///
+/// `type_name` is `null` when the source has no annotation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FunctionParam {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_name: Option<String>,
+}
+
+/// Typed view of a function symbol's metadata
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FunctionMetadata {
+    #[serde(default)]
+    pub params: Vec<FunctionParam>,
+    /// Return type annotation as written, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub return_type: Option<String>,
+}
+
+/// Parse the metadata of a `Function` symbol
+///
+/// Parameter lists are only stored when non-empty, so a function without
+/// `params` has none. Returns `None` for other kinds and malformed metadata.
+pub fn parse_function_metadata(symbol: &Symbol) -> Option<FunctionMetadata> {
+    if symbol.kind != SymbolKind::Function {
+        return None;
+    }
+    match symbol.metadata.as_deref() {
+        Some(metadata) => serde_json::from_str(metadata).ok(),
+        None => Some(FunctionMetadata::default()),
+    }
+}
+
+/// Sorted parameter types of a metadata object's `params` array
+///
+/// Whitespace inside a type is dropped and untyped parameters count as `?`.
+pub(crate) fn signature_param_types(metadata: &serde_json::Value) -> Option<Vec<String>> {
+    let mut types: Vec<String> = metadata["params"]
+        .as_array()?
+        .iter()
+        .map(|param| param["type"].as_str().unwrap_or("?").split_whitespace().collect())
+        .collect();
+    types.sort();
+    Some(types)
+}
+
+/// Stable FNV-1a hash of sorted parameter types, as hex
+///
+/// The types are hashed as a JSON array, so `["Map<A,B>"]` and
+/// `["Map<A", "B>"]` hash differently.
+pub(crate) fn signature_hash(types: &[String]) -> String {
+    let serialized = serde_json::to_string(types).unwrap_or_default();
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in serialized.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
 }
 
 /// Language detection based on file extension
+///
+/// Also recognizes languages without an analyzer yet so they are discovered
+/// and counted in language statistics; see `detect_language_has_analyzer`.
 pub fn detect_language(path: &str) -> Option<&'static str> {
+    // Declaration files carry only type information
+    if path.ends_with(".d.ts") {
+        return Some("typescript_declaration");
+    }
+
     let ext = std::path::Path::new(path)
         .extension()
         .and_then(|e| e.to_str())?;
@@ -99,10 +585,79 @@ pub fn detect_language(path: &str) -> Option<&'static str> {
         "ts" | "tsx" => Some("typescript"),
         "js" | "jsx" => Some("javascript"),
         "rs" => Some("rust"),
+        "go" => Some("go"),
+        "java" => Some("java"),
+        "c" | "h" => Some("c"),
+        "cpp" | "cxx" | "cc" | "hpp" => Some("cpp"),
+        "rb" | "rake" | "gemspec" => Some("ruby"),
+        "kt" | "kts" => Some("kotlin"),
+        "swift" => Some("swift"),
+        "cs" => Some("csharp"),
+        "php" | "php5" => Some("php"),
+        "lua" => Some("lua"),
+        "sh" | "bash" | "zsh" => Some("shell"),
+        // Templates and config files, indexed without symbols
+        "vue" => Some("vue"),
+        "svelte" => Some("svelte"),
+        "toml" => Some("toml"),
+        "json" => Some("json"),
+        "yaml" | "yml" => Some("yaml"),
         _ => None,
     }
 }
 
+/// Whether symbols can be extracted for a language returned by `detect_language`
+pub fn detect_language_has_analyzer(lang: &str) -> bool {
+    matches!(lang, "python" | "typescript" | "typescript_declaration" | "javascript" | "rust" | "go" | "java" | "c" | "cpp")
+}
+
+/// Errors returned by the public analyzer API (e.g. `analyze_python`)
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum IndexingError {
+    /// The parser could not be set up or failed on the input (`line` is 0-indexed)
+    #[error("Failed to parse {language} source at line {line}: {message}")]
+    ParseError { language: String, line: usize, message: String },
+
+    #[error("Storage error: {0}")]
+    StorageError(String),
+
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Unsupported language `{0}`")]
+    UnsupportedLanguage(String),
+
+    #[error("Failed to extract symbols from {path}: {reason}")]
+    SymbolExtractionFailed { path: String, reason: String },
+}
+
+impl IndexingError {
+    /// Parser setup or parse failure whose position is unknown
+    pub fn parse_failed(language: &str, err: impl Display) -> Self {
+        IndexingError::ParseError {
+            language: language.to_string(),
+            line: 0,
+            message: format!("{:#}", err),
+        }
+    }
+
+    /// Extraction failure for in-memory source, reported with the path `<source>`
+    pub fn extraction_failed(err: impl Display) -> Self {
+        IndexingError::SymbolExtractionFailed {
+            path: "<source>".to_string(),
+            reason: format!("{:#}", err),
+        }
+    }
+}
+
+/// Internal `anyhow` errors surface as storage errors, keeping their context chain
+impl From<anyhow::Error> for IndexingError {
+    fn from(err: anyhow::Error) -> Self {
+        IndexingError::StorageError(format!("{:#}", err))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -111,11 +666,63 @@ mod tests {
     fn test_detect_language() {
         assert_eq!(detect_language("test.py"), Some("python"));
         assert_eq!(detect_language("test.ts"), Some("typescript"));
+        assert_eq!(detect_language("types/index.d.ts"), Some("typescript_declaration"));
         assert_eq!(detect_language("test.js"), Some("javascript"));
         assert_eq!(detect_language("test.rs"), Some("rust"));
+        assert_eq!(detect_language("main.go"), Some("go"));
+        assert!(detect_language_has_analyzer("go"));
+        assert_eq!(detect_language("src/Main.java"), Some("java"));
+        assert!(detect_language_has_analyzer("java"));
+        assert_eq!(detect_language("src/main.c"), Some("c"));
+        assert_eq!(detect_language("include/util.h"), Some("c"));
+        assert!(detect_language_has_analyzer("c"));
+        for path in ["app.cpp", "app.cxx", "app.cc", "app.hpp"] {
+            assert_eq!(detect_language(path), Some("cpp"), "{}", path);
+        }
+        assert!(detect_language_has_analyzer("cpp"));
         assert_eq!(detect_language("test.txt"), None);
     }
 
+    #[test]
+    fn test_package_entry_point_metadata() {
+        assert_eq!(package_entry_point_metadata("pkg/__init__.py").as_deref(), Some(r#"{"package_init":true}"#));
+        assert_eq!(package_entry_point_metadata("__main__.py").as_deref(), Some(r#"{"package_main":true}"#));
+        assert_eq!(package_entry_point_metadata("pkg/__init__.pyi"), None);
+        assert_eq!(package_entry_point_metadata("pkg/init.py"), None);
+    }
+
+    #[test]
+    fn test_parse_function_metadata() {
+        let function = |metadata: Option<&str>| Symbol {
+            metadata: metadata.map(str::to_string),
+            ..Symbol::with_location("f", SymbolKind::Function, SymbolLocation::default(), None)
+        };
+
+        let parsed = parse_function_metadata(&function(Some(
+            r#"{"async":true,"params":[{"name":"x","type":"i32"},{"name":"y","type":null}]}"#,
+        )))
+        .unwrap();
+        assert_eq!(
+            parsed.params,
+            vec![
+                FunctionParam { name: "x".to_string(), type_name: Some("i32".to_string()) },
+                FunctionParam { name: "y".to_string(), type_name: None },
+            ]
+        );
+        assert_eq!(
+            serde_json::to_string(&parsed.params[1]).unwrap(),
+            r#"{"name":"y","type":null}"#
+        );
+
+        assert_eq!(parse_function_metadata(&function(None)), Some(FunctionMetadata::default()));
+        let returning = parse_function_metadata(&function(Some(r#"{"return_type":"str | None"}"#))).unwrap();
+        assert_eq!(returning.return_type.as_deref(), Some("str | None"));
+        assert_eq!(parse_function_metadata(&function(Some(r#"{"test":true}"#))), Some(FunctionMetadata::default()));
+        assert_eq!(parse_function_metadata(&function(Some(r#"{"params":"x"}"#))), None);
+        let class = Symbol { kind: SymbolKind::Class, ..function(None) };
+        assert_eq!(parse_function_metadata(&class), None);
+    }
+
     #[test]
     fn test_detect_language_with_pyi() {
         assert_eq!(detect_language("stubs.pyi"), Some("python"));
@@ -136,17 +743,101 @@ mod tests {
         assert_eq!(detect_language("Makefile"), None);
     }
 
+    #[test]
+    fn test_detect_language_without_analyzer() {
+        let cases = [
+            ("Rakefile.rake", "ruby"),
+            ("app.rb", "ruby"),
+            ("lib.gemspec", "ruby"),
+            ("Main.kt", "kotlin"),
+            ("build.gradle.kts", "kotlin"),
+            ("View.swift", "swift"),
+            ("Program.cs", "csharp"),
+            ("index.php", "php"),
+            ("legacy.php5", "php"),
+            ("init.lua", "lua"),
+            ("setup.sh", "shell"),
+            ("run.bash", "shell"),
+            ("prompt.zsh", "shell"),
+        ];
+        for (path, language) in cases {
+            assert_eq!(detect_language(path), Some(language), "{}", path);
+            assert!(!detect_language_has_analyzer(language), "{}", language);
+        }
+    }
+
+    #[test]
+    fn test_detect_language_templates_and_config() {
+        let cases = [
+            ("src/App.vue", "vue"),
+            ("src/routes/+page.svelte", "svelte"),
+            ("Cargo.toml", "toml"),
+            ("package.json", "json"),
+            ("config.yaml", "yaml"),
+            (".github/workflows/ci.yml", "yaml"),
+        ];
+        for (path, language) in cases {
+            assert_eq!(detect_language(path), Some(language), "{}", path);
+            assert!(!detect_language_has_analyzer(language), "{}", language);
+        }
+    }
+
+    #[test]
+    fn test_detect_language_has_analyzer() {
+        for path in ["a.py", "a.ts", "a.d.ts", "a.js", "a.rs"] {
+            assert!(detect_language_has_analyzer(detect_language(path).unwrap()), "{}", path);
+        }
+        assert!(!detect_language_has_analyzer("unknown"));
+    }
+
     #[test]
     fn test_detect_language_with_path() {
         assert_eq!(detect_language("/path/to/file.py"), Some("python"));
         assert_eq!(detect_language("../relative/path.rs"), Some("rust"));
     }
 
+    fn valid_symbol() -> Symbol {
+        Symbol {
+            line_start: 3,
+            line_end: 5,
+            metadata: Some(r#"{"async":true}"#.to_string()),
+            ..Symbol::with_location("load", SymbolKind::Function, SymbolLocation::default(), None)
+        }
+    }
+
+    #[test]
+    fn test_validate_accepts_valid_symbol() {
+        assert_eq!(valid_symbol().validate(), Ok(()));
+        // Single-line symbols and missing metadata are fine
+        assert_eq!(Symbol { line_end: 3, metadata: None, ..valid_symbol() }.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_empty_name() {
+        let err = Symbol { name: String::new(), ..valid_symbol() }.validate().unwrap_err();
+        assert_eq!(err.kind, ValidationErrorKind::EmptyName);
+    }
+
+    #[test]
+    fn test_validate_inverted_line_range() {
+        let err = Symbol { line_start: 5, line_end: 3, ..valid_symbol() }.validate().unwrap_err();
+        assert_eq!(err.kind, ValidationErrorKind::InvalidLineRange);
+        assert_eq!(err.to_string(), "Invalid symbol: line_end 3 is before line_start 5");
+    }
+
+    #[test]
+    fn test_validate_invalid_metadata() {
+        let err = Symbol { metadata: Some("{not json".to_string()), ..valid_symbol() }.validate().unwrap_err();
+        assert_eq!(err.kind, ValidationErrorKind::InvalidMetadata);
+        assert!(err.message.starts_with("metadata is not valid JSON"));
+    }
+
     #[test]
     fn test_symbol_kind_display() {
         assert_eq!(SymbolKind::Function.to_string(), "function");
         assert_eq!(SymbolKind::Class.to_string(), "class");
         assert_eq!(SymbolKind::Variable.to_string(), "variable");
+        assert_eq!(SymbolKind::Constant.to_string(), "constant");
         assert_eq!(SymbolKind::Import.to_string(), "import");
         assert_eq!(SymbolKind::Export.to_string(), "export");
         assert_eq!(SymbolKind::Module.to_string(), "module");
@@ -155,6 +846,10 @@ mod tests {
         assert_eq!(SymbolKind::Trait.to_string(), "trait");
         assert_eq!(SymbolKind::Interface.to_string(), "interface");
         assert_eq!(SymbolKind::Type.to_string(), "type");
+        assert_eq!(SymbolKind::Field.to_string(), "field");
+        assert_eq!(SymbolKind::EnumVariant.to_string(), "enum_variant");
+        assert_eq!(SymbolKind::Macro.to_string(), "macro");
+        assert_eq!(SymbolKind::Namespace.to_string(), "namespace");
     }
 
     #[test]
@@ -166,6 +861,8 @@ mod tests {
             kind: SymbolKind::Function,
             line_start: 10,
             line_end: 20,
+            column_start: 0,
+            column_end: 0,
             scope: Some("module".to_string()),
             metadata: Some(r#"{"returns": "str"}"#.to_string()),
         };
@@ -185,6 +882,8 @@ mod tests {
             kind: SymbolKind::Class,
             line_start: 0,
             line_end: 10,
+            column_start: 0,
+            column_end: 0,
             scope: None,
             metadata: None,
         };
@@ -193,6 +892,40 @@ mod tests {
         assert_eq!(symbol, cloned);
     }
 
+    #[test]
+    fn test_symbol_relocate() {
+        let symbol = Symbol {
+            id: Some(1),
+            file_id: 1,
+            name: "moved".to_string(),
+            kind: SymbolKind::Function,
+            line_start: 10,
+            line_end: 20,
+            column_start: 4,
+            column_end: 5,
+            scope: None,
+            metadata: None,
+        };
+
+        let down = symbol.relocate(3, 2);
+        assert_eq!((down.line_start, down.line_end), (13, 23));
+        assert_eq!((down.column_start, down.column_end), (6, 7));
+        assert_eq!(down.name, "moved");
+
+        // Negative deltas clamp at zero
+        let up = symbol.relocate(-15, -10);
+        assert_eq!((up.line_start, up.line_end), (0, 5));
+        assert_eq!((up.column_start, up.column_end), (0, 0));
+    }
+
+    #[test]
+    fn test_symbol_deserialize_without_columns() {
+        let json = r#"{"id":null,"file_id":1,"name":"f","kind":"function","line_start":1,"line_end":2,"scope":null,"metadata":null}"#;
+        let symbol: Symbol = serde_json::from_str(json).unwrap();
+        assert_eq!(symbol.column_start, 0);
+        assert_eq!(symbol.column_end, 0);
+    }
+
     #[test]
     fn test_symbol_kind_serialization() {
         // Test that SymbolKind can be serialized/deserialized
@@ -213,6 +946,8 @@ mod tests {
             kind: SymbolKind::Function,
             line_start: 1,
             line_end: 5,
+            column_start: 0,
+            column_end: 0,
             scope: None,
             metadata: None,
         };
@@ -230,8 +965,10 @@ mod tests {
             path: "/path/to/file.py".to_string(),
             language: "python".to_string(),
             size: 1024,
-            last_indexed: Some("2024-01-01T00:00:00Z".to_string()),
+            last_indexed: Some("2024-01-01T00:00:00Z".parse().unwrap()),
             parse_errors: 0,
+            index_duration_ms: None,
+            metadata: None,
         };
 
         assert_eq!(metadata.path, "/path/to/file.py");
@@ -247,6 +984,7 @@ mod tests {
             SymbolKind::Function,
             SymbolKind::Class,
             SymbolKind::Variable,
+            SymbolKind::Constant,
             SymbolKind::Import,
             SymbolKind::Export,
             SymbolKind::Module,
@@ -255,12 +993,18 @@ mod tests {
             SymbolKind::Trait,
             SymbolKind::Interface,
             SymbolKind::Type,
+            SymbolKind::Field,
+            SymbolKind::EnumVariant,
+            SymbolKind::Macro,
+            SymbolKind::Namespace,
         ];
 
         for kind in kinds {
             let s = kind.to_string();
             assert!(!s.is_empty());
+            assert_eq!(s.parse::<SymbolKind>().unwrap(), kind);
         }
+        assert!("widget".parse::<SymbolKind>().is_err());
     }
 
     #[cfg(feature = "deep-mode")]
@@ -276,4 +1020,156 @@ mod tests {
         use crate::analysis::deep::is_deep_mode_available;
         assert!(!is_deep_mode_available());
     }
+
+    #[test]
+    fn test_from_file_node_kind_table() {
+        let cases = [
+            ("python", "function_definition", Some(SymbolKind::Function)),
+            ("python", "class_definition", Some(SymbolKind::Class)),
+            ("python", "import_statement", Some(SymbolKind::Import)),
+            ("python", "import_from_statement", Some(SymbolKind::Import)),
+            ("python", "assignment", Some(SymbolKind::Variable)),
+            ("python", "decorator", None),
+            ("typescript", "function_declaration", Some(SymbolKind::Function)),
+            ("typescript", "function_signature", Some(SymbolKind::Function)),
+            ("typescript", "function", Some(SymbolKind::Function)),
+            ("typescript", "arrow_function", Some(SymbolKind::Function)),
+            ("typescript", "method_definition", Some(SymbolKind::Function)),
+            ("typescript", "method_signature", Some(SymbolKind::Function)),
+            ("typescript", "class_declaration", Some(SymbolKind::Class)),
+            ("typescript", "class", Some(SymbolKind::Class)),
+            ("typescript", "public_field_definition", Some(SymbolKind::Field)),
+            ("typescript", "interface_declaration", Some(SymbolKind::Interface)),
+            ("typescript", "type_alias_declaration", Some(SymbolKind::Type)),
+            ("typescript", "enum_declaration", Some(SymbolKind::Enum)),
+            ("typescript", "import_statement", Some(SymbolKind::Import)),
+            ("typescript", "export_statement", Some(SymbolKind::Export)),
+            ("typescript", "module", Some(SymbolKind::Module)),
+            ("typescript", "internal_module", Some(SymbolKind::Module)),
+            ("typescript_declaration", "module", Some(SymbolKind::Module)),
+            ("typescript", "lexical_declaration", Some(SymbolKind::Variable)),
+            ("typescript", "variable_declaration", Some(SymbolKind::Variable)),
+            ("javascript", "class_declaration", Some(SymbolKind::Class)),
+            ("rust", "function_item", Some(SymbolKind::Function)),
+            ("rust", "struct_item", Some(SymbolKind::Struct)),
+            ("rust", "enum_item", Some(SymbolKind::Enum)),
+            ("rust", "enum_variant", Some(SymbolKind::EnumVariant)),
+            ("rust", "trait_item", Some(SymbolKind::Trait)),
+            ("rust", "type_item", Some(SymbolKind::Type)),
+            ("rust", "use_declaration", Some(SymbolKind::Import)),
+            ("rust", "extern_crate_declaration", Some(SymbolKind::Import)),
+            ("rust", "function_signature_item", Some(SymbolKind::Function)),
+            ("rust", "const_item", Some(SymbolKind::Constant)),
+            ("rust", "static_item", Some(SymbolKind::Variable)),
+            ("rust", "macro_definition", Some(SymbolKind::Macro)),
+            ("rust", "mod_item", Some(SymbolKind::Namespace)),
+            ("go", "function_declaration", Some(SymbolKind::Function)),
+            ("go", "method_declaration", Some(SymbolKind::Function)),
+            ("go", "type_spec", Some(SymbolKind::Type)),
+            ("go", "type_alias", Some(SymbolKind::Type)),
+            ("go", "const_spec", Some(SymbolKind::Constant)),
+            ("go", "var_spec", Some(SymbolKind::Variable)),
+            ("go", "import_spec", Some(SymbolKind::Import)),
+            ("go", "struct_item", None),
+            ("java", "class_declaration", Some(SymbolKind::Class)),
+            ("java", "interface_declaration", Some(SymbolKind::Interface)),
+            ("java", "enum_declaration", Some(SymbolKind::Enum)),
+            ("java", "record_declaration", Some(SymbolKind::Class)),
+            ("java", "annotation_type_declaration", Some(SymbolKind::Interface)),
+            ("java", "method_declaration", Some(SymbolKind::Function)),
+            ("java", "constructor_declaration", Some(SymbolKind::Function)),
+            ("java", "field_declaration", Some(SymbolKind::Field)),
+            ("java", "import_declaration", Some(SymbolKind::Import)),
+            ("java", "package_declaration", None),
+            ("c", "function_definition", Some(SymbolKind::Function)),
+            ("c", "declaration", Some(SymbolKind::Variable)),
+            ("c", "struct_specifier", Some(SymbolKind::Struct)),
+            ("c", "union_specifier", Some(SymbolKind::Struct)),
+            ("c", "enum_specifier", Some(SymbolKind::Enum)),
+            ("c", "enumerator", Some(SymbolKind::EnumVariant)),
+            ("c", "preproc_include", Some(SymbolKind::Import)),
+            ("c", "class_specifier", None),
+            ("cpp", "function_definition", Some(SymbolKind::Function)),
+            ("cpp", "declaration", Some(SymbolKind::Variable)),
+            ("cpp", "field_declaration", Some(SymbolKind::Field)),
+            ("cpp", "class_specifier", Some(SymbolKind::Class)),
+            ("cpp", "struct_specifier", Some(SymbolKind::Struct)),
+            ("cpp", "union_specifier", Some(SymbolKind::Struct)),
+            ("cpp", "enum_specifier", Some(SymbolKind::Enum)),
+            ("cpp", "enumerator", Some(SymbolKind::EnumVariant)),
+            ("cpp", "namespace_definition", Some(SymbolKind::Namespace)),
+            ("cpp", "using_declaration", Some(SymbolKind::Import)),
+            ("cpp", "preproc_include", Some(SymbolKind::Import)),
+            ("cpp", "template_declaration", None),
+            ("rust", "impl_item", None),
+            ("rust", "class_definition", None),
+            ("cobol", "function_item", None),
+        ];
+
+        for (language, node_kind, expected) in cases {
+            assert_eq!(
+                SymbolKind::from_file_node_kind(language, node_kind),
+                expected,
+                "{} / {}",
+                language,
+                node_kind
+            );
+        }
+
+        // Every table entry is covered above
+        for (language, node_kind, _) in NODE_KIND_TABLE {
+            assert!(
+                cases.iter().any(|(l, k, _)| l == language && k == node_kind),
+                "missing case for {} / {}",
+                language,
+                node_kind
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_file_node_kind_consistent_across_languages() {
+        // Each node role maps to the same kind family in every language
+        let function_like = ["function_definition", "function_declaration", "function_item"];
+        let class_like = ["class_definition", "class_declaration"];
+        let import_like = ["import_statement", "import_from_statement", "use_declaration"];
+
+        for (language, node_kind, kind) in NODE_KIND_TABLE {
+            if function_like.contains(node_kind) {
+                assert_eq!(*kind, SymbolKind::Function, "{} / {}", language, node_kind);
+            }
+            if class_like.contains(node_kind) {
+                assert_eq!(*kind, SymbolKind::Class, "{} / {}", language, node_kind);
+            }
+            if import_like.contains(node_kind) {
+                assert_eq!(*kind, SymbolKind::Import, "{} / {}", language, node_kind);
+            }
+            if node_kind.ends_with("struct_item") {
+                assert_eq!(*kind, SymbolKind::Struct, "{} / {}", language, node_kind);
+            }
+        }
+    }
+
+    #[test]
+    fn test_indexing_error_from_anyhow() {
+        let err: anyhow::Error = anyhow::anyhow!("disk full").context("Failed to commit symbol batch");
+        let err = IndexingError::from(err);
+        assert!(matches!(&err, IndexingError::StorageError(message) if message == "Failed to commit symbol batch: disk full"));
+        assert_eq!(err.to_string(), "Storage error: Failed to commit symbol batch: disk full");
+    }
+
+    #[test]
+    fn test_indexing_error_display_and_source() {
+        use std::error::Error as _;
+
+        let err = IndexingError::parse_failed("rust", "Parse failed");
+        assert_eq!(err.to_string(), "Failed to parse rust source at line 0: Parse failed");
+
+        let err = IndexingError::extraction_failed("node has no name");
+        assert_eq!(err.to_string(), "Failed to extract symbols from <source>: node has no name");
+
+        let err = IndexingError::from(std::io::Error::new(std::io::ErrorKind::NotFound, "missing"));
+        assert!(err.source().is_some());
+        assert!(IndexingError::UnsupportedLanguage("cobol".to_string()).source().is_none());
+    }
 }
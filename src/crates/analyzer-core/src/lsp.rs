@@ -0,0 +1,473 @@
+// LSP module - Workspace symbol interchange with language servers
+// Exports the index as LSP `SymbolInformation` ndjson and imports it back for cold starts
+// Also converts analyzer output into `DocumentSymbol` trees for `textDocument/documentSymbol`
+
+use anyhow::{Context, Result};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use crate::storage::{delete_file_symbols, get_or_create_file, insert_symbol};
+use crate::{Symbol, SymbolKind, detect_language};
+
+/// SymbolKind -> LSP `SymbolKind` integer (LSP 3.17)
+///
+/// LSP has no import/export or macro kinds, so several entries share a value.
+/// Decoding picks the first entry, so canonical kinds are listed before their
+/// aliases.
+const LSP_KIND_TABLE: &[(SymbolKind, u8)] = &[
+    (SymbolKind::Module, 2),
+    (SymbolKind::Import, 2),
+    (SymbolKind::Namespace, 3),
+    (SymbolKind::Class, 5),
+    (SymbolKind::Field, 8),
+    (SymbolKind::Enum, 10),
+    (SymbolKind::Interface, 11),
+    (SymbolKind::Trait, 11),
+    (SymbolKind::Function, 12),
+    (SymbolKind::Macro, 12),
+    (SymbolKind::Variable, 13),
+    (SymbolKind::Export, 13),
+    (SymbolKind::Constant, 14),
+    (SymbolKind::EnumVariant, 22),
+    (SymbolKind::Struct, 23),
+    (SymbolKind::Type, 26),
+];
+
+/// Zero-based line/character position
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Position {
+    pub line: usize,
+    pub character: usize,
+}
+
+/// Half-open range between two positions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Range {
+    pub start: Position,
+    pub end: Position,
+}
+
+/// A range inside a document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Location {
+    pub uri: String,
+    pub range: Range,
+}
+
+/// LSP `SymbolInformation` as returned by `workspace/symbol`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolInformation {
+    pub name: String,
+    pub kind: u8,
+    pub location: Location,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub container_name: Option<String>,
+}
+
+/// LSP `DocumentSymbol` as returned by `textDocument/documentSymbol`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentSymbol {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    pub kind: u8,
+    pub range: Range,
+    pub selection_range: Range,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<DocumentSymbol>,
+}
+
+/// Summary of an ndjson import
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportStats {
+    /// Distinct files the imported symbols belong to
+    pub files: usize,
+    /// Symbols written to the database
+    pub symbols: usize,
+}
+
+/// Map a symbol kind to its LSP integer
+pub fn lsp_symbol_kind(kind: SymbolKind) -> u8 {
+    LSP_KIND_TABLE
+        .iter()
+        .find(|(k, _)| *k == kind)
+        .map(|(_, lsp)| *lsp)
+        .unwrap_or(13)
+}
+
+/// Map an LSP integer back to a symbol kind (unknown values become `Variable`)
+pub fn symbol_kind_from_lsp(kind: u8) -> SymbolKind {
+    LSP_KIND_TABLE
+        .iter()
+        .find(|(_, lsp)| *lsp == kind)
+        .map(|(k, _)| *k)
+        .unwrap_or(SymbolKind::Variable)
+}
+
+/// Build a `file://` URI for an indexed path (no percent-encoding)
+pub fn path_to_uri(path: &str) -> String {
+    format!("file://{}", path)
+}
+
+/// Inverse of `path_to_uri`; non-`file://` URIs are returned unchanged
+pub fn uri_to_path(uri: &str) -> &str {
+    uri.strip_prefix("file://").unwrap_or(uri)
+}
+
+/// Range covered by a symbol, from its start line/column to its end line/column
+fn symbol_range(symbol: &Symbol) -> Range {
+    Range {
+        start: Position { line: symbol.line_start, character: symbol.column_start },
+        end: Position { line: symbol.line_end, character: symbol.column_end },
+    }
+}
+
+/// Type annotation or return type from the symbol's metadata, if the analyzer recorded one
+fn symbol_detail(symbol: &Symbol) -> Option<String> {
+    let metadata: serde_json::Value = serde_json::from_str(symbol.metadata.as_deref()?).ok()?;
+    ["type", "return_type"]
+        .iter()
+        .find_map(|key| metadata.get(key)?.as_str())
+        .map(str::to_string)
+}
+
+/// Index of the symbol `symbols[index]` is nested under, if any
+///
+/// The parent is the tightest symbol named after the child's `scope` whose
+/// range contains the child. Equal ranges only nest under an earlier symbol,
+/// so the result is always a tree.
+fn find_parent(symbols: &[Symbol], index: usize) -> Option<usize> {
+    let child = &symbols[index];
+    let scope = child.scope.as_deref()?;
+    let span = |s: &Symbol| s.line_end.saturating_sub(s.line_start);
+
+    symbols
+        .iter()
+        .enumerate()
+        .filter(|(i, candidate)| {
+            *i != index
+                && candidate.name == scope
+                && candidate.line_start <= child.line_start
+                && candidate.line_end >= child.line_end
+                && (span(candidate) > span(child) || *i < index)
+        })
+        .min_by_key(|(i, candidate)| (span(candidate), *i))
+        .map(|(i, _)| i)
+}
+
+/// Convert the symbols of one document into a `DocumentSymbol` tree
+///
+/// Symbols are nested under their `scope`; anything whose container is not
+/// in `symbols` stays at the top level. Siblings are ordered by position. The
+/// selection range is the whole symbol, since analyzers do not record where
+/// the name itself sits.
+pub fn to_document_symbols(symbols: &[Symbol]) -> Vec<DocumentSymbol> {
+    let mut children = vec![Vec::new(); symbols.len()];
+    let mut roots = Vec::new();
+    for index in 0..symbols.len() {
+        match find_parent(symbols, index) {
+            Some(parent) => children[parent].push(index),
+            None => roots.push(index),
+        }
+    }
+
+    build_document_symbols(symbols, &children, roots)
+}
+
+fn build_document_symbols(symbols: &[Symbol], children: &[Vec<usize>], mut indices: Vec<usize>) -> Vec<DocumentSymbol> {
+    indices.sort_by_key(|&i| (symbols[i].line_start, symbols[i].column_start, i));
+    indices
+        .into_iter()
+        .map(|i| {
+            let symbol = &symbols[i];
+            let range = symbol_range(symbol);
+            DocumentSymbol {
+                name: symbol.name.clone(),
+                detail: symbol_detail(symbol),
+                kind: lsp_symbol_kind(symbol.kind),
+                range,
+                selection_range: range,
+                children: build_document_symbols(symbols, children, children[i].clone()),
+            }
+        })
+        .collect()
+}
+
+/// Convert symbols from any number of files into `workspace/symbol` results
+///
+/// `file_paths` maps each `file_id` to its indexed path; symbols whose file is
+/// missing from the map are skipped.
+pub fn to_workspace_symbols(symbols: &[Symbol], file_paths: &HashMap<i64, String>) -> Vec<SymbolInformation> {
+    symbols
+        .iter()
+        .filter_map(|symbol| {
+            let path = file_paths.get(&symbol.file_id)?;
+            Some(SymbolInformation {
+                name: symbol.name.clone(),
+                kind: lsp_symbol_kind(symbol.kind),
+                location: Location { uri: path_to_uri(path), range: symbol_range(symbol) },
+                container_name: symbol.scope.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Query every indexed symbol as LSP `SymbolInformation`, ordered by file and line
+pub fn find_symbols_as_lsp_response(conn: &Connection) -> Result<Vec<SymbolInformation>> {
+    let mut stmt = conn.prepare(
+        "SELECT s.name, s.kind, s.line_start, s.line_end, s.scope, f.path, s.column_start, s.column_end
+         FROM symbols s JOIN files f ON s.file_id = f.id
+         ORDER BY f.path, s.line_start, s.name",
+    )?;
+
+    let symbols = stmt
+        .query_map([], |row| {
+            let kind: String = row.get(1)?;
+            let path: String = row.get(5)?;
+            Ok(SymbolInformation {
+                name: row.get(0)?,
+                kind: lsp_symbol_kind(kind.parse().unwrap_or(SymbolKind::Variable)),
+                location: Location {
+                    uri: path_to_uri(&path),
+                    range: Range {
+                        start: Position { line: row.get(2)?, character: row.get(6)? },
+                        end: Position { line: row.get(3)?, character: row.get(7)? },
+                    },
+                },
+                container_name: row.get(4)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(symbols)
+}
+
+/// Write every indexed symbol to `path` as ndjson, one `SymbolInformation` per line
+///
+/// Returns the number of symbols written.
+pub fn export_lsp_workspace_symbols(conn: &Connection, path: &Path) -> Result<usize> {
+    let symbols = find_symbols_as_lsp_response(conn)?;
+
+    let file = File::create(path)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut writer = BufWriter::new(file);
+
+    for symbol in &symbols {
+        serde_json::to_writer(&mut writer, symbol)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush().context("Failed to flush workspace symbols")?;
+
+    Ok(symbols.len())
+}
+
+/// Repopulate the database from an ndjson file written by `export_lsp_workspace_symbols`
+///
+/// Files named in the export replace any symbols already stored for them.
+/// Blank lines are skipped.
+pub fn import_lsp_workspace_symbols(conn: &Connection, path: &Path) -> Result<ImportStats> {
+    let file = File::open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+
+    // Parse everything up front so a malformed line leaves the database untouched
+    let mut entries = Vec::new();
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let info: SymbolInformation = serde_json::from_str(&line)
+            .with_context(|| format!("Invalid SymbolInformation on line {}", index + 1))?;
+        entries.push(info);
+    }
+
+    let tx = conn
+        .unchecked_transaction()
+        .context("Failed to start transaction")?;
+
+    let mut file_ids: HashMap<String, i64> = HashMap::new();
+    let mut written = 0;
+    for info in entries {
+        let file_path = uri_to_path(&info.location.uri).to_string();
+
+        let file_id = match file_ids.get(&file_path) {
+            Some(id) => *id,
+            None => {
+                let language = detect_language(&file_path).unwrap_or("unknown");
+                let (id, _) = get_or_create_file(&tx, &file_path, language, 0)?;
+                delete_file_symbols(&tx, id)?;
+                file_ids.insert(file_path, id);
+                id
+            }
+        };
+
+        insert_symbol(&tx, &Symbol {
+            id: None,
+            file_id,
+            name: info.name,
+            kind: symbol_kind_from_lsp(info.kind),
+            line_start: info.location.range.start.line,
+            line_end: info.location.range.end.line,
+            column_start: info.location.range.start.character,
+            column_end: info.location.range.end.character,
+            scope: info.container_name,
+            metadata: None,
+        })?;
+        written += 1;
+    }
+
+    tx.commit().context("Failed to commit imported symbols")?;
+
+    Ok(ImportStats {
+        files: file_ids.len(),
+        symbols: written,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FileMetadata;
+    use crate::storage::{init_schema, upsert_file};
+    use tempfile::{NamedTempFile, TempDir};
+
+    fn symbol(file_id: i64, name: &str, kind: SymbolKind, line: usize, scope: Option<&str>) -> Symbol {
+        Symbol {
+            id: None,
+            file_id,
+            name: name.to_string(),
+            kind,
+            line_start: line,
+            line_end: line + 2,
+            // Members are indented inside their container
+            column_start: if scope.is_some() { 4 } else { 0 },
+            column_end: 20,
+            scope: scope.map(str::to_string),
+            metadata: None,
+        }
+    }
+
+    fn seed(conn: &Connection) {
+        for (path, language) in [("/repo/app.py", "python"), ("/repo/lib.rs", "rust")] {
+            let file = FileMetadata {
+                id: None,
+                path: path.to_string(),
+                language: language.to_string(),
+                size: 64,
+                last_indexed: None,
+                parse_errors: 0,
+                index_duration_ms: None,
+                metadata: None,
+            };
+            let file_id = upsert_file(conn, &file).unwrap();
+            insert_symbol(conn, &symbol(file_id, "Widget", SymbolKind::Class, 1, None)).unwrap();
+            insert_symbol(conn, &symbol(file_id, "render", SymbolKind::Function, 4, Some("Widget"))).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_lsp_kind_table_round_trips_canonical_kinds() {
+        for kind in [SymbolKind::Function, SymbolKind::Class, SymbolKind::Struct, SymbolKind::Interface, SymbolKind::Namespace] {
+            assert_eq!(symbol_kind_from_lsp(lsp_symbol_kind(kind)), kind);
+        }
+        assert_eq!(lsp_symbol_kind(SymbolKind::Function), 12);
+        assert_eq!(symbol_kind_from_lsp(99), SymbolKind::Variable);
+    }
+
+    #[test]
+    fn test_find_symbols_as_lsp_response() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+        seed(&conn);
+
+        let symbols = find_symbols_as_lsp_response(&conn).unwrap();
+        assert_eq!(symbols.len(), 4);
+        assert_eq!(symbols[0].location.uri, "file:///repo/app.py");
+        assert_eq!(symbols[1].container_name.as_deref(), Some("Widget"));
+        assert_eq!(symbols[1].location.range.end.line, 6);
+        assert_eq!(symbols[1].location.range.start.character, 4);
+        assert_eq!(symbols[1].location.range.end.character, 20);
+
+        let json = serde_json::to_value(&symbols[1]).unwrap();
+        assert_eq!(json["containerName"], "Widget");
+        assert_eq!(json["kind"], 12);
+    }
+
+    #[test]
+    fn test_export_import_round_trip_preserves_symbol_count() {
+        let dir = TempDir::new().unwrap();
+        let export_path = dir.path().join("symbols.ndjson");
+
+        let source = init_schema(&dir.path().join("source.db")).unwrap();
+        seed(&source);
+        assert_eq!(export_lsp_workspace_symbols(&source, &export_path).unwrap(), 4);
+
+        let contents = std::fs::read_to_string(&export_path).unwrap();
+        assert_eq!(contents.lines().count(), 4);
+
+        let target = init_schema(&dir.path().join("target.db")).unwrap();
+        let stats = import_lsp_workspace_symbols(&target, &export_path).unwrap();
+        assert_eq!(stats, ImportStats { files: 2, symbols: 4 });
+        assert_eq!(find_symbols_as_lsp_response(&target).unwrap(), find_symbols_as_lsp_response(&source).unwrap());
+
+        // Importing again replaces rather than duplicates
+        let stats = import_lsp_workspace_symbols(&target, &export_path).unwrap();
+        assert_eq!(stats.symbols, 4);
+        let count: i64 = target.query_row("SELECT COUNT(*) FROM symbols", [], |row| row.get(0)).unwrap();
+        assert_eq!(count, 4);
+    }
+
+    #[test]
+    fn test_to_document_symbols_nests_by_scope() {
+        let mut method = symbol(1, "render", SymbolKind::Function, 4, Some("Widget"));
+        method.metadata = Some(r#"{"return_type":"str"}"#.to_string());
+        let mut widget = symbol(1, "Widget", SymbolKind::Class, 1, None);
+        widget.line_end = 10;
+        let symbols = vec![
+            method,
+            widget,
+            symbol(1, "helper", SymbolKind::Function, 12, None),
+            // Container not in this document
+            symbol(1, "orphan", SymbolKind::Function, 20, Some("Missing")),
+        ];
+
+        let tree = to_document_symbols(&symbols);
+        let names: Vec<&str> = tree.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["Widget", "helper", "orphan"]);
+        assert_eq!(tree[0].kind, 5);
+        assert_eq!(tree[0].children.len(), 1);
+
+        let render = &tree[0].children[0];
+        assert_eq!(render.name, "render");
+        assert_eq!(render.detail.as_deref(), Some("str"));
+        assert_eq!(render.range.start, Position { line: 4, character: 4 });
+        assert_eq!(render.selection_range, render.range);
+
+        let json = serde_json::to_value(&tree[0]).unwrap();
+        assert!(json.get("selectionRange").is_some());
+        assert!(json["children"][0].get("children").is_none());
+    }
+
+    #[test]
+    fn test_to_workspace_symbols() {
+        let symbols = vec![
+            symbol(1, "Widget", SymbolKind::Class, 1, None),
+            symbol(2, "render", SymbolKind::Function, 4, Some("Widget")),
+            symbol(3, "unknown", SymbolKind::Function, 1, None),
+        ];
+        let paths = HashMap::from([(1, "/repo/app.py".to_string()), (2, "/repo/lib.rs".to_string())]);
+
+        let results = to_workspace_symbols(&symbols, &paths);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[1].location.uri, "file:///repo/lib.rs");
+        assert_eq!(results[1].container_name.as_deref(), Some("Widget"));
+        assert_eq!(results[1].kind, 12);
+    }
+}
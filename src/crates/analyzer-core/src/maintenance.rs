@@ -0,0 +1,143 @@
+// Maintenance module - Periodic housekeeping for long-running servers
+// Checkpoints the WAL and alerts when the index grows past a size threshold
+
+use anyhow::Result;
+use rusqlite::Connection;
+use std::time::{Duration, Instant};
+
+use crate::storage::{checkpoint_wal, get_index_size_bytes, WalCheckpointMode, WalStats};
+
+/// Index size exceeded the configured threshold
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizeAlert {
+    pub size_bytes: u64,
+    pub threshold_bytes: u64,
+}
+
+/// Outcome of one maintenance run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaintenanceReport {
+    /// Database size after the checkpoint
+    pub size_bytes: u64,
+    pub wal: WalStats,
+    /// Set when `size_bytes` exceeds the scheduler's threshold
+    pub size_alert: Option<SizeAlert>,
+}
+
+/// Runs maintenance at most once per interval
+///
+/// Call `tick` from the server's main loop; it does nothing until the
+/// interval has elapsed since the previous run.
+#[derive(Debug, Clone)]
+pub struct MaintenanceScheduler {
+    interval: Duration,
+    size_threshold_bytes: Option<u64>,
+    last_run: Option<Instant>,
+}
+
+impl MaintenanceScheduler {
+    /// Create a scheduler that is due immediately, then every `interval`
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            size_threshold_bytes: None,
+            last_run: None,
+        }
+    }
+
+    /// Alert when the index is larger than `bytes`
+    pub fn with_size_threshold(mut self, bytes: u64) -> Self {
+        self.size_threshold_bytes = Some(bytes);
+        self
+    }
+
+    /// Check whether the interval has elapsed since the last run
+    pub fn is_due(&self) -> bool {
+        self.last_run
+            .map_or(true, |last_run| last_run.elapsed() >= self.interval)
+    }
+
+    /// Run maintenance if due, otherwise return `None`
+    pub fn tick(&mut self, conn: &Connection) -> Result<Option<MaintenanceReport>> {
+        if !self.is_due() {
+            return Ok(None);
+        }
+        self.run(conn).map(Some)
+    }
+
+    /// Checkpoint the WAL and check the index size now, regardless of the interval
+    ///
+    /// Size alerts are also logged to stderr.
+    pub fn run(&mut self, conn: &Connection) -> Result<MaintenanceReport> {
+        let wal = checkpoint_wal(conn, WalCheckpointMode::Passive)?;
+        let size_bytes = get_index_size_bytes(conn)?;
+        self.last_run = Some(Instant::now());
+
+        let size_alert = self
+            .size_threshold_bytes
+            .filter(|threshold| size_bytes > *threshold)
+            .map(|threshold_bytes| SizeAlert {
+                size_bytes,
+                threshold_bytes,
+            });
+        if let Some(alert) = size_alert {
+            eprintln!(
+                "Warning: index size {} bytes exceeds threshold of {} bytes",
+                alert.size_bytes, alert.threshold_bytes
+            );
+        }
+
+        Ok(MaintenanceReport {
+            size_bytes,
+            wal,
+            size_alert,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::init_schema;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_tick_respects_interval() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let mut scheduler = MaintenanceScheduler::new(Duration::from_secs(3600));
+        assert!(scheduler.is_due());
+
+        let report = scheduler.tick(&conn).unwrap().unwrap();
+        assert!(report.size_bytes > 0);
+        assert!(report.size_alert.is_none());
+
+        assert!(!scheduler.is_due());
+        assert!(scheduler.tick(&conn).unwrap().is_none());
+
+        let mut scheduler = MaintenanceScheduler::new(Duration::ZERO);
+        scheduler.tick(&conn).unwrap().unwrap();
+        assert!(scheduler.tick(&conn).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_size_threshold_alert() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+        let size = get_index_size_bytes(&conn).unwrap();
+
+        let mut scheduler = MaintenanceScheduler::new(Duration::ZERO).with_size_threshold(size - 1);
+        let report = scheduler.run(&conn).unwrap();
+        assert_eq!(
+            report.size_alert,
+            Some(SizeAlert {
+                size_bytes: size,
+                threshold_bytes: size - 1,
+            })
+        );
+
+        let mut scheduler = MaintenanceScheduler::new(Duration::ZERO).with_size_threshold(size);
+        assert!(scheduler.run(&conn).unwrap().size_alert.is_none());
+    }
+}
@@ -0,0 +1,162 @@
+// Normalization module - Canonical symbol names for cross-language comparison
+// Maps snake_case, camelCase and PascalCase spellings onto one lowercase snake_case form
+
+use anyhow::{Context, Result};
+use rusqlite::{Connection, params};
+
+use crate::Symbol;
+
+/// Strip language-specific identifier decorations (`r#` raw identifiers,
+/// `#` private class members)
+fn strip_language_prefix<'a>(language: &str, name: &'a str) -> &'a str {
+    match language {
+        "rust" => name.strip_prefix("r#").unwrap_or(name),
+        "typescript" | "typescript_declaration" | "javascript" => name.strip_prefix('#').unwrap_or(name),
+        _ => name,
+    }
+}
+
+/// Normalize a symbol name to lowercase words joined by underscores
+///
+/// `MyClass`, `myClass`, `my_class` and `MY_CLASS` all become `my_class`.
+/// Acronyms stay together (`HTTPServer` -> `http_server`), digits stick to the
+/// preceding word, and leading/trailing underscores (`__init__`) are dropped.
+pub fn normalize_symbol_name(language: &str, name: &str) -> String {
+    let name = strip_language_prefix(language, name);
+    let chars: Vec<char> = name.chars().collect();
+
+    let mut words: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+
+        if c.is_uppercase() && !current.is_empty() {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            // `myClass` -> my|Class, `HTTPServer` -> HTTP|Server
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_is_lower) {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+        current.extend(c.to_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+
+    words.join("_")
+}
+
+/// Find symbols in any language whose normalized name matches `name`'s
+///
+/// Uses the `symbols.normalized_name` column maintained by the insert functions.
+pub fn find_similar_symbols_cross_language(conn: &Connection, name: &str) -> Result<Vec<Symbol>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end
+         FROM symbols WHERE normalized_name = ?1 ORDER BY file_id, line_start"
+    )?;
+
+    let symbols = stmt.query_map(params![normalize_symbol_name("", name)], |row| {
+        Ok(Symbol {
+            id: Some(row.get(0)?),
+            file_id: row.get(1)?,
+            name: row.get(2)?,
+            kind: row.get::<_, String>(3)?.parse().unwrap_or(crate::SymbolKind::Variable),
+            line_start: row.get(4)?,
+            line_end: row.get(5)?,
+            column_start: row.get(8)?,
+            column_end: row.get(9)?,
+            scope: row.get(6)?,
+            metadata: row.get(7)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()
+    .context("Failed to query similar symbols")?;
+
+    Ok(symbols)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{init_schema, insert_symbol, insert_symbols_batch, upsert_file};
+    use crate::{FileMetadata, SymbolKind};
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_normalize_naming_conventions() {
+        // snake_case
+        assert_eq!(normalize_symbol_name("python", "my_class"), "my_class");
+        assert_eq!(normalize_symbol_name("python", "parse_config_v2"), "parse_config_v2");
+        // camelCase
+        assert_eq!(normalize_symbol_name("typescript", "myClass"), "my_class");
+        assert_eq!(normalize_symbol_name("typescript", "parseHTMLString"), "parse_html_string");
+        // PascalCase
+        assert_eq!(normalize_symbol_name("rust", "MyClass"), "my_class");
+        assert_eq!(normalize_symbol_name("rust", "HTTPServer"), "http_server");
+        assert_eq!(normalize_symbol_name("rust", "Utf8Decoder"), "utf8_decoder");
+    }
+
+    #[test]
+    fn test_normalize_decorations() {
+        assert_eq!(normalize_symbol_name("python", "__init__"), "init");
+        assert_eq!(normalize_symbol_name("python", "MAX_RETRIES"), "max_retries");
+        assert_eq!(normalize_symbol_name("rust", "r#type"), "type");
+        assert_eq!(normalize_symbol_name("typescript", "#secretKey"), "secret_key");
+        assert_eq!(normalize_symbol_name("typescript", "$scope"), "scope");
+        assert_eq!(normalize_symbol_name("python", ""), "");
+    }
+
+    #[test]
+    fn test_find_similar_symbols_cross_language() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let mut file_ids = Vec::new();
+        for (path, language) in [("model.rs", "rust"), ("model.py", "python"), ("model.ts", "typescript")] {
+            let file = FileMetadata {
+                id: None,
+                path: path.to_string(),
+                language: language.to_string(),
+                size: 10,
+                last_indexed: None,
+                parse_errors: 0,
+                index_duration_ms: None,
+                metadata: None,
+            };
+            file_ids.push(upsert_file(&conn, &file).unwrap());
+        }
+
+        let symbol = |file_id, name: &str, line| Symbol {
+            id: None,
+            file_id,
+            name: name.to_string(),
+            kind: SymbolKind::Class,
+            line_start: line,
+            line_end: line,
+            column_start: 0,
+            column_end: 0,
+            scope: None,
+            metadata: None,
+        };
+        insert_symbol(&conn, &symbol(file_ids[0], "UserProfile", 0)).unwrap();
+        insert_symbol(&conn, &symbol(file_ids[0], "r#UserProfile", 5)).unwrap();
+        insert_symbols_batch(&conn, &[symbol(file_ids[1], "user_profile", 0), symbol(file_ids[1], "profile", 3)]).unwrap();
+        insert_symbol(&conn, &symbol(file_ids[2], "userProfile", 0)).unwrap();
+
+        for query in ["UserProfile", "user_profile", "userProfile"] {
+            let names: Vec<String> = find_similar_symbols_cross_language(&conn, query)
+                .unwrap()
+                .into_iter()
+                .map(|s| s.name)
+                .collect();
+            assert_eq!(names, vec!["UserProfile", "r#UserProfile", "user_profile", "userProfile"], "{}", query);
+        }
+        assert!(find_similar_symbols_cross_language(&conn, "UserProfiles").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,130 @@
+// Pool module - Reusable tree-sitter parsers shared across threads
+// Avoids re-loading the grammar for every file by checking parsers in and out
+
+use crossbeam_queue::ArrayQueue;
+use std::ops::{Deref, DerefMut};
+
+/// A parser that can clear its per-file state before reuse
+pub trait ResettableParser {
+    /// Make the parser ready for a new file without re-loading the grammar
+    fn reset(&mut self);
+}
+
+/// Fixed-size pool of parsers
+///
+/// `acquire` never blocks: when every pooled parser is checked out, a new one
+/// is created, and it is dropped on return if the pool is already full.
+pub struct ParserPool<P> {
+    parsers: ArrayQueue<P>,
+}
+
+impl<P: ResettableParser + Default> ParserPool<P> {
+    /// Create a pool holding `size` ready parsers (at least one)
+    pub fn new(size: usize) -> Self {
+        let parsers = ArrayQueue::new(size.max(1));
+        while parsers.push(P::default()).is_ok() {}
+        Self { parsers }
+    }
+
+    /// Check out a parser; it returns to the pool when the guard is dropped
+    pub fn acquire(&self) -> PooledParser<'_, P> {
+        let parser = self.parsers.pop().unwrap_or_default();
+        PooledParser {
+            parser: Some(parser),
+            pool: self,
+        }
+    }
+}
+
+impl<P> ParserPool<P> {
+    /// Maximum number of idle parsers kept
+    pub fn capacity(&self) -> usize {
+        self.parsers.capacity()
+    }
+
+    /// Number of parsers currently idle in the pool
+    pub fn available(&self) -> usize {
+        self.parsers.len()
+    }
+}
+
+/// A parser checked out of a `ParserPool`
+pub struct PooledParser<'a, P: ResettableParser> {
+    parser: Option<P>,
+    pool: &'a ParserPool<P>,
+}
+
+impl<P: ResettableParser> Deref for PooledParser<'_, P> {
+    type Target = P;
+
+    fn deref(&self) -> &P {
+        self.parser.as_ref().expect("pooled parser already returned")
+    }
+}
+
+impl<P: ResettableParser> DerefMut for PooledParser<'_, P> {
+    fn deref_mut(&mut self) -> &mut P {
+        self.parser.as_mut().expect("pooled parser already returned")
+    }
+}
+
+impl<P: ResettableParser> Drop for PooledParser<'_, P> {
+    fn drop(&mut self) {
+        if let Some(mut parser) = self.parser.take() {
+            parser.reset();
+            // Pool is full (extra parser created under contention): drop it
+            let _ = self.pool.parsers.push(parser);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct CountingParser {
+        parses: usize,
+        resets: usize,
+    }
+
+    impl ResettableParser for CountingParser {
+        fn reset(&mut self) {
+            self.resets += 1;
+        }
+    }
+
+    #[test]
+    fn test_acquire_returns_parser_on_drop() {
+        let pool: ParserPool<CountingParser> = ParserPool::new(2);
+        assert_eq!(pool.capacity(), 2);
+        assert_eq!(pool.available(), 2);
+
+        {
+            let mut parser = pool.acquire();
+            parser.parses += 1;
+            assert_eq!(pool.available(), 1);
+        }
+        assert_eq!(pool.available(), 2);
+
+        // The same parser comes back, reset once
+        let first = pool.acquire();
+        let second = pool.acquire();
+        let reused = [&first, &second].iter().filter(|p| p.parses == 1).count();
+        assert_eq!(reused, 1);
+        assert!(first.resets + second.resets == 1);
+    }
+
+    #[test]
+    fn test_acquire_beyond_capacity() {
+        let pool: ParserPool<CountingParser> = ParserPool::new(1);
+
+        let first = pool.acquire();
+        let second = pool.acquire();
+        assert_eq!(pool.available(), 0);
+
+        drop(first);
+        drop(second);
+        assert_eq!(pool.available(), 1);
+    }
+}
@@ -2,10 +2,30 @@
 // Implements efficient SQLite queries for MCP protocol
 
 use anyhow::{Context, Result};
-use rusqlite::{Connection, params};
+use chrono::{DateTime, SecondsFormat, Utc};
+use regex::Regex;
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{Connection, OptionalExtension, params, params_from_iter};
 use serde_json::json;
+use std::collections::HashMap;
+use std::str::FromStr;
+use thiserror::Error;
 
-use crate::{Symbol, SymbolKind, FileMetadata};
+use crate::storage::{self, compact_metadata};
+use crate::{signature_hash, signature_param_types, Symbol, SymbolKind, FileMetadata, ParseError};
+
+/// Errors caused by invalid query input
+#[derive(Debug, Error)]
+pub enum QueryError {
+    #[error("Invalid regex pattern `{pattern}`: {message}")]
+    InvalidRegex { pattern: String, message: String },
+
+    #[error("Invalid timestamp `{0}`")]
+    InvalidTimestamp(String),
+
+    #[error("Invalid full-text query `{query}`: {message}")]
+    InvalidFtsQuery { query: String, message: String },
+}
 
 /// Query symbols by name
 pub fn find_symbols_by_name(
@@ -13,7 +33,7 @@ pub fn find_symbols_by_name(
     name: &str,
 ) -> Result<Vec<Symbol>> {
     let mut stmt = conn.prepare(
-        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata
+        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end
          FROM symbols WHERE name = ?1"
     )?;
 
@@ -25,6 +45,8 @@ pub fn find_symbols_by_name(
             kind: parse_symbol_kind(&row.get::<_, String>(3)?),
             line_start: row.get(4)?,
             line_end: row.get(5)?,
+            column_start: row.get(8)?,
+            column_end: row.get(9)?,
             scope: row.get(6)?,
             metadata: row.get(7)?,
         })
@@ -40,7 +62,7 @@ pub fn find_symbols_by_kind(
     kind: SymbolKind,
 ) -> Result<Vec<Symbol>> {
     let mut stmt = conn.prepare(
-        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata
+        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end
          FROM symbols WHERE kind = ?1"
     )?;
 
@@ -52,6 +74,8 @@ pub fn find_symbols_by_kind(
             kind: parse_symbol_kind(&row.get::<_, String>(3)?),
             line_start: row.get(4)?,
             line_end: row.get(5)?,
+            column_start: row.get(8)?,
+            column_end: row.get(9)?,
             scope: row.get(6)?,
             metadata: row.get(7)?,
         })
@@ -64,7 +88,7 @@ pub fn find_symbols_by_kind(
 /// List all files in the index
 pub fn list_files(conn: &Connection) -> Result<Vec<FileMetadata>> {
     let mut stmt = conn.prepare(
-        "SELECT id, path, language, size, last_indexed, parse_errors FROM files"
+        "SELECT id, path, language, size, last_indexed, parse_errors, index_duration_ms, metadata FROM files"
     )?;
 
     let files = stmt.query_map([], |row| {
@@ -75,6 +99,8 @@ pub fn list_files(conn: &Connection) -> Result<Vec<FileMetadata>> {
             size: row.get(3)?,
             last_indexed: row.get(4)?,
             parse_errors: row.get(5)?,
+            index_duration_ms: row.get(6)?,
+            metadata: row.get(7)?,
         })
     })?
     .collect::<Result<Vec<_>, _>>()?;
@@ -82,6 +108,149 @@ pub fn list_files(conn: &Connection) -> Result<Vec<FileMetadata>> {
     Ok(files)
 }
 
+/// Query the files that took longest to index, with their duration in milliseconds
+///
+/// Files indexed before durations were recorded are skipped.
+pub fn find_slowest_files(conn: &Connection, limit: usize) -> Result<Vec<(FileMetadata, u64)>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, path, language, size, last_indexed, parse_errors, index_duration_ms, metadata
+         FROM files WHERE index_duration_ms IS NOT NULL
+         ORDER BY index_duration_ms DESC, path LIMIT ?1"
+    )?;
+
+    let files = stmt.query_map(params![limit as i64], |row| {
+        let duration_ms: u64 = row.get(6)?;
+        Ok((
+            FileMetadata {
+                id: Some(row.get(0)?),
+                path: row.get(1)?,
+                language: row.get(2)?,
+                size: row.get(3)?,
+                last_indexed: row.get(4)?,
+                parse_errors: row.get(5)?,
+                index_duration_ms: Some(duration_ms),
+                metadata: row.get(7)?,
+            },
+            duration_ms,
+        ))
+    })?
+    .collect::<Result<Vec<_>, _>>()
+    .context("Failed to query slowest files")?;
+
+    Ok(files)
+}
+
+/// Query files indexed at or after `since`, ordered by path
+///
+/// Compares through `julianday` so rows stored as RFC 3339 and legacy
+/// `YYYY-MM-DD HH:MM:SS` timestamps are both handled.
+pub fn find_files_modified_since(conn: &Connection, since: DateTime<Utc>) -> Result<Vec<FileMetadata>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, path, language, size, last_indexed, parse_errors, index_duration_ms, metadata
+         FROM files
+         WHERE julianday(last_indexed) >= julianday(?1)
+         ORDER BY path"
+    )?;
+
+    let since = since.to_rfc3339_opts(SecondsFormat::Micros, true);
+    let files = stmt.query_map(params![since], |row| {
+        Ok(FileMetadata {
+            id: Some(row.get(0)?),
+            path: row.get(1)?,
+            language: row.get(2)?,
+            size: row.get(3)?,
+            last_indexed: row.get(4)?,
+            parse_errors: row.get(5)?,
+            index_duration_ms: row.get(6)?,
+            metadata: row.get(7)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()
+    .context("Failed to query recently indexed files")?;
+
+    Ok(files)
+}
+
+/// Query files with at least one parse error, ordered by path
+pub fn find_files_with_errors(conn: &Connection) -> Result<Vec<FileMetadata>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, path, language, size, last_indexed, parse_errors, index_duration_ms, metadata
+         FROM files
+         WHERE parse_errors > 0
+         ORDER BY path"
+    )?;
+
+    let files = stmt.query_map([], |row| {
+        Ok(FileMetadata {
+            id: Some(row.get(0)?),
+            path: row.get(1)?,
+            language: row.get(2)?,
+            size: row.get(3)?,
+            last_indexed: row.get(4)?,
+            parse_errors: row.get(5)?,
+            index_duration_ms: row.get(6)?,
+            metadata: row.get(7)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()
+    .context("Failed to query files with parse errors")?;
+
+    Ok(files)
+}
+
+/// Query the stored parse errors of a file, in source order
+pub fn find_parse_errors_by_file(conn: &Connection, file_id: i64) -> Result<Vec<ParseError>> {
+    let mut stmt = conn.prepare(
+        "SELECT f.path, e.line_number, e.column_number, e.message
+         FROM parse_errors e
+         JOIN files f ON e.file_id = f.id
+         WHERE e.file_id = ?1
+         ORDER BY e.line_number, e.column_number"
+    )?;
+
+    let errors = stmt.query_map(params![file_id], |row| {
+        Ok(ParseError {
+            file_path: row.get(0)?,
+            line: row.get(1)?,
+            column: row.get(2)?,
+            message: row.get(3)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()
+    .context("Failed to query parse errors")?;
+
+    Ok(errors)
+}
+
+/// Query Python package entry points (`__init__.py` and `__main__.py`), ordered by path
+pub fn find_package_entry_points(conn: &Connection) -> Result<Vec<FileMetadata>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, path, language, size, last_indexed, parse_errors, index_duration_ms, metadata
+         FROM files
+         WHERE json_valid(metadata)
+           AND (json_extract(metadata, '$.package_init') = 1
+                OR json_extract(metadata, '$.package_main') = 1)
+         ORDER BY path"
+    )?;
+
+    let files = stmt.query_map([], |row| {
+        Ok(FileMetadata {
+            id: Some(row.get(0)?),
+            path: row.get(1)?,
+            language: row.get(2)?,
+            size: row.get(3)?,
+            last_indexed: row.get(4)?,
+            parse_errors: row.get(5)?,
+            index_duration_ms: row.get(6)?,
+            metadata: row.get(7)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()
+    .context("Failed to query package entry points")?;
+
+    Ok(files)
+}
+
 /// Get language statistics
 pub fn get_language_stats(conn: &Connection) -> Result<serde_json::Value> {
     let mut stmt = conn.prepare(
@@ -110,6 +279,44 @@ pub fn get_language_stats(conn: &Connection) -> Result<serde_json::Value> {
     Ok(json!(stats))
 }
 
+/// Get language statistics including symbol counts and files with parse errors
+///
+/// Extends `get_language_stats` with `symbol_count`, `avg_symbols_per_file`
+/// and `files_with_errors` per language, ordered by language.
+pub fn get_language_stats_extended(conn: &Connection) -> Result<serde_json::Value> {
+    let mut stmt = conn.prepare(
+        "SELECT f.language, COUNT(*), SUM(f.size), COALESCE(SUM(s.symbol_count), 0), SUM(f.parse_errors > 0)
+         FROM files f
+         LEFT JOIN (SELECT file_id, COUNT(*) AS symbol_count FROM symbols GROUP BY file_id) s ON s.file_id = f.id
+         GROUP BY f.language ORDER BY f.language",
+    )?;
+
+    let mut stats = Vec::new();
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, i64>(1)?,
+            row.get::<_, i64>(2)?,
+            row.get::<_, i64>(3)?,
+            row.get::<_, i64>(4)?,
+        ))
+    })?;
+
+    for row in rows {
+        let (language, count, total_size, symbol_count, files_with_errors) = row?;
+        stats.push(json!({
+            "language": language,
+            "file_count": count,
+            "total_size": total_size,
+            "symbol_count": symbol_count,
+            "avg_symbols_per_file": symbol_count as f64 / count as f64,
+            "files_with_errors": files_with_errors,
+        }));
+    }
+
+    Ok(json!(stats))
+}
+
 /// Query symbols by file path
 pub fn find_symbols_by_file_path(
     conn: &Connection,
@@ -124,7 +331,7 @@ pub fn find_symbols_by_file_path(
 
     // Query all symbols for this file
     let mut stmt = conn.prepare(
-        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata
+        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end
          FROM symbols WHERE file_id = ?1 ORDER BY line_start"
     )?;
 
@@ -136,6 +343,81 @@ pub fn find_symbols_by_file_path(
             kind: parse_symbol_kind(&row.get::<_, String>(3)?),
             line_start: row.get(4)?,
             line_end: row.get(5)?,
+            column_start: row.get(8)?,
+            column_end: row.get(9)?,
+            scope: row.get(6)?,
+            metadata: row.get(7)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(symbols)
+}
+
+/// Query one page of the symbols named `name`, ordered by file_id then line_start
+pub fn find_symbols_by_name_paged(
+    conn: &Connection,
+    name: &str,
+    limit: usize,
+    offset: usize,
+) -> Result<Vec<Symbol>> {
+    QueryBuilder::new()
+        .filter_name(name)
+        .order_by(SymbolField::FileId, false)
+        .order_by(SymbolField::LineStart, false)
+        .limit(limit)
+        .offset(offset)
+        .execute(conn)
+}
+
+/// Query one page of a file's symbols, ordered by line_start then name
+pub fn find_symbols_by_file_path_paged(
+    conn: &Connection,
+    file_path: &str,
+    limit: usize,
+    offset: usize,
+) -> Result<Vec<Symbol>> {
+    let file_id: i64 = conn.query_row(
+        "SELECT id FROM files WHERE path = ?1",
+        params![file_path],
+        |row| row.get(0)
+    ).context("File not found in database")?;
+
+    QueryBuilder::new()
+        .filter_file_id(file_id)
+        .order_by(SymbolField::LineStart, false)
+        .order_by(SymbolField::Name, false)
+        .limit(limit)
+        .offset(offset)
+        .execute(conn)
+}
+
+/// Query symbols in every file whose path matches a glob pattern
+///
+/// `*` matches any run of characters (including `/`) and `?` a single
+/// character, so `src/api/*` covers the whole subtree. Matching follows SQL
+/// `LIKE` and is case-insensitive for ASCII.
+pub fn find_symbols_by_file_path_pattern(conn: &Connection, pattern: &str) -> Result<Vec<Symbol>> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT s.id, s.file_id, s.name, s.kind, s.line_start, s.line_end, s.scope, s.metadata, s.column_start, s.column_end
+        FROM symbols s
+        JOIN files f ON s.file_id = f.id
+        WHERE f.path LIKE ?1 ESCAPE '\'
+        ORDER BY f.path, s.line_start
+        "#,
+    )?;
+
+    let symbols = stmt.query_map(params![glob_to_like(pattern)], |row| {
+        Ok(Symbol {
+            id: Some(row.get(0)?),
+            file_id: row.get(1)?,
+            name: row.get(2)?,
+            kind: parse_symbol_kind(&row.get::<_, String>(3)?),
+            line_start: row.get(4)?,
+            line_end: row.get(5)?,
+            column_start: row.get(8)?,
+            column_end: row.get(9)?,
             scope: row.get(6)?,
             metadata: row.get(7)?,
         })
@@ -145,6 +427,32 @@ pub fn find_symbols_by_file_path(
     Ok(symbols)
 }
 
+/// List files whose path matches a glob pattern (see `find_symbols_by_file_path_pattern`)
+pub fn find_files_by_path_pattern(conn: &Connection, pattern: &str) -> Result<Vec<FileMetadata>> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT id, path, language, size, last_indexed, parse_errors, index_duration_ms, metadata
+        FROM files WHERE path LIKE ?1 ESCAPE '\' ORDER BY path
+        "#,
+    )?;
+
+    let files = stmt.query_map(params![glob_to_like(pattern)], |row| {
+        Ok(FileMetadata {
+            id: Some(row.get(0)?),
+            path: row.get(1)?,
+            language: row.get(2)?,
+            size: row.get(3)?,
+            last_indexed: row.get(4)?,
+            parse_errors: row.get(5)?,
+            index_duration_ms: row.get(6)?,
+            metadata: row.get(7)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(files)
+}
+
 /// Query import symbols for a file
 pub fn find_imports_by_file(
     conn: &Connection,
@@ -159,7 +467,7 @@ pub fn find_imports_by_file(
 
     // Query import symbols for this file
     let mut stmt = conn.prepare(
-        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata
+        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end
          FROM symbols WHERE file_id = ?1 AND kind = 'import' ORDER BY line_start"
     )?;
 
@@ -171,6 +479,8 @@ pub fn find_imports_by_file(
             kind: parse_symbol_kind(&row.get::<_, String>(3)?),
             line_start: row.get(4)?,
             line_end: row.get(5)?,
+            column_start: row.get(8)?,
+            column_end: row.get(9)?,
             scope: row.get(6)?,
             metadata: row.get(7)?,
         })
@@ -194,7 +504,7 @@ pub fn find_exports_by_file(
 
     // Query export symbols for this file
     let mut stmt = conn.prepare(
-        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata
+        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end
          FROM symbols WHERE file_id = ?1 AND kind = 'export' ORDER BY line_start"
     )?;
 
@@ -206,6 +516,8 @@ pub fn find_exports_by_file(
             kind: parse_symbol_kind(&row.get::<_, String>(3)?),
             line_start: row.get(4)?,
             line_end: row.get(5)?,
+            column_start: row.get(8)?,
+            column_end: row.get(9)?,
             scope: row.get(6)?,
             metadata: row.get(7)?,
         })
@@ -229,58 +541,65 @@ pub fn get_file_path_by_id(
     Ok(path)
 }
 
-/// Analyze query plan for a given SQL statement
-pub fn analyze_query_plan(
-    conn: &Connection,
-    query: &str,
-) -> Result<String> {
-    let mut stmt = conn.prepare(&format!("EXPLAIN QUERY PLAN {}", query))?;
-
-    let mut plan = String::new();
-    let rows = stmt.query_map([], |row| {
-        let detail: String = row.get(3)?;
-        Ok(detail)
-    })?;
-
-    for row in rows {
-        let detail = row?;
-        plan.push_str(&detail);
-        plan.push('\n');
-    }
-
-    Ok(plan)
+/// Get a symbol by id, failing if it does not exist
+pub fn get_symbol_by_id(conn: &Connection, symbol_id: i64) -> Result<Symbol> {
+    find_symbol_by_id(conn, symbol_id)?.context("Symbol not found in database")
 }
 
-/// Run ANALYZE to update query planner statistics
-pub fn update_query_statistics(conn: &Connection) -> Result<()> {
-    conn.execute("ANALYZE", [])?;
-    Ok(())
+/// Find a symbol by id; same as `storage::get_symbol_by_id`
+pub fn find_symbol_by_id(conn: &Connection, symbol_id: i64) -> Result<Option<Symbol>> {
+    storage::get_symbol_by_id(conn, symbol_id)
 }
 
-/// Optimize database by running VACUUM and ANALYZE
-pub fn optimize_database(conn: &Connection) -> Result<()> {
-    // VACUUM reclaims space from deleted records
-    conn.execute("VACUUM", [])?;
+/// Query symbols of a file that start within `line_start..=line_end`
+pub fn find_symbols_in_range(
+    conn: &Connection,
+    file_id: i64,
+    line_start: usize,
+    line_end: usize,
+) -> Result<Vec<Symbol>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end
+         FROM symbols WHERE file_id = ?1 AND line_start BETWEEN ?2 AND ?3
+         ORDER BY line_start"
+    )?;
 
-    // ANALYZE updates query planner statistics
-    conn.execute("ANALYZE", [])?;
+    let symbols = stmt.query_map(params![file_id, line_start, line_end], |row| {
+        Ok(Symbol {
+            id: Some(row.get(0)?),
+            file_id: row.get(1)?,
+            name: row.get(2)?,
+            kind: parse_symbol_kind(&row.get::<_, String>(3)?),
+            line_start: row.get(4)?,
+            line_end: row.get(5)?,
+            column_start: row.get(8)?,
+            column_end: row.get(9)?,
+            scope: row.get(6)?,
+            metadata: row.get(7)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
 
-    Ok(())
+    Ok(symbols)
 }
 
-/// Query symbols by name and kind (optimized with composite index)
-pub fn find_symbols_by_name_and_kind(
+/// Query symbols of a file whose line range overlaps `start_line..=end_line`
+///
+/// Used for diff-aware re-indexing: symbols that merely touch the changed
+/// region (ending on `start_line` or starting on `end_line`) are included.
+pub fn find_symbols_modified_between(
     conn: &Connection,
-    name: &str,
-    kind: SymbolKind,
+    file_id: i64,
+    start_line: usize,
+    end_line: usize,
 ) -> Result<Vec<Symbol>> {
-    // Uses idx_symbols_name_kind composite index
     let mut stmt = conn.prepare(
-        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata
-         FROM symbols WHERE name = ?1 AND kind = ?2"
+        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end
+         FROM symbols WHERE file_id = ?1 AND NOT (line_end < ?2 OR line_start > ?3)
+         ORDER BY line_start"
     )?;
 
-    let symbols = stmt.query_map(params![name, kind.to_string()], |row| {
+    let symbols = stmt.query_map(params![file_id, start_line, end_line], |row| {
         Ok(Symbol {
             id: Some(row.get(0)?),
             file_id: row.get(1)?,
@@ -288,6 +607,8 @@ pub fn find_symbols_by_name_and_kind(
             kind: parse_symbol_kind(&row.get::<_, String>(3)?),
             line_start: row.get(4)?,
             line_end: row.get(5)?,
+            column_start: row.get(8)?,
+            column_end: row.get(9)?,
             scope: row.get(6)?,
             metadata: row.get(7)?,
         })
@@ -297,19 +618,22 @@ pub fn find_symbols_by_name_and_kind(
     Ok(symbols)
 }
 
-/// Query symbols by file_id and kind (optimized with composite index)
-pub fn find_symbols_by_file_and_kind(
+/// Query symbols of a file whose line range overlaps `start_line..=end_line`
+///
+/// Bounding `line_start` lets the lookup use `idx_symbols_file_line`.
+pub fn find_symbols_by_line_range(
     conn: &Connection,
     file_id: i64,
-    kind: SymbolKind,
+    start_line: usize,
+    end_line: usize,
 ) -> Result<Vec<Symbol>> {
-    // Uses idx_symbols_file_kind composite index
     let mut stmt = conn.prepare(
-        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata
-         FROM symbols WHERE file_id = ?1 AND kind = ?2 ORDER BY line_start"
+        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end
+         FROM symbols WHERE file_id = ?1 AND line_start <= ?3 AND line_end >= ?2
+         ORDER BY line_start"
     )?;
 
-    let symbols = stmt.query_map(params![file_id, kind.to_string()], |row| {
+    let symbols = stmt.query_map(params![file_id, start_line, end_line], |row| {
         Ok(Symbol {
             id: Some(row.get(0)?),
             file_id: row.get(1)?,
@@ -317,6 +641,8 @@ pub fn find_symbols_by_file_and_kind(
             kind: parse_symbol_kind(&row.get::<_, String>(3)?),
             line_start: row.get(4)?,
             line_end: row.get(5)?,
+            column_start: row.get(8)?,
+            column_end: row.get(9)?,
             scope: row.get(6)?,
             metadata: row.get(7)?,
         })
@@ -326,268 +652,2949 @@ pub fn find_symbols_by_file_and_kind(
     Ok(symbols)
 }
 
-fn parse_symbol_kind(s: &str) -> SymbolKind {
-    match s.to_lowercase().as_str() {
-        "function" => SymbolKind::Function,
-        "class" => SymbolKind::Class,
-        "variable" => SymbolKind::Variable,
-        "import" => SymbolKind::Import,
-        "export" => SymbolKind::Export,
-        "module" => SymbolKind::Module,
-        "struct" => SymbolKind::Struct,
-        "enum" => SymbolKind::Enum,
-        "trait" => SymbolKind::Trait,
-        "interface" => SymbolKind::Interface,
-        "type" => SymbolKind::Type,
-        _ => SymbolKind::Variable, // Default fallback
-    }
+/// Find the innermost symbol of a file that spans `line`
+///
+/// The symbol with the fewest lines wins; ties go to the one starting last.
+pub fn find_symbol_at_line(conn: &Connection, file_id: i64, line: usize) -> Result<Option<Symbol>> {
+    let symbol = conn.query_row(
+        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end
+         FROM symbols WHERE file_id = ?1 AND line_start <= ?2 AND line_end >= ?2
+         ORDER BY line_end - line_start, line_start DESC, column_start DESC
+         LIMIT 1",
+        params![file_id, line],
+        |row| {
+            Ok(Symbol {
+                id: Some(row.get(0)?),
+                file_id: row.get(1)?,
+                name: row.get(2)?,
+                kind: parse_symbol_kind(&row.get::<_, String>(3)?),
+                line_start: row.get(4)?,
+                line_end: row.get(5)?,
+                column_start: row.get(8)?,
+                column_end: row.get(9)?,
+                scope: row.get(6)?,
+                metadata: row.get(7)?,
+            })
+        },
+    ).optional()?;
+
+    Ok(symbol)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::storage::{init_schema, upsert_file, insert_symbol};
-    use tempfile::NamedTempFile;
+/// A symbol together with its neighbours in the same file
+#[derive(Debug, Clone)]
+pub struct SymbolContext {
+    pub symbol: Symbol,
+    pub file_path: String,
+    /// Symbols starting in the `context_lines` lines above the symbol
+    pub preceding_symbols: Vec<Symbol>,
+    /// Symbols starting in the `context_lines` lines below the symbol's end
+    pub following_symbols: Vec<Symbol>,
+}
 
-    #[test]
-    fn test_find_symbols_by_name() {
-        let temp_file = NamedTempFile::new().unwrap();
-        let conn = init_schema(temp_file.path()).unwrap();
+/// Fetch a symbol and the symbols within `context_lines` lines around it
+///
+/// Symbols nested inside the target (e.g. methods of a class) are not context.
+pub fn get_symbol_context(
+    conn: &Connection,
+    symbol_id: i64,
+    context_lines: usize,
+) -> Result<SymbolContext> {
+    let symbol = get_symbol_by_id(conn, symbol_id)?;
+    let file_path = get_file_path_by_id(conn, symbol.file_id)?;
+
+    let preceding_symbols = if symbol.line_start == 0 {
+        Vec::new()
+    } else {
+        find_symbols_in_range(
+            conn,
+            symbol.file_id,
+            symbol.line_start.saturating_sub(context_lines),
+            symbol.line_start - 1,
+        )?
+    };
+    let following_symbols = find_symbols_in_range(
+        conn,
+        symbol.file_id,
+        symbol.line_end + 1,
+        symbol.line_end + context_lines,
+    )?;
 
-        let file = FileMetadata {
-            id: None,
-            path: "test.py".to_string(),
-            language: "python".to_string(),
-            size: 1024,
-            last_indexed: None,
-            parse_errors: 0,
-        };
-        let file_id = upsert_file(&conn, &file).unwrap();
+    Ok(SymbolContext {
+        symbol,
+        file_path,
+        preceding_symbols,
+        following_symbols,
+    })
+}
 
-        let symbol = Symbol {
-            id: None,
-            file_id,
-            name: "my_function".to_string(),
-            kind: SymbolKind::Function,
-            line_start: 10,
-            line_end: 20,
-            scope: None,
-            metadata: None,
-        };
-        insert_symbol(&conn, &symbol).unwrap();
+/// A row of the `change_log` audit table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangeLogEntry {
+    pub id: i64,
+    /// `FileChangeKind` as text (`create`, `modify`, `delete`, ...)
+    pub event_kind: String,
+    pub file_path: String,
+    /// UTC timestamp as stored by SQLite (`YYYY-MM-DD HH:MM:SS`)
+    pub timestamp: String,
+    pub symbols_added: usize,
+    pub symbols_removed: usize,
+}
 
-        let found = find_symbols_by_name(&conn, "my_function").unwrap();
-        assert_eq!(found.len(), 1);
-        assert_eq!(found[0].name, "my_function");
-    }
+/// Normalize an ISO 8601 timestamp to SQLite's `YYYY-MM-DD HH:MM:SS` form
+pub(crate) fn normalize_timestamp(conn: &Connection, iso: &str) -> Result<String> {
+    let normalized: Option<String> = conn.query_row("SELECT datetime(?1)", params![iso], |row| row.get(0))?;
+    normalized.ok_or_else(|| QueryError::InvalidTimestamp(iso.to_string()).into())
+}
 
-    #[test]
-    fn test_language_stats() {
-        let temp_file = NamedTempFile::new().unwrap();
+/// Query change log entries recorded at or after `since` (ISO 8601), oldest first
+pub fn query_change_log(conn: &Connection, since: &str) -> Result<Vec<ChangeLogEntry>> {
+    let since = normalize_timestamp(conn, since)?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, event_kind, file_path, timestamp, symbols_added, symbols_removed
+         FROM change_log WHERE timestamp >= ?1 ORDER BY timestamp, id"
+    )?;
+
+    let entries = stmt.query_map(params![since], |row| {
+        Ok(ChangeLogEntry {
+            id: row.get(0)?,
+            event_kind: row.get(1)?,
+            file_path: row.get(2)?,
+            timestamp: row.get(3)?,
+            symbols_added: row.get(4)?,
+            symbols_removed: row.get(5)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(entries)
+}
+
+/// Delete change log entries recorded before `older_than` (ISO 8601)
+///
+/// Returns the number of entries removed.
+pub fn prune_change_log(conn: &Connection, older_than: &str) -> Result<usize> {
+    let older_than = normalize_timestamp(conn, older_than)?;
+
+    conn.execute("DELETE FROM change_log WHERE timestamp < ?1", params![older_than])
+        .context("Failed to prune change log")
+}
+
+/// Analyze query plan for a given SQL statement
+pub fn analyze_query_plan(
+    conn: &Connection,
+    query: &str,
+) -> Result<String> {
+    let mut stmt = conn.prepare(&format!("EXPLAIN QUERY PLAN {}", query))?;
+
+    let mut plan = String::new();
+    let rows = stmt.query_map([], |row| {
+        let detail: String = row.get(3)?;
+        Ok(detail)
+    })?;
+
+    for row in rows {
+        let detail = row?;
+        plan.push_str(&detail);
+        plan.push('\n');
+    }
+
+    Ok(plan)
+}
+
+/// Representative SQL for each query function, used by `explain_query`
+const QUERY_PLANS: &[(&str, &str)] = &[
+    ("find_symbols_by_name", "SELECT * FROM symbols WHERE name = 'x'"),
+    ("find_symbols_by_kind", "SELECT * FROM symbols WHERE kind = 'function'"),
+    ("find_symbols_by_name_and_kind", "SELECT * FROM symbols WHERE name = 'x' AND kind = 'function'"),
+    ("find_references_by_name", "SELECT * FROM symbols WHERE name = 'x' ORDER BY file_id, line_start"),
+    ("count_references_by_name", "SELECT COUNT(*) FROM symbols WHERE name = 'x'"),
+    ("count_symbols_by_kind", "SELECT kind, COUNT(*) FROM symbols GROUP BY kind"),
+    ("count_symbols_by_file", "SELECT COUNT(*) FROM symbols WHERE file_id = 1"),
+    ("count_symbols_total", "SELECT COUNT(*) FROM symbols"),
+    ("find_duplicate_symbol_names", "SELECT name, COUNT(*) AS n FROM symbols WHERE kind = 'function' GROUP BY name HAVING n > 1 ORDER BY n DESC, name"),
+    ("find_duplicate_symbols_in_file", "SELECT name, COUNT(*) AS n FROM symbols WHERE file_id = 1 GROUP BY name HAVING n > 1 ORDER BY n DESC, name"),
+    ("find_symbols_by_name_prefix", "SELECT * FROM symbols WHERE name >= 'get' AND name < 'geu' ORDER BY name LIMIT 10"),
+    ("find_symbols_by_name_prefix_and_kind", "SELECT * FROM symbols INDEXED BY idx_symbols_name_kind WHERE name >= 'get' AND name < 'geu' AND kind = 'function' ORDER BY name LIMIT 10"),
+    ("find_symbols_by_file_and_kind", "SELECT * FROM symbols WHERE file_id = 1 AND kind = 'function' ORDER BY line_start"),
+    ("find_symbols_by_file_path", "SELECT * FROM symbols WHERE file_id = 1 ORDER BY line_start"),
+    ("find_symbols_in_scope", "WITH RECURSIVE scoped(id) AS (SELECT id FROM symbols WHERE file_id = 1 AND scope = 'Foo' UNION SELECT child.id FROM scoped JOIN symbols parent ON parent.id = scoped.id JOIN symbols child ON child.file_id = 1 AND child.scope = parent.name AND child.line_start BETWEEN parent.line_start AND parent.line_end) SELECT s.* FROM symbols s JOIN scoped ON s.id = scoped.id ORDER BY s.line_start, s.column_start"),
+    ("find_symbols_by_file_path_pattern", "SELECT s.* FROM symbols s JOIN files f ON s.file_id = f.id WHERE f.path LIKE 'src/%' ESCAPE '\\' ORDER BY f.path, s.line_start"),
+    ("find_files_by_path_pattern", "SELECT * FROM files WHERE path LIKE 'src/%' ESCAPE '\\' ORDER BY path"),
+    ("find_imports_by_file", "SELECT * FROM symbols WHERE file_id = 1 AND kind = 'import' ORDER BY line_start"),
+    ("find_exports_by_file", "SELECT * FROM symbols WHERE file_id = 1 AND kind = 'export' ORDER BY line_start"),
+    ("get_file_path_by_id", "SELECT path FROM files WHERE id = 1"),
+    ("get_file_by_path", "SELECT * FROM files WHERE path = 'x'"),
+    ("list_files", "SELECT * FROM files"),
+    ("get_language_stats", "SELECT language, COUNT(*), SUM(size) FROM files GROUP BY language"),
+    ("get_language_stats_extended", "SELECT f.language, COUNT(*), SUM(f.size), COALESCE(SUM(s.symbol_count), 0), SUM(f.parse_errors > 0) FROM files f LEFT JOIN (SELECT file_id, COUNT(*) AS symbol_count FROM symbols GROUP BY file_id) s ON s.file_id = f.id GROUP BY f.language ORDER BY f.language"),
+    ("search_symbols_across_languages", "SELECT s.*, f.path FROM symbols s JOIN files f ON s.file_id = f.id WHERE s.name LIKE '%cfg%' AND f.language IN ('rust', 'python') ORDER BY length(s.name)"),
+    ("find_cross_language_type_usages", "SELECT s.*, f.path FROM symbols s JOIN files f ON s.file_id = f.id WHERE s.name = 'Config' OR s.scope = 'Config' OR s.metadata REGEXP 'Config' ORDER BY f.language, f.path, s.line_start"),
+    ("find_symbols_grouped_by_file", "SELECT s.*, f.path FROM symbols s JOIN files f ON s.file_id = f.id WHERE s.name = 'Config' ORDER BY f.path, s.line_start"),
+    ("find_slowest_files", "SELECT * FROM files WHERE index_duration_ms IS NOT NULL ORDER BY index_duration_ms DESC, path LIMIT 10"),
+    ("find_files_modified_since", "SELECT * FROM files WHERE julianday(last_indexed) >= julianday('2024-01-01T00:00:00Z') ORDER BY path"),
+    ("find_files_with_errors", "SELECT * FROM files WHERE parse_errors > 0 ORDER BY path"),
+    ("find_parse_errors_by_file", "SELECT f.path, e.* FROM parse_errors e JOIN files f ON e.file_id = f.id WHERE e.file_id = 1 ORDER BY e.line_number, e.column_number"),
+    ("find_package_entry_points", "SELECT * FROM files WHERE json_extract(metadata, '$.package_init') = 1 OR json_extract(metadata, '$.package_main') = 1 ORDER BY path"),
+    ("find_symbols_modified_between", "SELECT * FROM symbols WHERE file_id = 1 AND NOT (line_end < 10 OR line_start > 20) ORDER BY line_start"),
+    ("find_symbols_in_range", "SELECT * FROM symbols WHERE file_id = 1 AND line_start BETWEEN 10 AND 20 ORDER BY line_start"),
+    ("find_symbols_by_line_range", "SELECT * FROM symbols WHERE file_id = 1 AND line_start <= 20 AND line_end >= 10 ORDER BY line_start"),
+    ("find_symbol_at_line", "SELECT * FROM symbols WHERE file_id = 1 AND line_start <= 10 AND line_end >= 10 ORDER BY line_end - line_start, line_start DESC, column_start DESC LIMIT 1"),
+    ("find_type_guards", "SELECT * FROM symbols WHERE json_extract(metadata, '$.type_guard') = 1 ORDER BY file_id, line_start"),
+    ("find_utility_types", "SELECT * FROM symbols WHERE json_extract(metadata, '$.mapped_type') = 1 OR json_extract(metadata, '$.conditional_type') = 1 ORDER BY file_id, line_start"),
+    ("find_test_symbols", "SELECT * FROM symbols WHERE json_extract(metadata, '$.test') = 1 AND file_id = 1 ORDER BY file_id, line_start"),
+    ("find_ffi_symbols", "SELECT * FROM symbols WHERE json_extract(metadata, '$.ffi') = 1 ORDER BY file_id, line_start"),
+    ("find_public_symbols_by_file", "SELECT * FROM symbols WHERE file_id = 1 AND json_extract(metadata, '$.visibility') = 'pub' ORDER BY line_start"),
+    ("find_symbols_with_base", "SELECT * FROM symbols WHERE EXISTS (SELECT 1 FROM json_each(metadata, '$.extends') WHERE value = 'Base') OR EXISTS (SELECT 1 FROM json_each(metadata, '$.implements') WHERE value = 'Base') ORDER BY file_id, line_start"),
+    ("find_impl_trait_functions", "SELECT * FROM symbols WHERE json_extract(metadata, '$.return_impl_trait') IS NOT NULL ORDER BY file_id, line_start"),
+    ("find_symbols_by_name_regex", "SELECT * FROM symbols WHERE name REGEXP 'x' ORDER BY file_id, line_start LIMIT 10"),
+    ("search_symbols_fts", "SELECT s.* FROM symbol_fts JOIN symbols s ON s.id = symbol_fts.rowid WHERE symbol_fts MATCH '{name} : (x*)' ORDER BY rank LIMIT 10"),
+];
+
+/// Summary of the query plan for one query function
+#[derive(Debug, Clone)]
+pub struct ExplainReport {
+    pub function: String,
+    pub uses_index: bool,
+    pub index_name: Option<String>,
+    pub scan_rows_estimate: Option<u64>,
+    pub plan_text: String,
+}
+
+/// Get the query plan for a query function by name (e.g. "find_symbols_by_name")
+pub fn explain_query(conn: &Connection, fn_name: &str) -> Result<String> {
+    let sql = QUERY_PLANS
+        .iter()
+        .find(|(name, _)| *name == fn_name)
+        .map(|(_, sql)| *sql)
+        .ok_or_else(|| anyhow::anyhow!("Unknown query function: {}", fn_name))?;
+
+    analyze_query_plan(conn, sql)
+}
+
+/// Explain every registered query function
+pub fn explain_all_queries(conn: &Connection) -> Result<Vec<ExplainReport>> {
+    QUERY_PLANS
+        .iter()
+        .map(|(name, sql)| {
+            let plan_text = analyze_query_plan(conn, sql)?;
+            Ok(parse_explain_report(name, plan_text))
+        })
+        .collect()
+}
+
+fn parse_explain_report(function: &str, plan_text: String) -> ExplainReport {
+    let index_name = plan_text
+        .split_whitespace()
+        .skip_while(|word| *word != "INDEX")
+        .nth(1)
+        .map(|name| name.to_string());
+    let uses_index = index_name.is_some() || plan_text.contains("PRIMARY KEY");
+
+    // Older SQLite versions annotate plans with "(~N rows)"
+    let scan_rows_estimate = plan_text
+        .split("(~")
+        .nth(1)
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|n| n.parse().ok());
+
+    ExplainReport {
+        function: function.to_string(),
+        uses_index,
+        index_name,
+        scan_rows_estimate,
+        plan_text,
+    }
+}
+
+/// Run ANALYZE to update query planner statistics
+pub fn update_query_statistics(conn: &Connection) -> Result<()> {
+    conn.execute("ANALYZE", [])?;
+    Ok(())
+}
+
+/// Optimize database by compacting symbol metadata and the full-text index, then running VACUUM and ANALYZE
+pub fn optimize_database(conn: &Connection) -> Result<()> {
+    // Drop default-valued metadata keys before VACUUM reclaims the space
+    compact_metadata(conn)?;
+
+    // Merge full-text index segments into one b-tree
+    conn.execute("INSERT INTO symbol_fts(symbol_fts) VALUES('optimize')", [])?;
+
+    // VACUUM reclaims space from deleted records
+    conn.execute("VACUUM", [])?;
+
+    // ANALYZE updates query planner statistics
+    conn.execute("ANALYZE", [])?;
+
+    Ok(())
+}
+
+/// Query symbols by name and kind (optimized with composite index)
+pub fn find_symbols_by_name_and_kind(
+    conn: &Connection,
+    name: &str,
+    kind: SymbolKind,
+) -> Result<Vec<Symbol>> {
+    // Uses idx_symbols_name_kind composite index
+    let mut stmt = conn.prepare(
+        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end
+         FROM symbols WHERE name = ?1 AND kind = ?2"
+    )?;
+
+    let symbols = stmt.query_map(params![name, kind.to_string()], |row| {
+        Ok(Symbol {
+            id: Some(row.get(0)?),
+            file_id: row.get(1)?,
+            name: row.get(2)?,
+            kind: parse_symbol_kind(&row.get::<_, String>(3)?),
+            line_start: row.get(4)?,
+            line_end: row.get(5)?,
+            column_start: row.get(8)?,
+            column_end: row.get(9)?,
+            scope: row.get(6)?,
+            metadata: row.get(7)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(symbols)
+}
+
+/// Query up to `limit` symbols whose name starts with `prefix`, ordered by name
+///
+/// Matching is case-sensitive. The prefix is turned into a `name` range rather
+/// than a `LIKE` pattern, since SQLite's case-insensitive `LIKE` cannot use
+/// `idx_symbols_name`.
+pub fn find_symbols_by_name_prefix(
+    conn: &Connection,
+    prefix: &str,
+    limit: usize,
+) -> Result<Vec<Symbol>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end
+         FROM symbols WHERE name >= ?1 AND name < ?2 ORDER BY name LIMIT ?3"
+    )?;
+
+    let symbols = stmt.query_map(params![prefix, prefix_upper_bound(prefix), limit as i64], |row| {
+        Ok(Symbol {
+            id: Some(row.get(0)?),
+            file_id: row.get(1)?,
+            name: row.get(2)?,
+            kind: parse_symbol_kind(&row.get::<_, String>(3)?),
+            line_start: row.get(4)?,
+            line_end: row.get(5)?,
+            column_start: row.get(8)?,
+            column_end: row.get(9)?,
+            scope: row.get(6)?,
+            metadata: row.get(7)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(symbols)
+}
+
+/// Query up to `limit` symbols of `kind` whose name starts with `prefix`, ordered by name
+pub fn find_symbols_by_name_prefix_and_kind(
+    conn: &Connection,
+    prefix: &str,
+    kind: SymbolKind,
+    limit: usize,
+) -> Result<Vec<Symbol>> {
+    // Walk idx_symbols_name_kind in name order so LIMIT stops early; the planner
+    // would otherwise prefer idx_symbols_kind and sort every symbol of that kind
+    let mut stmt = conn.prepare(
+        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end
+         FROM symbols INDEXED BY idx_symbols_name_kind
+         WHERE name >= ?1 AND name < ?2 AND kind = ?3 ORDER BY name LIMIT ?4"
+    )?;
+
+    let symbols = stmt.query_map(
+        params![prefix, prefix_upper_bound(prefix), kind.to_string(), limit as i64],
+        |row| {
+            Ok(Symbol {
+                id: Some(row.get(0)?),
+                file_id: row.get(1)?,
+                name: row.get(2)?,
+                kind: parse_symbol_kind(&row.get::<_, String>(3)?),
+                line_start: row.get(4)?,
+                line_end: row.get(5)?,
+                column_start: row.get(8)?,
+                column_end: row.get(9)?,
+                scope: row.get(6)?,
+                metadata: row.get(7)?,
+            })
+        },
+    )?
+    .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(symbols)
+}
+
+/// Query every symbol named `name` across all files, optionally of one `kind`
+///
+/// A lightweight stand-in for "find references": definitions and imports
+/// sharing the name are returned alike, ordered by file_id then line_start.
+pub fn find_references_by_name(
+    conn: &Connection,
+    name: &str,
+    kind: Option<SymbolKind>,
+) -> Result<Vec<Symbol>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end
+         FROM symbols WHERE name = ?1 AND (?2 IS NULL OR kind = ?2)
+         ORDER BY file_id, line_start"
+    )?;
+
+    let symbols = stmt.query_map(params![name, kind.map(|k| k.to_string())], |row| {
+        Ok(Symbol {
+            id: Some(row.get(0)?),
+            file_id: row.get(1)?,
+            name: row.get(2)?,
+            kind: parse_symbol_kind(&row.get::<_, String>(3)?),
+            line_start: row.get(4)?,
+            line_end: row.get(5)?,
+            column_start: row.get(8)?,
+            column_end: row.get(9)?,
+            scope: row.get(6)?,
+            metadata: row.get(7)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(symbols)
+}
+
+/// Number of symbols `find_references_by_name` would return
+pub fn count_references_by_name(conn: &Connection, name: &str, kind: Option<SymbolKind>) -> Result<usize> {
+    let count = conn.query_row(
+        "SELECT COUNT(*) FROM symbols WHERE name = ?1 AND (?2 IS NULL OR kind = ?2)",
+        params![name, kind.map(|k| k.to_string())],
+        |row| row.get(0),
+    )?;
+
+    Ok(count)
+}
+
+/// Number of indexed symbols of each kind (kinds without symbols are absent)
+pub fn count_symbols_by_kind(conn: &Connection) -> Result<HashMap<SymbolKind, usize>> {
+    let mut stmt = conn.prepare("SELECT kind, COUNT(*) FROM symbols GROUP BY kind")?;
+
+    let mut counts = HashMap::new();
+    let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, usize>(1)?)))?;
+    for row in rows {
+        let (kind, count) = row?;
+        // Unknown kinds fall back to `Variable`, so merge rather than overwrite
+        *counts.entry(parse_symbol_kind(&kind)).or_insert(0) += count;
+    }
+
+    Ok(counts)
+}
+
+/// Number of symbols stored for a file
+pub fn count_symbols_by_file(conn: &Connection, file_id: i64) -> Result<usize> {
+    let count = conn.query_row(
+        "SELECT COUNT(*) FROM symbols WHERE file_id = ?1",
+        params![file_id],
+        |row| row.get(0),
+    )?;
+
+    Ok(count)
+}
+
+/// Number of symbols in the whole index
+pub fn count_symbols_total(conn: &Connection) -> Result<usize> {
+    let count = conn.query_row("SELECT COUNT(*) FROM symbols", [], |row| row.get(0))?;
+
+    Ok(count)
+}
+
+/// `(name, count)` for symbols of `kind` whose name is used more than once
+/// across the index, most frequent first
+pub fn find_duplicate_symbol_names(conn: &Connection, kind: SymbolKind) -> Result<Vec<(String, usize)>> {
+    let mut stmt = conn.prepare(
+        "SELECT name, COUNT(*) AS n FROM symbols WHERE kind = ?1
+         GROUP BY name HAVING n > 1 ORDER BY n DESC, name",
+    )?;
+
+    let duplicates = stmt
+        .query_map(params![kind.to_string()], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(duplicates)
+}
+
+/// `(name, count)` for names declared more than once within a file, of any
+/// kind, most frequent first
+pub fn find_duplicate_symbols_in_file(conn: &Connection, file_id: i64) -> Result<Vec<(String, usize)>> {
+    let mut stmt = conn.prepare(
+        "SELECT name, COUNT(*) AS n FROM symbols WHERE file_id = ?1
+         GROUP BY name HAVING n > 1 ORDER BY n DESC, name",
+    )?;
+
+    let duplicates = stmt
+        .query_map(params![file_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(duplicates)
+}
+
+/// Exclusive upper bound of the names starting with `prefix`
+///
+/// The last character that can be incremented is bumped and anything after it
+/// dropped (`get` -> `geu`). Without one every name qualifies, so an empty
+/// BLOB is used: SQLite sorts BLOBs after all text.
+fn prefix_upper_bound(prefix: &str) -> SqlValue {
+    let mut chars: Vec<char> = prefix.chars().collect();
+    while let Some(last) = chars.pop() {
+        let next = match last {
+            '\u{D7FF}' => Some('\u{E000}'),
+            c => char::from_u32(c as u32 + 1),
+        };
+        if let Some(next) = next {
+            chars.push(next);
+            return SqlValue::Text(chars.into_iter().collect());
+        }
+    }
+    SqlValue::Blob(Vec::new())
+}
+
+/// Query symbols by file_id and kind (optimized with composite index)
+pub fn find_symbols_by_file_and_kind(
+    conn: &Connection,
+    file_id: i64,
+    kind: SymbolKind,
+) -> Result<Vec<Symbol>> {
+    // Uses idx_symbols_file_kind composite index
+    let mut stmt = conn.prepare(
+        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end
+         FROM symbols WHERE file_id = ?1 AND kind = ?2 ORDER BY line_start"
+    )?;
+
+    let symbols = stmt.query_map(params![file_id, kind.to_string()], |row| {
+        Ok(Symbol {
+            id: Some(row.get(0)?),
+            file_id: row.get(1)?,
+            name: row.get(2)?,
+            kind: parse_symbol_kind(&row.get::<_, String>(3)?),
+            line_start: row.get(4)?,
+            line_end: row.get(5)?,
+            column_start: row.get(8)?,
+            column_end: row.get(9)?,
+            scope: row.get(6)?,
+            metadata: row.get(7)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(symbols)
+}
+
+/// Query every symbol of a file nested under `scope_name`, directly or through
+/// intermediate scopes (e.g. a class's methods and the functions inside them)
+///
+/// Scopes only record the container's name, so a nested symbol must also lie
+/// within its container's lines; this keeps same-named methods of different
+/// classes apart. Ordered by position.
+pub fn find_symbols_in_scope(conn: &Connection, file_id: i64, scope_name: &str) -> Result<Vec<Symbol>> {
+    let mut stmt = conn.prepare(
+        "WITH RECURSIVE scoped(id) AS (
+             SELECT id FROM symbols WHERE file_id = ?1 AND scope = ?2
+             UNION
+             SELECT child.id FROM scoped
+             JOIN symbols parent ON parent.id = scoped.id
+             JOIN symbols child ON child.file_id = ?1 AND child.scope = parent.name
+                 AND child.line_start BETWEEN parent.line_start AND parent.line_end
+         )
+         SELECT s.id, s.file_id, s.name, s.kind, s.line_start, s.line_end, s.scope, s.metadata, s.column_start, s.column_end
+         FROM symbols s JOIN scoped ON s.id = scoped.id
+         ORDER BY s.line_start, s.column_start"
+    )?;
+
+    let symbols = stmt.query_map(params![file_id, scope_name], |row| {
+        Ok(Symbol {
+            id: Some(row.get(0)?),
+            file_id: row.get(1)?,
+            name: row.get(2)?,
+            kind: parse_symbol_kind(&row.get::<_, String>(3)?),
+            line_start: row.get(4)?,
+            line_end: row.get(5)?,
+            column_start: row.get(8)?,
+            column_end: row.get(9)?,
+            scope: row.get(6)?,
+            metadata: row.get(7)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(symbols)
+}
+
+/// Query TypeScript utility types (mapped and conditional type aliases)
+pub fn find_utility_types(conn: &Connection) -> Result<Vec<Symbol>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end
+         FROM symbols
+         WHERE json_extract(metadata, '$.mapped_type') = 1 OR json_extract(metadata, '$.conditional_type') = 1
+         ORDER BY file_id, line_start"
+    )?;
+
+    let symbols = stmt.query_map([], |row| {
+        Ok(Symbol {
+            id: Some(row.get(0)?),
+            file_id: row.get(1)?,
+            name: row.get(2)?,
+            kind: parse_symbol_kind(&row.get::<_, String>(3)?),
+            line_start: row.get(4)?,
+            line_end: row.get(5)?,
+            column_start: row.get(8)?,
+            column_end: row.get(9)?,
+            scope: row.get(6)?,
+            metadata: row.get(7)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(symbols)
+}
+
+/// Query TypeScript type guard functions (`x is T` return types)
+pub fn find_type_guards(conn: &Connection) -> Result<Vec<Symbol>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end
+         FROM symbols WHERE json_extract(metadata, '$.type_guard') = 1 ORDER BY file_id, line_start"
+    )?;
+
+    let symbols = stmt.query_map([], |row| {
+        Ok(Symbol {
+            id: Some(row.get(0)?),
+            file_id: row.get(1)?,
+            name: row.get(2)?,
+            kind: parse_symbol_kind(&row.get::<_, String>(3)?),
+            line_start: row.get(4)?,
+            line_end: row.get(5)?,
+            column_start: row.get(8)?,
+            column_end: row.get(9)?,
+            scope: row.get(6)?,
+            metadata: row.get(7)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(symbols)
+}
+
+/// Query Rust functions that return `impl Trait` (including `async fn`)
+pub fn find_impl_trait_functions(conn: &Connection) -> Result<Vec<Symbol>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end
+         FROM symbols WHERE json_extract(metadata, '$.return_impl_trait') IS NOT NULL ORDER BY file_id, line_start"
+    )?;
+
+    let symbols = stmt.query_map([], |row| {
+        Ok(Symbol {
+            id: Some(row.get(0)?),
+            file_id: row.get(1)?,
+            name: row.get(2)?,
+            kind: parse_symbol_kind(&row.get::<_, String>(3)?),
+            line_start: row.get(4)?,
+            line_end: row.get(5)?,
+            column_start: row.get(8)?,
+            column_end: row.get(9)?,
+            scope: row.get(6)?,
+            metadata: row.get(7)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(symbols)
+}
+
+/// Query test functions (`"test": true` metadata, e.g. Rust `#[test]`),
+/// optionally restricted to one file
+pub fn find_test_symbols(conn: &Connection, file_id: Option<i64>) -> Result<Vec<Symbol>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end
+         FROM symbols WHERE json_extract(metadata, '$.test') = 1 AND (?1 IS NULL OR file_id = ?1)
+         ORDER BY file_id, line_start"
+    )?;
+
+    let symbols = stmt.query_map(params![file_id], |row| {
+        Ok(Symbol {
+            id: Some(row.get(0)?),
+            file_id: row.get(1)?,
+            name: row.get(2)?,
+            kind: parse_symbol_kind(&row.get::<_, String>(3)?),
+            line_start: row.get(4)?,
+            line_end: row.get(5)?,
+            column_start: row.get(8)?,
+            column_end: row.get(9)?,
+            scope: row.get(6)?,
+            metadata: row.get(7)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(symbols)
+}
+
+/// Query FFI declarations (`"ffi": true` metadata, e.g. Rust `extern "C"` items)
+pub fn find_ffi_symbols(conn: &Connection) -> Result<Vec<Symbol>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end
+         FROM symbols WHERE json_extract(metadata, '$.ffi') = 1 ORDER BY file_id, line_start"
+    )?;
+
+    let symbols = stmt.query_map([], |row| {
+        Ok(Symbol {
+            id: Some(row.get(0)?),
+            file_id: row.get(1)?,
+            name: row.get(2)?,
+            kind: parse_symbol_kind(&row.get::<_, String>(3)?),
+            line_start: row.get(4)?,
+            line_end: row.get(5)?,
+            column_start: row.get(8)?,
+            column_end: row.get(9)?,
+            scope: row.get(6)?,
+            metadata: row.get(7)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(symbols)
+}
+
+/// Query the `pub` items of a file (`"visibility": "pub"` metadata)
+///
+/// Restricted visibilities such as `pub(crate)` are not part of the public
+/// API and are excluded.
+pub fn find_public_symbols_by_file(conn: &Connection, file_id: i64) -> Result<Vec<Symbol>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end
+         FROM symbols WHERE file_id = ?1 AND json_extract(metadata, '$.visibility') = 'pub' ORDER BY line_start"
+    )?;
+
+    let symbols = stmt.query_map(params![file_id], |row| {
+        Ok(Symbol {
+            id: Some(row.get(0)?),
+            file_id: row.get(1)?,
+            name: row.get(2)?,
+            kind: parse_symbol_kind(&row.get::<_, String>(3)?),
+            line_start: row.get(4)?,
+            line_end: row.get(5)?,
+            column_start: row.get(8)?,
+            column_end: row.get(9)?,
+            scope: row.get(6)?,
+            metadata: row.get(7)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(symbols)
+}
+
+/// Query classes, interfaces and types that extend or implement `base_name`
+///
+/// Matches `metadata["extends"]` and `metadata["implements"]` entries exactly,
+/// so a module-qualified base (`typing.Protocol`) only matches by that name.
+pub fn find_symbols_with_base(conn: &Connection, base_name: &str) -> Result<Vec<Symbol>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end
+         FROM symbols
+         WHERE json_valid(metadata)
+           AND (EXISTS (SELECT 1 FROM json_each(metadata, '$.extends') WHERE value = ?1)
+                OR EXISTS (SELECT 1 FROM json_each(metadata, '$.implements') WHERE value = ?1))
+         ORDER BY file_id, line_start"
+    )?;
+
+    let symbols = stmt.query_map([base_name], |row| {
+        Ok(Symbol {
+            id: Some(row.get(0)?),
+            file_id: row.get(1)?,
+            name: row.get(2)?,
+            kind: parse_symbol_kind(&row.get::<_, String>(3)?),
+            line_start: row.get(4)?,
+            line_end: row.get(5)?,
+            column_start: row.get(8)?,
+            column_end: row.get(9)?,
+            scope: row.get(6)?,
+            metadata: row.get(7)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(symbols)
+}
+
+/// Find stored symbols that fail `Symbol::validate`, as `(id, reason)` in id order
+pub fn validate_all_symbols(conn: &Connection) -> Result<Vec<(i64, String)>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end
+         FROM symbols
+         WHERE name = '' OR line_end < line_start OR (metadata IS NOT NULL AND NOT json_valid(metadata))
+         ORDER BY id"
+    )?;
+
+    let symbols = stmt.query_map([], |row| {
+        Ok(Symbol {
+            id: Some(row.get(0)?),
+            file_id: row.get(1)?,
+            name: row.get(2)?,
+            kind: parse_symbol_kind(&row.get::<_, String>(3)?),
+            line_start: row.get(4)?,
+            line_end: row.get(5)?,
+            column_start: row.get(8)?,
+            column_end: row.get(9)?,
+            scope: row.get(6)?,
+            metadata: row.get(7)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()
+    .context("Failed to scan symbols for validation")?;
+
+    Ok(symbols
+        .into_iter()
+        .filter_map(|symbol| {
+            let err = symbol.validate().err()?;
+            Some((symbol.id?, err.message))
+        })
+        .collect())
+}
+
+/// Query symbols whose name matches a regular expression
+///
+/// Uses the `REGEXP` function registered by `init_schema`. Invalid patterns
+/// fail with `QueryError::InvalidRegex`.
+pub fn find_symbols_by_name_regex(
+    conn: &Connection,
+    pattern: &str,
+    limit: usize,
+) -> Result<Vec<Symbol>> {
+    Regex::new(pattern).map_err(|e| QueryError::InvalidRegex {
+        pattern: pattern.to_string(),
+        message: e.to_string(),
+    })?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end
+         FROM symbols WHERE name REGEXP ?1 ORDER BY file_id, line_start LIMIT ?2"
+    )?;
+
+    let symbols = stmt.query_map(params![pattern, limit as i64], |row| {
+        Ok(Symbol {
+            id: Some(row.get(0)?),
+            file_id: row.get(1)?,
+            name: row.get(2)?,
+            kind: parse_symbol_kind(&row.get::<_, String>(3)?),
+            line_start: row.get(4)?,
+            line_end: row.get(5)?,
+            column_start: row.get(8)?,
+            column_end: row.get(9)?,
+            scope: row.get(6)?,
+            metadata: row.get(7)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()
+    .context("Failed to query symbols by regex")?;
+
+    Ok(symbols)
+}
+
+/// Full-text search over symbol names using the `symbol_fts` index, best match first
+///
+/// `query` uses FTS5 syntax restricted to the name column: `get_*` matches
+/// names whose first word starts with `get`, and `"create user"` matches
+/// `create_user`. Names are split into words on `_` and punctuation. Invalid
+/// syntax fails with `QueryError::InvalidFtsQuery`.
+pub fn search_symbols_fts(conn: &Connection, query: &str, limit: Option<usize>) -> Result<Vec<Symbol>> {
+    let mut stmt = conn.prepare(
+        "SELECT s.id, s.file_id, s.name, s.kind, s.line_start, s.line_end, s.scope, s.metadata, s.column_start, s.column_end
+         FROM symbol_fts JOIN symbols s ON s.id = symbol_fts.rowid
+         WHERE symbol_fts MATCH ?1
+         ORDER BY rank, s.file_id, s.line_start
+         LIMIT ?2"
+    )?;
+
+    // SQLite treats a negative LIMIT as no limit
+    let limit = limit.map_or(-1, |limit| limit as i64);
+    let symbols = stmt
+        .query_map(params![format!("{{name}} : ({})", query), limit], |row| {
+            Ok(Symbol {
+                id: Some(row.get(0)?),
+                file_id: row.get(1)?,
+                name: row.get(2)?,
+                kind: parse_symbol_kind(&row.get::<_, String>(3)?),
+                line_start: row.get(4)?,
+                line_end: row.get(5)?,
+                column_start: row.get(8)?,
+                column_end: row.get(9)?,
+                scope: row.get(6)?,
+                metadata: row.get(7)?,
+            })
+        })
+        .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| match e {
+            // The statement is already prepared, so a generic SQL error comes from the MATCH expression
+            rusqlite::Error::SqliteFailure(err, Some(ref message)) if err.code == rusqlite::ErrorCode::Unknown => {
+                anyhow::Error::new(QueryError::InvalidFtsQuery {
+                    query: query.to_string(),
+                    message: message.clone(),
+                })
+            }
+            e => anyhow::Error::new(e).context("Failed to run full-text search"),
+        })?;
+
+    Ok(symbols)
+}
+
+/// Search symbol names across files of the given languages
+///
+/// Matches names containing `query` (ASCII case-insensitive). An empty
+/// `languages` slice searches every language. Results are ranked exact match,
+/// case-insensitive match, prefix, then substring, shorter names first.
+pub fn search_symbols_across_languages(
+    conn: &Connection,
+    query: &str,
+    languages: &[&str],
+) -> Result<Vec<(Symbol, String)>> {
+    let escaped = escape_like(query);
+    let mut values = vec![
+        SqlValue::Text(query.to_string()),
+        SqlValue::Text(escaped.clone()),
+        SqlValue::Text(format!("{}%", escaped)),
+        SqlValue::Text(format!("%{}%", escaped)),
+    ];
+
+    let mut sql = String::from(
+        "SELECT s.id, s.file_id, s.name, s.kind, s.line_start, s.line_end, s.scope, s.metadata, s.column_start, s.column_end, f.path
+         FROM symbols s JOIN files f ON s.file_id = f.id
+         WHERE s.name LIKE ?4 ESCAPE '\\'",
+    );
+    if !languages.is_empty() {
+        let placeholders = vec!["?"; languages.len()].join(", ");
+        sql.push_str(&format!(" AND f.language IN ({})", placeholders));
+        values.extend(languages.iter().map(|lang| SqlValue::Text(lang.to_string())));
+    }
+    sql.push_str(
+        " ORDER BY CASE
+             WHEN s.name = ?1 THEN 0
+             WHEN s.name LIKE ?2 ESCAPE '\\' THEN 1
+             WHEN s.name LIKE ?3 ESCAPE '\\' THEN 2
+             ELSE 3
+           END, length(s.name), f.path, s.line_start",
+    );
+
+    let mut stmt = conn.prepare(&sql)?;
+    let results = stmt
+        .query_map(params_from_iter(values), symbol_with_path)?
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to search symbols across languages")?;
+
+    Ok(results)
+}
+
+/// Find where a type name shows up in every indexed language
+///
+/// Covers symbols named `type_name` (definitions, FFI wrappers, imports),
+/// members scoped under it, and symbols whose metadata mentions it as a
+/// whole word (e.g. return types). Ordered by language, path and line.
+pub fn find_cross_language_type_usages(conn: &Connection, type_name: &str) -> Result<Vec<(Symbol, String)>> {
+    let mut stmt = conn.prepare(
+        "SELECT s.id, s.file_id, s.name, s.kind, s.line_start, s.line_end, s.scope, s.metadata, s.column_start, s.column_end, f.path
+         FROM symbols s JOIN files f ON s.file_id = f.id
+         WHERE s.name = ?1 OR s.scope = ?1 OR s.metadata REGEXP ?2
+         ORDER BY f.language, f.path, s.line_start"
+    )?;
+
+    let word = format!(r"\b{}\b", regex::escape(type_name));
+    let results = stmt
+        .query_map(params![type_name, word], symbol_with_path)?
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to query type usages")?;
+
+    Ok(results)
+}
+
+/// Find every symbol named `name`, grouped by the path of its file
+///
+/// Paths come from the same query, so callers don't need a
+/// `get_file_path_by_id` lookup per symbol. Each group is ordered by line.
+pub fn find_symbols_grouped_by_file(conn: &Connection, name: &str) -> Result<HashMap<String, Vec<Symbol>>> {
+    let mut stmt = conn.prepare(
+        "SELECT s.id, s.file_id, s.name, s.kind, s.line_start, s.line_end, s.scope, s.metadata, s.column_start, s.column_end, f.path
+         FROM symbols s JOIN files f ON s.file_id = f.id
+         WHERE s.name = ?1
+         ORDER BY f.path, s.line_start"
+    )?;
+
+    let mut grouped: HashMap<String, Vec<Symbol>> = HashMap::new();
+    for row in stmt.query_map(params![name], symbol_with_path)? {
+        let (symbol, path) = row.context("Failed to query symbols by file")?;
+        grouped.entry(path).or_default().push(symbol);
+    }
+
+    Ok(grouped)
+}
+
+/// Map `s.id, s.file_id, s.name, s.kind, s.line_start, s.line_end, s.scope, s.metadata, s.column_start, s.column_end, f.path`
+fn symbol_with_path(row: &rusqlite::Row) -> rusqlite::Result<(Symbol, String)> {
+    let symbol = Symbol {
+        id: Some(row.get(0)?),
+        file_id: row.get(1)?,
+        name: row.get(2)?,
+        kind: parse_symbol_kind(&row.get::<_, String>(3)?),
+        line_start: row.get(4)?,
+        line_end: row.get(5)?,
+        column_start: row.get(8)?,
+        column_end: row.get(9)?,
+        scope: row.get(6)?,
+        metadata: row.get(7)?,
+    };
+    Ok((symbol, row.get(10)?))
+}
+
+/// Find functions whose parameter types resemble those of `symbol_id`
+///
+/// Compares the types in `metadata["params"]` (`[{"name": "x", "type": "i32"}, ...]`)
+/// as multisets using Jaccard similarity, so `foo(x: i32, y: String)` and
+/// `bar(a: i32, b: String)` score 1.0. Untyped parameters count as `?`.
+/// Returns matches scoring at least `threshold`, best first.
+///
+/// Read-only: candidates whose stored `metadata["signature_hash"]` equals the
+/// target's score 1.0 without parsing their metadata, and candidates whose
+/// parameter count alone rules out `threshold` are skipped.
+pub fn find_symbols_with_similar_signatures(
+    conn: &Connection,
+    symbol_id: i64,
+    threshold: f32,
+) -> Result<Vec<(Symbol, f32)>> {
+    let target = get_symbol_by_id(conn, symbol_id)?;
+    let Some(target_types) = target
+        .metadata
+        .as_deref()
+        .and_then(|m| serde_json::from_str(m).ok())
+        .and_then(|m| signature_param_types(&m))
+    else {
+        return Ok(Vec::new());
+    };
+    let target_hash = signature_hash(&target_types);
+
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end,
+            json_extract(metadata, '$.signature_hash'), json_array_length(metadata, '$.params')
+        FROM symbols
+        WHERE kind = 'function' AND id != ?1
+          AND json_valid(metadata) AND json_type(metadata, '$.params') = 'array'
+        ORDER BY file_id, line_start
+        "#,
+    )?;
+    let candidates = stmt.query_map(params![symbol_id], |row| {
+        let symbol = Symbol {
+            id: Some(row.get(0)?),
+            file_id: row.get(1)?,
+            name: row.get(2)?,
+            kind: parse_symbol_kind(&row.get::<_, String>(3)?),
+            line_start: row.get(4)?,
+            line_end: row.get(5)?,
+            column_start: row.get(8)?,
+            column_end: row.get(9)?,
+            scope: row.get(6)?,
+            metadata: row.get(7)?,
+        };
+        Ok((symbol, row.get::<_, Option<String>>(10)?, row.get::<_, usize>(11)?))
+    })?;
+
+    let mut matches = Vec::new();
+    for candidate in candidates {
+        let (symbol, hash, param_count) = candidate?;
+
+        let similarity = if hash.as_deref() == Some(target_hash.as_str()) {
+            1.0
+        } else {
+            // Jaccard similarity is at most the ratio of the two list sizes
+            let (fewer, more) = if param_count < target_types.len() {
+                (param_count, target_types.len())
+            } else {
+                (target_types.len(), param_count)
+            };
+            if more > 0 && (fewer as f32 / more as f32) < threshold {
+                continue;
+            }
+
+            let Some(types) = symbol
+                .metadata
+                .as_deref()
+                .and_then(|m| serde_json::from_str(m).ok())
+                .and_then(|m| signature_param_types(&m))
+            else {
+                continue;
+            };
+            jaccard_similarity(&target_types, &types)
+        };
+        if similarity >= threshold {
+            matches.push((symbol, similarity));
+        }
+    }
+
+    matches.sort_by(|a, b| b.1.total_cmp(&a.1));
+    Ok(matches)
+}
+
+/// Multiset Jaccard similarity of two sorted lists; two empty lists are identical
+fn jaccard_similarity(a: &[String], b: &[String]) -> f32 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+
+    // Merge-walk the sorted lists counting shared elements
+    let (mut i, mut j, mut shared) = (0, 0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Equal => {
+                shared += 1;
+                i += 1;
+                j += 1;
+            }
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+        }
+    }
+    shared as f32 / (a.len() + b.len() - shared) as f32
+}
+
+/// Escape `%`, `_` and `\` for a `LIKE ... ESCAPE '\'` pattern
+fn escape_like(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Convert a glob (`*`, `?`) to a `LIKE ... ESCAPE '\'` pattern
+fn glob_to_like(pattern: &str) -> String {
+    escape_like(pattern).replace('*', "%").replace('?', "_")
+}
+
+/// Symbol columns `QueryBuilder` can sort by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolField {
+    Name,
+    Kind,
+    FileId,
+    LineStart,
+    LineEnd,
+    Scope,
+}
+
+impl SymbolField {
+    fn column(self) -> &'static str {
+        match self {
+            SymbolField::Name => "name",
+            SymbolField::Kind => "kind",
+            SymbolField::FileId => "file_id",
+            SymbolField::LineStart => "line_start",
+            SymbolField::LineEnd => "line_end",
+            SymbolField::Scope => "scope",
+        }
+    }
+}
+
+impl FromStr for SymbolField {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "name" => Ok(SymbolField::Name),
+            "kind" => Ok(SymbolField::Kind),
+            "file_id" => Ok(SymbolField::FileId),
+            "line_start" => Ok(SymbolField::LineStart),
+            "line_end" => Ok(SymbolField::LineEnd),
+            "scope" => Ok(SymbolField::Scope),
+            other => anyhow::bail!("Unknown symbol field: {}", other),
+        }
+    }
+}
+
+/// Composable symbol query; all values are bound as parameters
+///
+/// ```ignore
+/// let symbols = QueryBuilder::new()
+///     .filter_kind(SymbolKind::Function)
+///     .filter_file("src/main.py")
+///     .order_by(SymbolField::LineStart, false)
+///     .limit(10)
+///     .execute(&conn)?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct QueryBuilder {
+    name: Option<String>,
+    kind: Option<SymbolKind>,
+    file: Option<String>,
+    file_id: Option<i64>,
+    scope: Option<String>,
+    line_range: Option<(usize, usize)>,
+    order: Vec<(SymbolField, bool)>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+}
+
+impl QueryBuilder {
+    /// Create a query matching every symbol
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only symbols with this exact name
+    pub fn filter_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Only symbols of this kind
+    pub fn filter_kind(mut self, kind: SymbolKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Only symbols in the file with this path
+    pub fn filter_file(mut self, path: &str) -> Self {
+        self.file = Some(path.to_string());
+        self
+    }
+
+    /// Only symbols in the file with this id
+    pub fn filter_file_id(mut self, file_id: i64) -> Self {
+        self.file_id = Some(file_id);
+        self
+    }
+
+    /// Only symbols whose scope is exactly `scope`
+    pub fn filter_scope(mut self, scope: &str) -> Self {
+        self.scope = Some(scope.to_string());
+        self
+    }
+
+    /// Only symbols lying entirely within lines `start..=end`
+    pub fn filter_line_range(mut self, start: usize, end: usize) -> Self {
+        self.line_range = Some((start, end));
+        self
+    }
+
+    /// Sort by `field`; may be called repeatedly for secondary keys
+    pub fn order_by(mut self, field: SymbolField, desc: bool) -> Self {
+        self.order.push((field, desc));
+        self
+    }
+
+    /// Return at most `n` symbols
+    pub fn limit(mut self, n: usize) -> Self {
+        self.limit = Some(n);
+        self
+    }
+
+    /// Skip the first `n` symbols
+    pub fn offset(mut self, n: usize) -> Self {
+        self.offset = Some(n);
+        self
+    }
+
+    /// Build the SQL statement and its positional parameters
+    pub fn to_sql(&self) -> (String, Vec<SqlValue>) {
+        let mut conditions = Vec::new();
+        let mut values = Vec::new();
+
+        if let Some(name) = &self.name {
+            conditions.push("name = ?");
+            values.push(SqlValue::Text(name.clone()));
+        }
+        if let Some(kind) = self.kind {
+            conditions.push("kind = ?");
+            values.push(SqlValue::Text(kind.to_string()));
+        }
+        if let Some(file) = &self.file {
+            conditions.push("file_id = (SELECT id FROM files WHERE path = ?)");
+            values.push(SqlValue::Text(file.clone()));
+        }
+        if let Some(file_id) = self.file_id {
+            conditions.push("file_id = ?");
+            values.push(SqlValue::Integer(file_id));
+        }
+        if let Some(scope) = &self.scope {
+            conditions.push("scope = ?");
+            values.push(SqlValue::Text(scope.clone()));
+        }
+        if let Some((start, end)) = self.line_range {
+            conditions.push("line_start >= ? AND line_end <= ?");
+            values.push(SqlValue::Integer(start as i64));
+            values.push(SqlValue::Integer(end as i64));
+        }
+
+        let mut sql = String::from(
+            "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end FROM symbols",
+        );
+        if !conditions.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&conditions.join(" AND "));
+        }
+
+        if !self.order.is_empty() {
+            let order: Vec<String> = self
+                .order
+                .iter()
+                .map(|(field, desc)| format!("{} {}", field.column(), if *desc { "DESC" } else { "ASC" }))
+                .collect();
+            sql.push_str(" ORDER BY ");
+            sql.push_str(&order.join(", "));
+        }
+
+        // SQLite requires LIMIT whenever OFFSET is used; -1 means no limit
+        if self.limit.is_some() || self.offset.is_some() {
+            sql.push_str(" LIMIT ?");
+            values.push(SqlValue::Integer(self.limit.map_or(-1, |n| n as i64)));
+        }
+        if let Some(offset) = self.offset {
+            sql.push_str(" OFFSET ?");
+            values.push(SqlValue::Integer(offset as i64));
+        }
+
+        (sql, values)
+    }
+
+    /// Get the SQLite query plan for the built query
+    pub fn explain(&self, conn: &Connection) -> Result<String> {
+        let (sql, values) = self.to_sql();
+        let mut stmt = conn.prepare(&format!("EXPLAIN QUERY PLAN {}", sql))?;
+
+        let mut plan = String::new();
+        let rows = stmt.query_map(params_from_iter(values), |row| row.get::<_, String>(3))?;
+        for row in rows {
+            plan.push_str(&row?);
+            plan.push('\n');
+        }
+
+        Ok(plan)
+    }
+
+    /// Run the query
+    pub fn execute(&self, conn: &Connection) -> Result<Vec<Symbol>> {
+        let (sql, values) = self.to_sql();
+        let mut stmt = conn.prepare(&sql)?;
+
+        let symbols = stmt.query_map(params_from_iter(values), |row| {
+            Ok(Symbol {
+                id: Some(row.get(0)?),
+                file_id: row.get(1)?,
+                name: row.get(2)?,
+                kind: parse_symbol_kind(&row.get::<_, String>(3)?),
+                line_start: row.get(4)?,
+                line_end: row.get(5)?,
+                column_start: row.get(8)?,
+                column_end: row.get(9)?,
+                scope: row.get(6)?,
+                metadata: row.get(7)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to execute symbol query")?;
+
+        Ok(symbols)
+    }
+}
+
+pub(crate) fn parse_symbol_kind(s: &str) -> SymbolKind {
+    s.parse().unwrap_or(SymbolKind::Variable) // Default fallback
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{init_schema, upsert_file, insert_symbol, insert_change_log};
+    use std::collections::HashMap;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_find_symbols_by_name() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let file = FileMetadata {
+            id: None,
+            path: "test.py".to_string(),
+            language: "python".to_string(),
+            size: 1024,
+            last_indexed: None,
+            parse_errors: 0,
+            index_duration_ms: None,
+            metadata: None,
+        };
+        let file_id = upsert_file(&conn, &file).unwrap();
+
+        let symbol = Symbol {
+            id: None,
+            file_id,
+            name: "my_function".to_string(),
+            kind: SymbolKind::Function,
+            line_start: 10,
+            line_end: 20,
+            column_start: 0,
+            column_end: 0,
+            scope: None,
+            metadata: None,
+        };
+        insert_symbol(&conn, &symbol).unwrap();
+
+        let found = find_symbols_by_name(&conn, "my_function").unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].name, "my_function");
+    }
+
+    #[test]
+    fn test_language_stats() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let file1 = FileMetadata {
+            id: None,
+            path: "test1.py".to_string(),
+            language: "python".to_string(),
+            size: 1024,
+            last_indexed: None,
+            parse_errors: 0,
+            index_duration_ms: None,
+            metadata: None,
+        };
+        upsert_file(&conn, &file1).unwrap();
+
+        let file2 = FileMetadata {
+            id: None,
+            path: "test2.py".to_string(),
+            language: "python".to_string(),
+            size: 2048,
+            last_indexed: None,
+            parse_errors: 0,
+            index_duration_ms: None,
+            metadata: None,
+        };
+        upsert_file(&conn, &file2).unwrap();
+
+        let stats = get_language_stats(&conn).unwrap();
+        let stats_array = stats.as_array().unwrap();
+        assert_eq!(stats_array.len(), 1);
+        assert_eq!(stats_array[0]["language"], "python");
+        assert_eq!(stats_array[0]["file_count"], 2);
+    }
+
+    #[test]
+    fn test_language_stats_extended() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let file = |path: &str, language: &str, parse_errors| FileMetadata {
+            id: None,
+            path: path.to_string(),
+            language: language.to_string(),
+            size: 100,
+            last_indexed: None,
+            parse_errors,
+            index_duration_ms: None,
+            metadata: None,
+        };
+        let a = upsert_file(&conn, &file("a.py", "python", 0)).unwrap();
+        upsert_file(&conn, &file("b.py", "python", 2)).unwrap();
+        upsert_file(&conn, &file("c.rs", "rust", 0)).unwrap();
+        for (name, line) in [("f", 1), ("g", 2), ("h", 3)] {
+            let symbol = Symbol {
+                id: None,
+                file_id: a,
+                name: name.to_string(),
+                kind: SymbolKind::Function,
+                line_start: line,
+                line_end: line,
+                column_start: 0,
+                column_end: 0,
+                scope: None,
+                metadata: None,
+            };
+            insert_symbol(&conn, &symbol).unwrap();
+        }
+
+        let stats = get_language_stats_extended(&conn).unwrap();
+        assert_eq!(
+            stats,
+            json!([
+                {"language": "python", "file_count": 2, "total_size": 200, "symbol_count": 3, "avg_symbols_per_file": 1.5, "files_with_errors": 1},
+                {"language": "rust", "file_count": 1, "total_size": 100, "symbol_count": 0, "avg_symbols_per_file": 0.0, "files_with_errors": 0},
+            ])
+        );
+    }
+
+    #[test]
+    fn test_find_symbols_by_file_path() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let file = FileMetadata {
+            id: None,
+            path: "test.py".to_string(),
+            language: "python".to_string(),
+            size: 1024,
+            last_indexed: None,
+            parse_errors: 0,
+            index_duration_ms: None,
+            metadata: None,
+        };
+        let file_id = upsert_file(&conn, &file).unwrap();
+
+        let symbol1 = Symbol {
+            id: None,
+            file_id,
+            name: "my_function".to_string(),
+            kind: SymbolKind::Function,
+            line_start: 10,
+            line_end: 20,
+            column_start: 0,
+            column_end: 0,
+            scope: None,
+            metadata: None,
+        };
+        insert_symbol(&conn, &symbol1).unwrap();
+
+        let symbol2 = Symbol {
+            id: None,
+            file_id,
+            name: "MyClass".to_string(),
+            kind: SymbolKind::Class,
+            line_start: 25,
+            line_end: 40,
+            column_start: 0,
+            column_end: 0,
+            scope: None,
+            metadata: None,
+        };
+        insert_symbol(&conn, &symbol2).unwrap();
+
+        let found = find_symbols_by_file_path(&conn, "test.py").unwrap();
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].name, "my_function");
+        assert_eq!(found[1].name, "MyClass");
+    }
+
+    #[test]
+    fn test_find_imports_by_file() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let file = FileMetadata {
+            id: None,
+            path: "test.py".to_string(),
+            language: "python".to_string(),
+            size: 1024,
+            last_indexed: None,
+            parse_errors: 0,
+            index_duration_ms: None,
+            metadata: None,
+        };
+        let file_id = upsert_file(&conn, &file).unwrap();
+
+        let import1 = Symbol {
+            id: None,
+            file_id,
+            name: "os".to_string(),
+            kind: SymbolKind::Import,
+            line_start: 1,
+            line_end: 1,
+            column_start: 0,
+            column_end: 0,
+            scope: None,
+            metadata: None,
+        };
+        insert_symbol(&conn, &import1).unwrap();
+
+        let import2 = Symbol {
+            id: None,
+            file_id,
+            name: "sys".to_string(),
+            kind: SymbolKind::Import,
+            line_start: 2,
+            line_end: 2,
+            column_start: 0,
+            column_end: 0,
+            scope: None,
+            metadata: None,
+        };
+        insert_symbol(&conn, &import2).unwrap();
+
+        let function = Symbol {
+            id: None,
+            file_id,
+            name: "my_function".to_string(),
+            kind: SymbolKind::Function,
+            line_start: 10,
+            line_end: 20,
+            column_start: 0,
+            column_end: 0,
+            scope: None,
+            metadata: None,
+        };
+        insert_symbol(&conn, &function).unwrap();
+
+        let imports = find_imports_by_file(&conn, "test.py").unwrap();
+        assert_eq!(imports.len(), 2);
+        assert_eq!(imports[0].name, "os");
+        assert_eq!(imports[1].name, "sys");
+    }
+
+    #[test]
+    fn test_get_file_path_by_id() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let file = FileMetadata {
+            id: None,
+            path: "test.py".to_string(),
+            language: "python".to_string(),
+            size: 1024,
+            last_indexed: None,
+            parse_errors: 0,
+            index_duration_ms: None,
+            metadata: None,
+        };
+        let file_id = upsert_file(&conn, &file).unwrap();
+
+        let path = get_file_path_by_id(&conn, file_id).unwrap();
+        assert_eq!(path, "test.py");
+    }
+
+    #[test]
+    fn test_find_symbols_by_name_and_kind() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let file = FileMetadata {
+            id: None,
+            path: "test.py".to_string(),
+            language: "python".to_string(),
+            size: 1024,
+            last_indexed: None,
+            parse_errors: 0,
+            index_duration_ms: None,
+            metadata: None,
+        };
+        let file_id = upsert_file(&conn, &file).unwrap();
+
+        // Insert function with same name
+        let symbol1 = Symbol {
+            id: None,
+            file_id,
+            name: "process".to_string(),
+            kind: SymbolKind::Function,
+            line_start: 10,
+            line_end: 20,
+            column_start: 0,
+            column_end: 0,
+            scope: None,
+            metadata: None,
+        };
+        insert_symbol(&conn, &symbol1).unwrap();
+
+        // Insert variable with same name
+        let symbol2 = Symbol {
+            id: None,
+            file_id,
+            name: "process".to_string(),
+            kind: SymbolKind::Variable,
+            line_start: 5,
+            line_end: 5,
+            column_start: 0,
+            column_end: 0,
+            scope: None,
+            metadata: None,
+        };
+        insert_symbol(&conn, &symbol2).unwrap();
+
+        // Query for function only
+        let functions = find_symbols_by_name_and_kind(&conn, "process", SymbolKind::Function).unwrap();
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0].kind, SymbolKind::Function);
+
+        // Query for variable only
+        let variables = find_symbols_by_name_and_kind(&conn, "process", SymbolKind::Variable).unwrap();
+        assert_eq!(variables.len(), 1);
+        assert_eq!(variables[0].kind, SymbolKind::Variable);
+    }
+
+    #[test]
+    fn test_find_references_by_name() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let file_ids: Vec<i64> = ["config.py", "app.py"]
+            .iter()
+            .map(|path| {
+                let file = FileMetadata {
+                    id: None,
+                    path: path.to_string(),
+                    language: "python".to_string(),
+                    size: 256,
+                    last_indexed: None,
+                    parse_errors: 0,
+                    index_duration_ms: None,
+                    metadata: None,
+                };
+                upsert_file(&conn, &file).unwrap()
+            })
+            .collect();
+
+        let symbol = |file_id, name: &str, kind, line_start| Symbol {
+            id: None,
+            file_id,
+            name: name.to_string(),
+            kind,
+            line_start,
+            line_end: line_start,
+            column_start: 0,
+            column_end: 0,
+            scope: None,
+            metadata: None,
+        };
+        insert_symbol(&conn, &symbol(file_ids[1], "load", SymbolKind::Function, 30)).unwrap();
+        insert_symbol(&conn, &symbol(file_ids[1], "load", SymbolKind::Import, 1)).unwrap();
+        insert_symbol(&conn, &symbol(file_ids[0], "load", SymbolKind::Function, 12)).unwrap();
+        insert_symbol(&conn, &symbol(file_ids[0], "save", SymbolKind::Function, 20)).unwrap();
+
+        let locations = |kind| {
+            find_references_by_name(&conn, "load", kind)
+                .unwrap()
+                .into_iter()
+                .map(|s| (s.file_id, s.line_start))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(locations(None), vec![(file_ids[0], 12), (file_ids[1], 1), (file_ids[1], 30)]);
+        assert_eq!(locations(Some(SymbolKind::Function)), vec![(file_ids[0], 12), (file_ids[1], 30)]);
+        assert_eq!(locations(Some(SymbolKind::Class)), vec![]);
+
+        assert_eq!(count_references_by_name(&conn, "load", None).unwrap(), 3);
+        assert_eq!(count_references_by_name(&conn, "load", Some(SymbolKind::Import)).unwrap(), 1);
+        assert_eq!(count_references_by_name(&conn, "unused", None).unwrap(), 0);
+
+        let by_kind = count_symbols_by_kind(&conn).unwrap();
+        assert_eq!(by_kind, HashMap::from([(SymbolKind::Function, 3), (SymbolKind::Import, 1)]));
+        assert_eq!(count_symbols_by_file(&conn, file_ids[0]).unwrap(), 2);
+        assert_eq!(count_symbols_by_file(&conn, file_ids[1] + 1).unwrap(), 0);
+        assert_eq!(count_symbols_total(&conn).unwrap(), 4);
+
+        assert_eq!(
+            find_duplicate_symbol_names(&conn, SymbolKind::Function).unwrap(),
+            vec![("load".to_string(), 2)]
+        );
+        assert_eq!(find_duplicate_symbol_names(&conn, SymbolKind::Import).unwrap(), vec![]);
+        assert_eq!(find_duplicate_symbols_in_file(&conn, file_ids[1]).unwrap(), vec![("load".to_string(), 2)]);
+        assert_eq!(find_duplicate_symbols_in_file(&conn, file_ids[0]).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_find_symbols_by_name_prefix() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let file = FileMetadata {
+            id: None,
+            path: "test.py".to_string(),
+            language: "python".to_string(),
+            size: 1024,
+            last_indexed: None,
+            parse_errors: 0,
+            index_duration_ms: None,
+            metadata: None,
+        };
+        let file_id = upsert_file(&conn, &file).unwrap();
+
+        let symbol = |name: &str, kind, line_start| Symbol {
+            id: None,
+            file_id,
+            name: name.to_string(),
+            kind,
+            line_start,
+            line_end: line_start,
+            column_start: 0,
+            column_end: 0,
+            scope: None,
+            metadata: None,
+        };
+        insert_symbol(&conn, &symbol("get_user", SymbolKind::Function, 1)).unwrap();
+        insert_symbol(&conn, &symbol("get_item", SymbolKind::Function, 2)).unwrap();
+        insert_symbol(&conn, &symbol("getter", SymbolKind::Variable, 3)).unwrap();
+        insert_symbol(&conn, &symbol("GetConfig", SymbolKind::Class, 4)).unwrap();
+        insert_symbol(&conn, &symbol("ge", SymbolKind::Variable, 5)).unwrap();
+        insert_symbol(&conn, &symbol("set_user", SymbolKind::Function, 6)).unwrap();
+        insert_symbol(&conn, &symbol("get\u{10FFFF}tail", SymbolKind::Variable, 7)).unwrap();
+
+        let names = |symbols: Vec<Symbol>| symbols.into_iter().map(|s| s.name).collect::<Vec<_>>();
+
+        assert_eq!(
+            names(find_symbols_by_name_prefix(&conn, "get", 10).unwrap()),
+            vec!["get_item", "get_user", "getter", "get\u{10FFFF}tail"]
+        );
+        assert_eq!(names(find_symbols_by_name_prefix(&conn, "get", 2).unwrap()), vec!["get_item", "get_user"]);
+        // `_` and `%` match literally and case is significant
+        assert_eq!(names(find_symbols_by_name_prefix(&conn, "get_u", 10).unwrap()), vec!["get_user"]);
+        assert!(find_symbols_by_name_prefix(&conn, "get%", 10).unwrap().is_empty());
+        assert_eq!(names(find_symbols_by_name_prefix(&conn, "Get", 10).unwrap()), vec!["GetConfig"]);
+        assert_eq!(find_symbols_by_name_prefix(&conn, "", 100).unwrap().len(), 7);
+
+        assert_eq!(
+            names(find_symbols_by_name_prefix_and_kind(&conn, "get", SymbolKind::Function, 10).unwrap()),
+            vec!["get_item", "get_user"]
+        );
+        assert!(find_symbols_by_name_prefix_and_kind(&conn, "set", SymbolKind::Class, 10).unwrap().is_empty());
+
+        for report in explain_all_queries(&conn).unwrap() {
+            if report.function.starts_with("find_symbols_by_name_prefix") {
+                let index = report.index_name.unwrap_or_default();
+                assert!(index.starts_with("idx_symbols_name"), "{}", report.plan_text);
+                assert!(!report.plan_text.contains("TEMP B-TREE"), "{}", report.plan_text);
+            }
+        }
+    }
+
+    #[test]
+    fn test_optimize_database() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let file = FileMetadata {
+            id: None,
+            path: "test.py".to_string(),
+            language: "python".to_string(),
+            size: 1024,
+            last_indexed: None,
+            parse_errors: 0,
+            index_duration_ms: None,
+            metadata: None,
+        };
+        let file_id = upsert_file(&conn, &file).unwrap();
+
+        let symbol = Symbol {
+            id: None,
+            file_id,
+            name: "test_func".to_string(),
+            kind: SymbolKind::Function,
+            line_start: 1,
+            line_end: 10,
+            column_start: 0,
+            column_end: 0,
+            scope: None,
+            metadata: Some(r#"{"async":false}"#.to_string()),
+        };
+        insert_symbol(&conn, &symbol).unwrap();
+
+        // Run optimization
+        optimize_database(&conn).unwrap();
+
+        // Verify database still works after optimization, with metadata compacted
+        let symbols = find_symbols_by_name(&conn, "test_func").unwrap();
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(symbols[0].metadata, None);
+    }
+
+    #[test]
+    fn test_analyze_query_plan() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let query = "SELECT * FROM symbols WHERE name = 'test'";
+        let plan = analyze_query_plan(&conn, query).unwrap();
+
+        // Query plan should mention the index
+        assert!(plan.contains("idx_symbols_name") || plan.contains("SEARCH"));
+    }
+
+    #[test]
+    fn test_find_type_guards() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let file = FileMetadata {
+            id: None,
+            path: "guards.ts".to_string(),
+            language: "typescript".to_string(),
+            size: 128,
+            last_indexed: None,
+            parse_errors: 0,
+            index_duration_ms: None,
+            metadata: None,
+        };
+        let file_id = upsert_file(&conn, &file).unwrap();
+
+        let guard = Symbol {
+            id: None,
+            file_id,
+            name: "isString".to_string(),
+            kind: SymbolKind::Function,
+            line_start: 1,
+            line_end: 1,
+            column_start: 0,
+            column_end: 0,
+            scope: None,
+            metadata: Some(r#"{"type_guard":true,"guard_type":"string"}"#.to_string()),
+        };
+        insert_symbol(&conn, &guard).unwrap();
+
+        let plain = Symbol {
+            id: None,
+            file_id,
+            name: "plain".to_string(),
+            kind: SymbolKind::Function,
+            line_start: 2,
+            line_end: 2,
+            column_start: 0,
+            column_end: 0,
+            scope: None,
+            metadata: None,
+        };
+        insert_symbol(&conn, &plain).unwrap();
+
+        let guards = find_type_guards(&conn).unwrap();
+        assert_eq!(guards.len(), 1);
+        assert_eq!(guards[0].name, "isString");
+    }
+
+    #[test]
+    fn test_find_utility_types() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let file = FileMetadata {
+            id: None,
+            path: "types.ts".to_string(),
+            language: "typescript".to_string(),
+            size: 128,
+            last_indexed: None,
+            parse_errors: 0,
+            index_duration_ms: None,
+            metadata: None,
+        };
+        let file_id = upsert_file(&conn, &file).unwrap();
+
+        for (line, name, metadata) in [
+            (0, "Readonly", Some(r#"{"mapped_type":true}"#)),
+            (1, "NonNullable", Some(r#"{"conditional_type":true,"condition":"T extends null"}"#)),
+            (2, "UserId", None),
+        ] {
+            let symbol = Symbol {
+                id: None,
+                file_id,
+                name: name.to_string(),
+                kind: SymbolKind::Type,
+                line_start: line,
+                line_end: line,
+                column_start: 0,
+                column_end: 0,
+                scope: None,
+                metadata: metadata.map(str::to_string),
+            };
+            insert_symbol(&conn, &symbol).unwrap();
+        }
+
+        let names: Vec<String> = find_utility_types(&conn).unwrap().into_iter().map(|s| s.name).collect();
+        assert_eq!(names, vec!["Readonly", "NonNullable"]);
+    }
+
+    #[test]
+    fn test_get_symbol_context() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let file = FileMetadata {
+            id: None,
+            path: "service.py".to_string(),
+            language: "python".to_string(),
+            size: 512,
+            last_indexed: None,
+            parse_errors: 0,
+            index_duration_ms: None,
+            metadata: None,
+        };
+        let file_id = upsert_file(&conn, &file).unwrap();
+
+        // class Service (0-20) with methods at 2, 6, 10, 14 and a local inside `handle`
+        let symbol = |name: &str, kind, line_start, line_end, scope: Option<&str>| Symbol {
+            id: None,
+            file_id,
+            name: name.to_string(),
+            kind,
+            line_start,
+            line_end,
+            column_start: 0,
+            column_end: 0,
+            scope: scope.map(str::to_string),
+            metadata: None,
+        };
+        insert_symbol(&conn, &symbol("Service", SymbolKind::Class, 0, 20, None)).unwrap();
+        insert_symbol(&conn, &symbol("__init__", SymbolKind::Function, 2, 4, Some("Service"))).unwrap();
+        insert_symbol(&conn, &symbol("load", SymbolKind::Function, 6, 8, Some("Service"))).unwrap();
+        let target = insert_symbol(&conn, &symbol("handle", SymbolKind::Function, 10, 12, Some("Service"))).unwrap();
+        insert_symbol(&conn, &symbol("result", SymbolKind::Variable, 11, 11, Some("handle"))).unwrap();
+        insert_symbol(&conn, &symbol("save", SymbolKind::Function, 14, 16, Some("Service"))).unwrap();
+
+        let names = |symbols: &[Symbol]| symbols.iter().map(|s| s.name.clone()).collect::<Vec<_>>();
+
+        let context = get_symbol_context(&conn, target, 4).unwrap();
+        assert_eq!(context.symbol.name, "handle");
+        assert_eq!(context.file_path, "service.py");
+        assert_eq!(names(&context.preceding_symbols), vec!["load"]);
+        assert_eq!(names(&context.following_symbols), vec!["save"]);
+
+        let context = get_symbol_context(&conn, target, 10).unwrap();
+        assert_eq!(names(&context.preceding_symbols), vec!["Service", "__init__", "load"]);
+
+        let context = get_symbol_context(&conn, target, 0).unwrap();
+        assert!(context.preceding_symbols.is_empty());
+        assert!(context.following_symbols.is_empty());
+
+        assert!(get_symbol_context(&conn, target + 100, 4).is_err());
+    }
+
+    /// A Rust `Config` struct exposed to Python through a `Config` wrapper class
+    fn seed_cross_language(conn: &Connection) {
+        let files = [
+            ("src/config.rs", "rust", vec![
+                ("Config", SymbolKind::Struct, None, None),
+                ("new", SymbolKind::Function, Some("Config"), None),
+                ("default_config", SymbolKind::Function, None, Some(r#"{"return_type":"Config"}"#)),
+                ("ConfigError", SymbolKind::Enum, None, None),
+            ]),
+            ("python/contexta/config.py", "python", vec![
+                ("config", SymbolKind::Import, None, None),
+                ("Config", SymbolKind::Class, None, None),
+                ("load", SymbolKind::Function, Some("Config"), None),
+                ("parse_configuration", SymbolKind::Function, None, None),
+            ]),
+            ("web/config.ts", "typescript", vec![
+                ("AppConfig", SymbolKind::Interface, None, Some(r#"{"extends":"BaseConfig"}"#)),
+            ]),
+        ];
+
+        for (path, language, symbols) in files {
+            let file = FileMetadata {
+                id: None,
+                path: path.to_string(),
+                language: language.to_string(),
+                size: 256,
+                last_indexed: None,
+                parse_errors: 0,
+                index_duration_ms: None,
+                metadata: None,
+            };
+            let file_id = upsert_file(conn, &file).unwrap();
+            for (line, (name, kind, scope, metadata)) in symbols.into_iter().enumerate() {
+                let symbol = Symbol {
+                    id: None,
+                    file_id,
+                    name: name.to_string(),
+                    kind,
+                    line_start: line,
+                    line_end: line,
+                    column_start: 0,
+                    column_end: 0,
+                    scope: scope.map(str::to_string),
+                    metadata: metadata.map(str::to_string),
+                };
+                insert_symbol(conn, &symbol).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_search_symbols_across_languages() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+        seed_cross_language(&conn);
+
+        let results = search_symbols_across_languages(&conn, "Config", &["rust", "python"]).unwrap();
+        let found: Vec<(&str, &str)> = results.iter().map(|(s, path)| (s.name.as_str(), path.as_str())).collect();
+        assert_eq!(
+            found,
+            vec![
+                // Exact matches, then case-insensitive, prefix, substring
+                ("Config", "python/contexta/config.py"),
+                ("Config", "src/config.rs"),
+                ("config", "python/contexta/config.py"),
+                ("ConfigError", "src/config.rs"),
+                ("default_config", "src/config.rs"),
+                ("parse_configuration", "python/contexta/config.py"),
+            ]
+        );
+
+        // No language filter searches everything
+        let all = search_symbols_across_languages(&conn, "config", &[]).unwrap();
+        assert_eq!(all.len(), 7);
+        assert_eq!(all[0].0.name, "config");
+        assert!(all.iter().any(|(s, _)| s.name == "AppConfig"));
+
+        assert_eq!(search_symbols_across_languages(&conn, "Config", &["typescript"]).unwrap().len(), 1);
+        assert!(search_symbols_across_languages(&conn, "Config", &["go"]).unwrap().is_empty());
+
+        // LIKE wildcards in the query are literal
+        assert!(search_symbols_across_languages(&conn, "%", &[]).unwrap().is_empty());
+        assert_eq!(search_symbols_across_languages(&conn, "default_", &[]).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_find_cross_language_type_usages() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+        seed_cross_language(&conn);
+
+        let usages = find_cross_language_type_usages(&conn, "Config").unwrap();
+        let found: Vec<(&str, &str)> = usages.iter().map(|(s, path)| (s.name.as_str(), path.as_str())).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("Config", "python/contexta/config.py"),
+                ("load", "python/contexta/config.py"),
+                ("Config", "src/config.rs"),
+                ("new", "src/config.rs"),
+                ("default_config", "src/config.rs"),
+            ]
+        );
+
+        // Whole-word metadata matches only
+        let usages = find_cross_language_type_usages(&conn, "BaseConfig").unwrap();
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].0.name, "AppConfig");
+        assert!(find_cross_language_type_usages(&conn, "Base").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_find_symbols_grouped_by_file() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+        seed_cross_language(&conn);
+
+        let grouped = find_symbols_grouped_by_file(&conn, "Config").unwrap();
+        let mut paths: Vec<&str> = grouped.keys().map(String::as_str).collect();
+        paths.sort();
+        assert_eq!(paths, vec!["python/contexta/config.py", "src/config.rs"]);
+        assert_eq!(grouped["src/config.rs"].len(), 1);
+        assert_eq!(grouped["src/config.rs"][0].kind, SymbolKind::Struct);
+        assert_eq!(grouped["python/contexta/config.py"][0].kind, SymbolKind::Class);
+
+        // Exact, case-sensitive names only
+        let lowercase = find_symbols_grouped_by_file(&conn, "config").unwrap();
+        assert_eq!(lowercase.len(), 1);
+        assert_eq!(lowercase["python/contexta/config.py"][0].kind, SymbolKind::Import);
+        assert!(find_symbols_grouped_by_file(&conn, "Missing").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_find_slowest_files() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        for (path, duration) in [("fast.py", Some(3)), ("slow.rs", Some(250)), ("medium.ts", Some(40)), ("legacy.py", None)] {
+            let file = FileMetadata {
+                id: None,
+                path: path.to_string(),
+                language: "python".to_string(),
+                size: 100,
+                last_indexed: None,
+                parse_errors: 0,
+                index_duration_ms: duration,
+                metadata: None,
+            };
+            upsert_file(&conn, &file).unwrap();
+        }
+
+        let slowest = find_slowest_files(&conn, 2).unwrap();
+        let found: Vec<(&str, u64)> = slowest.iter().map(|(file, ms)| (file.path.as_str(), *ms)).collect();
+        assert_eq!(found, vec![("slow.rs", 250), ("medium.ts", 40)]);
+        assert_eq!(slowest[0].0.index_duration_ms, Some(250));
+
+        // Files without a recorded duration are skipped
+        assert_eq!(find_slowest_files(&conn, 10).unwrap().len(), 3);
+        assert!(list_files(&conn).unwrap().iter().any(|f| f.path == "legacy.py" && f.index_duration_ms.is_none()));
+    }
+
+    #[test]
+    fn test_find_files_modified_since() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        for path in ["b.py", "a.py", "old.py", "legacy.py"] {
+            let file = FileMetadata {
+                id: None,
+                path: path.to_string(),
+                language: "python".to_string(),
+                size: 100,
+                last_indexed: None,
+                parse_errors: 0,
+                index_duration_ms: None,
+                metadata: None,
+            };
+            upsert_file(&conn, &file).unwrap();
+        }
+        conn.execute("UPDATE files SET last_indexed = '2020-01-01T00:00:00Z' WHERE path = 'old.py'", [])
+            .unwrap();
+        // Written by SQLite's CURRENT_TIMESTAMP before timestamps were RFC 3339
+        conn.execute("UPDATE files SET last_indexed = '2024-06-01 12:00:00' WHERE path = 'legacy.py'", [])
+            .unwrap();
+
+        let paths = |since: &str| -> Vec<String> {
+            find_files_modified_since(&conn, since.parse().unwrap())
+                .unwrap()
+                .into_iter()
+                .map(|f| f.path)
+                .collect()
+        };
+        assert_eq!(paths("2024-06-01T12:00:00Z"), vec!["a.py", "b.py", "legacy.py"]);
+        assert_eq!(paths("2024-06-01T12:00:01Z"), vec!["a.py", "b.py"]);
+        assert_eq!(paths("2000-01-01T00:00:00Z").len(), 4);
+
+        let recent = find_files_modified_since(&conn, "2024-06-01T00:00:00Z".parse().unwrap()).unwrap();
+        let legacy = recent.iter().find(|f| f.path == "legacy.py").unwrap();
+        assert_eq!(legacy.last_indexed, Some("2024-06-01T12:00:00Z".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_find_parse_errors() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let mut file_ids = Vec::new();
+        for path in ["clean.py", "b.py", "a.py"] {
+            let file = FileMetadata {
+                id: None,
+                path: path.to_string(),
+                language: "python".to_string(),
+                size: 100,
+                last_indexed: None,
+                parse_errors: 0,
+                index_duration_ms: None,
+                metadata: None,
+            };
+            file_ids.push(upsert_file(&conn, &file).unwrap());
+        }
+        storage::replace_parse_errors(&conn, file_ids[1], &[ParseError::new(2, 0, "missing `:`")]).unwrap();
+        storage::replace_parse_errors(
+            &conn,
+            file_ids[2],
+            &[ParseError::new(7, 1, "unexpected `)`"), ParseError::new(3, 4, "missing `)`")],
+        )
+        .unwrap();
+
+        let files: Vec<String> = find_files_with_errors(&conn).unwrap().into_iter().map(|f| f.path).collect();
+        assert_eq!(files, vec!["a.py", "b.py"]);
+
+        let errors = find_parse_errors_by_file(&conn, file_ids[2]).unwrap();
+        assert_eq!(
+            errors,
+            vec![
+                ParseError::new(3, 4, "missing `)`").with_file_path("a.py"),
+                ParseError::new(7, 1, "unexpected `)`").with_file_path("a.py"),
+            ]
+        );
+        assert!(find_parse_errors_by_file(&conn, file_ids[0]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_validate_all_symbols() {
+        let temp_file = NamedTempFile::new().unwrap();
         let conn = init_schema(temp_file.path()).unwrap();
-
-        let file1 = FileMetadata {
+        let file = FileMetadata {
             id: None,
-            path: "test1.py".to_string(),
+            path: "broken.py".to_string(),
             language: "python".to_string(),
-            size: 1024,
+            size: 10,
             last_indexed: None,
             parse_errors: 0,
+            index_duration_ms: None,
+            metadata: None,
         };
-        upsert_file(&conn, &file1).unwrap();
+        let file_id = upsert_file(&conn, &file).unwrap();
 
-        let file2 = FileMetadata {
+        let symbol = |name: &str, line_start, line_end, metadata: Option<&str>| Symbol {
             id: None,
-            path: "test2.py".to_string(),
-            language: "python".to_string(),
-            size: 2048,
-            last_indexed: None,
-            parse_errors: 0,
+            file_id,
+            name: name.to_string(),
+            kind: SymbolKind::Function,
+            line_start,
+            line_end,
+            column_start: 0,
+            column_end: 0,
+            scope: None,
+            metadata: metadata.map(str::to_string),
         };
-        upsert_file(&conn, &file2).unwrap();
+        let ok = insert_symbol(&conn, &symbol("ok", 0, 1, Some("{}"))).unwrap();
+        let empty = insert_symbol(&conn, &symbol("", 2, 2, None)).unwrap();
+        let inverted = insert_symbol(&conn, &symbol("inverted", 9, 4, None)).unwrap();
+        let bad_json = insert_symbol(&conn, &symbol("bad_json", 10, 11, Some("{oops"))).unwrap();
+
+        let invalid = validate_all_symbols(&conn).unwrap();
+        let ids: Vec<i64> = invalid.iter().map(|(id, _)| *id).collect();
+        assert_eq!(ids, vec![empty, inverted, bad_json]);
+        assert!(!ids.contains(&ok));
+        assert_eq!(invalid[1].1, "line_end 4 is before line_start 9");
+    }
 
-        let stats = get_language_stats(&conn).unwrap();
-        let stats_array = stats.as_array().unwrap();
-        assert_eq!(stats_array.len(), 1);
-        assert_eq!(stats_array[0]["language"], "python");
-        assert_eq!(stats_array[0]["file_count"], 2);
+    #[test]
+    fn test_find_package_entry_points() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        for path in ["pkg/__main__.py", "pkg/util.py", "pkg/__init__.py", "pkg/sub/__init__.py"] {
+            let file = FileMetadata {
+                id: None,
+                path: path.to_string(),
+                language: "python".to_string(),
+                size: 10,
+                last_indexed: None,
+                parse_errors: 0,
+                index_duration_ms: None,
+                metadata: crate::package_entry_point_metadata(path),
+            };
+            upsert_file(&conn, &file).unwrap();
+        }
+
+        let entry_points = find_package_entry_points(&conn).unwrap();
+        let paths: Vec<&str> = entry_points.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["pkg/__init__.py", "pkg/__main__.py", "pkg/sub/__init__.py"]);
+        assert_eq!(entry_points[1].metadata.as_deref(), Some(r#"{"package_main":true}"#));
     }
 
     #[test]
-    fn test_find_symbols_by_file_path() {
+    fn test_find_symbols_modified_between() {
         let temp_file = NamedTempFile::new().unwrap();
         let conn = init_schema(temp_file.path()).unwrap();
 
         let file = FileMetadata {
             id: None,
-            path: "test.py".to_string(),
+            path: "large.py".to_string(),
             language: "python".to_string(),
-            size: 1024,
+            size: 2048,
             last_indexed: None,
             parse_errors: 0,
+            index_duration_ms: None,
+            metadata: None,
         };
         let file_id = upsert_file(&conn, &file).unwrap();
 
-        let symbol1 = Symbol {
+        let symbol = |name: &str, line_start, line_end| Symbol {
             id: None,
             file_id,
-            name: "my_function".to_string(),
+            name: name.to_string(),
             kind: SymbolKind::Function,
-            line_start: 10,
-            line_end: 20,
+            line_start,
+            line_end,
+            column_start: 0,
+            column_end: 0,
             scope: None,
             metadata: None,
         };
-        insert_symbol(&conn, &symbol1).unwrap();
-
-        let symbol2 = Symbol {
-            id: None,
-            file_id,
-            name: "MyClass".to_string(),
-            kind: SymbolKind::Class,
-            line_start: 25,
-            line_end: 40,
-            scope: None,
-            metadata: None,
+        // Changed region below is 10..=20
+        insert_symbol(&conn, &symbol("before", 0, 5)).unwrap();
+        insert_symbol(&conn, &symbol("touches_start", 6, 10)).unwrap();
+        insert_symbol(&conn, &symbol("overlaps_start", 8, 12)).unwrap();
+        insert_symbol(&conn, &symbol("contained", 14, 16)).unwrap();
+        insert_symbol(&conn, &symbol("overlaps_end", 18, 25)).unwrap();
+        insert_symbol(&conn, &symbol("touches_end", 20, 30)).unwrap();
+        insert_symbol(&conn, &symbol("after", 31, 40)).unwrap();
+        insert_symbol(&conn, &symbol("enclosing", 0, 50)).unwrap();
+
+        let names = |start, end| {
+            find_symbols_modified_between(&conn, file_id, start, end)
+                .unwrap()
+                .into_iter()
+                .map(|s| s.name)
+                .collect::<Vec<_>>()
         };
-        insert_symbol(&conn, &symbol2).unwrap();
 
-        let found = find_symbols_by_file_path(&conn, "test.py").unwrap();
-        assert_eq!(found.len(), 2);
-        assert_eq!(found[0].name, "my_function");
-        assert_eq!(found[1].name, "MyClass");
+        assert_eq!(
+            names(10, 20),
+            vec!["enclosing", "touches_start", "overlaps_start", "contained", "overlaps_end", "touches_end"]
+        );
+        // Single-line change
+        assert_eq!(names(15, 15), vec!["enclosing", "contained"]);
+        // Change in a gap between symbols
+        assert_eq!(names(60, 70), Vec::<String>::new());
+        assert!(find_symbols_modified_between(&conn, file_id + 1, 0, 100).unwrap().is_empty());
     }
 
     #[test]
-    fn test_find_imports_by_file() {
+    fn test_find_symbols_by_line_range_and_at_line() {
         let temp_file = NamedTempFile::new().unwrap();
         let conn = init_schema(temp_file.path()).unwrap();
 
         let file = FileMetadata {
             id: None,
-            path: "test.py".to_string(),
+            path: "shapes.py".to_string(),
             language: "python".to_string(),
-            size: 1024,
+            size: 512,
             last_indexed: None,
             parse_errors: 0,
+            index_duration_ms: None,
+            metadata: None,
         };
         let file_id = upsert_file(&conn, &file).unwrap();
 
-        let import1 = Symbol {
+        let symbol = |name: &str, kind, line_start, line_end| Symbol {
             id: None,
             file_id,
-            name: "os".to_string(),
-            kind: SymbolKind::Import,
-            line_start: 1,
-            line_end: 1,
+            name: name.to_string(),
+            kind,
+            line_start,
+            line_end,
+            column_start: 0,
+            column_end: 0,
             scope: None,
             metadata: None,
         };
-        insert_symbol(&conn, &import1).unwrap();
+        insert_symbol(&conn, &symbol("os", SymbolKind::Import, 0, 0)).unwrap();
+        insert_symbol(&conn, &symbol("Shape", SymbolKind::Class, 2, 12)).unwrap();
+        insert_symbol(&conn, &symbol("area", SymbolKind::Function, 3, 6)).unwrap();
+        insert_symbol(&conn, &symbol("scale", SymbolKind::Function, 8, 12)).unwrap();
+        insert_symbol(&conn, &symbol("helper", SymbolKind::Function, 15, 18)).unwrap();
+
+        let names = |start, end| {
+            find_symbols_by_line_range(&conn, file_id, start, end)
+                .unwrap()
+                .into_iter()
+                .map(|s| s.name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(5, 9), vec!["Shape", "area", "scale"]);
+        assert_eq!(names(12, 15), vec!["Shape", "scale", "helper"]);
+        assert_eq!(names(13, 14), Vec::<String>::new());
+
+        let at = |line| find_symbol_at_line(&conn, file_id, line).unwrap().map(|s| s.name);
+        assert_eq!(at(0).as_deref(), Some("os"));
+        assert_eq!(at(4).as_deref(), Some("area"));
+        assert_eq!(at(7).as_deref(), Some("Shape"));
+        // `scale` ends on the same line as `Shape` but spans fewer lines
+        assert_eq!(at(12).as_deref(), Some("scale"));
+        assert_eq!(at(14), None);
+        assert_eq!(find_symbol_at_line(&conn, file_id + 1, 4).unwrap(), None);
+
+        for report in explain_all_queries(&conn).unwrap() {
+            if matches!(report.function.as_str(), "find_symbols_by_line_range" | "find_symbol_at_line") {
+                assert_eq!(report.index_name.as_deref(), Some("idx_symbols_file_line"), "{}", report.plan_text);
+            }
+        }
+    }
 
-        let import2 = Symbol {
+    #[test]
+    fn test_find_symbols_in_scope() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let file = FileMetadata {
             id: None,
-            file_id,
-            name: "sys".to_string(),
-            kind: SymbolKind::Import,
-            line_start: 2,
-            line_end: 2,
-            scope: None,
+            path: "models.py".to_string(),
+            language: "python".to_string(),
+            size: 512,
+            last_indexed: None,
+            parse_errors: 0,
+            index_duration_ms: None,
             metadata: None,
         };
-        insert_symbol(&conn, &import2).unwrap();
+        let file_id = upsert_file(&conn, &file).unwrap();
 
-        let function = Symbol {
+        let symbol = |name: &str, kind, line_start, line_end, scope: Option<&str>| Symbol {
             id: None,
             file_id,
-            name: "my_function".to_string(),
-            kind: SymbolKind::Function,
-            line_start: 10,
-            line_end: 20,
-            scope: None,
+            name: name.to_string(),
+            kind,
+            line_start,
+            line_end,
+            column_start: 0,
+            column_end: 0,
+            scope: scope.map(str::to_string),
             metadata: None,
         };
-        insert_symbol(&conn, &function).unwrap();
+        insert_symbol(&conn, &symbol("Foo", SymbolKind::Class, 0, 10, None)).unwrap();
+        insert_symbol(&conn, &symbol("load", SymbolKind::Function, 1, 6, Some("Foo"))).unwrap();
+        insert_symbol(&conn, &symbol("parse", SymbolKind::Function, 2, 4, Some("load"))).unwrap();
+        insert_symbol(&conn, &symbol("token", SymbolKind::Variable, 3, 3, Some("parse"))).unwrap();
+        insert_symbol(&conn, &symbol("save", SymbolKind::Function, 7, 9, Some("Foo"))).unwrap();
+        // Another class with a same-named method and a nested function
+        insert_symbol(&conn, &symbol("Bar", SymbolKind::Class, 12, 20, None)).unwrap();
+        insert_symbol(&conn, &symbol("load", SymbolKind::Function, 13, 18, Some("Bar"))).unwrap();
+        insert_symbol(&conn, &symbol("decode", SymbolKind::Function, 14, 16, Some("load"))).unwrap();
+
+        let names = |scope| {
+            find_symbols_in_scope(&conn, file_id, scope)
+                .unwrap()
+                .into_iter()
+                .map(|s| s.name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names("Foo"), vec!["load", "parse", "token", "save"]);
+        assert_eq!(names("Bar"), vec!["load", "decode"]);
+        assert_eq!(names("parse"), vec!["token"]);
+        assert!(names("Missing").is_empty());
+        assert!(find_symbols_in_scope(&conn, file_id + 1, "Foo").unwrap().is_empty());
+    }
 
-        let imports = find_imports_by_file(&conn, "test.py").unwrap();
-        assert_eq!(imports.len(), 2);
-        assert_eq!(imports[0].name, "os");
-        assert_eq!(imports[1].name, "sys");
+    #[test]
+    fn test_query_and_prune_change_log() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        for (kind, path, timestamp) in [
+            ("create", "a.py", "2024-01-01 09:00:00"),
+            ("modify", "a.py", "2024-03-01 09:00:00"),
+            ("delete", "b.py", "2024-06-01 09:00:00"),
+        ] {
+            let id = insert_change_log(&conn, kind, path, 2, 1).unwrap();
+            conn.execute("UPDATE change_log SET timestamp = ?1 WHERE id = ?2", params![timestamp, id]).unwrap();
+        }
+
+        let entries = query_change_log(&conn, "2024-02-15T00:00:00Z").unwrap();
+        let kinds: Vec<_> = entries.iter().map(|e| e.event_kind.as_str()).collect();
+        assert_eq!(kinds, vec!["modify", "delete"]);
+        assert_eq!(entries[0].file_path, "a.py");
+        assert_eq!(entries[0].timestamp, "2024-03-01 09:00:00");
+        assert_eq!((entries[0].symbols_added, entries[0].symbols_removed), (2, 1));
+
+        // The bound is inclusive for queries and exclusive for pruning
+        assert_eq!(query_change_log(&conn, "2024-06-01T09:00:00").unwrap().len(), 1);
+        assert_eq!(prune_change_log(&conn, "2024-03-01T09:00:00").unwrap(), 1);
+        assert_eq!(query_change_log(&conn, "1970-01-01").unwrap().len(), 2);
+
+        let err = query_change_log(&conn, "not a date").unwrap_err();
+        assert!(matches!(err.downcast_ref::<QueryError>(), Some(QueryError::InvalidTimestamp(_))));
+        assert!(prune_change_log(&conn, "yesterday").is_err());
     }
 
     #[test]
-    fn test_get_file_path_by_id() {
+    fn test_find_impl_trait_functions() {
         let temp_file = NamedTempFile::new().unwrap();
         let conn = init_schema(temp_file.path()).unwrap();
 
         let file = FileMetadata {
             id: None,
-            path: "test.py".to_string(),
-            language: "python".to_string(),
-            size: 1024,
+            path: "lib.rs".to_string(),
+            language: "rust".to_string(),
+            size: 128,
             last_indexed: None,
             parse_errors: 0,
+            index_duration_ms: None,
+            metadata: None,
         };
         let file_id = upsert_file(&conn, &file).unwrap();
 
-        let path = get_file_path_by_id(&conn, file_id).unwrap();
-        assert_eq!(path, "test.py");
+        let symbols = vec![
+            Symbol {
+                id: None,
+                file_id,
+                name: "iter".to_string(),
+                kind: SymbolKind::Function,
+                line_start: 1,
+                line_end: 1,
+                column_start: 0,
+                column_end: 0,
+                scope: None,
+                metadata: Some(r#"{"return_impl_trait":["Iterator<Item = i32>"]}"#.to_string()),
+            },
+            Symbol {
+                id: None,
+                file_id,
+                name: "fetch".to_string(),
+                kind: SymbolKind::Function,
+                line_start: 2,
+                line_end: 2,
+                column_start: 0,
+                column_end: 0,
+                scope: None,
+                metadata: Some(r#"{"async":true,"return_impl_trait":["Future<Output = Result<()>>"]}"#.to_string()),
+            },
+            Symbol {
+                id: None,
+                file_id,
+                name: "plain".to_string(),
+                kind: SymbolKind::Function,
+                line_start: 3,
+                line_end: 3,
+                column_start: 0,
+                column_end: 0,
+                scope: None,
+                metadata: None,
+            },
+        ];
+        for symbol in &symbols {
+            insert_symbol(&conn, symbol).unwrap();
+        }
+
+        let names: Vec<String> = find_impl_trait_functions(&conn).unwrap().into_iter().map(|s| s.name).collect();
+        assert_eq!(names, vec!["iter", "fetch"]);
     }
 
     #[test]
-    fn test_find_symbols_by_name_and_kind() {
+    fn test_find_test_symbols() {
         let temp_file = NamedTempFile::new().unwrap();
         let conn = init_schema(temp_file.path()).unwrap();
 
         let file = FileMetadata {
             id: None,
-            path: "test.py".to_string(),
-            language: "python".to_string(),
-            size: 1024,
+            path: "lib.rs".to_string(),
+            language: "rust".to_string(),
+            size: 128,
             last_indexed: None,
             parse_errors: 0,
+            index_duration_ms: None,
+            metadata: None,
         };
         let file_id = upsert_file(&conn, &file).unwrap();
 
-        // Insert function with same name
-        let symbol1 = Symbol {
+        for (line, name, metadata) in [
+            (1, "simple_test", Some(r#"{"test":true}"#)),
+            (2, "slow_test", Some(r#"{"test":true,"ignore":true}"#)),
+            (3, "bench_parse", Some(r#"{"bench":true}"#)),
+            (4, "production", None),
+        ] {
+            let symbol = Symbol {
+                id: None,
+                file_id,
+                name: name.to_string(),
+                kind: SymbolKind::Function,
+                line_start: line,
+                line_end: line,
+                column_start: 0,
+                column_end: 0,
+                scope: None,
+                metadata: metadata.map(str::to_string),
+            };
+            insert_symbol(&conn, &symbol).unwrap();
+        }
+
+        let names: Vec<String> = find_test_symbols(&conn, None).unwrap().into_iter().map(|s| s.name).collect();
+        assert_eq!(names, vec!["simple_test", "slow_test"]);
+
+        assert_eq!(find_test_symbols(&conn, Some(file_id)).unwrap().len(), 2);
+        assert!(find_test_symbols(&conn, Some(file_id + 1)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_find_ffi_symbols() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let file = FileMetadata {
             id: None,
-            file_id,
-            name: "process".to_string(),
-            kind: SymbolKind::Function,
-            line_start: 10,
-            line_end: 20,
-            scope: None,
+            path: "ffi.rs".to_string(),
+            language: "rust".to_string(),
+            size: 128,
+            last_indexed: None,
+            parse_errors: 0,
+            index_duration_ms: None,
             metadata: None,
         };
-        insert_symbol(&conn, &symbol1).unwrap();
+        let file_id = upsert_file(&conn, &file).unwrap();
 
-        // Insert variable with same name
-        let symbol2 = Symbol {
+        for (line, name, kind, metadata) in [
+            (0, "libc", SymbolKind::Import, None),
+            (2, "malloc", SymbolKind::Function, Some(r#"{"ffi":true,"abi":"C"}"#)),
+            (3, "errno", SymbolKind::Variable, Some(r#"{"ffi":true,"abi":"C"}"#)),
+            (6, "wrapper", SymbolKind::Function, Some(r#"{"async":true}"#)),
+        ] {
+            let symbol = Symbol {
+                id: None,
+                file_id,
+                name: name.to_string(),
+                kind,
+                line_start: line,
+                line_end: line,
+                column_start: 0,
+                column_end: 0,
+                scope: None,
+                metadata: metadata.map(str::to_string),
+            };
+            insert_symbol(&conn, &symbol).unwrap();
+        }
+
+        let names: Vec<String> = find_ffi_symbols(&conn).unwrap().into_iter().map(|s| s.name).collect();
+        assert_eq!(names, vec!["malloc", "errno"]);
+    }
+
+    #[test]
+    fn test_find_public_symbols_by_file() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let file = FileMetadata {
             id: None,
-            file_id,
-            name: "process".to_string(),
-            kind: SymbolKind::Variable,
-            line_start: 5,
-            line_end: 5,
-            scope: None,
+            path: "lib.rs".to_string(),
+            language: "rust".to_string(),
+            size: 128,
+            last_indexed: None,
+            parse_errors: 0,
+            index_duration_ms: None,
             metadata: None,
         };
-        insert_symbol(&conn, &symbol2).unwrap();
+        let file_id = upsert_file(&conn, &file).unwrap();
 
-        // Query for function only
-        let functions = find_symbols_by_name_and_kind(&conn, "process", SymbolKind::Function).unwrap();
-        assert_eq!(functions.len(), 1);
-        assert_eq!(functions[0].kind, SymbolKind::Function);
+        for (line, name, metadata) in [
+            (0, "open", Some(r#"{"visibility":"pub","async":true}"#)),
+            (2, "Handle", Some(r#"{"visibility":"pub(crate)"}"#)),
+            (4, "helper", None),
+            (6, "LIMIT", Some(r#"{"visibility":"pub"}"#)),
+        ] {
+            let symbol = Symbol {
+                id: None,
+                file_id,
+                name: name.to_string(),
+                kind: SymbolKind::Function,
+                line_start: line,
+                line_end: line,
+                column_start: 0,
+                column_end: 0,
+                scope: None,
+                metadata: metadata.map(str::to_string),
+            };
+            insert_symbol(&conn, &symbol).unwrap();
+        }
+
+        let names: Vec<String> = find_public_symbols_by_file(&conn, file_id)
+            .unwrap()
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(names, vec!["open", "LIMIT"]);
+        assert!(find_public_symbols_by_file(&conn, file_id + 1).unwrap().is_empty());
+    }
 
-        // Query for variable only
-        let variables = find_symbols_by_name_and_kind(&conn, "process", SymbolKind::Variable).unwrap();
-        assert_eq!(variables.len(), 1);
-        assert_eq!(variables[0].kind, SymbolKind::Variable);
+    #[test]
+    fn test_find_symbols_with_base() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let file = FileMetadata {
+            id: None,
+            path: "shapes.ts".to_string(),
+            language: "typescript".to_string(),
+            size: 128,
+            last_indexed: None,
+            parse_errors: 0,
+            index_duration_ms: None,
+            metadata: None,
+        };
+        let file_id = upsert_file(&conn, &file).unwrap();
+
+        for (line, name, kind, metadata) in [
+            (1, "Shape", SymbolKind::Interface, None),
+            (2, "Circle", SymbolKind::Class, Some(r#"{"extends":["Base"],"implements":["Shape"]}"#)),
+            (3, "Square", SymbolKind::Class, Some(r#"{"implements":["Shape","Serializable"]}"#)),
+            (4, "Polygon", SymbolKind::Class, Some(r#"{"extends":["Shape"]}"#)),
+            (5, "ShapeFactory", SymbolKind::Class, Some(r#"{"extends":["Shapes"]}"#)),
+            (6, "draw", SymbolKind::Function, Some(r#"{"params":[]}"#)),
+        ] {
+            let symbol = Symbol {
+                id: None,
+                file_id,
+                name: name.to_string(),
+                kind,
+                line_start: line,
+                line_end: line,
+                column_start: 0,
+                column_end: 0,
+                scope: None,
+                metadata: metadata.map(str::to_string),
+            };
+            insert_symbol(&conn, &symbol).unwrap();
+        }
+
+        let names = |base: &str| -> Vec<String> {
+            find_symbols_with_base(&conn, base).unwrap().into_iter().map(|s| s.name).collect()
+        };
+        assert_eq!(names("Shape"), vec!["Circle", "Square", "Polygon"]);
+        assert_eq!(names("Base"), vec!["Circle"]);
+        assert!(names("Missing").is_empty());
     }
 
     #[test]
-    fn test_optimize_database() {
+    fn test_explain_query_uses_intended_indexes() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let reports = explain_all_queries(&conn).unwrap();
+        let report = |name: &str| reports.iter().find(|r| r.function == name).unwrap();
+
+        let by_name = report("find_symbols_by_name");
+        assert!(by_name.uses_index);
+        assert!(by_name.index_name.as_deref().unwrap().starts_with("idx_symbols_name"));
+
+        assert_eq!(
+            report("find_symbols_by_name_and_kind").index_name.as_deref(),
+            Some("idx_symbols_name_kind")
+        );
+        let by_file = report("find_symbols_by_file_and_kind");
+        assert!(by_file.index_name.as_deref().unwrap().starts_with("idx_symbols_file_"));
+        assert!(report("get_file_path_by_id").uses_index);
+        assert!(!report("list_files").uses_index);
+
+        let plan = explain_query(&conn, "find_symbols_by_kind").unwrap();
+        assert!(plan.contains("idx_symbols_kind"));
+        assert!(explain_query(&conn, "no_such_query").is_err());
+    }
+
+    fn path_pattern_fixture() -> (NamedTempFile, Connection) {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        for (path, name) in [
+            ("/src/api/users.py", "get_user"),
+            ("/src/api/v2/orders.py", "get_order"),
+            ("/src/apiary.py", "bees"),
+            ("src/api/routes.py", "register"),
+            ("src/db/models.py", "User"),
+            ("src/db_100%/dump.py", "dump"),
+        ] {
+            let file = FileMetadata {
+                id: None,
+                path: path.to_string(),
+                language: "python".to_string(),
+                size: 10,
+                last_indexed: None,
+                parse_errors: 0,
+                index_duration_ms: None,
+                metadata: None,
+            };
+            let file_id = upsert_file(&conn, &file).unwrap();
+            insert_symbol(&conn, &Symbol {
+                id: None,
+                file_id,
+                name: name.to_string(),
+                kind: SymbolKind::Function,
+                line_start: 0,
+                line_end: 1,
+                column_start: 0,
+                column_end: 0,
+                scope: None,
+                metadata: None,
+            }).unwrap();
+        }
+
+        (temp_file, conn)
+    }
+
+    #[test]
+    fn test_find_symbols_by_file_path_pattern() {
+        let (_temp_file, conn) = path_pattern_fixture();
+        let names = |pattern: &str| -> Vec<String> {
+            find_symbols_by_file_path_pattern(&conn, pattern)
+                .unwrap()
+                .into_iter()
+                .map(|s| s.name)
+                .collect()
+        };
+
+        // Absolute prefix covers the subtree but not sibling `/src/apiary.py`
+        assert_eq!(names("/src/api/*"), vec!["get_user", "get_order"]);
+        // Relative prefix doesn't match absolute paths
+        assert_eq!(names("src/api/*"), vec!["register"]);
+        assert_eq!(names("*/api/*.py"), vec!["get_user", "get_order", "register"]);
+        assert_eq!(names("src/d?/*"), vec!["User"]);
+        assert_eq!(names("src/d?_100%/*"), vec!["dump"]);
+        assert!(names("src/db_1000/*").is_empty());
+    }
+
+    #[test]
+    fn test_find_files_by_path_pattern() {
+        let (_temp_file, conn) = path_pattern_fixture();
+        let paths = |pattern: &str| -> Vec<String> {
+            find_files_by_path_pattern(&conn, pattern)
+                .unwrap()
+                .into_iter()
+                .map(|f| f.path)
+                .collect()
+        };
+
+        assert_eq!(paths("/src/*"), vec!["/src/api/users.py", "/src/api/v2/orders.py", "/src/apiary.py"]);
+        assert_eq!(paths("src/db*/*.py"), vec!["src/db/models.py", "src/db_100%/dump.py"]);
+        assert_eq!(paths("src/db_*"), vec!["src/db_100%/dump.py"]);
+        assert!(paths("*.rs").is_empty());
+    }
+
+    #[test]
+    fn test_find_symbols_with_similar_signatures() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+        let file = FileMetadata {
+            id: None,
+            path: "lib.rs".to_string(),
+            language: "rust".to_string(),
+            size: 10,
+            last_indexed: None,
+            parse_errors: 0,
+            index_duration_ms: None,
+            metadata: None,
+        };
+        let file_id = upsert_file(&conn, &file).unwrap();
+
+        let mut ids = HashMap::new();
+        for (line, (name, kind, metadata)) in [
+            ("foo", SymbolKind::Function, Some(r#"{"params":[{"name":"x","type":"i32"},{"name":"y","type":"String"}]}"#)),
+            ("bar", SymbolKind::Function, Some(r#"{"params":[{"name":"b","type":"String"},{"name":"a","type":"i32"}]}"#)),
+            ("baz", SymbolKind::Function, Some(r#"{"params":[{"name":"n","type":"i32"}]}"#)),
+            ("qux", SymbolKind::Function, Some(r#"{"params":[{"name":"f","type":"f64"},{"name":"g","type":"bool"}]}"#)),
+            ("untyped", SymbolKind::Function, Some(r#"{"params":[{"name":"x"}]}"#)),
+            ("map", SymbolKind::Function, Some(r#"{"params":[{"name":"m","type":"Map<A,B>"}]}"#)),
+            ("split_map", SymbolKind::Function, Some(r#"{"params":[{"name":"a","type":"Map<A"},{"name":"b","type":"B>"}]}"#)),
+            ("no_params", SymbolKind::Function, None),
+            ("Foo", SymbolKind::Struct, Some(r#"{"params":[{"name":"x","type":"i32"},{"name":"y","type":"String"}]}"#)),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let symbol = Symbol {
+                id: None,
+                file_id,
+                name: name.to_string(),
+                kind,
+                line_start: line,
+                line_end: line,
+                column_start: 0,
+                column_end: 0,
+                scope: None,
+                metadata: metadata.map(str::to_string),
+            };
+            ids.insert(name, insert_symbol(&conn, &symbol).unwrap());
+        }
+
+        let similar = find_symbols_with_similar_signatures(&conn, ids["foo"], 0.5).unwrap();
+        let scored: Vec<(&str, f32)> = similar.iter().map(|(s, score)| (s.name.as_str(), *score)).collect();
+        assert_eq!(scored, vec![("bar", 1.0), ("baz", 0.5)]);
+
+        // Hashes are stored on insert and equal for identical signatures in any order
+        let hash = |name: &str| -> serde_json::Value {
+            let metadata = get_symbol_by_id(&conn, ids[name]).unwrap().metadata.unwrap();
+            serde_json::from_str::<serde_json::Value>(&metadata).unwrap()["signature_hash"].clone()
+        };
+        assert!(hash("foo").is_string());
+        assert_eq!(hash("foo"), hash("bar"));
+        assert_ne!(hash("bar"), hash("qux"));
+        assert_ne!(hash("map"), hash("split_map"));
+        assert!(hash("Foo").is_null());
+        assert!(find_symbols_with_similar_signatures(&conn, ids["map"], 1.0).unwrap().is_empty());
+
+        // Lookups never write, so they work on read-only connections
+        let read_only = Connection::open_with_flags(temp_file.path(), rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY).unwrap();
+        let similar = find_symbols_with_similar_signatures(&read_only, ids["foo"], 0.5).unwrap();
+        assert_eq!(similar.len(), 2);
+
+        assert_eq!(find_symbols_with_similar_signatures(&conn, ids["foo"], 0.0).unwrap().len(), 6);
+        assert!(find_symbols_with_similar_signatures(&conn, ids["no_params"], 0.0).unwrap().is_empty());
+        assert!(find_symbols_with_similar_signatures(&conn, 9999, 0.0).is_err());
+    }
+
+    fn regex_fixture() -> (NamedTempFile, Connection) {
         let temp_file = NamedTempFile::new().unwrap();
         let conn = init_schema(temp_file.path()).unwrap();
 
@@ -598,38 +3605,226 @@ mod tests {
             size: 1024,
             last_indexed: None,
             parse_errors: 0,
+            index_duration_ms: None,
+            metadata: None,
         };
         let file_id = upsert_file(&conn, &file).unwrap();
 
-        let symbol = Symbol {
-            id: None,
-            file_id,
-            name: "test_func".to_string(),
-            kind: SymbolKind::Function,
-            line_start: 1,
-            line_end: 10,
-            scope: None,
-            metadata: None,
-        };
-        insert_symbol(&conn, &symbol).unwrap();
+        let symbols = [
+            ("UserService", SymbolKind::Class),
+            ("foo", SymbolKind::Function),
+            ("foo_bar", SymbolKind::Function),
+            ("get_foo", SymbolKind::Function),
+        ];
+        for (line, (name, kind)) in symbols.into_iter().enumerate() {
+            let symbol = Symbol {
+                id: None,
+                file_id,
+                name: name.to_string(),
+                kind,
+                line_start: line,
+                line_end: line,
+                column_start: 0,
+                column_end: 0,
+                scope: None,
+                metadata: None,
+            };
+            insert_symbol(&conn, &symbol).unwrap();
+        }
+
+        (temp_file, conn)
+    }
 
-        // Run optimization
-        optimize_database(&conn).unwrap();
+    #[test]
+    fn test_find_symbols_by_name_regex_uppercase() {
+        let (_temp_file, conn) = regex_fixture();
 
-        // Verify database still works after optimization
-        let symbols = find_symbols_by_name(&conn, "test_func").unwrap();
-        assert_eq!(symbols.len(), 1);
+        let results = find_symbols_by_name_regex(&conn, "^[A-Z]", 10).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "UserService");
     }
 
     #[test]
-    fn test_analyze_query_plan() {
+    fn test_find_symbols_by_name_regex_word_boundary() {
+        let (_temp_file, conn) = regex_fixture();
+
+        // `_` is a word character, so only the exact name matches
+        let results = find_symbols_by_name_regex(&conn, r"\bfoo\b", 10).unwrap();
+        let names: Vec<_> = results.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["foo"]);
+
+        let limited = find_symbols_by_name_regex(&conn, "foo", 2).unwrap();
+        assert_eq!(limited.len(), 2);
+    }
+
+    #[test]
+    fn test_find_symbols_by_name_regex_invalid_pattern() {
+        let (_temp_file, conn) = regex_fixture();
+
+        let err = find_symbols_by_name_regex(&conn, "(unclosed", 10).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<QueryError>(),
+            Some(QueryError::InvalidRegex { .. })
+        ));
+    }
+
+    #[test]
+    fn test_search_symbols_fts_prefix_and_phrase() {
+        let (_temp_file, conn) = regex_fixture();
+
+        let names = |query: &str, limit| -> Vec<String> {
+            search_symbols_fts(&conn, query, limit).unwrap().into_iter().map(|s| s.name).collect()
+        };
+        assert_eq!(names("get_*", None), vec!["get_foo"]);
+        assert_eq!(names("\"foo bar\"", None), vec!["foo_bar"]);
+        assert_eq!(names("user*", None), vec!["UserService"]);
+
+        // Every name containing the word `foo`, the shortest (best ranked) first
+        let all = names("foo", None);
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0], "foo");
+        assert_eq!(names("foo", Some(2)).len(), 2);
+    }
+
+    #[test]
+    fn test_search_symbols_fts_invalid_query() {
+        let (_temp_file, conn) = regex_fixture();
+
+        let err = search_symbols_fts(&conn, "\"unclosed", None).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<QueryError>(),
+            Some(QueryError::InvalidFtsQuery { .. })
+        ));
+    }
+
+    fn builder_fixture() -> (NamedTempFile, Connection) {
         let temp_file = NamedTempFile::new().unwrap();
         let conn = init_schema(temp_file.path()).unwrap();
 
-        let query = "SELECT * FROM symbols WHERE name = 'test'";
-        let plan = analyze_query_plan(&conn, query).unwrap();
+        for path in ["app.py", "other.py"] {
+            let file = FileMetadata {
+                id: None,
+                path: path.to_string(),
+                language: "python".to_string(),
+                size: 1024,
+                last_indexed: None,
+                parse_errors: 0,
+                index_duration_ms: None,
+                metadata: None,
+            };
+            upsert_file(&conn, &file).unwrap();
+            let file_id = crate::storage::get_file_by_path(&conn, path).unwrap().unwrap().id.unwrap();
+
+            for (line, (name, kind, scope)) in [
+                ("App", SymbolKind::Class, None),
+                ("run", SymbolKind::Function, Some("App")),
+                ("stop", SymbolKind::Function, Some("App")),
+                ("run", SymbolKind::Function, None),
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                let symbol = Symbol {
+                    id: None,
+                    file_id,
+                    name: name.to_string(),
+                    kind,
+                    line_start: line * 10,
+                    line_end: line * 10 + 5,
+                    column_start: 0,
+                    column_end: 0,
+                    scope: scope.map(|s| s.to_string()),
+                    metadata: None,
+                };
+                insert_symbol(&conn, &symbol).unwrap();
+            }
+        }
+
+        (temp_file, conn)
+    }
 
-        // Query plan should mention the index
-        assert!(plan.contains("idx_symbols_name") || plan.contains("SEARCH"));
+    #[test]
+    fn test_query_builder_combined_filters() {
+        let (_temp_file, conn) = builder_fixture();
+
+        let builder = QueryBuilder::new()
+            .filter_name("run")
+            .filter_kind(SymbolKind::Function)
+            .filter_file("app.py")
+            .filter_scope("App")
+            .filter_line_range(0, 20);
+
+        let results = builder.execute(&conn).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "run");
+        assert_eq!(results[0].line_start, 10);
+
+        // Both the symbol lookup and the file path subquery are index searches
+        let plan = builder.explain(&conn).unwrap();
+        assert!(plan.contains("SEARCH symbols USING INDEX"), "{}", plan);
+        assert!(plan.contains("SEARCH files USING"), "{}", plan);
+        assert!(!plan.contains("SCAN"), "{}", plan);
+    }
+
+    #[test]
+    fn test_query_builder_order_limit_offset() {
+        let (_temp_file, conn) = builder_fixture();
+
+        let names: Vec<String> = QueryBuilder::new()
+            .filter_file("other.py")
+            .order_by(SymbolField::LineStart, true)
+            .limit(2)
+            .offset(1)
+            .execute(&conn)
+            .unwrap()
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(names, vec!["stop", "run"]);
+
+        let all = QueryBuilder::new().offset(6).execute(&conn).unwrap();
+        assert_eq!(all.len(), 2);
+    }
+
+    #[test]
+    fn test_paged_queries() {
+        let (_temp_file, conn) = builder_fixture();
+        let file_id = |path| crate::storage::get_file_by_path(&conn, path).unwrap().unwrap().id.unwrap();
+        let (app, other) = (file_id("app.py"), file_id("other.py"));
+
+        let locations = |symbols: Vec<Symbol>| symbols.into_iter().map(|s| (s.file_id, s.line_start)).collect::<Vec<_>>();
+        assert_eq!(
+            locations(find_symbols_by_name_paged(&conn, "run", 10, 0).unwrap()),
+            vec![(app, 10), (app, 30), (other, 10), (other, 30)]
+        );
+        assert_eq!(
+            locations(find_symbols_by_name_paged(&conn, "run", 2, 1).unwrap()),
+            vec![(app, 30), (other, 10)]
+        );
+        assert!(find_symbols_by_name_paged(&conn, "run", 2, 4).unwrap().is_empty());
+
+        let names = |symbols: Vec<Symbol>| symbols.into_iter().map(|s| s.name).collect::<Vec<_>>();
+        assert_eq!(names(find_symbols_by_file_path_paged(&conn, "other.py", 3, 2).unwrap()), vec!["stop", "run"]);
+        assert_eq!(names(find_symbols_by_file_path_paged(&conn, "other.py", 1, 0).unwrap()), vec!["App"]);
+        assert!(find_symbols_by_file_path_paged(&conn, "missing.py", 10, 0).is_err());
+
+        let by_id = QueryBuilder::new().filter_file_id(other).execute(&conn).unwrap();
+        assert_eq!(by_id.len(), 4);
+        assert!(by_id.iter().all(|s| s.file_id == other));
+    }
+
+    #[test]
+    fn test_query_builder_binds_values() {
+        let (_temp_file, conn) = builder_fixture();
+
+        // Values are parameters, never spliced into the SQL
+        let injected = "x' OR '1'='1";
+        let (sql, values) = QueryBuilder::new().filter_name(injected).to_sql();
+        assert!(!sql.contains(injected));
+        assert_eq!(values.len(), 1);
+        assert!(QueryBuilder::new().filter_name(injected).execute(&conn).unwrap().is_empty());
+
+        assert_eq!("line_start".parse::<SymbolField>().unwrap(), SymbolField::LineStart);
+        assert!("name; DROP TABLE symbols".parse::<SymbolField>().is_err());
     }
 }
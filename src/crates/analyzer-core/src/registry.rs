@@ -0,0 +1,145 @@
+// Registry module - Maps languages to their symbol analyzers
+// Language crates depend on analyzer-core, so they are plugged in at runtime
+
+use std::collections::HashMap;
+
+use tree_sitter::Tree;
+
+use crate::{IndexingError, ParseError, Symbol};
+
+/// Symbols and syntax errors produced by one analyzer run
+///
+/// Syntax errors do not fail analysis: symbols are extracted from the
+/// recovered tree and the errors are returned alongside them.
+pub type AnalyzerOutput = (Vec<Symbol>, Vec<ParseError>);
+
+/// Signature shared by all language analyzers (e.g. `analyze_python`)
+pub type AnalyzerFn = fn(&str) -> Result<AnalyzerOutput, IndexingError>;
+
+/// `AnalyzerOutput` plus the new parse tree, kept for the next incremental parse
+pub type IncrementalAnalyzerOutput = (Vec<Symbol>, Vec<ParseError>, Tree);
+
+/// Signature of incremental analyzers (e.g. `analyze_rust_incremental`)
+///
+/// The old tree must already be edited (`Tree::edit`) to match the new
+/// source; `None` parses from scratch.
+pub type IncrementalAnalyzerFn = fn(&str, Option<&Tree>) -> Result<IncrementalAnalyzerOutput, IndexingError>;
+
+/// Languages recorded in the index without symbol extraction (templates and config files)
+pub const FILE_ONLY_LANGUAGES: &[&str] = &["vue", "svelte", "toml", "json", "yaml"];
+
+/// Stub analyzer for `FILE_ONLY_LANGUAGES`: accepts any source and extracts nothing
+///
+/// Registering it means the file is still stored with its language tag, so it
+/// shows up in language statistics instead of being skipped.
+pub fn analyze_file_only(_source: &str) -> Result<AnalyzerOutput, IndexingError> {
+    Ok((Vec::new(), Vec::new()))
+}
+
+/// Language name -> analyzer lookup table
+#[derive(Debug, Clone, Default)]
+pub struct AnalyzerRegistry {
+    analyzers: HashMap<String, AnalyzerFn>,
+}
+
+impl AnalyzerRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an analyzer for a language (as returned by `detect_language`)
+    pub fn register(&mut self, language: &str, analyzer: AnalyzerFn) -> &mut Self {
+        self.analyzers.insert(language.to_string(), analyzer);
+        self
+    }
+
+    /// Register `analyze_file_only` for every language in `FILE_ONLY_LANGUAGES`
+    pub fn register_file_only_languages(&mut self) -> &mut Self {
+        for language in FILE_ONLY_LANGUAGES {
+            self.register(language, analyze_file_only);
+        }
+        self
+    }
+
+    /// Get the analyzer registered for a language
+    pub fn get(&self, language: &str) -> Option<AnalyzerFn> {
+        self.analyzers.get(language).copied()
+    }
+
+    /// Check whether a language has a registered analyzer
+    pub fn supports(&self, language: &str) -> bool {
+        self.analyzers.contains_key(language)
+    }
+
+    /// Run the analyzer for a language, or `None` if no analyzer is registered
+    pub fn analyze(&self, language: &str, source: &str) -> Option<Result<AnalyzerOutput, IndexingError>> {
+        self.get(language).map(|analyzer| analyzer(source))
+    }
+
+    /// List registered languages (sorted)
+    pub fn languages(&self) -> Vec<&str> {
+        let mut languages: Vec<&str> = self.analyzers.keys().map(|l| l.as_str()).collect();
+        languages.sort_unstable();
+        languages
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SymbolKind;
+
+    fn fake_analyzer(source: &str) -> Result<AnalyzerOutput, IndexingError> {
+        let symbols = vec![Symbol {
+            id: None,
+            file_id: 0,
+            name: source.to_string(),
+            kind: SymbolKind::Function,
+            line_start: 0,
+            line_end: 0,
+            column_start: 0,
+            column_end: 0,
+            scope: None,
+            metadata: None,
+        }];
+        Ok((symbols, Vec::new()))
+    }
+
+    #[test]
+    fn test_register_and_analyze() {
+        let mut registry = AnalyzerRegistry::new();
+        registry.register("python", fake_analyzer);
+
+        assert!(registry.supports("python"));
+        assert!(!registry.supports("rust"));
+
+        let (symbols, errors) = registry.analyze("python", "main").unwrap().unwrap();
+        assert!(errors.is_empty());
+        assert_eq!(symbols[0].name, "main");
+        assert!(registry.analyze("rust", "main").is_none());
+    }
+
+    #[test]
+    fn test_file_only_languages() {
+        let mut registry = AnalyzerRegistry::new();
+        registry.register_file_only_languages();
+
+        for path in ["App.vue", "Page.svelte", "Cargo.toml", "package.json", "ci.yml"] {
+            let language = crate::detect_language(path).unwrap();
+            let (symbols, errors) = registry.analyze(language, "{ \"key\": 1 }").unwrap().unwrap();
+            assert!(symbols.is_empty() && errors.is_empty(), "{}", path);
+        }
+        assert_eq!(registry.languages(), vec!["json", "svelte", "toml", "vue", "yaml"]);
+    }
+
+    #[test]
+    fn test_languages_sorted() {
+        let mut registry = AnalyzerRegistry::new();
+        registry
+            .register("typescript", fake_analyzer)
+            .register("python", fake_analyzer);
+
+        assert_eq!(registry.languages(), vec!["python", "typescript"]);
+    }
+}
@@ -0,0 +1,222 @@
+// Scoring module - Heuristic importance of symbols for ranking
+// Public, documented types rank above private helpers inside test code
+
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection};
+use serde_json::{Map, Value};
+
+use crate::{Symbol, SymbolKind};
+
+/// Score a symbol between 0.0 and 1.0
+///
+/// Starts from a per-kind base (`Class`/`Struct` 0.7, `Function` 0.5, others
+/// 0.3) and adjusts it using metadata: `visibility` of `pub`/`public`/`export`
+/// +0.2, a `doc` +0.15, `test` -0.3. Names shorter than three characters
+/// get -0.1.
+pub fn compute_symbol_importance(symbol: &Symbol) -> f32 {
+    let metadata = parse_metadata(symbol);
+
+    let mut score: f32 = match symbol.kind {
+        SymbolKind::Class | SymbolKind::Struct => 0.7,
+        SymbolKind::Function => 0.5,
+        _ => 0.3,
+    };
+
+    let visibility = metadata.get("visibility").and_then(Value::as_str);
+    if matches!(visibility, Some("pub" | "public" | "export")) {
+        score += 0.2;
+    }
+    if metadata.get("doc").and_then(Value::as_str).is_some_and(|doc| !doc.trim().is_empty()) {
+        score += 0.15;
+    }
+    if metadata.get("test") == Some(&Value::Bool(true)) {
+        score -= 0.3;
+    }
+    if symbol.name.chars().count() < 3 {
+        score -= 0.1;
+    }
+
+    (score.clamp(0.0, 1.0) * 100.0).round() / 100.0
+}
+
+/// Compute the importance of every stored symbol and save it as `metadata["importance"]`
+///
+/// Returns the number of symbols scored.
+pub fn store_importance_scores(conn: &Connection) -> Result<usize> {
+    let mut stmt = conn.prepare(
+        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end FROM symbols"
+    )?;
+    let symbols = stmt.query_map([], |row| {
+        Ok(Symbol {
+            id: Some(row.get(0)?),
+            file_id: row.get(1)?,
+            name: row.get(2)?,
+            kind: row.get::<_, String>(3)?.parse().unwrap_or(SymbolKind::Variable),
+            line_start: row.get(4)?,
+            line_end: row.get(5)?,
+            column_start: row.get(8)?,
+            column_end: row.get(9)?,
+            scope: row.get(6)?,
+            metadata: row.get(7)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()
+    .context("Failed to load symbols for scoring")?;
+
+    let tx = conn.unchecked_transaction().context("Failed to start transaction")?;
+    {
+        let mut stmt = tx.prepare("UPDATE symbols SET metadata = ?1 WHERE id = ?2")?;
+        for symbol in &symbols {
+            let mut metadata = parse_metadata(symbol);
+            // Round again after widening so 0.85f32 is stored as 0.85, not 0.8500000238
+            let importance = (compute_symbol_importance(symbol) as f64 * 100.0).round() / 100.0;
+            metadata.insert("importance".to_string(), Value::from(importance));
+            stmt.execute(params![Value::Object(metadata).to_string(), symbol.id])?;
+        }
+    }
+    tx.commit().context("Failed to commit importance scores")?;
+
+    Ok(symbols.len())
+}
+
+/// Symbols with a stored importance of at least `min_score`, highest first
+pub fn find_important_symbols(conn: &Connection, min_score: f32, limit: usize) -> Result<Vec<(Symbol, f32)>> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end,
+               json_extract(metadata, '$.importance') AS importance
+        FROM symbols
+        WHERE json_valid(metadata) AND json_extract(metadata, '$.importance') >= ?1
+        ORDER BY importance DESC, file_id, line_start
+        LIMIT ?2
+        "#,
+    )?;
+
+    let symbols = stmt.query_map(params![min_score as f64, limit as i64], |row| {
+        let importance: f64 = row.get(10)?;
+        Ok((
+            Symbol {
+                id: Some(row.get(0)?),
+                file_id: row.get(1)?,
+                name: row.get(2)?,
+                kind: row.get::<_, String>(3)?.parse().unwrap_or(SymbolKind::Variable),
+                line_start: row.get(4)?,
+                line_end: row.get(5)?,
+                column_start: row.get(8)?,
+                column_end: row.get(9)?,
+                scope: row.get(6)?,
+                metadata: row.get(7)?,
+            },
+            importance as f32,
+        ))
+    })?
+    .collect::<Result<Vec<_>, _>>()
+    .context("Failed to query important symbols")?;
+
+    Ok(symbols)
+}
+
+fn parse_metadata(symbol: &Symbol) -> Map<String, Value> {
+    symbol
+        .metadata
+        .as_deref()
+        .and_then(|m| serde_json::from_str::<Value>(m).ok())
+        .and_then(|v| match v {
+            Value::Object(map) => Some(map),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{init_schema, insert_symbols_batch, upsert_file};
+    use crate::FileMetadata;
+    use tempfile::NamedTempFile;
+
+    fn symbol(name: &str, kind: SymbolKind, metadata: Option<&str>) -> Symbol {
+        Symbol {
+            id: None,
+            file_id: 1,
+            name: name.to_string(),
+            kind,
+            line_start: 0,
+            line_end: 0,
+            column_start: 0,
+            column_end: 0,
+            scope: None,
+            metadata: metadata.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_public_function_outranks_test_helper() {
+        let api = symbol(
+            "parse_config",
+            SymbolKind::Function,
+            Some(r#"{"visibility":"pub","doc":"Parse the config file"}"#),
+        );
+        let helper = symbol("mk", SymbolKind::Function, Some(r#"{"test":true}"#));
+
+        assert_eq!(compute_symbol_importance(&api), 0.85);
+        assert_eq!(compute_symbol_importance(&helper), 0.1);
+        assert!(compute_symbol_importance(&api) > compute_symbol_importance(&helper));
+    }
+
+    #[test]
+    fn test_importance_bases_and_bounds() {
+        assert_eq!(compute_symbol_importance(&symbol("Config", SymbolKind::Struct, None)), 0.7);
+        assert_eq!(compute_symbol_importance(&symbol("Config", SymbolKind::Class, Some("not json"))), 0.7);
+        assert_eq!(compute_symbol_importance(&symbol("os", SymbolKind::Import, None)), 0.2);
+        // pub(crate) is not part of the public API
+        assert_eq!(
+            compute_symbol_importance(&symbol("load", SymbolKind::Function, Some(r#"{"visibility":"pub(crate)"}"#))),
+            0.5
+        );
+
+        let documented = r#"{"visibility":"public","doc":"A widget"}"#;
+        assert_eq!(compute_symbol_importance(&symbol("Widget", SymbolKind::Class, Some(documented))), 1.0);
+    }
+
+    #[test]
+    fn test_store_and_find_important_symbols() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+        let file = FileMetadata {
+            id: None,
+            path: "lib.rs".to_string(),
+            language: "rust".to_string(),
+            size: 10,
+            last_indexed: None,
+            parse_errors: 0,
+            index_duration_ms: None,
+            metadata: None,
+        };
+        let file_id = upsert_file(&conn, &file).unwrap();
+
+        let symbols: Vec<Symbol> = [
+            symbol("parse_config", SymbolKind::Function, Some(r#"{"visibility":"pub","doc":"Parse"}"#)),
+            symbol("Config", SymbolKind::Struct, None),
+            symbol("mk", SymbolKind::Function, Some(r#"{"test":true}"#)),
+        ]
+        .into_iter()
+        .map(|s| Symbol { file_id, ..s })
+        .collect();
+        insert_symbols_batch(&conn, &symbols).unwrap();
+
+        assert!(find_important_symbols(&conn, 0.0, 10).unwrap().is_empty());
+        assert_eq!(store_importance_scores(&conn).unwrap(), 3);
+
+        let found = find_important_symbols(&conn, 0.5, 10).unwrap();
+        let ranked: Vec<(&str, f32)> = found.iter().map(|(s, score)| (s.name.as_str(), *score)).collect();
+        assert_eq!(ranked, vec![("parse_config", 0.85), ("Config", 0.7)]);
+
+        // Existing metadata is kept alongside the score
+        let metadata: Value = serde_json::from_str(found[0].0.metadata.as_deref().unwrap()).unwrap();
+        assert_eq!(metadata["doc"], "Parse");
+        assert_eq!(metadata["importance"], 0.85);
+
+        assert_eq!(find_important_symbols(&conn, 0.0, 1).unwrap().len(), 1);
+    }
+}
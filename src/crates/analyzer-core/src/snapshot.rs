@@ -0,0 +1,256 @@
+// Snapshot module - Point-in-time dumps of the whole index as NDJSON
+// Files come first so a restore can insert symbols as soon as their file exists
+
+use anyhow::{Context, Result};
+use chrono::SecondsFormat;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Read, Write};
+
+use crate::export::{query_symbol_batch, EXPORT_BATCH_SIZE};
+use crate::query::{find_parse_errors_by_file, list_files};
+use crate::{FileMetadata, ParseError, Symbol};
+
+/// One line of a snapshot, tagged with `"type"`
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum SnapshotRecord {
+    File(FileMetadata),
+    Symbol(Symbol),
+    ParseError(ParseError),
+}
+
+/// Records written back by `restore_snapshot`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RestoredCounts {
+    pub files: usize,
+    pub symbols: usize,
+}
+
+/// Write every file, symbol and parse error in the index to `writer`, one JSON object per line
+///
+/// Row ids are kept, so snapshots of the same index diff cleanly.
+pub fn create_snapshot(conn: &Connection, writer: &mut impl Write) -> Result<()> {
+    let files = list_files(conn)?;
+    for file in &files {
+        write_record(writer, &SnapshotRecord::File(file.clone()))?;
+    }
+
+    let mut after_id = 0;
+    loop {
+        let batch = query_symbol_batch(conn, after_id, EXPORT_BATCH_SIZE)?;
+        let Some(last) = batch.last() else {
+            break;
+        };
+        after_id = last.id.unwrap_or(after_id);
+        let full = batch.len() == EXPORT_BATCH_SIZE;
+
+        for symbol in batch {
+            write_record(writer, &SnapshotRecord::Symbol(symbol))?;
+        }
+        if !full {
+            break;
+        }
+    }
+
+    for file in files.iter().filter(|file| file.parse_errors > 0) {
+        let Some(file_id) = file.id else {
+            continue;
+        };
+        for error in find_parse_errors_by_file(conn, file_id)? {
+            write_record(writer, &SnapshotRecord::ParseError(error))?;
+        }
+    }
+
+    writer.flush().context("Failed to flush snapshot")
+}
+
+fn write_record(writer: &mut impl Write, record: &SnapshotRecord) -> Result<()> {
+    serde_json::to_writer(&mut *writer, record)?;
+    writer.write_all(b"\n").context("Failed to write snapshot")
+}
+
+/// Replace the whole index with the records of a snapshot written by `create_snapshot`
+///
+/// The snapshot is parsed before anything is deleted, so a malformed line
+/// leaves the database untouched. Blank lines are skipped.
+pub fn restore_snapshot(conn: &Connection, reader: &mut impl Read) -> Result<RestoredCounts> {
+    let mut files = Vec::new();
+    let mut symbols = Vec::new();
+    let mut parse_errors = Vec::new();
+    for (index, line) in BufReader::new(reader).lines().enumerate() {
+        let line = line.context("Failed to read snapshot")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let record = serde_json::from_str(&line)
+            .with_context(|| format!("Invalid snapshot record on line {}", index + 1))?;
+        match record {
+            SnapshotRecord::File(file) => files.push(file),
+            SnapshotRecord::Symbol(symbol) => symbols.push(symbol),
+            SnapshotRecord::ParseError(error) => parse_errors.push(error),
+        }
+    }
+
+    let tx = conn
+        .unchecked_transaction()
+        .context("Failed to start transaction")?;
+
+    // Symbols and parse errors go with their files (ON DELETE CASCADE)
+    tx.execute("DELETE FROM files", [])
+        .context("Failed to clear index")?;
+
+    {
+        let mut stmt = tx.prepare(
+            "INSERT INTO files (id, path, language, size, last_indexed, parse_errors, index_duration_ms, metadata)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        )?;
+        for file in &files {
+            stmt.execute(params![
+                file.id,
+                file.path,
+                file.language,
+                file.size,
+                file.last_indexed
+                    .map(|t| t.to_rfc3339_opts(SecondsFormat::Micros, true)),
+                file.parse_errors,
+                file.index_duration_ms,
+                file.metadata,
+            ])
+            .with_context(|| format!("Failed to restore file {}", file.path))?;
+        }
+    }
+
+    {
+        let mut stmt = tx.prepare(
+            "INSERT INTO symbols (id, file_id, name, kind, line_start, line_end, scope, metadata, normalized_name,
+                column_start, column_end)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8,
+                normalize_symbol_name((SELECT language FROM files WHERE id = ?2), ?3), ?9, ?10)",
+        )?;
+        for symbol in &symbols {
+            stmt.execute(params![
+                symbol.id,
+                symbol.file_id,
+                symbol.name,
+                symbol.kind.to_string(),
+                symbol.line_start,
+                symbol.line_end,
+                symbol.scope,
+                symbol.metadata,
+                symbol.column_start,
+                symbol.column_end,
+            ])
+            .with_context(|| format!("Failed to restore symbol `{}`", symbol.name))?;
+        }
+    }
+
+    {
+        let mut stmt = tx.prepare(
+            "INSERT INTO parse_errors (file_id, line_number, column_number, message)
+             SELECT id, ?2, ?3, ?4 FROM files WHERE path = ?1",
+        )?;
+        for error in &parse_errors {
+            stmt.execute(params![error.file_path, error.line, error.column, error.message])
+                .context("Failed to restore parse error")?;
+        }
+    }
+
+    tx.commit().context("Failed to commit snapshot")?;
+
+    Ok(RestoredCounts {
+        files: files.len(),
+        symbols: symbols.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::find_symbols_by_name;
+    use crate::storage::{init_schema, insert_symbols_batch, replace_parse_errors, upsert_file};
+    use crate::SymbolKind;
+    use tempfile::TempDir;
+
+    fn seed(conn: &Connection, path: &str, names: &[&str]) -> i64 {
+        let file_id = upsert_file(conn, &FileMetadata {
+            id: None,
+            path: path.to_string(),
+            language: "python".to_string(),
+            size: 64,
+            last_indexed: None,
+            parse_errors: 0,
+            index_duration_ms: Some(3),
+            metadata: None,
+        })
+        .unwrap();
+        let symbols: Vec<Symbol> = names
+            .iter()
+            .enumerate()
+            .map(|(line, name)| Symbol {
+                id: None,
+                file_id,
+                name: name.to_string(),
+                kind: SymbolKind::Function,
+                line_start: line,
+                line_end: line,
+                column_start: 0,
+                column_end: 10,
+                scope: None,
+                metadata: Some(r#"{"async":true}"#.to_string()),
+            })
+            .collect();
+        insert_symbols_batch(conn, &symbols).unwrap();
+        file_id
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let source = init_schema(&dir.path().join("source.db")).unwrap();
+        seed(&source, "a.py", &["load", "save"]);
+        let b = seed(&source, "b.py", &["main"]);
+        replace_parse_errors(&source, b, &[ParseError::new(4, 2, "missing `:`")]).unwrap();
+
+        let mut snapshot = Vec::new();
+        create_snapshot(&source, &mut snapshot).unwrap();
+        let text = String::from_utf8(snapshot.clone()).unwrap();
+        assert_eq!(text.lines().count(), 6);
+        assert!(text.lines().next().unwrap().starts_with(r#"{"type":"file""#));
+
+        // Restoring replaces whatever the target index held
+        let target = init_schema(&dir.path().join("target.db")).unwrap();
+        seed(&target, "stale.py", &["old"]);
+        let counts = restore_snapshot(&target, &mut snapshot.as_slice()).unwrap();
+        assert_eq!(counts, RestoredCounts { files: 2, symbols: 3 });
+
+        assert!(find_symbols_by_name(&target, "old").unwrap().is_empty());
+        let save = &find_symbols_by_name(&target, "save").unwrap()[0];
+        assert_eq!(save, &find_symbols_by_name(&source, "save").unwrap()[0]);
+        assert_eq!(list_files(&target).unwrap().len(), 2);
+        assert_eq!(
+            find_parse_errors_by_file(&target, b).unwrap(),
+            vec![ParseError::new(4, 2, "missing `:`").with_file_path("b.py")]
+        );
+
+        // A second snapshot of the restored index is identical
+        let mut again = Vec::new();
+        create_snapshot(&target, &mut again).unwrap();
+        assert_eq!(String::from_utf8(again).unwrap(), text);
+    }
+
+    #[test]
+    fn test_restore_rejects_malformed_snapshot() {
+        let dir = TempDir::new().unwrap();
+        let conn = init_schema(&dir.path().join("index.db")).unwrap();
+        seed(&conn, "a.py", &["keep"]);
+
+        let snapshot = "{\"type\":\"file\"}\nnot json\n";
+        let err = restore_snapshot(&conn, &mut snapshot.as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("line 1"));
+
+        // Nothing was deleted
+        assert_eq!(find_symbols_by_name(&conn, "keep").unwrap().len(), 1);
+    }
+}
@@ -2,10 +2,20 @@
 // Implements schema initialization, WAL mode, and connection management
 
 use anyhow::{Context, Result};
-use rusqlite::{Connection, params};
+use chrono::{SecondsFormat, Utc};
+use regex::Regex;
+use rusqlite::functions::FunctionFlags;
+use rusqlite::{Connection, OptionalExtension, Transaction, TransactionBehavior, params};
+use serde_json::Value;
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashMap};
 use std::path::Path;
+use std::sync::Arc;
+use thiserror::Error;
 
-use crate::{FileMetadata, Symbol};
+use crate::normalization::normalize_symbol_name;
+use crate::query::parse_symbol_kind;
+use crate::{signature_hash, signature_param_types, FileMetadata, ParseError, Symbol, SymbolKind};
 
 /// Initialize SQLite database schema with WAL mode
 pub fn init_schema(db_path: &Path) -> Result<Connection> {
@@ -49,6 +59,10 @@ pub fn init_schema(db_path: &Path) -> Result<Connection> {
     conn.pragma_update(None, "page_size", 4096)
         .context("Failed to set page size")?;
 
+    // Custom SQL functions (REGEXP operator, name normalization)
+    register_regexp_function(&conn)?;
+    register_normalize_function(&conn)?;
+
     // Larger WAL checkpoint threshold (10000 pages ~= 40MB)
     // Fewer checkpoints = better write performance
     conn.pragma_update(None, "wal_autocheckpoint", 10000)
@@ -67,7 +81,10 @@ pub fn init_schema(db_path: &Path) -> Result<Connection> {
             language TEXT NOT NULL,
             size INTEGER NOT NULL,
             last_indexed TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            parse_errors INTEGER DEFAULT 0
+            parse_errors INTEGER DEFAULT 0,
+            index_duration_ms INTEGER,
+            metadata TEXT,
+            content_hash BLOB
         );
 
         CREATE TABLE IF NOT EXISTS symbols (
@@ -79,9 +96,20 @@ pub fn init_schema(db_path: &Path) -> Result<Connection> {
             line_end INTEGER NOT NULL,
             scope TEXT,
             metadata TEXT,
+            normalized_name TEXT,
+            column_start INTEGER NOT NULL DEFAULT 0,
+            column_end INTEGER NOT NULL DEFAULT 0,
             UNIQUE(file_id, name, line_start)
         );
 
+        CREATE TABLE IF NOT EXISTS parse_errors (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            file_id INTEGER NOT NULL REFERENCES files(id) ON DELETE CASCADE,
+            line_number INTEGER NOT NULL,
+            column_number INTEGER NOT NULL,
+            message TEXT NOT NULL
+        );
+
         CREATE TABLE IF NOT EXISTS dependencies (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
             file_id INTEGER NOT NULL REFERENCES files(id) ON DELETE CASCADE,
@@ -102,6 +130,18 @@ pub fn init_schema(db_path: &Path) -> Result<Connection> {
         CREATE INDEX IF NOT EXISTS idx_symbols_name_kind ON symbols(name, kind);
         CREATE INDEX IF NOT EXISTS idx_symbols_file_line ON symbols(file_id, line_start);
 
+        CREATE TABLE IF NOT EXISTS change_log (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            event_kind TEXT NOT NULL,
+            file_path TEXT NOT NULL,
+            timestamp TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+            symbols_added INTEGER NOT NULL DEFAULT 0,
+            symbols_removed INTEGER NOT NULL DEFAULT 0
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_change_log_timestamp ON change_log(timestamp);
+        CREATE INDEX IF NOT EXISTS idx_parse_errors_file_id ON parse_errors(file_id);
+
         -- Dependency indexes
         CREATE INDEX IF NOT EXISTS idx_dependencies_file_id ON dependencies(file_id);
         CREATE INDEX IF NOT EXISTS idx_dependencies_import_path ON dependencies(import_path);
@@ -109,39 +149,479 @@ pub fn init_schema(db_path: &Path) -> Result<Connection> {
     )
     .context("Failed to create database schema")?;
 
+    #[cfg(feature = "deep-mode")]
+    crate::analysis::deep::init_deep_schema(&conn)?;
+
+    // Enforce REFERENCES ... ON DELETE CASCADE (off by default in SQLite)
+    set_pragma_foreign_keys(&conn, true)?;
+
+    run_migrations(&conn)?;
+
     Ok(conn)
 }
 
+/// Current schema version, stored in `PRAGMA user_version`
+pub const SCHEMA_VERSION: i64 = 7;
+
+/// Bring an existing database up to `SCHEMA_VERSION`
+///
+/// Version history:
+/// - 1: `symbol_fts` full-text index over symbol names and docs
+/// - 2: `files.index_duration_ms`
+/// - 3: `symbols.normalized_name` for cross-language name matching
+/// - 4: `files.metadata`
+/// - 5: `symbols.column_start` and `symbols.column_end`
+/// - 6: `files.content_hash` (SHA-256 of the indexed content)
+/// - 7: `parse_errors` table (created with the schema, so no data migration)
+fn run_migrations(conn: &Connection) -> Result<()> {
+    let version: i64 = conn
+        .pragma_query_value(None, "user_version", |row| row.get(0))
+        .context("Failed to read schema version")?;
+
+    if version < 1 {
+        migrate_to_fts5(conn)?;
+    }
+
+    // Fresh databases already have the column from `CREATE TABLE`
+    if version < 2 && !table_has_column(conn, "files", "index_duration_ms")? {
+        conn.execute("ALTER TABLE files ADD COLUMN index_duration_ms INTEGER", [])
+            .context("Failed to add files.index_duration_ms")?;
+    }
+
+    if version < 3 {
+        migrate_normalized_names(conn)?;
+    }
+
+    if version < 4 && !table_has_column(conn, "files", "metadata")? {
+        conn.execute("ALTER TABLE files ADD COLUMN metadata TEXT", [])
+            .context("Failed to add files.metadata")?;
+    }
+
+    // Symbols stored before version 5 read back as column 0
+    if version < 5 && !table_has_column(conn, "symbols", "column_start")? {
+        conn.execute_batch(
+            r#"
+            ALTER TABLE symbols ADD COLUMN column_start INTEGER NOT NULL DEFAULT 0;
+            ALTER TABLE symbols ADD COLUMN column_end INTEGER NOT NULL DEFAULT 0;
+            "#,
+        )
+        .context("Failed to add symbol column offsets")?;
+    }
+
+    if version < 6 && !table_has_column(conn, "files", "content_hash")? {
+        conn.execute("ALTER TABLE files ADD COLUMN content_hash BLOB", [])
+            .context("Failed to add files.content_hash")?;
+    }
+
+    if version < SCHEMA_VERSION {
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION)
+            .context("Failed to update schema version")?;
+    }
+
+    Ok(())
+}
+
+/// Add and backfill `symbols.normalized_name`, then index it
+fn migrate_normalized_names(conn: &Connection) -> Result<()> {
+    if !table_has_column(conn, "symbols", "normalized_name")? {
+        conn.execute("ALTER TABLE symbols ADD COLUMN normalized_name TEXT", [])
+            .context("Failed to add symbols.normalized_name")?;
+    }
+
+    conn.execute_batch(
+        r#"
+        UPDATE symbols SET normalized_name = normalize_symbol_name(
+            (SELECT language FROM files WHERE files.id = symbols.file_id), name)
+        WHERE normalized_name IS NULL;
+
+        CREATE INDEX IF NOT EXISTS idx_symbols_normalized_name ON symbols(normalized_name);
+        "#,
+    )
+    .context("Failed to backfill normalized symbol names")
+}
+
+/// Check whether `table` has a column named `column`
+fn table_has_column(conn: &Connection, table: &str, column: &str) -> Result<bool> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let names = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to read table info")?;
+    Ok(names.iter().any(|name| name == column))
+}
+
+/// Check whether the `symbol_fts` full-text table exists
+pub fn schema_has_fts5(conn: &Connection) -> bool {
+    conn.query_row(
+        "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'symbol_fts'",
+        [],
+        |_| Ok(()),
+    )
+    .is_ok()
+}
+
+/// Create the `symbol_fts` table and its sync triggers, then (re)populate it
+/// from existing symbols
+///
+/// Safe to run repeatedly. Returns the number of symbols indexed.
+pub fn migrate_to_fts5(conn: &Connection) -> Result<usize> {
+    let tx = conn
+        .unchecked_transaction()
+        .context("Failed to start transaction")?;
+
+    // rowid = symbols.id; `doc` comes from metadata["doc"] when present
+    tx.execute_batch(
+        r#"
+        CREATE VIRTUAL TABLE IF NOT EXISTS symbol_fts USING fts5(name, doc);
+
+        CREATE TRIGGER IF NOT EXISTS symbols_fts_insert AFTER INSERT ON symbols BEGIN
+            INSERT INTO symbol_fts (rowid, name, doc)
+            VALUES (new.id, new.name, COALESCE(CASE WHEN json_valid(new.metadata)
+                THEN json_extract(new.metadata, '$.doc') END, ''));
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS symbols_fts_delete AFTER DELETE ON symbols BEGIN
+            DELETE FROM symbol_fts WHERE rowid = old.id;
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS symbols_fts_update AFTER UPDATE ON symbols BEGIN
+            DELETE FROM symbol_fts WHERE rowid = old.id;
+            INSERT INTO symbol_fts (rowid, name, doc)
+            VALUES (new.id, new.name, COALESCE(CASE WHEN json_valid(new.metadata)
+                THEN json_extract(new.metadata, '$.doc') END, ''));
+        END;
+
+        DELETE FROM symbol_fts;
+        "#,
+    )
+    .context("Failed to create FTS5 schema")?;
+
+    let migrated = tx
+        .execute(
+            r#"
+            INSERT INTO symbol_fts (rowid, name, doc)
+            SELECT id, name, COALESCE(CASE WHEN json_valid(metadata)
+                THEN json_extract(metadata, '$.doc') END, '')
+            FROM symbols
+            "#,
+            [],
+        )
+        .context("Failed to populate FTS5 table")?;
+
+    tx.commit().context("Failed to commit FTS5 migration")?;
+    Ok(migrated)
+}
+
+/// Enable or disable foreign key enforcement for this connection
+pub fn set_pragma_foreign_keys(conn: &Connection, enabled: bool) -> Result<()> {
+    conn.pragma_update(None, "foreign_keys", enabled)
+        .context("Failed to set foreign_keys pragma")
+}
+
+/// Errors raised when writing symbols
+#[derive(Debug, Error)]
+pub enum SymbolInsertError {
+    /// The symbol's `file_id` does not reference an indexed file
+    #[error("Symbol `{name}` references missing file id {file_id}")]
+    OrphanedFile {
+        file_id: i64,
+        name: String,
+        #[source]
+        source: rusqlite::Error,
+    },
+}
+
+/// Wrap foreign key violations as `SymbolInsertError::OrphanedFile`
+fn symbol_insert_error(err: rusqlite::Error, symbol: &Symbol) -> anyhow::Error {
+    match err {
+        rusqlite::Error::SqliteFailure(ref e, _)
+            if e.extended_code == rusqlite::ffi::SQLITE_CONSTRAINT_FOREIGNKEY =>
+        {
+            SymbolInsertError::OrphanedFile {
+                file_id: symbol.file_id,
+                name: symbol.name.clone(),
+                source: err,
+            }
+            .into()
+        }
+        err => anyhow::Error::new(err).context("Failed to insert symbol"),
+    }
+}
+
+/// WAL checkpoint mode (see `PRAGMA wal_checkpoint`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WalCheckpointMode {
+    /// Checkpoint as many frames as possible without waiting on readers/writers
+    Passive,
+    /// Wait for writers, then checkpoint every frame
+    Full,
+    /// Like `Full`, and wait for readers so the next writer restarts the WAL
+    Restart,
+    /// Like `Restart`, and truncate the WAL file to zero bytes
+    Truncate,
+}
+
+impl WalCheckpointMode {
+    fn as_sql(&self) -> &'static str {
+        match self {
+            WalCheckpointMode::Passive => "PASSIVE",
+            WalCheckpointMode::Full => "FULL",
+            WalCheckpointMode::Restart => "RESTART",
+            WalCheckpointMode::Truncate => "TRUNCATE",
+        }
+    }
+}
+
+impl std::str::FromStr for WalCheckpointMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "passive" => Ok(WalCheckpointMode::Passive),
+            "full" => Ok(WalCheckpointMode::Full),
+            "restart" => Ok(WalCheckpointMode::Restart),
+            "truncate" => Ok(WalCheckpointMode::Truncate),
+            _ => Err(anyhow::anyhow!("Unknown WAL checkpoint mode: {}", s)),
+        }
+    }
+}
+
+/// Result of a WAL checkpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WalStats {
+    /// Frames in the WAL file (-1 if the database is not in WAL mode)
+    pub pages_in_wal: i64,
+    /// Frames copied back into the database file
+    pub checkpointed_pages: i64,
+}
+
+/// Explicitly checkpoint the WAL so it does not grow unboundedly
+pub fn checkpoint_wal(conn: &Connection, mode: WalCheckpointMode) -> Result<WalStats> {
+    let sql = format!("PRAGMA wal_checkpoint({})", mode.as_sql());
+    let stats = conn
+        .query_row(&sql, [], |row| {
+            Ok(WalStats {
+                pages_in_wal: row.get(1)?,
+                checkpointed_pages: row.get(2)?,
+            })
+        })
+        .context("Failed to checkpoint WAL")?;
+    Ok(stats)
+}
+
+/// Size of the database in bytes (`page_count * page_size`, including committed WAL pages)
+pub fn get_index_size_bytes(conn: &Connection) -> Result<u64> {
+    let page_count: u64 = conn
+        .pragma_query_value(None, "page_count", |row| row.get(0))
+        .context("Failed to read page_count")?;
+    let page_size: u64 = conn
+        .pragma_query_value(None, "page_size", |row| row.get(0))
+        .context("Failed to read page_size")?;
+    Ok(page_count * page_size)
+}
+
+/// Bytes used by each table and index, from SQLite's `dbstat` virtual table
+pub fn get_table_size_breakdown(conn: &Connection) -> Result<HashMap<String, u64>> {
+    let mut stmt = conn
+        .prepare("SELECT name, SUM(pgsize) FROM dbstat GROUP BY name")
+        .context("Failed to query dbstat")?;
+
+    let sizes = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<HashMap<_, _>, _>>()?;
+
+    Ok(sizes)
+}
+
+type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// Register `normalize_symbol_name(language, name)` (see `normalization`);
+/// a NULL language applies no language-specific rules
+fn register_normalize_function(conn: &Connection) -> Result<()> {
+    conn.create_scalar_function(
+        "normalize_symbol_name",
+        2,
+        FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC,
+        |ctx| {
+            let language: Option<String> = ctx.get(0)?;
+            let name: String = ctx.get(1)?;
+            Ok(normalize_symbol_name(language.as_deref().unwrap_or(""), &name))
+        },
+    )
+    .context("Failed to register normalize_symbol_name function")
+}
+
+/// Register `regexp(pattern, text)` so queries can use `name REGEXP ?`
+///
+/// Compiled patterns are cached per statement by SQLite's auxiliary data.
+fn register_regexp_function(conn: &Connection) -> Result<()> {
+    conn.create_scalar_function(
+        "regexp",
+        2,
+        FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC,
+        |ctx| {
+            let regex: Arc<Regex> = ctx.get_or_create_aux(0, |pattern| -> Result<_, BoxError> {
+                Ok(Regex::new(pattern.as_str()?)?)
+            })?;
+            let text = ctx
+                .get_raw(1)
+                .as_str_or_null()
+                .map_err(|e| rusqlite::Error::UserFunctionError(e.into()))?;
+            Ok(text.is_some_and(|text| regex.is_match(text)))
+        },
+    )
+    .context("Failed to register REGEXP function")
+}
+
+/// Current time as stored in `files.last_indexed` (RFC 3339, UTC)
+///
+/// Rows written by older versions hold SQLite's `YYYY-MM-DD HH:MM:SS`; both
+/// forms read back as `DateTime<Utc>`.
+fn index_timestamp() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
 /// Insert or update file metadata
+///
+/// Returns the row id for `file.path`, which is stable across repeated upserts.
+/// `last_indexed` is set to the current time. Any stored content hash is
+/// cleared; record the new one with `set_file_content_hash`.
 pub fn upsert_file(conn: &Connection, file: &FileMetadata) -> Result<i64> {
+    // `last_insert_rowid` is not updated on the conflict path, so ask for the id explicitly
+    let file_id = conn
+        .query_row(
+            r#"
+            INSERT INTO files (path, language, size, last_indexed, parse_errors, index_duration_ms, metadata)
+            VALUES (?1, ?2, ?3, ?7, ?4, ?5, ?6)
+            ON CONFLICT(path) DO UPDATE SET
+                language = excluded.language,
+                size = excluded.size,
+                last_indexed = excluded.last_indexed,
+                parse_errors = excluded.parse_errors,
+                index_duration_ms = excluded.index_duration_ms,
+                metadata = excluded.metadata,
+                content_hash = NULL
+            RETURNING id
+            "#,
+            params![
+                file.path,
+                file.language,
+                file.size,
+                file.parse_errors,
+                file.index_duration_ms,
+                file.metadata,
+                index_timestamp(),
+            ],
+            |row| row.get(0),
+        )
+        .context("Failed to upsert file metadata")?;
+
+    Ok(file_id)
+}
+
+/// Replace the stored parse errors of a file and set `files.parse_errors` to their count
+pub fn replace_parse_errors(conn: &Connection, file_id: i64, errors: &[ParseError]) -> Result<usize> {
+    conn.execute("DELETE FROM parse_errors WHERE file_id = ?1", params![file_id])
+        .context("Failed to delete parse errors")?;
+
+    let mut stmt = conn
+        .prepare_cached(
+            "INSERT INTO parse_errors (file_id, line_number, column_number, message) VALUES (?1, ?2, ?3, ?4)",
+        )
+        .context("Failed to prepare parse error insert")?;
+    for error in errors {
+        stmt.execute(params![file_id, error.line, error.column, error.message])
+            .context("Failed to insert parse error")?;
+    }
+
     conn.execute(
-        r#"
-        INSERT INTO files (path, language, size, last_indexed, parse_errors)
-        VALUES (?1, ?2, ?3, CURRENT_TIMESTAMP, ?4)
-        ON CONFLICT(path) DO UPDATE SET
-            language = excluded.language,
-            size = excluded.size,
-            last_indexed = CURRENT_TIMESTAMP,
-            parse_errors = excluded.parse_errors
-        "#,
-        params![file.path, file.language, file.size, file.parse_errors],
+        "UPDATE files SET parse_errors = ?1 WHERE id = ?2",
+        params![errors.len(), file_id],
     )
-    .context("Failed to upsert file metadata")?;
+    .context("Failed to update parse error count")?;
 
-    Ok(conn.last_insert_rowid())
+    Ok(errors.len())
+}
+
+/// Get the id of a file row, inserting a minimal row if the path is new.
+///
+/// Returns `(file_id, created)` where `created` is `true` only when this call
+/// inserted the row. Uses `INSERT OR IGNORE` so concurrent indexers racing on
+/// the same path always resolve to the same primary key.
+pub fn get_or_create_file(
+    conn: &Connection,
+    path: &str,
+    language: &str,
+    size: u64,
+) -> Result<(i64, bool)> {
+    let inserted = conn
+        .execute(
+            r#"
+            INSERT OR IGNORE INTO files (path, language, size, last_indexed, parse_errors)
+            VALUES (?1, ?2, ?3, ?4, 0)
+            "#,
+            params![path, language, size, index_timestamp()],
+        )
+        .context("Failed to insert file")?;
+
+    let file_id: i64 = conn
+        .query_row("SELECT id FROM files WHERE path = ?1", params![path], |row| row.get(0))
+        .context("Failed to resolve file id")?;
+
+    Ok((file_id, inserted > 0))
+}
+
+/// Record the SHA-256 digest of the content last indexed for `path`
+pub fn set_file_content_hash(conn: &Connection, path: &str, hash: &[u8; 32]) -> Result<()> {
+    conn.execute("UPDATE files SET content_hash = ?1 WHERE path = ?2", params![hash, path])
+        .context("Failed to store content hash")?;
+    Ok(())
+}
+
+/// SHA-256 digest of the content last indexed for `path`, if recorded
+pub fn get_file_content_hash(conn: &Connection, path: &str) -> Result<Option<[u8; 32]>> {
+    let hash = conn
+        .query_row("SELECT content_hash FROM files WHERE path = ?1", params![path], |row| row.get(0))
+        .optional()
+        .context("Failed to read content hash")?;
+    Ok(hash.flatten())
+}
+
+/// Metadata to store for `symbol`: functions with a `params` array also get
+/// `signature_hash`, used by `find_symbols_with_similar_signatures`
+fn storage_metadata(symbol: &Symbol) -> Option<Cow<'_, str>> {
+    let metadata = symbol.metadata.as_deref()?;
+    if symbol.kind != SymbolKind::Function || !metadata.contains("\"params\"") {
+        return Some(Cow::Borrowed(metadata));
+    }
+
+    let Ok(mut value) = serde_json::from_str::<Value>(metadata) else {
+        return Some(Cow::Borrowed(metadata));
+    };
+    match signature_param_types(&value) {
+        Some(types) if value.is_object() => {
+            value["signature_hash"] = signature_hash(&types).into();
+            Some(Cow::Owned(value.to_string()))
+        }
+        _ => Some(Cow::Borrowed(metadata)),
+    }
 }
 
 /// Insert symbol
 pub fn insert_symbol(conn: &Connection, symbol: &Symbol) -> Result<i64> {
     conn.execute(
         r#"
-        INSERT INTO symbols (file_id, name, kind, line_start, line_end, scope, metadata)
-        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+        INSERT INTO symbols (file_id, name, kind, line_start, line_end, scope, metadata, normalized_name,
+            column_start, column_end)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7,
+            normalize_symbol_name((SELECT language FROM files WHERE id = ?1), ?2), ?8, ?9)
         ON CONFLICT(file_id, name, line_start) DO UPDATE SET
             kind = excluded.kind,
             line_end = excluded.line_end,
             scope = excluded.scope,
-            metadata = excluded.metadata
+            metadata = excluded.metadata,
+            column_start = excluded.column_start,
+            column_end = excluded.column_end
         "#,
         params![
             symbol.file_id,
@@ -150,25 +630,271 @@ pub fn insert_symbol(conn: &Connection, symbol: &Symbol) -> Result<i64> {
             symbol.line_start,
             symbol.line_end,
             symbol.scope,
-            symbol.metadata,
+            storage_metadata(symbol),
+            symbol.column_start,
+            symbol.column_end,
         ],
     )
-    .context("Failed to insert symbol")?;
+    .map_err(|e| symbol_insert_error(e, symbol))?;
 
     Ok(conn.last_insert_rowid())
 }
 
+/// Insert a symbol after checking it with `Symbol::validate`
+pub fn insert_symbol_validated(conn: &Connection, symbol: &Symbol) -> Result<i64> {
+    symbol.validate()?;
+    insert_symbol(conn, symbol)
+}
+
+/// Insert many symbols in a single `BEGIN IMMEDIATE` transaction with one prepared statement
+///
+/// Returns the number of new rows; symbols that conflict with an existing
+/// row update it but are not counted.
+pub fn insert_symbols_batch(conn: &Connection, symbols: &[Symbol]) -> Result<usize> {
+    // Take the write lock up front so the batch cannot fail half-way on SQLITE_BUSY
+    let tx = Transaction::new_unchecked(conn, TransactionBehavior::Immediate)
+        .context("Failed to start transaction")?;
+    let inserted = insert_symbols_in_transaction(&tx, symbols)?;
+
+    tx.commit().context("Failed to commit symbol batch")?;
+    Ok(inserted)
+}
+
+/// `insert_symbols_batch` inside a transaction the caller commits
+///
+/// Lets callers store symbols atomically with other writes to the same file.
+pub fn insert_symbols_in_transaction(tx: &Transaction, symbols: &[Symbol]) -> Result<usize> {
+    // An upsert reports a change either way, so count rows of the touched files instead
+    let file_ids: BTreeSet<i64> = symbols.iter().map(|symbol| symbol.file_id).collect();
+    let before = count_file_symbols(tx, &file_ids)?;
+
+    let mut stmt = tx
+        .prepare(
+            r#"
+            INSERT INTO symbols (file_id, name, kind, line_start, line_end, scope, metadata, normalized_name,
+                column_start, column_end)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7,
+                normalize_symbol_name((SELECT language FROM files WHERE id = ?1), ?2), ?8, ?9)
+            ON CONFLICT(file_id, name, line_start) DO UPDATE SET
+                kind = excluded.kind,
+                line_end = excluded.line_end,
+                scope = excluded.scope,
+                metadata = excluded.metadata,
+                column_start = excluded.column_start,
+                column_end = excluded.column_end
+            "#,
+        )
+        .context("Failed to prepare symbol insert")?;
+
+    for symbol in symbols {
+        stmt.execute(params![
+            symbol.file_id,
+            symbol.name,
+            symbol.kind.to_string(),
+            symbol.line_start,
+            symbol.line_end,
+            symbol.scope,
+            storage_metadata(symbol),
+            symbol.column_start,
+            symbol.column_end,
+        ])
+        .map_err(|e| symbol_insert_error(e, symbol))?;
+    }
+
+    Ok(count_file_symbols(tx, &file_ids)? - before)
+}
+
+/// Total number of symbols stored for `file_ids`
+fn count_file_symbols(conn: &Connection, file_ids: &BTreeSet<i64>) -> Result<usize> {
+    let mut stmt = conn.prepare_cached("SELECT COUNT(*) FROM symbols WHERE file_id = ?1")?;
+    let mut total = 0;
+    for file_id in file_ids {
+        total += stmt.query_row(params![file_id], |row| row.get::<_, usize>(0))?;
+    }
+    Ok(total)
+}
+
 /// Delete all symbols for a file (used during re-indexing)
-pub fn delete_file_symbols(conn: &Connection, file_id: i64) -> Result<()> {
+///
+/// Returns the number of symbols removed.
+pub fn delete_file_symbols(conn: &Connection, file_id: i64) -> Result<usize> {
     conn.execute("DELETE FROM symbols WHERE file_id = ?1", params![file_id])
-        .context("Failed to delete file symbols")?;
-    Ok(())
+        .context("Failed to delete file symbols")
+}
+
+/// Delete a file's symbols whose line range overlaps `start_line..=end_line`
+///
+/// Counterpart of `query::find_symbols_modified_between` for surgical updates.
+/// Returns the number of symbols removed.
+pub fn delete_symbols_in_range(conn: &Connection, file_id: i64, start_line: usize, end_line: usize) -> Result<usize> {
+    conn.execute(
+        "DELETE FROM symbols WHERE file_id = ?1 AND NOT (line_end < ?2 OR line_start > ?3)",
+        params![file_id, start_line, end_line],
+    )
+    .context("Failed to delete symbols in range")
+}
+
+/// Drop default-valued keys (`false`, `null`, `[]`, `{}`) from symbol metadata
+///
+/// Metadata left empty becomes NULL. Rows that are not JSON objects are left
+/// untouched (see `validate_metadata_json`). Returns the number of bytes saved.
+pub fn compact_metadata(conn: &Connection) -> Result<u64> {
+    let mut stmt = conn.prepare("SELECT id, metadata FROM symbols WHERE metadata IS NOT NULL")?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to read symbol metadata")?;
+
+    let tx = conn
+        .unchecked_transaction()
+        .context("Failed to start transaction")?;
+
+    let mut saved = 0;
+    {
+        let mut update = tx.prepare("UPDATE symbols SET metadata = ?2 WHERE id = ?1")?;
+        for (id, metadata) in rows {
+            let Ok(Value::Object(mut map)) = serde_json::from_str::<Value>(&metadata) else {
+                continue;
+            };
+            map.retain(|_, value| !is_default_metadata_value(value));
+
+            let compacted = (!map.is_empty()).then(|| Value::Object(map).to_string());
+            let compacted_len = compacted.as_ref().map_or(0, String::len);
+            if compacted_len < metadata.len() {
+                update.execute(params![id, compacted])?;
+                saved += (metadata.len() - compacted_len) as u64;
+            }
+        }
+    }
+
+    tx.commit().context("Failed to commit compacted metadata")?;
+    Ok(saved)
+}
+
+fn is_default_metadata_value(value: &Value) -> bool {
+    match value {
+        Value::Null | Value::Bool(false) => true,
+        Value::Array(items) => items.is_empty(),
+        Value::Object(map) => map.is_empty(),
+        _ => false,
+    }
+}
+
+/// IDs of symbols whose metadata is not valid JSON
+pub fn validate_metadata_json(conn: &Connection) -> Result<Vec<i64>> {
+    let mut stmt = conn.prepare(
+        "SELECT id FROM symbols WHERE metadata IS NOT NULL AND NOT json_valid(metadata) ORDER BY id",
+    )?;
+    let ids = stmt
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to validate symbol metadata")?;
+    Ok(ids)
+}
+
+/// Append an entry to the `change_log` audit table
+pub fn insert_change_log(
+    conn: &Connection,
+    event_kind: &str,
+    file_path: &str,
+    symbols_added: usize,
+    symbols_removed: usize,
+) -> Result<i64> {
+    conn.execute(
+        r#"
+        INSERT INTO change_log (event_kind, file_path, symbols_added, symbols_removed)
+        VALUES (?1, ?2, ?3, ?4)
+        "#,
+        params![event_kind, file_path, symbols_added, symbols_removed],
+    )
+    .context("Failed to insert change log entry")?;
+
+    Ok(conn.last_insert_rowid())
+}
+
+/// Delete all symbols belonging to any of the given files (single statement)
+pub fn bulk_delete_symbols_by_file_ids(conn: &Connection, file_ids: &[i64]) -> Result<usize> {
+    if file_ids.is_empty() {
+        return Ok(0);
+    }
+
+    let ids = serde_json::to_string(file_ids)?;
+    let deleted = conn
+        .execute(
+            "DELETE FROM symbols WHERE file_id IN (SELECT value FROM json_each(?1))",
+            params![ids],
+        )
+        .context("Failed to bulk delete symbols")?;
+    Ok(deleted)
+}
+
+/// Delete many files and their symbols in one transaction; dependencies
+/// cascade via foreign keys
+///
+/// Either everything is deleted or, on error, nothing is. Returns the number
+/// of file rows deleted.
+pub fn bulk_delete_files(conn: &Connection, file_ids: &[i64]) -> Result<usize> {
+    if file_ids.is_empty() {
+        return Ok(0);
+    }
+
+    let tx = conn
+        .unchecked_transaction()
+        .context("Failed to start transaction")?;
+    bulk_delete_symbols_by_file_ids(&tx, file_ids)?;
+
+    let ids = serde_json::to_string(file_ids)?;
+    let deleted = tx
+        .execute(
+            "DELETE FROM files WHERE id IN (SELECT value FROM json_each(?1))",
+            params![ids],
+        )
+        .context("Failed to bulk delete files")?;
+
+    tx.commit().context("Failed to commit bulk delete")?;
+    Ok(deleted)
+}
+
+/// Remove a file (and its symbols/dependencies) from the index by path
+///
+/// Returns `false` if the path was not indexed.
+pub fn delete_file_by_path(conn: &Connection, path: &str) -> Result<bool> {
+    match get_file_by_path(conn, path)?.and_then(|file| file.id) {
+        Some(file_id) => Ok(bulk_delete_files(conn, &[file_id])? > 0),
+        None => Ok(false),
+    }
+}
+
+/// Shift symbols starting after `after_line` in a file by `line_delta` lines.
+///
+/// Returns the number of symbols updated.
+pub fn relocate_symbols_after_line(
+    conn: &Connection,
+    file_id: i64,
+    after_line: usize,
+    line_delta: i64,
+) -> Result<usize> {
+    let updated = conn
+        .execute(
+            r#"
+            UPDATE symbols
+            SET line_start = MAX(line_start + ?1, 0),
+                line_end = MAX(line_end + ?1, 0)
+            WHERE file_id = ?2 AND line_start > ?3
+            "#,
+            params![line_delta, file_id, after_line],
+        )
+        .context("Failed to relocate symbols")?;
+    Ok(updated)
 }
 
 /// Get file by path
 pub fn get_file_by_path(conn: &Connection, path: &str) -> Result<Option<FileMetadata>> {
     let mut stmt = conn
-        .prepare("SELECT id, path, language, size, last_indexed, parse_errors FROM files WHERE path = ?1")
+        .prepare(
+            "SELECT id, path, language, size, last_indexed, parse_errors, index_duration_ms, metadata
+             FROM files WHERE path = ?1",
+        )
         .context("Failed to prepare statement")?;
 
     let mut rows = stmt
@@ -183,15 +909,45 @@ pub fn get_file_by_path(conn: &Connection, path: &str) -> Result<Option<FileMeta
             size: row.get(3)?,
             last_indexed: row.get(4)?,
             parse_errors: row.get(5)?,
+            index_duration_ms: row.get(6)?,
+            metadata: row.get(7)?,
         }))
     } else {
         Ok(None)
     }
 }
 
+/// Get symbol by id
+///
+/// Returns `None` when no symbol has this id.
+pub fn get_symbol_by_id(conn: &Connection, id: i64) -> Result<Option<Symbol>> {
+    conn.query_row(
+        "SELECT id, file_id, name, kind, line_start, line_end, scope, metadata, column_start, column_end
+         FROM symbols WHERE id = ?1",
+        params![id],
+        |row| {
+            Ok(Symbol {
+                id: Some(row.get(0)?),
+                file_id: row.get(1)?,
+                name: row.get(2)?,
+                kind: parse_symbol_kind(&row.get::<_, String>(3)?),
+                line_start: row.get(4)?,
+                line_end: row.get(5)?,
+                column_start: row.get(8)?,
+                column_end: row.get(9)?,
+                scope: row.get(6)?,
+                metadata: row.get(7)?,
+            })
+        },
+    )
+    .optional()
+    .context("Failed to query symbol")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::DateTime;
     use tempfile::NamedTempFile;
 
     #[test]
@@ -218,6 +974,8 @@ mod tests {
             size: 1024,
             last_indexed: None,
             parse_errors: 0,
+            index_duration_ms: None,
+            metadata: None,
         };
 
         let file_id = upsert_file(&conn, &file).unwrap();
@@ -229,6 +987,74 @@ mod tests {
         assert_eq!(retrieved.unwrap().path, "test.py");
     }
 
+    #[test]
+    fn test_upsert_file_idempotency() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let file1 = FileMetadata {
+            id: None,
+            path: "a.py".to_string(),
+            language: "python".to_string(),
+            size: 100,
+            last_indexed: None,
+            parse_errors: 0,
+            index_duration_ms: None,
+            metadata: None,
+        };
+        let file2 = FileMetadata {
+            path: "b.py".to_string(),
+            ..file1.clone()
+        };
+
+        let first_id = upsert_file(&conn, &file1).unwrap();
+        // Another insert in between must not leak into the conflict path's return value
+        let other_id = upsert_file(&conn, &file2).unwrap();
+        let second_id = upsert_file(&conn, &file1).unwrap();
+
+        assert_eq!(first_id, second_id);
+        assert_ne!(first_id, other_id);
+        assert_eq!(get_file_by_path(&conn, "a.py").unwrap().unwrap().id, Some(first_id));
+        assert_eq!(get_file_by_path(&conn, "b.py").unwrap().unwrap().id, Some(other_id));
+
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM files", [], |row| row.get(0)).unwrap();
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn test_upsert_file_updates_size() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let mut file = FileMetadata {
+            id: None,
+            path: "test.py".to_string(),
+            language: "python".to_string(),
+            size: 1024,
+            last_indexed: None,
+            parse_errors: 0,
+            index_duration_ms: None,
+            metadata: None,
+        };
+        let file_id = upsert_file(&conn, &file).unwrap();
+
+        // Backdate (in SQLite's legacy format) so the second upsert is strictly newer
+        conn.execute(
+            "UPDATE files SET last_indexed = '2000-01-01 00:00:00' WHERE id = ?1",
+            params![file_id],
+        )
+        .unwrap();
+
+        file.size = 4096;
+        upsert_file(&conn, &file).unwrap();
+
+        let retrieved = get_file_by_path(&conn, "test.py").unwrap().unwrap();
+        assert_eq!(retrieved.id, Some(file_id));
+        assert_eq!(retrieved.size, 4096);
+        let backdated: DateTime<Utc> = "2000-01-01T00:00:00Z".parse().unwrap();
+        assert!(retrieved.last_indexed.unwrap() > backdated);
+    }
+
     #[test]
     fn test_symbol_insert() {
         let temp_file = NamedTempFile::new().unwrap();
@@ -241,6 +1067,8 @@ mod tests {
             size: 1024,
             last_indexed: None,
             parse_errors: 0,
+            index_duration_ms: None,
+            metadata: None,
         };
 
         let file_id = upsert_file(&conn, &file).unwrap();
@@ -252,6 +1080,8 @@ mod tests {
             kind: crate::SymbolKind::Function,
             line_start: 10,
             line_end: 20,
+            column_start: 0,
+            column_end: 0,
             scope: None,
             metadata: None,
         };
@@ -259,4 +1089,873 @@ mod tests {
         let symbol_id = insert_symbol(&conn, &symbol).unwrap();
         assert!(symbol_id > 0);
     }
+
+    #[test]
+    fn test_get_or_create_file_is_idempotent() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let (first_id, created) = get_or_create_file(&conn, "test.py", "python", 1024).unwrap();
+        assert!(first_id > 0);
+        assert!(created);
+
+        let (second_id, created) = get_or_create_file(&conn, "test.py", "python", 2048).unwrap();
+        assert_eq!(first_id, second_id);
+        assert!(!created);
+
+        // Existing row is left untouched
+        let retrieved = get_file_by_path(&conn, "test.py").unwrap().unwrap();
+        assert_eq!(retrieved.size, 1024);
+    }
+
+    #[test]
+    fn test_file_content_hash() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        assert_eq!(get_file_content_hash(&conn, "a.py").unwrap(), None);
+        let (_, created) = get_or_create_file(&conn, "a.py", "python", 1).unwrap();
+        assert!(created);
+        assert_eq!(get_file_content_hash(&conn, "a.py").unwrap(), None);
+
+        set_file_content_hash(&conn, "a.py", &[7; 32]).unwrap();
+        assert_eq!(get_file_content_hash(&conn, "a.py").unwrap(), Some([7; 32]));
+
+        // Re-upserting the file invalidates the recorded hash
+        let file = get_file_by_path(&conn, "a.py").unwrap().unwrap();
+        upsert_file(&conn, &file).unwrap();
+        assert_eq!(get_file_content_hash(&conn, "a.py").unwrap(), None);
+    }
+
+    #[test]
+    fn test_replace_parse_errors() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+        let (file_id, _) = get_or_create_file(&conn, "a.py", "python", 1).unwrap();
+        let count_rows = || -> i64 {
+            conn.query_row("SELECT COUNT(*) FROM parse_errors", [], |row| row.get(0)).unwrap()
+        };
+
+        let errors = vec![ParseError::new(1, 4, "missing `)`"), ParseError::new(3, 0, "unexpected `@`")];
+        assert_eq!(replace_parse_errors(&conn, file_id, &errors).unwrap(), 2);
+        assert_eq!(get_file_by_path(&conn, "a.py").unwrap().unwrap().parse_errors, 2);
+        assert_eq!(count_rows(), 2);
+
+        // Re-indexing replaces the previous errors
+        replace_parse_errors(&conn, file_id, &errors[..1]).unwrap();
+        assert_eq!(get_file_by_path(&conn, "a.py").unwrap().unwrap().parse_errors, 1);
+        assert_eq!(count_rows(), 1);
+
+        // Errors are removed with their file
+        delete_file_by_path(&conn, "a.py").unwrap();
+        assert_eq!(count_rows(), 0);
+    }
+
+    #[test]
+    fn test_get_or_create_file_matches_upsert_id() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let file = FileMetadata {
+            id: None,
+            path: "lib.rs".to_string(),
+            language: "rust".to_string(),
+            size: 10,
+            last_indexed: None,
+            parse_errors: 0,
+            index_duration_ms: None,
+            metadata: None,
+        };
+        let upserted_id = upsert_file(&conn, &file).unwrap();
+
+        let (file_id, created) = get_or_create_file(&conn, "lib.rs", "rust", 10).unwrap();
+        assert_eq!(file_id, upserted_id);
+        assert!(!created);
+
+        let (other_id, created) = get_or_create_file(&conn, "main.rs", "rust", 10).unwrap();
+        assert_ne!(other_id, file_id);
+        assert!(created);
+    }
+
+    #[test]
+    fn test_relocate_symbols_after_line() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let (file_id, _) = get_or_create_file(&conn, "test.py", "python", 1024).unwrap();
+        for (name, line) in [("above", 2), ("below", 10), ("further", 20)] {
+            let symbol = Symbol {
+                id: None,
+                file_id,
+                name: name.to_string(),
+                kind: crate::SymbolKind::Function,
+                line_start: line,
+                line_end: line + 3,
+                column_start: 0,
+                column_end: 0,
+                scope: None,
+                metadata: None,
+            };
+            insert_symbol(&conn, &symbol).unwrap();
+        }
+
+        let updated = relocate_symbols_after_line(&conn, file_id, 5, 4).unwrap();
+        assert_eq!(updated, 2);
+
+        let lines = |name: &str| -> (i64, i64) {
+            conn.query_row(
+                "SELECT line_start, line_end FROM symbols WHERE name = ?1",
+                params![name],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap()
+        };
+        assert_eq!(lines("above"), (2, 5));
+        assert_eq!(lines("below"), (14, 17));
+        assert_eq!(lines("further"), (24, 27));
+
+        // Deleting lines moves symbols back up
+        let updated = relocate_symbols_after_line(&conn, file_id, 5, -4).unwrap();
+        assert_eq!(updated, 2);
+        assert_eq!(lines("below"), (10, 13));
+    }
+
+    #[test]
+    fn test_index_size_grows_after_bulk_insert() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let initial = get_index_size_bytes(&conn).unwrap();
+        assert!(initial > 0);
+        let initial_symbols = get_table_size_breakdown(&conn).unwrap()["symbols"];
+        assert!(initial_symbols > 0);
+
+        let (file_id, _) = get_or_create_file(&conn, "bulk.py", "python", 10).unwrap();
+        let symbols: Vec<Symbol> = (0..2000)
+            .map(|i| Symbol {
+                id: None,
+                file_id,
+                name: format!("function_with_a_long_name_{}", i),
+                kind: crate::SymbolKind::Function,
+                line_start: i,
+                line_end: i + 1,
+                column_start: 0,
+                column_end: 0,
+                scope: None,
+                metadata: None,
+            })
+            .collect();
+        insert_symbols_batch(&conn, &symbols).unwrap();
+
+        assert!(get_index_size_bytes(&conn).unwrap() > initial);
+        let breakdown = get_table_size_breakdown(&conn).unwrap();
+        assert!(breakdown["symbols"] > initial_symbols);
+        assert!(breakdown.contains_key("files"));
+        assert!(breakdown.contains_key("idx_symbols_name"));
+    }
+
+    #[test]
+    fn test_checkpoint_wal_truncate() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let (file_id, _) = get_or_create_file(&conn, "test.py", "python", 1024).unwrap();
+        for i in 0..500 {
+            let symbol = Symbol {
+                id: None,
+                file_id,
+                name: format!("symbol_{}", i),
+                kind: crate::SymbolKind::Function,
+                line_start: i,
+                line_end: i,
+                column_start: 0,
+                column_end: 0,
+                scope: None,
+                metadata: None,
+            };
+            insert_symbol(&conn, &symbol).unwrap();
+        }
+
+        let wal_path = format!("{}-wal", temp_file.path().display());
+        let wal_size = || std::fs::metadata(&wal_path).map(|m| m.len()).unwrap_or(0);
+        let before = wal_size();
+        assert!(before > 0);
+
+        let stats = checkpoint_wal(&conn, WalCheckpointMode::Truncate).unwrap();
+        assert_eq!(stats.pages_in_wal, stats.checkpointed_pages);
+        assert!(wal_size() < before);
+    }
+
+    #[test]
+    fn test_wal_checkpoint_mode_from_str() {
+        assert_eq!("passive".parse::<WalCheckpointMode>().unwrap(), WalCheckpointMode::Passive);
+        assert_eq!("TRUNCATE".parse::<WalCheckpointMode>().unwrap(), WalCheckpointMode::Truncate);
+        assert!("sometimes".parse::<WalCheckpointMode>().is_err());
+    }
+
+    #[test]
+    fn test_bulk_delete_files_removes_symbols() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let mut file_ids = Vec::new();
+        for i in 0..5 {
+            let (file_id, _) =
+                get_or_create_file(&conn, &format!("file{}.py", i), "python", 10).unwrap();
+            for line in 0..3 {
+                let symbol = Symbol {
+                    id: None,
+                    file_id,
+                    name: format!("f{}", line),
+                    kind: crate::SymbolKind::Function,
+                    line_start: line,
+                    line_end: line,
+                    column_start: 0,
+                    column_end: 0,
+                    scope: None,
+                    metadata: None,
+                };
+                insert_symbol(&conn, &symbol).unwrap();
+            }
+            file_ids.push(file_id);
+        }
+
+        let deleted = bulk_delete_files(&conn, &file_ids[..3]).unwrap();
+        assert_eq!(deleted, 3);
+
+        let count = |sql: &str| -> i64 { conn.query_row(sql, [], |row| row.get(0)).unwrap() };
+        assert_eq!(count("SELECT COUNT(*) FROM files"), 2);
+        assert_eq!(count("SELECT COUNT(*) FROM symbols"), 6);
+        assert!(get_file_by_path(&conn, "file0.py").unwrap().is_none());
+        assert!(get_file_by_path(&conn, "file4.py").unwrap().is_some());
+
+        assert_eq!(bulk_delete_files(&conn, &[]).unwrap(), 0);
+        assert_eq!(bulk_delete_symbols_by_file_ids(&conn, &[]).unwrap(), 0);
+
+        // A failing file delete rolls back the symbol delete too
+        conn.execute_batch(
+            "CREATE TRIGGER block_file_delete BEFORE DELETE ON files BEGIN SELECT RAISE(ABORT, 'blocked'); END;",
+        )
+        .unwrap();
+        assert!(bulk_delete_files(&conn, &file_ids[3..]).is_err());
+        assert_eq!(count("SELECT COUNT(*) FROM files"), 2);
+        assert_eq!(count("SELECT COUNT(*) FROM symbols"), 6);
+    }
+
+    #[test]
+    fn test_bulk_delete_symbols_by_file_ids() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let (a, _) = get_or_create_file(&conn, "a.py", "python", 10).unwrap();
+        let (b, _) = get_or_create_file(&conn, "b.py", "python", 10).unwrap();
+        for file_id in [a, b] {
+            let symbol = Symbol {
+                id: None,
+                file_id,
+                name: "main".to_string(),
+                kind: crate::SymbolKind::Function,
+                line_start: 0,
+                line_end: 1,
+                column_start: 0,
+                column_end: 0,
+                scope: None,
+                metadata: None,
+            };
+            insert_symbol(&conn, &symbol).unwrap();
+        }
+
+        assert_eq!(bulk_delete_symbols_by_file_ids(&conn, &[a]).unwrap(), 1);
+        // Files themselves are kept
+        assert!(get_file_by_path(&conn, "a.py").unwrap().is_some());
+    }
+
+    #[test]
+    fn test_insert_symbols_batch() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let (file_id, _) = get_or_create_file(&conn, "test.py", "python", 1024).unwrap();
+        let symbols: Vec<Symbol> = (0..50)
+            .map(|i| Symbol {
+                id: None,
+                file_id,
+                name: format!("func_{}", i),
+                kind: crate::SymbolKind::Function,
+                line_start: i,
+                line_end: i + 1,
+                column_start: 0,
+                column_end: 0,
+                scope: None,
+                metadata: None,
+            })
+            .collect();
+
+        assert_eq!(insert_symbols_batch(&conn, &symbols).unwrap(), 50);
+
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM symbols", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 50);
+
+        // Conflicting rows are updated but only new rows are counted
+        let mut again = symbols[40..].to_vec();
+        again[0].metadata = Some(r#"{"async":true}"#.to_string());
+        again.push(Symbol { name: "func_50".to_string(), line_start: 50, ..symbols[0].clone() });
+        assert_eq!(insert_symbols_batch(&conn, &again).unwrap(), 1);
+        let updated = &crate::query::find_symbols_by_name(&conn, "func_40").unwrap()[0];
+        assert_eq!(updated.metadata.as_deref(), Some(r#"{"async":true}"#));
+    }
+
+    #[test]
+    fn test_insert_symbols_batch_rolls_back_on_error() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let (file_id, _) = get_or_create_file(&conn, "test.py", "python", 1024).unwrap();
+        let symbol = Symbol {
+            id: None,
+            file_id,
+            name: "ok".to_string(),
+            kind: crate::SymbolKind::Function,
+            line_start: 0,
+            line_end: 1,
+            column_start: 0,
+            column_end: 0,
+            scope: None,
+            metadata: None,
+        };
+        let orphan = Symbol { file_id: file_id + 1, ..symbol.clone() };
+
+        assert!(insert_symbols_batch(&conn, &[symbol, orphan]).is_err());
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM symbols", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 0);
+        assert!(conn.is_autocommit());
+    }
+
+    #[test]
+    fn test_get_symbol_by_id() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let (file_id, _) = get_or_create_file(&conn, "nav.py", "python", 10).unwrap();
+        let symbol = Symbol {
+            id: None,
+            file_id,
+            name: "jump".to_string(),
+            kind: crate::SymbolKind::Function,
+            line_start: 4,
+            line_end: 9,
+            column_start: 0,
+            column_end: 12,
+            scope: Some("Editor".to_string()),
+            metadata: Some(r#"{"async":true}"#.to_string()),
+        };
+        let id = insert_symbol(&conn, &symbol).unwrap();
+
+        assert_eq!(get_symbol_by_id(&conn, id).unwrap(), Some(Symbol { id: Some(id), ..symbol }));
+        assert_eq!(get_symbol_by_id(&conn, id + 1).unwrap(), None);
+        assert!(crate::query::get_symbol_by_id(&conn, id + 1).is_err());
+    }
+
+    #[test]
+    fn test_delete_file_by_path() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let (file_id, _) = get_or_create_file(&conn, "gone.py", "python", 10).unwrap();
+        let symbol = Symbol {
+            id: None,
+            file_id,
+            name: "f".to_string(),
+            kind: crate::SymbolKind::Function,
+            line_start: 0,
+            line_end: 0,
+            column_start: 0,
+            column_end: 0,
+            scope: None,
+            metadata: None,
+        };
+        insert_symbol(&conn, &symbol).unwrap();
+
+        assert!(delete_file_by_path(&conn, "gone.py").unwrap());
+        assert!(!delete_file_by_path(&conn, "gone.py").unwrap());
+
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM symbols", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn test_insert_symbol_orphaned_file() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let symbol = Symbol {
+            id: None,
+            file_id: 999,
+            name: "orphan".to_string(),
+            kind: crate::SymbolKind::Function,
+            line_start: 0,
+            line_end: 0,
+            column_start: 0,
+            column_end: 0,
+            scope: None,
+            metadata: None,
+        };
+
+        let err = insert_symbol(&conn, &symbol).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<SymbolInsertError>(),
+            Some(SymbolInsertError::OrphanedFile { file_id: 999, .. })
+        ));
+        assert!(format!("{:#}", err).contains("FOREIGN KEY constraint failed"));
+
+        // Enforcement can be switched off per connection
+        set_pragma_foreign_keys(&conn, false).unwrap();
+        assert!(insert_symbol(&conn, &symbol).is_ok());
+    }
+
+    #[test]
+    fn test_insert_symbol_validated() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+        let (file_id, _) = get_or_create_file(&conn, "main.py", "python", 10).unwrap();
+
+        let symbol = Symbol {
+            id: None,
+            file_id,
+            name: "main".to_string(),
+            kind: crate::SymbolKind::Function,
+            line_start: 4,
+            line_end: 8,
+            column_start: 0,
+            column_end: 0,
+            scope: None,
+            metadata: None,
+        };
+        assert!(insert_symbol_validated(&conn, &symbol).is_ok());
+
+        let invalid = [
+            (Symbol { name: String::new(), ..symbol.clone() }, crate::ValidationErrorKind::EmptyName),
+            (Symbol { line_end: 2, ..symbol.clone() }, crate::ValidationErrorKind::InvalidLineRange),
+            (
+                Symbol { metadata: Some("[1,".to_string()), ..symbol.clone() },
+                crate::ValidationErrorKind::InvalidMetadata,
+            ),
+        ];
+        for (symbol, kind) in invalid {
+            let err = insert_symbol_validated(&conn, &symbol).unwrap_err();
+            assert_eq!(err.downcast_ref::<crate::SymbolValidationError>().unwrap().kind, kind);
+        }
+
+        // Rejected symbols never reach the database
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM symbols", [], |row| row.get(0)).unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_delete_symbols_in_range() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let (file_id, _) = get_or_create_file(&conn, "large.py", "python", 10).unwrap();
+        let (other_id, _) = get_or_create_file(&conn, "other.py", "python", 10).unwrap();
+        for (id, name, line_start, line_end) in [
+            (file_id, "before", 0, 9),
+            (file_id, "touches", 9, 10),
+            (file_id, "inside", 12, 14),
+            (file_id, "after", 21, 30),
+            (other_id, "inside", 12, 14),
+        ] {
+            let symbol = Symbol {
+                id: None,
+                file_id: id,
+                name: name.to_string(),
+                kind: crate::SymbolKind::Function,
+                line_start,
+                line_end,
+                column_start: 0,
+                column_end: 0,
+                scope: None,
+                metadata: None,
+            };
+            insert_symbol(&conn, &symbol).unwrap();
+        }
+
+        assert_eq!(delete_symbols_in_range(&conn, file_id, 10, 20).unwrap(), 2);
+        assert_eq!(delete_symbols_in_range(&conn, file_id, 10, 20).unwrap(), 0);
+
+        let mut stmt = conn.prepare("SELECT name FROM symbols ORDER BY file_id, line_start").unwrap();
+        let names: Vec<String> = stmt.query_map([], |row| row.get(0)).unwrap().map(|r| r.unwrap()).collect();
+        assert_eq!(names, vec!["before", "after", "inside"]);
+    }
+
+    #[test]
+    fn test_compact_metadata_reduces_size() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let (file_id, _) = get_or_create_file(&conn, "lib.rs", "rust", 10).unwrap();
+        let metadata = [
+            Some(r#"{"async":false,"test":false,"return_impl_trait":null}"#),
+            Some(r#"{"async":true,"test":false,"bounds":[],"extra":{}}"#),
+            Some(r#"{"type":"int","count":0,"name":""}"#),
+            Some("not json"),
+            Some("[false]"),
+            None,
+        ];
+        for (line, metadata) in metadata.into_iter().enumerate() {
+            let symbol = Symbol {
+                id: None,
+                file_id,
+                name: format!("symbol_{}", line),
+                kind: crate::SymbolKind::Function,
+                line_start: line,
+                line_end: line,
+                column_start: 0,
+                column_end: 0,
+                scope: None,
+                metadata: metadata.map(str::to_string),
+            };
+            insert_symbol(&conn, &symbol).unwrap();
+        }
+
+        let total_size = |conn: &Connection| -> u64 {
+            conn.query_row("SELECT COALESCE(SUM(length(metadata)), 0) FROM symbols", [], |row| row.get(0))
+                .unwrap()
+        };
+        let before = total_size(&conn);
+
+        let saved = compact_metadata(&conn).unwrap();
+        assert!(saved > 0);
+        assert_eq!(total_size(&conn), before - saved);
+
+        let stored = |name: &str| -> Option<String> {
+            conn.query_row("SELECT metadata FROM symbols WHERE name = ?1", [name], |row| row.get(0))
+                .unwrap()
+        };
+        assert_eq!(stored("symbol_0"), None);
+        assert_eq!(stored("symbol_1").as_deref(), Some(r#"{"async":true}"#));
+        // Zero and empty strings are real values
+        assert_eq!(stored("symbol_2").as_deref(), Some(r#"{"type":"int","count":0,"name":""}"#));
+        assert_eq!(stored("symbol_3").as_deref(), Some("not json"));
+        assert_eq!(stored("symbol_4").as_deref(), Some("[false]"));
+
+        // Already compact
+        assert_eq!(compact_metadata(&conn).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_validate_metadata_json() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let (file_id, _) = get_or_create_file(&conn, "app.py", "python", 10).unwrap();
+        let mut ids = Vec::new();
+        for (line, metadata) in [Some(r#"{"ok":true}"#), Some("{broken"), None, Some("")].into_iter().enumerate() {
+            let symbol = Symbol {
+                id: None,
+                file_id,
+                name: format!("symbol_{}", line),
+                kind: crate::SymbolKind::Function,
+                line_start: line,
+                line_end: line,
+                column_start: 0,
+                column_end: 0,
+                scope: None,
+                metadata: metadata.map(str::to_string),
+            };
+            ids.push(insert_symbol(&conn, &symbol).unwrap());
+        }
+
+        assert_eq!(validate_metadata_json(&conn).unwrap(), vec![ids[1], ids[3]]);
+    }
+
+    #[test]
+    fn test_delete_file_cascades_to_symbols() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+
+        let (file_id, _) = get_or_create_file(&conn, "cascade.py", "python", 10).unwrap();
+        let symbol = Symbol {
+            id: None,
+            file_id,
+            name: "f".to_string(),
+            kind: crate::SymbolKind::Function,
+            line_start: 0,
+            line_end: 0,
+            column_start: 0,
+            column_end: 0,
+            scope: None,
+            metadata: None,
+        };
+        insert_symbol(&conn, &symbol).unwrap();
+        conn.execute(
+            "INSERT INTO dependencies (file_id, import_path) VALUES (?1, 'os')",
+            params![file_id],
+        )
+        .unwrap();
+
+        conn.execute("DELETE FROM files WHERE id = ?1", params![file_id]).unwrap();
+
+        let symbols: i64 = conn
+            .query_row("SELECT COUNT(*) FROM symbols", [], |row| row.get(0))
+            .unwrap();
+        let dependencies: i64 = conn
+            .query_row("SELECT COUNT(*) FROM dependencies", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(symbols, 0);
+        assert_eq!(dependencies, 0);
+    }
+
+    #[test]
+    fn test_migration_adds_index_duration_column() {
+        let temp_file = NamedTempFile::new().unwrap();
+
+        // Version 1 database without `index_duration_ms`
+        {
+            let conn = Connection::open(temp_file.path()).unwrap();
+            conn.execute_batch(
+                r#"
+                CREATE TABLE files (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    path TEXT UNIQUE NOT NULL,
+                    language TEXT NOT NULL,
+                    size INTEGER NOT NULL,
+                    last_indexed TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                    parse_errors INTEGER DEFAULT 0
+                );
+                INSERT INTO files (path, language, size) VALUES ('old.py', 'python', 10);
+                PRAGMA user_version = 1;
+                "#,
+            )
+            .unwrap();
+        }
+
+        let conn = init_schema(temp_file.path()).unwrap();
+        assert!(table_has_column(&conn, "files", "index_duration_ms").unwrap());
+
+        let old = get_file_by_path(&conn, "old.py").unwrap().unwrap();
+        assert_eq!(old.index_duration_ms, None);
+
+        upsert_file(&conn, &FileMetadata { index_duration_ms: Some(12), ..old }).unwrap();
+        assert_eq!(get_file_by_path(&conn, "old.py").unwrap().unwrap().index_duration_ms, Some(12));
+
+        // Reopening does not try to add the column again
+        drop(conn);
+        init_schema(temp_file.path()).unwrap();
+    }
+
+    #[test]
+    fn test_migration_backfills_normalized_names() {
+        let temp_file = NamedTempFile::new().unwrap();
+
+        // Version 2 database whose symbols predate `normalized_name`
+        {
+            let conn = Connection::open(temp_file.path()).unwrap();
+            conn.execute_batch(
+                r#"
+                CREATE TABLE files (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    path TEXT UNIQUE NOT NULL,
+                    language TEXT NOT NULL,
+                    size INTEGER NOT NULL,
+                    last_indexed TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                    parse_errors INTEGER DEFAULT 0,
+                    index_duration_ms INTEGER
+                );
+                CREATE TABLE symbols (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    file_id INTEGER NOT NULL REFERENCES files(id) ON DELETE CASCADE,
+                    name TEXT NOT NULL,
+                    kind TEXT NOT NULL,
+                    line_start INTEGER NOT NULL,
+                    line_end INTEGER NOT NULL,
+                    scope TEXT,
+                    metadata TEXT,
+                    UNIQUE(file_id, name, line_start)
+                );
+                INSERT INTO files (path, language, size) VALUES ('lib.rs', 'rust', 10);
+                INSERT INTO symbols (file_id, name, kind, line_start, line_end) VALUES (1, 'r#MyType', 'struct', 0, 0);
+                PRAGMA user_version = 2;
+                "#,
+            )
+            .unwrap();
+        }
+
+        let conn = init_schema(temp_file.path()).unwrap();
+        let normalized: String = conn
+            .query_row("SELECT normalized_name FROM symbols WHERE name = 'r#MyType'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(normalized, "my_type");
+
+        let plan: String = conn
+            .query_row(
+                "EXPLAIN QUERY PLAN SELECT * FROM symbols WHERE normalized_name = 'my_type'",
+                [],
+                |row| row.get(3),
+            )
+            .unwrap();
+        assert!(plan.contains("idx_symbols_normalized_name"), "{}", plan);
+    }
+
+    #[test]
+    fn test_migration_adds_symbol_columns() {
+        let temp_file = NamedTempFile::new().unwrap();
+
+        // Version 4 database whose symbols have no column offsets
+        {
+            let conn = init_schema(temp_file.path()).unwrap();
+            conn.execute_batch(
+                r#"
+                DROP TABLE symbols;
+                CREATE TABLE symbols (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    file_id INTEGER NOT NULL REFERENCES files(id) ON DELETE CASCADE,
+                    name TEXT NOT NULL,
+                    kind TEXT NOT NULL,
+                    line_start INTEGER NOT NULL,
+                    line_end INTEGER NOT NULL,
+                    scope TEXT,
+                    metadata TEXT,
+                    normalized_name TEXT,
+                    UNIQUE(file_id, name, line_start)
+                );
+                INSERT INTO files (path, language, size) VALUES ('old.py', 'python', 10);
+                INSERT INTO symbols (file_id, name, kind, line_start, line_end) VALUES (1, 'old', 'function', 0, 1);
+                PRAGMA user_version = 4;
+                "#,
+            )
+            .unwrap();
+        }
+
+        let conn = init_schema(temp_file.path()).unwrap();
+        assert!(table_has_column(&conn, "symbols", "column_start").unwrap());
+        assert!(table_has_column(&conn, "symbols", "column_end").unwrap());
+
+        let old = &crate::query::find_symbols_by_name(&conn, "old").unwrap()[0];
+        assert_eq!((old.column_start, old.column_end), (0, 0));
+
+        // New symbols keep their offsets, including on conflict updates
+        let method = Symbol {
+            id: None,
+            file_id: old.file_id,
+            name: "method".to_string(),
+            kind: crate::SymbolKind::Function,
+            line_start: 3,
+            line_end: 4,
+            column_start: 4,
+            column_end: 17,
+            scope: Some("Old".to_string()),
+            metadata: None,
+        };
+        insert_symbol(&conn, &method).unwrap();
+        insert_symbols_batch(&conn, &[Symbol { column_end: 21, ..method }]).unwrap();
+        let stored = &crate::query::find_symbols_by_name(&conn, "method").unwrap()[0];
+        assert_eq!((stored.column_start, stored.column_end), (4, 21));
+
+        drop(conn);
+        init_schema(temp_file.path()).unwrap();
+    }
+
+    #[test]
+    fn test_migrate_to_fts5_existing_symbols() {
+        let temp_file = NamedTempFile::new().unwrap();
+
+        // Database created before FTS5 support
+        {
+            let conn = init_schema(temp_file.path()).unwrap();
+            conn.execute_batch(
+                r#"
+                DROP TRIGGER symbols_fts_insert;
+                DROP TRIGGER symbols_fts_delete;
+                DROP TRIGGER symbols_fts_update;
+                DROP TABLE symbol_fts;
+                PRAGMA user_version = 0;
+                "#,
+            )
+            .unwrap();
+            assert!(!schema_has_fts5(&conn));
+
+            let (file_id, _) = get_or_create_file(&conn, "legacy.py", "python", 10).unwrap();
+            for (line, name) in ["parse_config", "render_page"].into_iter().enumerate() {
+                let symbol = Symbol {
+                    id: None,
+                    file_id,
+                    name: name.to_string(),
+                    kind: crate::SymbolKind::Function,
+                    line_start: line,
+                    line_end: line,
+                    column_start: 0,
+                    column_end: 0,
+                    scope: None,
+                    metadata: Some(r#"{"doc":"Load settings from disk"}"#.to_string()),
+                };
+                insert_symbol(&conn, &symbol).unwrap();
+            }
+
+            assert_eq!(migrate_to_fts5(&conn).unwrap(), 2);
+            assert!(schema_has_fts5(&conn));
+
+            let name: String = conn
+                .query_row(
+                    "SELECT s.name FROM symbol_fts JOIN symbols s ON s.id = symbol_fts.rowid
+                     WHERE symbol_fts MATCH 'settings' ORDER BY s.line_start LIMIT 1",
+                    [],
+                    |row| row.get(0),
+                )
+                .unwrap();
+            assert_eq!(name, "parse_config");
+
+            // Re-running rebuilds rather than duplicating rows
+            assert_eq!(migrate_to_fts5(&conn).unwrap(), 2);
+        }
+
+        // Reopening records the schema version
+        let conn = init_schema(temp_file.path()).unwrap();
+        let version: i64 = conn
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .unwrap();
+        assert_eq!(version, SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_fts5_triggers_track_symbols() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let conn = init_schema(temp_file.path()).unwrap();
+        assert!(schema_has_fts5(&conn));
+
+        let (file_id, _) = get_or_create_file(&conn, "live.py", "python", 10).unwrap();
+        let symbol = Symbol {
+            id: None,
+            file_id,
+            name: "tokenize".to_string(),
+            kind: crate::SymbolKind::Function,
+            line_start: 0,
+            line_end: 0,
+            column_start: 0,
+            column_end: 0,
+            scope: None,
+            metadata: None,
+        };
+        insert_symbol(&conn, &symbol).unwrap();
+
+        let count = |conn: &Connection| -> i64 {
+            conn.query_row(
+                "SELECT COUNT(*) FROM symbol_fts WHERE symbol_fts MATCH 'tokenize'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap()
+        };
+        assert_eq!(count(&conn), 1);
+
+        // Cascaded deletes remove the FTS row too
+        bulk_delete_files(&conn, &[file_id]).unwrap();
+        assert_eq!(count(&conn), 0);
+    }
 }
@@ -0,0 +1,48 @@
+// Integration tests for one-shot directory analysis against this crate's own sources
+
+use analyzer_core::indexer::{analyze_directory, analyze_directory_parallel, IndexerConfig};
+use analyzer_core::registry::AnalyzerRegistry;
+use analyzer_core::SymbolKind;
+use std::path::{Path, PathBuf};
+
+fn registry() -> AnalyzerRegistry {
+    let mut registry = AnalyzerRegistry::new();
+    registry
+        .register("python", analyzer_python::analyze_python)
+        .register("typescript", analyzer_typescript::analyze_typescript)
+        .register(
+            "typescript_declaration",
+            analyzer_typescript::analyze_typescript_declaration,
+        )
+        .register("javascript", analyzer_typescript::analyze_typescript)
+        .register("rust", analyzer_rust::analyze_rust);
+    registry
+}
+
+fn crate_src() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("src")
+}
+
+#[test]
+fn test_analyze_directory_on_own_sources() {
+    let root = crate_src();
+    let results = analyze_directory(&root, &IndexerConfig::default(), &registry()).unwrap();
+
+    let lib = results.get(&root.join("lib.rs")).expect("lib.rs should be analyzed");
+    assert_eq!(lib.language, "rust");
+    assert!(lib.symbols.iter().any(|s| s.kind == SymbolKind::Function));
+}
+
+#[test]
+fn test_analyze_directory_parallel_matches_sequential() {
+    let root = crate_src();
+    let config = IndexerConfig::default();
+
+    let sequential = analyze_directory(&root, &config, &registry()).unwrap();
+    let parallel = analyze_directory_parallel(&root, &config, &registry()).unwrap();
+
+    assert_eq!(sequential.len(), parallel.len());
+    for (path, result) in &sequential {
+        assert_eq!(result.symbols, parallel[path].symbols);
+    }
+}
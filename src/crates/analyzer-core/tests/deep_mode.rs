@@ -0,0 +1,163 @@
+// Integration tests for Deep Mode cross-file analysis over two Python modules
+// and public API surface comparison between two versions of a crate
+
+#![cfg(feature = "deep-mode")]
+
+use analyzer_core::analysis::deep::{
+    compare_api_surfaces, load_cross_file_relations, store_cross_file_relations, ApiSurface, CrossFileRelation,
+    DeepMode, RelationKind, SymbolTable,
+};
+use analyzer_core::storage::init_schema;
+use analyzer_core::{Symbol, SymbolKind, SymbolLocation};
+use std::path::PathBuf;
+
+const MODELS: &str = r#"
+class Animal:
+    def speak(self):
+        pass
+
+def make_sound(animal):
+    return animal.speak()
+"#;
+
+const APP: &str = r#"
+from models import Animal, make_sound
+
+class Dog(Animal):
+    def speak(self):
+        return "woof"
+
+def main():
+    dog = Dog()
+    make_sound(dog)
+    return Animal
+"#;
+
+fn project() -> (Vec<(PathBuf, String)>, SymbolTable) {
+    let files = vec![
+        (PathBuf::from("models.py"), MODELS.to_string()),
+        (PathBuf::from("app.py"), APP.to_string()),
+    ];
+    let symbols = files
+        .iter()
+        .map(|(path, source)| (path.clone(), analyzer_python::analyze_python(source).unwrap().0))
+        .collect();
+    (files, symbols)
+}
+
+fn relation(source_symbol: &str, target_symbol: &str, relation_kind: RelationKind) -> CrossFileRelation {
+    CrossFileRelation {
+        source_file: "app.py".to_string(),
+        source_symbol: source_symbol.to_string(),
+        target_file: "models.py".to_string(),
+        target_symbol: target_symbol.to_string(),
+        relation_kind,
+    }
+}
+
+#[test]
+fn test_analyze_cross_file_relations() {
+    let (files, symbols) = project();
+    let mut deep = DeepMode::new();
+
+    let mut relations = deep.analyze_cross_file(&files, &symbols).unwrap();
+    relations.sort_by(|a, b| (&a.source_symbol, &a.target_symbol).cmp(&(&b.source_symbol, &b.target_symbol)));
+
+    assert_eq!(
+        relations,
+        vec![
+            relation("Dog", "Animal", RelationKind::Inherits),
+            relation("main", "Animal", RelationKind::Uses),
+            relation("main", "make_sound", RelationKind::Calls),
+        ]
+    );
+    assert_eq!(deep.get_audit_trail().len(), 1);
+}
+
+#[test]
+fn test_store_cross_file_relations() {
+    let (files, symbols) = project();
+    let relations = DeepMode::new().analyze_cross_file(&files, &symbols).unwrap();
+
+    let db = tempfile::NamedTempFile::new().unwrap();
+    let conn = init_schema(db.path()).unwrap();
+    assert_eq!(store_cross_file_relations(&conn, &relations).unwrap(), 3);
+
+    // Storing again replaces the previous results for the same source files
+    assert_eq!(store_cross_file_relations(&conn, &relations).unwrap(), 3);
+
+    let mut stored = load_cross_file_relations(&conn).unwrap();
+    let mut expected = relations.clone();
+    stored.sort_by(|a, b| (&a.source_symbol, &a.target_symbol).cmp(&(&b.source_symbol, &b.target_symbol)));
+    expected.sort_by(|a, b| (&a.source_symbol, &a.target_symbol).cmp(&(&b.source_symbol, &b.target_symbol)));
+    assert_eq!(stored, expected);
+}
+
+fn api_symbol(name: &str, kind: SymbolKind, scope: Option<&str>, metadata: &str) -> Symbol {
+    Symbol {
+        metadata: Some(metadata.to_string()),
+        ..Symbol::with_location(name, kind, SymbolLocation::default(), scope)
+    }
+}
+
+fn crate_v1() -> Vec<Symbol> {
+    vec![
+        api_symbol("Config", SymbolKind::Struct, None, r#"{"visibility":"pub"}"#),
+        api_symbol("parse", SymbolKind::Function, None, r#"{"visibility":"pub","doc":"Parse input","params":[{"name":"input","type":"&str"}]}"#),
+        api_symbol("legacy", SymbolKind::Function, None, r#"{"visibility":"pub"}"#),
+        api_symbol("Mode", SymbolKind::Struct, None, r#"{"visibility":"pub"}"#),
+        api_symbol("__private", SymbolKind::Function, None, r#"{"visibility":"pub","doc_hidden":true}"#),
+        api_symbol("helper", SymbolKind::Function, None, r#"{"visibility":"pub(crate)"}"#),
+        api_symbol("check_parse", SymbolKind::Function, None, r#"{"visibility":"pub","test":true}"#),
+        api_symbol("new", SymbolKind::Function, Some("Config"), r#"{"visibility":"pub"}"#),
+    ]
+}
+
+fn crate_v2() -> Vec<Symbol> {
+    vec![
+        api_symbol("Config", SymbolKind::Struct, None, r#"{"visibility":"pub"}"#),
+        api_symbol("parse", SymbolKind::Function, None, r#"{"visibility":"pub","doc":"Parse input","params":[{"name":"input","type":"&[u8]"}]}"#),
+        api_symbol("Mode", SymbolKind::Enum, None, r#"{"visibility":"pub"}"#),
+        api_symbol("parse_file", SymbolKind::Function, None, r#"{"visibility":"pub"}"#),
+    ]
+}
+
+#[test]
+fn test_detect_api_surface() {
+    let mut deep = DeepMode::new();
+    let surface = deep.detect_api_surface(&crate_v1());
+
+    let names: Vec<&str> = surface.iter().map(|s| s.name.as_str()).collect();
+    // Types first, then the documented function; hidden, crate-private,
+    // test and nested symbols are left out
+    assert_eq!(names, vec!["Config", "Mode", "parse", "legacy"]);
+    assert_eq!(deep.get_audit_trail().len(), 1);
+}
+
+#[test]
+fn test_compare_api_surfaces() {
+    let mut deep = DeepMode::new();
+    let mut surface = |symbols: Vec<Symbol>, version: &str| ApiSurface {
+        symbols: deep.detect_api_surface(&symbols),
+        language: "rust".to_string(),
+        version: Some(version.to_string()),
+    };
+    let v1 = surface(crate_v1(), "1.0.0");
+    let v2 = surface(crate_v2(), "2.0.0");
+
+    let diff = compare_api_surfaces(&v1, &v2);
+    assert_eq!(diff.removals, vec!["function legacy"]);
+    assert_eq!(diff.additions, vec!["function parse_file"]);
+    assert_eq!(
+        diff.breaking_changes,
+        vec![
+            "function legacy was removed",
+            "function parse changed parameters",
+            "struct Mode changed kind to enum",
+        ]
+    );
+
+    // Identical surfaces produce an empty diff
+    let same = compare_api_surfaces(&v2, &v2);
+    assert!(same.breaking_changes.is_empty() && same.additions.is_empty() && same.removals.is_empty());
+}
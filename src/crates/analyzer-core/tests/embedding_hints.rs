@@ -0,0 +1,20 @@
+// Integration tests for embedding hints built from real analyzer output
+
+use analyzer_core::embedding::compute_embedding_hint;
+
+#[test]
+fn test_hint_from_python_analyzer_metadata() {
+    let source = "\
+def parse(raw, strict: bool = False) -> dict:
+    \"\"\"Parse a raw payload.\"\"\"
+    return {}
+";
+    let (symbols, _) = analyzer_python::analyze_python(source).unwrap();
+    let parse = symbols.iter().find(|s| s.name == "parse").unwrap();
+
+    let hint = compute_embedding_hint(parse, source);
+    assert_eq!(
+        hint,
+        "function `parse` at line 1 takes raw, strict: bool, returns dict. Parse a raw payload."
+    );
+}
@@ -0,0 +1,56 @@
+// Integration tests for streaming the index to an async file sink
+
+use analyzer_core::export::export_index_to_json_async;
+use analyzer_core::storage::{init_schema, insert_symbols_batch, upsert_file};
+use analyzer_core::{FileMetadata, Symbol, SymbolKind};
+use serde_json::Value;
+use tempfile::TempDir;
+use tokio::io::BufWriter;
+
+#[tokio::test]
+async fn test_export_to_buffered_file() {
+    let dir = TempDir::new().unwrap();
+    let db_path = dir.path().join("index.db");
+    let output_path = dir.path().join("index.json");
+
+    {
+        let conn = init_schema(&db_path).unwrap();
+        for (path, count) in [("app.py", 1500), ("lib.rs", 700)] {
+            let file_id = upsert_file(&conn, &FileMetadata {
+                id: None,
+                path: path.to_string(),
+                language: analyzer_core::detect_language(path).unwrap().to_string(),
+                size: 100,
+                last_indexed: None,
+                parse_errors: 0,
+                index_duration_ms: None,
+                metadata: None,
+            })
+            .unwrap();
+
+            let symbols: Vec<Symbol> = (0..count)
+                .map(|i| Symbol {
+                    id: None,
+                    file_id,
+                    name: format!("symbol_{}", i),
+                    kind: SymbolKind::Function,
+                    line_start: i,
+                    line_end: i + 1,
+                    column_start: 0,
+                    column_end: 0,
+                    scope: None,
+                    metadata: None,
+                })
+                .collect();
+            insert_symbols_batch(&conn, &symbols).unwrap();
+        }
+    }
+
+    let file = tokio::fs::File::create(&output_path).await.unwrap();
+    let written = export_index_to_json_async(&db_path, BufWriter::new(file)).await.unwrap();
+    assert_eq!(written, 2200);
+
+    let json: Value = serde_json::from_slice(&tokio::fs::read(&output_path).await.unwrap()).unwrap();
+    assert_eq!(json["files"].as_array().unwrap().len(), 2);
+    assert_eq!(json["symbols"].as_array().unwrap().len(), 2200);
+}
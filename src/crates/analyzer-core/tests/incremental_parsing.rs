@@ -0,0 +1,71 @@
+// Integration tests for re-parsing changed files against their previous tree
+
+use analyzer_core::incremental::IncrementalIndexer;
+use analyzer_core::registry::AnalyzerFn;
+use std::path::Path;
+
+fn indexer() -> IncrementalIndexer {
+    let mut indexer = IncrementalIndexer::new();
+    indexer
+        .register("python", analyzer_python::analyze_python_incremental)
+        .register("typescript", analyzer_typescript::analyze_typescript_incremental)
+        .register("rust", analyzer_rust::analyze_rust_incremental);
+    indexer
+}
+
+#[test]
+fn test_incremental_edits_match_full_parse() {
+    let cases: [(&str, &str, AnalyzerFn, [&str; 3]); 3] = [
+        (
+            "app.py",
+            "python",
+            analyzer_python::analyze_python,
+            [
+                "def load():\n    pass\n",
+                "def load():\n    pass\n\nclass Cache:\n    def get(self, key):\n        pass\n",
+                "class Cache:\n    def get(self, key):\n        pass\n",
+            ],
+        ),
+        (
+            "app.ts",
+            "typescript",
+            analyzer_typescript::analyze_typescript,
+            [
+                "function load() {}\n",
+                "function load() {}\nclass Cache {\n  get(key: string) {}\n}\n",
+                "function reload() {}\nclass Cache {\n  get(key: string) {}\n}\n",
+            ],
+        ),
+        (
+            "lib.rs",
+            "rust",
+            analyzer_rust::analyze_rust,
+            [
+                "fn load() {}\n",
+                "fn load() {}\nstruct Cache;\nimpl Cache {\n    fn get(&self) {}\n}\n",
+                "fn load() {}\nstruct Cache;\nimpl Cache {\n    fn get(&self) {}\n    fn put(&self {}\n}\n",
+            ],
+        ),
+    ];
+
+    let mut indexer = indexer();
+    for (path, language, analyze, versions) in cases {
+        let path = Path::new(path);
+        for source in versions {
+            let output = indexer.analyze(path, language, source).unwrap().unwrap();
+            assert_eq!(output, analyze(source).unwrap(), "{} after edit:\n{}", path.display(), source);
+            assert!(indexer.has_tree(path));
+        }
+
+        indexer.forget(path);
+        assert!(!indexer.has_tree(path));
+    }
+}
+
+#[test]
+fn test_unsupported_language_is_not_cached() {
+    let mut indexer = indexer();
+    assert!(indexer.analyze(Path::new("Main.java"), "java", "class Main {}").is_none());
+    assert!(!indexer.has_tree(Path::new("Main.java")));
+    assert!(!indexer.supports("java"));
+}
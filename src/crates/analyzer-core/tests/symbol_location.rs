@@ -0,0 +1,79 @@
+// Property tests for the tree-sitter Node <-> Symbol adapters
+
+use analyzer_core::{Symbol, SymbolKind, SymbolLocation};
+use analyzer_python::PythonParser;
+use proptest::prelude::*;
+use tree_sitter::Node;
+
+/// (line, column) of a byte offset in `source`
+fn line_column(source: &str, byte: usize) -> (usize, usize) {
+    let before = &source[..byte];
+    let line = before.matches('\n').count();
+    let column = byte - before.rfind('\n').map_or(0, |i| i + 1);
+    (line, column)
+}
+
+fn find_kind<'a>(node: Node<'a>, kind: &str) -> Option<Node<'a>> {
+    if node.kind() == kind {
+        return Some(node);
+    }
+    let mut cursor = node.walk();
+    let found = node.children(&mut cursor).find_map(|child| find_kind(child, kind));
+    found
+}
+
+/// Python keywords are valid matches for the name strategy but not valid identifiers
+const PYTHON_KEYWORDS: &[&str] = &[
+    "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del", "elif",
+    "else", "except", "finally", "for", "from", "global", "if", "import", "in", "is", "lambda",
+    "nonlocal", "not", "or", "pass", "raise", "return", "try", "while", "with", "yield",
+];
+
+proptest! {
+    #[test]
+    fn location_round_trips_through_byte_offsets(
+        blank_lines in 0usize..20,
+        indent in 0usize..3,
+        name in "[a-z_][a-z0-9_]{0,15}".prop_filter("not a keyword", |n| !PYTHON_KEYWORDS.contains(&n.as_str())),
+        body_lines in 1usize..5,
+    ) {
+        // Nest inside `if True:` blocks so the definition starts at a non-zero column
+        let mut source = "\n".repeat(blank_lines);
+        for level in 0..indent {
+            source.push_str(&format!("{}if True:\n", "    ".repeat(level)));
+        }
+        let pad = "    ".repeat(indent);
+        source.push_str(&format!("{}def {}():\n", pad, name));
+        for _ in 0..body_lines {
+            source.push_str(&format!("{}    pass\n", pad));
+        }
+
+        let mut parser = PythonParser::new().unwrap();
+        let tree = parser.parse(&source).unwrap();
+        let node = find_kind(tree.root_node(), "function_definition").unwrap();
+
+        let loc = SymbolLocation::from_node(&node);
+        prop_assert_eq!(line_column(&source, loc.byte_start), (loc.line_start, loc.column_start));
+        prop_assert_eq!(line_column(&source, loc.byte_end), (loc.line_end, loc.column_end));
+        prop_assert_eq!(loc.line_start, blank_lines + indent);
+        prop_assert_eq!(loc.column_start, indent * 4);
+
+        let symbol = Symbol::try_from((node, source.as_str(), SymbolKind::Function, Some("outer"))).unwrap();
+        prop_assert_eq!(&symbol.name, &name);
+        prop_assert_eq!(symbol.scope.as_deref(), Some("outer"));
+
+        // Symbol -> location -> Symbol is lossless for the shared fields
+        let rebuilt = Symbol::with_location(name.clone(), SymbolKind::Function, loc, Some("outer"));
+        prop_assert_eq!(rebuilt, symbol);
+    }
+}
+
+#[test]
+fn try_from_requires_name() {
+    let source = "x = 1\n";
+    let mut parser = PythonParser::new().unwrap();
+    let tree = parser.parse(source).unwrap();
+    let node = find_kind(tree.root_node(), "assignment").unwrap();
+
+    assert!(Symbol::try_from((node, source, SymbolKind::Variable, None)).is_err());
+}
@@ -0,0 +1,57 @@
+//! C++ code analyzer using Tree-sitter
+//!
+//! Provides parsing and symbol extraction for C++ source and header files.
+
+pub mod parser;
+pub mod symbol_extract;
+
+pub use parser::{CppParser, CppParserPool};
+pub use symbol_extract::extract_symbols;
+
+use analyzer_core::{IndexingError, ParseError, Symbol};
+
+/// Analyze a C++ source file and extract symbols
+///
+/// Syntax errors are reported as parse errors; symbols outside the broken
+/// regions are still extracted.
+pub fn analyze_cpp(source: &str) -> Result<(Vec<Symbol>, Vec<ParseError>), IndexingError> {
+    let mut parser = CppParser::new().map_err(|e| IndexingError::parse_failed("cpp", e))?;
+    let tree = parser
+        .parse(source)
+        .map_err(|e| IndexingError::parse_failed("cpp", e))?;
+    let (symbols, mut errors) = extract_symbols(&tree, source, None).map_err(IndexingError::extraction_failed)?;
+    errors.splice(0..0, ParseError::from_tree(&tree, source));
+    Ok((symbols, errors))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use analyzer_core::SymbolKind;
+
+    #[test]
+    fn test_analyze_class() {
+        let source = r#"
+#include <string>
+
+namespace app {
+class Greeter {
+public:
+    std::string greet() const { return "Hello, world!"; }
+};
+}
+"#;
+        let (symbols, errors) = analyze_cpp(source).unwrap();
+        assert!(symbols.iter().any(|s| s.name == "Greeter" && matches!(s.kind, SymbolKind::Class)));
+        assert!(symbols.iter().any(|s| s.name == "greet" && s.scope.as_deref() == Some("Greeter")));
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_analyze_reports_parse_errors() {
+        let source = "class Broken {\n    int x\n    void method(;\n};\n\nint after() { return 0; }\n";
+        let (symbols, errors) = analyze_cpp(source).unwrap();
+        assert!(!errors.is_empty());
+        assert!(symbols.iter().any(|s| s.name == "after" && matches!(s.kind, SymbolKind::Function)));
+    }
+}
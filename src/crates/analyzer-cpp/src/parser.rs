@@ -0,0 +1,110 @@
+//! C++ Tree-sitter parser
+//!
+//! Wraps the tree-sitter-cpp parser for use in the analyzer.
+
+use analyzer_core::pool::{ParserPool, ResettableParser};
+use anyhow::{Context, Result};
+use tree_sitter::{Parser, Tree};
+
+/// C++ language parser
+pub struct CppParser {
+    parser: Parser,
+}
+
+impl CppParser {
+    /// Create a new C++ parser
+    pub fn new() -> Result<Self> {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_cpp::LANGUAGE.into())
+            .context("Failed to set C++ language for parser")?;
+
+        Ok(Self { parser })
+    }
+
+    /// Parse C++ source code
+    pub fn parse(&mut self, source: &str) -> Result<Tree> {
+        self.parser
+            .parse(source, None)
+            .context("Failed to parse C++ source")
+    }
+
+    /// Parse with old tree for incremental parsing
+    pub fn parse_with_old_tree(&mut self, source: &str, old_tree: &Tree) -> Result<Tree> {
+        self.parser
+            .parse(source, Some(old_tree))
+            .context("Failed to incrementally parse C++ source")
+    }
+
+    /// Clear per-file parse state, keeping the loaded grammar
+    pub fn reset(&mut self) {
+        self.parser.reset();
+    }
+}
+
+impl Default for CppParser {
+    fn default() -> Self {
+        Self::new().expect("Failed to create C++ parser")
+    }
+}
+
+impl ResettableParser for CppParser {
+    fn reset(&mut self) {
+        CppParser::reset(self);
+    }
+}
+
+/// Pool of reusable C++ parsers (`CppParserPool::new(size)`)
+pub type CppParserPool = ParserPool<CppParser>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_simple_function() {
+        let mut parser = CppParser::new().unwrap();
+        let source = "int add(int a, int b) {\n    return a + b;\n}";
+        let tree = parser.parse(source).unwrap();
+
+        let root = tree.root_node();
+        assert_eq!(root.kind(), "translation_unit");
+        assert!(root.child_count() > 0);
+    }
+
+    #[test]
+    fn test_parse_class() {
+        let mut parser = CppParser::new().unwrap();
+        let source = "namespace app {\nclass Point {\n    int x;\n};\n}";
+        let tree = parser.parse(source).unwrap();
+
+        let sexp = tree.root_node().to_sexp();
+        assert!(sexp.contains("namespace_definition"));
+        assert!(sexp.contains("class_specifier"));
+    }
+
+    #[test]
+    fn test_parse_invalid_syntax() {
+        let mut parser = CppParser::new().unwrap();
+        let source = "class Broken {\n    void method(\n    // Unclosed parenthesis";
+        let tree = parser.parse(source).unwrap();
+
+        // Tree-sitter should still produce a tree even with errors
+        assert!(tree.root_node().has_error());
+    }
+
+    #[test]
+    fn test_reset_and_pool_reuse() {
+        let mut parser = CppParser::new().unwrap();
+        parser.parse("class broken {").unwrap();
+        parser.reset();
+        assert!(!parser.parse("int ok() { return 0; }").unwrap().root_node().has_error());
+
+        let pool = CppParserPool::new(2);
+        for source in ["int a;", "namespace b {}", "template <typename T> struct C {};\n"] {
+            let tree = pool.acquire().parse(source).unwrap();
+            assert!(!tree.root_node().has_error());
+        }
+        assert_eq!(pool.available(), 2);
+    }
+}
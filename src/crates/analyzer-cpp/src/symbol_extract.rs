@@ -0,0 +1,562 @@
+//! C++ symbol extraction
+//!
+//! Extracts functions, methods, classes, structs, unions, enums, namespaces,
+//! templates, global variables, fields, using declarations, and includes from C++ AST.
+
+use analyzer_core::{ExtractConfig, ParseError, Symbol, SymbolKind, SymbolLocation};
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+use tree_sitter::{Node, Tree, TreeCursor};
+
+/// Extract symbols from a C++ parse tree
+///
+/// Subtrees nested deeper than `config.max_depth` (default
+/// [`ExtractConfig::default`]) are skipped and reported as parse errors.
+pub fn extract_symbols(
+    tree: &Tree,
+    source: &str,
+    config: Option<ExtractConfig>,
+) -> Result<(Vec<Symbol>, Vec<ParseError>)> {
+    let config = config.unwrap_or_default();
+    let mut symbols = Vec::new();
+    let mut errors = Vec::new();
+    let root = tree.root_node();
+    let mut cursor = root.walk();
+
+    extract_from_node(&mut cursor, source, &mut symbols, None, 0, &config, &mut errors)?;
+
+    Ok((symbols, errors))
+}
+
+/// Recursively extract symbols from a node
+///
+/// Members and nested types are scoped to the enclosing class, and
+/// declarations in a namespace to the namespace. Out-of-line definitions
+/// (`void Foo::bar() {}`) are scoped to their qualifier instead. Function
+/// bodies are not entered, so local types, variables and lambdas are skipped.
+/// Nodes without a name (anonymous namespaces and classes, declarators
+/// broken by syntax errors) produce no symbol, but their children are still
+/// searched.
+fn extract_from_node(
+    cursor: &mut TreeCursor,
+    source: &str,
+    symbols: &mut Vec<Symbol>,
+    parent_scope: Option<String>,
+    depth: usize,
+    config: &ExtractConfig,
+    errors: &mut Vec<ParseError>,
+) -> Result<()> {
+    let node = cursor.node();
+    if depth >= config.max_depth {
+        // Leaf tokens cannot hold symbols, so only skipped subtrees are reported
+        if node.named_child_count() > 0 {
+            errors.push(ParseError::nesting_too_deep(&node, config.max_depth));
+        }
+        return Ok(());
+    }
+
+    match node.kind() {
+        "function_definition" => {
+            if let Some(symbol) = extract_function(node, source, parent_scope.as_deref())? {
+                symbols.push(symbol);
+            }
+            return Ok(());
+        }
+        "declaration" | "field_declaration" => {
+            symbols.extend(extract_declaration(node, source, parent_scope.as_deref())?);
+            // Initializers hold no symbols, but the type may define a class
+            return recurse_into_type(cursor, source, symbols, parent_scope, depth, config, errors);
+        }
+        "class_specifier" | "struct_specifier" | "union_specifier" | "enum_specifier" => {
+            if let Some(symbol) = extract_type(node, source, parent_scope.as_deref())? {
+                let type_scope = Some(symbol.name.clone());
+                symbols.push(symbol);
+                recurse(cursor, source, symbols, type_scope, depth, config, errors)?;
+                return Ok(());
+            }
+        }
+        "enumerator" => {
+            if parent_scope.is_some() {
+                symbols.push(Symbol::try_from((node, source, symbol_kind(node)?, parent_scope.as_deref()))?);
+            }
+            return Ok(());
+        }
+        "namespace_definition" => {
+            if let Some(name) = node.child_by_field_name("name") {
+                let symbol = Symbol::with_location(
+                    node_text(name, source),
+                    symbol_kind(node)?,
+                    SymbolLocation::from_node(&node),
+                    parent_scope.as_deref(),
+                );
+                let namespace_scope = Some(symbol.name.clone());
+                symbols.push(symbol);
+                recurse(cursor, source, symbols, namespace_scope, depth, config, errors)?;
+                return Ok(());
+            }
+        }
+        "template_declaration" => {
+            let first = symbols.len();
+            recurse(cursor, source, symbols, parent_scope, depth, config, errors)?;
+            // The templated entity is the first symbol its declaration produced
+            if let (Some(symbol), Some(parameters)) = (symbols.get_mut(first), node.child_by_field_name("parameters")) {
+                merge_metadata(symbol, "template", json!(node_text(parameters, source)));
+            }
+            return Ok(());
+        }
+        "using_declaration" => {
+            if let Some(symbol) = extract_using(node, source, parent_scope.as_deref())? {
+                symbols.push(symbol);
+            }
+            return Ok(());
+        }
+        "preproc_include" => {
+            if let Some(symbol) = extract_include(node, source)? {
+                symbols.push(symbol);
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    recurse(cursor, source, symbols, parent_scope, depth, config, errors)
+}
+
+/// Extract symbols from every child of the cursor's node
+fn recurse(
+    cursor: &mut TreeCursor,
+    source: &str,
+    symbols: &mut Vec<Symbol>,
+    parent_scope: Option<String>,
+    depth: usize,
+    config: &ExtractConfig,
+    errors: &mut Vec<ParseError>,
+) -> Result<()> {
+    if cursor.goto_first_child() {
+        loop {
+            extract_from_node(cursor, source, symbols, parent_scope.clone(), depth + 1, config, errors)?;
+            if !cursor.goto_next_sibling() {
+                break;
+            }
+        }
+        cursor.goto_parent();
+    }
+    Ok(())
+}
+
+/// Extract symbols from the `type` child of the cursor's node
+fn recurse_into_type(
+    cursor: &mut TreeCursor,
+    source: &str,
+    symbols: &mut Vec<Symbol>,
+    parent_scope: Option<String>,
+    depth: usize,
+    config: &ExtractConfig,
+    errors: &mut Vec<ParseError>,
+) -> Result<()> {
+    if cursor.goto_first_child() {
+        loop {
+            if cursor.field_name() == Some("type") {
+                extract_from_node(cursor, source, symbols, parent_scope.clone(), depth + 1, config, errors)?;
+            }
+            if !cursor.goto_next_sibling() {
+                break;
+            }
+        }
+        cursor.goto_parent();
+    }
+    Ok(())
+}
+
+/// Extract a function or method definition, named after its declarator
+///
+/// Storage classes such as `static` are stored as `metadata["storage"]`.
+fn extract_function(node: Node, source: &str, scope: Option<&str>) -> Result<Option<Symbol>> {
+    let Some(declarator) = node.child_by_field_name("declarator").and_then(declarator_name) else {
+        return Ok(None);
+    };
+
+    let (name, qualifier) = qualified_name(declarator.name, source);
+    let scope = qualifier.as_deref().or(scope);
+    let location = SymbolLocation::from_node(&node);
+    let mut symbol = Symbol::with_location(name, symbol_kind(node)?, location, scope);
+    symbol.metadata = storage_metadata(node, source).map(|m| Value::Object(m).to_string());
+    Ok(Some(symbol))
+}
+
+/// Extract every name declared by a declaration or class member declaration
+///
+/// Function declarations are `Function`s with `metadata["prototype"] = true`;
+/// other names are `Variable`s, or `Field`s in a class body. Storage classes
+/// such as `extern` are stored as `metadata["storage"]`.
+fn extract_declaration(node: Node, source: &str, scope: Option<&str>) -> Result<Vec<Symbol>> {
+    let storage = storage_metadata(node, source);
+
+    let mut cursor = node.walk();
+    let mut symbols = Vec::new();
+    for declarator in node.children_by_field_name("declarator", &mut cursor) {
+        let Some(declarator) = declarator_name(declarator) else {
+            continue;
+        };
+
+        let mut metadata = storage.clone().unwrap_or_default();
+        let kind = if declarator.is_function {
+            metadata.insert("prototype".to_string(), true.into());
+            SymbolKind::Function
+        } else {
+            symbol_kind(node)?
+        };
+
+        let (name, qualifier) = qualified_name(declarator.name, source);
+        let location = SymbolLocation::from_node(&node);
+        let mut symbol = Symbol::with_location(name, kind, location, qualifier.as_deref().or(scope));
+        symbol.metadata = (!metadata.is_empty()).then(|| Value::Object(metadata).to_string());
+        symbols.push(symbol);
+    }
+    Ok(symbols)
+}
+
+/// Extract a class, struct, union, or enum that has a name and a body
+///
+/// Bare references such as `class Foo;` define nothing. Unions are `Struct`s
+/// with `metadata["union"] = true`.
+fn extract_type(node: Node, source: &str, scope: Option<&str>) -> Result<Option<Symbol>> {
+    let (Some(name), Some(_)) = (node.child_by_field_name("name"), node.child_by_field_name("body")) else {
+        return Ok(None);
+    };
+
+    // `template <> class Box<bool>` is named `Box`
+    let name = match name.kind() {
+        "template_type" => name.child_by_field_name("name").unwrap_or(name),
+        _ => name,
+    };
+    let (name, qualifier) = qualified_name(name, source);
+    let location = SymbolLocation::from_node(&node);
+    let mut symbol = Symbol::with_location(name, symbol_kind(node)?, location, qualifier.as_deref().or(scope));
+    if node.kind() == "union_specifier" {
+        symbol.metadata = Some(json!({ "union": true }).to_string());
+    }
+    Ok(Some(symbol))
+}
+
+/// Extract a `using` declaration as an import named after what it brings into scope
+///
+/// `using namespace std;` gets `metadata["namespace"] = true`.
+fn extract_using(node: Node, source: &str, scope: Option<&str>) -> Result<Option<Symbol>> {
+    let Some(target) = node.named_child(0) else {
+        return Ok(None);
+    };
+
+    let mut symbol =
+        Symbol::with_location(node_text(target, source), symbol_kind(node)?, SymbolLocation::from_node(&node), scope);
+    let mut cursor = node.walk();
+    if node.children(&mut cursor).any(|child| child.kind() == "namespace") {
+        symbol.metadata = Some(json!({ "namespace": true }).to_string());
+    }
+    Ok(Some(symbol))
+}
+
+/// Extract an `#include` as a symbol named after the included path
+///
+/// `<...>` includes get `metadata["system"] = true`.
+fn extract_include(node: Node, source: &str) -> Result<Option<Symbol>> {
+    let Some(path) = node.child_by_field_name("path") else {
+        return Ok(None);
+    };
+
+    let name = node_text(path, source).trim_matches(['"', '<', '>']).to_string();
+    let mut symbol = Symbol::with_location(name, symbol_kind(node)?, SymbolLocation::from_node(&node), None);
+    if path.kind() == "system_lib_string" {
+        symbol.metadata = Some(json!({ "system": true }).to_string());
+    }
+    Ok(Some(symbol))
+}
+
+/// The name a declarator declares
+struct DeclaredName<'tree> {
+    name: Node<'tree>,
+    /// The name sits directly in a `function_declarator`
+    is_function: bool,
+}
+
+/// Follow a declarator (`*name`, `&name`, `name[4]`, `name(int)`, `name = 1`)
+/// down to the declared name
+///
+/// `int (*fp)(int)` declares a pointer, not a function.
+fn declarator_name(declarator: Node) -> Option<DeclaredName> {
+    let mut node = declarator;
+    let mut is_function = false;
+    loop {
+        match node.kind() {
+            "identifier" | "field_identifier" | "type_identifier" | "qualified_identifier" | "destructor_name"
+            | "operator_name" | "template_function" => return Some(DeclaredName { name: node, is_function }),
+            _ => {
+                is_function = node.kind() == "function_declarator";
+                node = node.child_by_field_name("declarator").or_else(|| node.named_child(0))?;
+            }
+        }
+    }
+}
+
+/// Split a possibly qualified name into its last segment and its qualifier
+///
+/// `ns::Foo<T>::bar` becomes `("bar", Some("ns::Foo"))`; template arguments
+/// are dropped from both.
+fn qualified_name(node: Node, source: &str) -> (String, Option<String>) {
+    let mut qualifier = Vec::new();
+    let mut node = node;
+    while node.kind() == "qualified_identifier" || node.kind() == "qualified_type_identifier" {
+        if let Some(scope) = node.child_by_field_name("scope") {
+            qualifier.push(without_template_arguments(scope, source));
+        }
+        let Some(name) = node.child_by_field_name("name") else {
+            break;
+        };
+        node = name;
+    }
+
+    let qualifier = (!qualifier.is_empty()).then(|| qualifier.join("::"));
+    (without_template_arguments(node, source), qualifier)
+}
+
+/// Text of a name without its template arguments (`Box<int>` -> `Box`)
+fn without_template_arguments(node: Node, source: &str) -> String {
+    match node.kind() {
+        "template_type" | "template_function" => node
+            .child_by_field_name("name")
+            .map_or_else(|| node_text(node, source), |name| node_text(name, source)),
+        _ => node_text(node, source),
+    }
+}
+
+/// Storage class specifiers of a declaration, e.g. `{"storage": "static"}`
+fn storage_metadata(node: Node, source: &str) -> Option<Map<String, Value>> {
+    let mut cursor = node.walk();
+    let storage: Vec<String> = node
+        .children(&mut cursor)
+        .filter(|child| child.kind() == "storage_class_specifier")
+        .map(|child| node_text(child, source))
+        .collect();
+    if storage.is_empty() {
+        return None;
+    }
+
+    let mut metadata = Map::new();
+    metadata.insert("storage".to_string(), json!(storage.join(" ")));
+    Some(metadata)
+}
+
+/// Set `metadata[key] = value`, keeping any existing metadata
+fn merge_metadata(symbol: &mut Symbol, key: &str, value: Value) {
+    let mut metadata = match symbol.metadata.as_deref().and_then(|m| serde_json::from_str::<Value>(m).ok()) {
+        Some(Value::Object(map)) => map,
+        _ => Map::new(),
+    };
+    metadata.insert(key.to_string(), value);
+    symbol.metadata = Some(Value::Object(metadata).to_string());
+}
+
+/// Look up the symbol kind for a node in the shared mapping table
+fn symbol_kind(node: Node) -> Result<SymbolKind> {
+    SymbolKind::from_file_node_kind("cpp", node.kind())
+        .ok_or_else(|| anyhow::anyhow!("Node kind `{}` does not define a symbol", node.kind()))
+}
+
+/// Get text content of a node
+fn node_text(node: Node, source: &str) -> String {
+    source[node.byte_range()].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::CppParser;
+
+    fn extract(source: &str) -> Vec<Symbol> {
+        let mut parser = CppParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        extract_symbols(&tree, source, None).unwrap().0
+    }
+
+    fn metadata(symbol: &Symbol) -> Option<Value> {
+        symbol.metadata.as_deref().map(|m| serde_json::from_str(m).unwrap())
+    }
+
+    fn names(symbols: &[Symbol]) -> Vec<(&str, SymbolKind, Option<&str>)> {
+        symbols.iter().map(|s| (s.name.as_str(), s.kind, s.scope.as_deref())).collect()
+    }
+
+    #[test]
+    fn test_extract_classes_and_methods() {
+        let source = r#"
+class Shape {
+public:
+    Shape();
+    virtual ~Shape();
+    double area() const { return 0; }
+    bool operator==(const Shape &other) const;
+
+private:
+    int sides;
+    struct Cache { double value; } cache;
+};
+
+double Shape::perimeter() const {
+    auto twice = [](double x) { int local = 2; return x * local; };
+    return twice(1);
+}
+"#;
+        let symbols = extract(source);
+
+        assert_eq!(
+            names(&symbols),
+            [
+                ("Shape", SymbolKind::Class, None),
+                ("Shape", SymbolKind::Function, Some("Shape")),
+                ("~Shape", SymbolKind::Function, Some("Shape")),
+                ("area", SymbolKind::Function, Some("Shape")),
+                ("operator==", SymbolKind::Function, Some("Shape")),
+                ("sides", SymbolKind::Field, Some("Shape")),
+                ("cache", SymbolKind::Field, Some("Shape")),
+                ("Cache", SymbolKind::Struct, Some("Shape")),
+                ("value", SymbolKind::Field, Some("Cache")),
+                ("perimeter", SymbolKind::Function, Some("Shape")),
+            ]
+        );
+        assert_eq!(metadata(&symbols[1]), Some(json!({"prototype": true})));
+        assert_eq!(metadata(&symbols[3]), None);
+    }
+
+    #[test]
+    fn test_extract_namespaces_and_using() {
+        let source = r#"
+#include <vector>
+
+namespace geometry {
+namespace detail {
+int helper(int x);
+}
+
+union Number { int i; float f; };
+enum class Axis { X, Y };
+}
+
+namespace {
+int hidden = 0;
+}
+
+using std::vector;
+using namespace geometry;
+
+void geometry::detail::run() {}
+"#;
+        let symbols = extract(source);
+
+        assert_eq!(
+            names(&symbols),
+            [
+                ("vector", SymbolKind::Import, None),
+                ("geometry", SymbolKind::Namespace, None),
+                ("detail", SymbolKind::Namespace, Some("geometry")),
+                ("helper", SymbolKind::Function, Some("detail")),
+                ("Number", SymbolKind::Struct, Some("geometry")),
+                ("i", SymbolKind::Field, Some("Number")),
+                ("f", SymbolKind::Field, Some("Number")),
+                ("Axis", SymbolKind::Enum, Some("geometry")),
+                ("X", SymbolKind::EnumVariant, Some("Axis")),
+                ("Y", SymbolKind::EnumVariant, Some("Axis")),
+                ("hidden", SymbolKind::Variable, None),
+                ("std::vector", SymbolKind::Import, None),
+                ("geometry", SymbolKind::Import, None),
+                ("run", SymbolKind::Function, Some("geometry::detail")),
+            ]
+        );
+        assert_eq!(metadata(&symbols[0]), Some(json!({"system": true})));
+        assert_eq!(metadata(&symbols[4]), Some(json!({"union": true})));
+        assert_eq!(metadata(&symbols[11]), None);
+        assert_eq!(metadata(&symbols[12]), Some(json!({"namespace": true})));
+    }
+
+    #[test]
+    fn test_extract_templates() {
+        let source = r#"
+template <typename T>
+class Box {
+public:
+    T get() const { return value; }
+    T value;
+};
+
+template <typename T, int N>
+static T sum(const T (&values)[N]) { return T(); }
+
+template <>
+class Box<bool> {};
+"#;
+        let symbols = extract(source);
+
+        assert_eq!(
+            names(&symbols),
+            [
+                ("Box", SymbolKind::Class, None),
+                ("get", SymbolKind::Function, Some("Box")),
+                ("value", SymbolKind::Field, Some("Box")),
+                ("sum", SymbolKind::Function, None),
+                ("Box", SymbolKind::Class, None),
+            ]
+        );
+        assert_eq!(metadata(&symbols[0]), Some(json!({"template": "<typename T>"})));
+        assert_eq!(metadata(&symbols[1]), None);
+        assert_eq!(
+            metadata(&symbols[3]),
+            Some(json!({"storage": "static", "template": "<typename T, int N>"}))
+        );
+        assert_eq!(metadata(&symbols[4]), Some(json!({"template": "<>"})));
+    }
+
+    #[test]
+    fn test_extract_with_syntax_errors() {
+        let source = r#"
+int ok() { return 0; }
+
+class Broken {
+    int x
+    void method(;
+};
+
+namespace after {
+int value = 1;
+}
+"#;
+        let symbols = extract(source);
+
+        assert_eq!(
+            names(&symbols),
+            [
+                ("ok", SymbolKind::Function, None),
+                ("Broken", SymbolKind::Class, None),
+                ("x", SymbolKind::Field, Some("Broken")),
+                ("method", SymbolKind::Function, Some("Broken")),
+                ("after", SymbolKind::Namespace, None),
+                ("value", SymbolKind::Variable, Some("after")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_max_depth_skips_deep_nesting() {
+        let source = "namespace a { namespace b { namespace c { void deep(); } } }\nvoid top();\n";
+        let mut parser = CppParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+
+        let (symbols, errors) = extract_symbols(&tree, source, None).unwrap();
+        assert!(symbols.iter().any(|s| s.name == "deep"));
+        assert!(errors.is_empty());
+
+        let (symbols, errors) = extract_symbols(&tree, source, Some(ExtractConfig { max_depth: 4 })).unwrap();
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["a", "b", "top"]);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line, 0);
+    }
+}
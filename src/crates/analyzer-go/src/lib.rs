@@ -0,0 +1,76 @@
+//! Go code analyzer using Tree-sitter
+//!
+//! Provides parsing and symbol extraction for Go source files.
+
+pub mod parser;
+pub mod symbol_extract;
+
+pub use parser::{GoParser, GoParserPool};
+pub use symbol_extract::extract_symbols;
+
+use analyzer_core::{IndexingError, ParseError, Symbol};
+
+/// Analyze a Go source file and extract symbols
+pub fn analyze_go(source: &str) -> Result<(Vec<Symbol>, Vec<ParseError>), IndexingError> {
+    let mut parser = GoParser::new().map_err(|e| IndexingError::parse_failed("go", e))?;
+    let tree = parser
+        .parse(source)
+        .map_err(|e| IndexingError::parse_failed("go", e))?;
+    let symbols = extract_symbols(&tree, source).map_err(IndexingError::extraction_failed)?;
+    Ok((symbols, ParseError::from_tree(&tree, source)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use analyzer_core::SymbolKind;
+
+    #[test]
+    fn test_analyze_simple_function() {
+        let source = r#"
+package main
+
+func helloWorld() {
+    println("Hello, world!")
+}
+"#;
+        let (symbols, _) = analyze_go(source).unwrap();
+        assert!(symbols.iter().any(|s| s.name == "helloWorld" && matches!(s.kind, SymbolKind::Function)));
+    }
+
+    #[test]
+    fn test_analyze_struct() {
+        let source = r#"
+package main
+
+type MyStruct struct {
+    value int
+}
+
+func NewMyStruct() *MyStruct {
+    return &MyStruct{value: 42}
+}
+
+func (s *MyStruct) GetValue() int {
+    return s.value
+}
+"#;
+        let (symbols, _) = analyze_go(source).unwrap();
+        assert!(symbols.iter().any(|s| s.name == "MyStruct" && matches!(s.kind, SymbolKind::Struct)));
+        assert!(symbols.iter().any(|s| s.name == "NewMyStruct" && matches!(s.kind, SymbolKind::Function)));
+        assert!(symbols.iter().any(|s| s.name == "GetValue" && s.scope.as_deref() == Some("MyStruct")));
+    }
+
+    #[test]
+    fn test_analyze_interface() {
+        let source = r#"
+package main
+
+type MyInterface interface {
+    DoSomething()
+}
+"#;
+        let (symbols, _) = analyze_go(source).unwrap();
+        assert!(symbols.iter().any(|s| s.name == "MyInterface" && matches!(s.kind, SymbolKind::Interface)));
+    }
+}
@@ -0,0 +1,109 @@
+//! Go Tree-sitter parser
+//!
+//! Wraps the tree-sitter-go parser for use in the analyzer.
+
+use analyzer_core::pool::{ParserPool, ResettableParser};
+use anyhow::{Context, Result};
+use tree_sitter::{Parser, Tree};
+
+/// Go language parser
+pub struct GoParser {
+    parser: Parser,
+}
+
+impl GoParser {
+    /// Create a new Go parser
+    pub fn new() -> Result<Self> {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_go::LANGUAGE.into())
+            .context("Failed to set Go language for parser")?;
+
+        Ok(Self { parser })
+    }
+
+    /// Parse Go source code
+    pub fn parse(&mut self, source: &str) -> Result<Tree> {
+        self.parser
+            .parse(source, None)
+            .context("Failed to parse Go source")
+    }
+
+    /// Parse with old tree for incremental parsing
+    pub fn parse_with_old_tree(&mut self, source: &str, old_tree: &Tree) -> Result<Tree> {
+        self.parser
+            .parse(source, Some(old_tree))
+            .context("Failed to incrementally parse Go source")
+    }
+
+    /// Clear per-file parse state, keeping the loaded grammar
+    pub fn reset(&mut self) {
+        self.parser.reset();
+    }
+}
+
+impl Default for GoParser {
+    fn default() -> Self {
+        Self::new().expect("Failed to create Go parser")
+    }
+}
+
+impl ResettableParser for GoParser {
+    fn reset(&mut self) {
+        GoParser::reset(self);
+    }
+}
+
+/// Pool of reusable Go parsers (`GoParserPool::new(size)`)
+pub type GoParserPool = ParserPool<GoParser>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_simple_function() {
+        let mut parser = GoParser::new().unwrap();
+        let source = "package main\n\nfunc hello() {\n    println(\"Hello\")\n}";
+        let tree = parser.parse(source).unwrap();
+
+        let root = tree.root_node();
+        assert_eq!(root.kind(), "source_file");
+        assert!(root.child_count() > 0);
+    }
+
+    #[test]
+    fn test_parse_struct() {
+        let mut parser = GoParser::new().unwrap();
+        let source = "package main\n\ntype MyStruct struct {\n    value int\n}";
+        let tree = parser.parse(source).unwrap();
+
+        let root = tree.root_node();
+        assert!(root.to_sexp().contains("struct_type"));
+    }
+
+    #[test]
+    fn test_parse_invalid_syntax() {
+        let mut parser = GoParser::new().unwrap();
+        let source = "package main\n\nfunc hello(\n    // Unclosed parenthesis";
+        let tree = parser.parse(source).unwrap();
+
+        // Tree-sitter should still produce a tree even with errors
+        assert!(tree.root_node().has_error());
+    }
+
+    #[test]
+    fn test_reset_and_pool_reuse() {
+        let mut parser = GoParser::new().unwrap();
+        parser.parse("func broken(").unwrap();
+        parser.reset();
+        assert!(!parser.parse("package p\nfunc ok() {}").unwrap().root_node().has_error());
+
+        let pool = GoParserPool::new(2);
+        for source in ["package a", "package b\nfunc b() {}", "package c\ntype C int\n"] {
+            let tree = pool.acquire().parse(source).unwrap();
+            assert!(!tree.root_node().has_error());
+        }
+        assert_eq!(pool.available(), 2);
+    }
+}
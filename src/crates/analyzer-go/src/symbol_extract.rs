@@ -0,0 +1,294 @@
+//! Go symbol extraction
+//!
+//! Extracts functions, methods, type declarations, constants, variables, and imports from Go AST.
+
+use analyzer_core::{Symbol, SymbolKind, SymbolLocation};
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+use tree_sitter::{Node, Tree, TreeCursor};
+
+/// Extract symbols from a Go parse tree
+pub fn extract_symbols(tree: &Tree, source: &str) -> Result<Vec<Symbol>> {
+    let mut symbols = Vec::new();
+    let root = tree.root_node();
+    let mut cursor = root.walk();
+
+    extract_from_node(&mut cursor, source, &mut symbols)?;
+
+    Ok(symbols)
+}
+
+/// Recursively extract package-level symbols from a node
+///
+/// Function and method bodies are not entered, so local types, constants and
+/// variables are skipped.
+fn extract_from_node(cursor: &mut TreeCursor, source: &str, symbols: &mut Vec<Symbol>) -> Result<()> {
+    let node = cursor.node();
+
+    match node.kind() {
+        "function_declaration" => {
+            symbols.push(Symbol::try_from((node, source, symbol_kind(node)?, None))?);
+        }
+        "method_declaration" => {
+            let receiver = receiver_type(node, source);
+            symbols.push(Symbol::try_from((node, source, symbol_kind(node)?, receiver.as_deref()))?);
+        }
+        "type_spec" | "type_alias" => {
+            symbols.push(extract_type(node, source)?);
+        }
+        "const_spec" | "var_spec" => {
+            symbols.extend(extract_value_spec(node, source)?);
+        }
+        "import_spec" => {
+            if let Some(symbol) = extract_import(node, source)? {
+                symbols.push(symbol);
+            }
+        }
+        _ => {
+            // Recurse into children
+            if cursor.goto_first_child() {
+                loop {
+                    extract_from_node(cursor, source, symbols)?;
+                    if !cursor.goto_next_sibling() {
+                        break;
+                    }
+                }
+                cursor.goto_parent();
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Name of the type a method is declared on, without pointer or type arguments
+///
+/// `func (p *Pair[T]) Swap()` is scoped to `Pair`.
+fn receiver_type(node: Node, source: &str) -> Option<String> {
+    let receiver = node.child_by_field_name("receiver")?;
+    let mut cursor = receiver.walk();
+    let parameter = receiver
+        .named_children(&mut cursor)
+        .find(|child| child.kind() == "parameter_declaration")?;
+
+    let mut ty = parameter.child_by_field_name("type")?;
+    if ty.kind() == "pointer_type" {
+        ty = ty.named_child(0)?;
+    }
+    if ty.kind() == "generic_type" {
+        ty = ty.child_by_field_name("type")?;
+    }
+    Some(node_text(ty, source))
+}
+
+/// Extract a type declaration
+///
+/// Struct and interface types get their own kinds; other named types and
+/// aliases are `Type`, with `metadata["alias"] = true` for `type A = B`.
+fn extract_type(node: Node, source: &str) -> Result<Symbol> {
+    let kind = match node.child_by_field_name("type").map(|ty| ty.kind()) {
+        Some("struct_type") => SymbolKind::Struct,
+        Some("interface_type") => SymbolKind::Interface,
+        _ => symbol_kind(node)?,
+    };
+
+    let mut symbol = Symbol::try_from((node, source, kind, None))?;
+    if node.kind() == "type_alias" {
+        symbol.metadata = Some(json!({ "alias": true }).to_string());
+    }
+    Ok(symbol)
+}
+
+/// Extract every name declared by a `const` or `var` spec (`const A, B = 1, 2`)
+///
+/// An explicit type is stored as `metadata["type"]`.
+fn extract_value_spec(node: Node, source: &str) -> Result<Vec<Symbol>> {
+    let kind = symbol_kind(node)?;
+    let mut metadata = Map::new();
+    if let Some(ty) = node.child_by_field_name("type") {
+        metadata.insert("type".to_string(), json!(node_text(ty, source)));
+    }
+    let metadata = (!metadata.is_empty()).then(|| Value::Object(metadata).to_string());
+
+    let mut cursor = node.walk();
+    let symbols = node
+        .children_by_field_name("name", &mut cursor)
+        .filter(|name| name.is_named())
+        .map(|name| Symbol {
+            metadata: metadata.clone(),
+            ..Symbol::with_location(node_text(name, source), kind, SymbolLocation::from_node(&node), None)
+        })
+        .collect();
+    Ok(symbols)
+}
+
+/// Extract an import as a symbol named after its package path
+///
+/// A local package name (including `_` and `.`) is stored as `metadata["alias"]`.
+fn extract_import(node: Node, source: &str) -> Result<Option<Symbol>> {
+    let Some(path) = node.child_by_field_name("path") else {
+        return Ok(None);
+    };
+
+    let name = node_text(path, source).trim_matches(['"', '`']).to_string();
+    let mut symbol = Symbol::with_location(name, symbol_kind(node)?, SymbolLocation::from_node(&node), None);
+    if let Some(alias) = node.child_by_field_name("name") {
+        symbol.metadata = Some(json!({ "alias": node_text(alias, source) }).to_string());
+    }
+    Ok(Some(symbol))
+}
+
+/// Look up the symbol kind for a node in the shared mapping table
+fn symbol_kind(node: Node) -> Result<SymbolKind> {
+    SymbolKind::from_file_node_kind("go", node.kind())
+        .ok_or_else(|| anyhow::anyhow!("Node kind `{}` does not define a symbol", node.kind()))
+}
+
+/// Get text content of a node
+fn node_text(node: Node, source: &str) -> String {
+    source[node.byte_range()].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::GoParser;
+
+    fn extract(source: &str) -> Vec<Symbol> {
+        let mut parser = GoParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        extract_symbols(&tree, source).unwrap()
+    }
+
+    fn metadata(symbol: &Symbol) -> Option<Value> {
+        symbol.metadata.as_deref().map(|m| serde_json::from_str(m).unwrap())
+    }
+
+    #[test]
+    fn test_extract_function() {
+        let source = r#"
+package main
+
+func myFunction() {
+    println("test")
+}
+"#;
+        let symbols = extract(source);
+
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(symbols[0].name, "myFunction");
+        assert_eq!(symbols[0].kind, SymbolKind::Function);
+        assert_eq!(symbols[0].line_start, 3);
+    }
+
+    #[test]
+    fn test_extract_struct_with_methods() {
+        let source = r#"
+package main
+
+type Pair[T any] struct {
+    a, b T
+}
+
+func (p *Pair[T]) Swap() {}
+
+func (Pair[T]) First() T { var zero T; return zero }
+"#;
+        let symbols = extract(source);
+
+        let names: Vec<(&str, SymbolKind, Option<&str>)> = symbols
+            .iter()
+            .map(|s| (s.name.as_str(), s.kind, s.scope.as_deref()))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                ("Pair", SymbolKind::Struct, None),
+                ("Swap", SymbolKind::Function, Some("Pair")),
+                ("First", SymbolKind::Function, Some("Pair")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_type_declarations() {
+        let source = r#"
+package main
+
+type Reader interface {
+    Read(p []byte) (int, error)
+}
+
+type (
+    Celsius float64
+    ID = string
+)
+"#;
+        let symbols = extract(source);
+
+        assert_eq!(symbols.len(), 3);
+        assert_eq!((symbols[0].name.as_str(), symbols[0].kind), ("Reader", SymbolKind::Interface));
+        assert_eq!((symbols[1].name.as_str(), symbols[1].kind), ("Celsius", SymbolKind::Type));
+        assert_eq!(metadata(&symbols[1]), None);
+        assert_eq!((symbols[2].name.as_str(), symbols[2].kind), ("ID", SymbolKind::Type));
+        assert_eq!(metadata(&symbols[2]), Some(json!({"alias": true})));
+    }
+
+    #[test]
+    fn test_extract_consts_and_vars() {
+        let source = r#"
+package main
+
+const Pi = 3.14
+
+const (
+    A, B = 1, 2
+)
+
+var count int
+
+func main() {
+    const local = 1
+    var x = 2
+}
+"#;
+        let symbols = extract(source);
+
+        let names: Vec<(&str, SymbolKind)> = symbols.iter().map(|s| (s.name.as_str(), s.kind)).collect();
+        assert_eq!(
+            names,
+            [
+                ("Pi", SymbolKind::Constant),
+                ("A", SymbolKind::Constant),
+                ("B", SymbolKind::Constant),
+                ("count", SymbolKind::Variable),
+                ("main", SymbolKind::Function),
+            ]
+        );
+        assert_eq!(symbols[1].line_start, symbols[2].line_start);
+        assert_eq!(metadata(&symbols[3]), Some(json!({"type": "int"})));
+    }
+
+    #[test]
+    fn test_extract_imports() {
+        let source = r#"
+package main
+
+import (
+    "fmt"
+    str "strings"
+    _ "embed"
+)
+
+import "os"
+"#;
+        let symbols = extract(source);
+
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["fmt", "strings", "embed", "os"]);
+        assert!(symbols.iter().all(|s| s.kind == SymbolKind::Import));
+        assert_eq!(metadata(&symbols[0]), None);
+        assert_eq!(metadata(&symbols[1]), Some(json!({"alias": "str"})));
+        assert_eq!(metadata(&symbols[2]), Some(json!({"alias": "_"})));
+    }
+}
@@ -0,0 +1,72 @@
+//! Java code analyzer using Tree-sitter
+//!
+//! Provides parsing and symbol extraction for Java source files.
+
+pub mod parser;
+pub mod symbol_extract;
+
+pub use parser::{JavaParser, JavaParserPool};
+pub use symbol_extract::extract_symbols;
+
+use analyzer_core::{IndexingError, ParseError, Symbol};
+
+/// Analyze a Java source file and extract symbols
+pub fn analyze_java(source: &str) -> Result<(Vec<Symbol>, Vec<ParseError>), IndexingError> {
+    let mut parser = JavaParser::new().map_err(|e| IndexingError::parse_failed("java", e))?;
+    let tree = parser
+        .parse(source)
+        .map_err(|e| IndexingError::parse_failed("java", e))?;
+    let symbols = extract_symbols(&tree, source).map_err(IndexingError::extraction_failed)?;
+    Ok((symbols, ParseError::from_tree(&tree, source)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use analyzer_core::SymbolKind;
+
+    #[test]
+    fn test_analyze_simple_method() {
+        let source = r#"
+public class Main {
+    public static void main(String[] args) {
+        System.out.println("Hello, world!");
+    }
+}
+"#;
+        let (symbols, _) = analyze_java(source).unwrap();
+        assert!(symbols.iter().any(|s| s.name == "main" && matches!(s.kind, SymbolKind::Function)));
+    }
+
+    #[test]
+    fn test_analyze_class() {
+        let source = r#"
+public class MyClass {
+    private int value;
+
+    public MyClass() {
+        this.value = 42;
+    }
+
+    public int getValue() {
+        return value;
+    }
+}
+"#;
+        let (symbols, _) = analyze_java(source).unwrap();
+        assert!(symbols.iter().any(|s| s.name == "MyClass" && matches!(s.kind, SymbolKind::Class)));
+        assert!(symbols.iter().any(|s| s.name == "value" && matches!(s.kind, SymbolKind::Field)));
+        assert!(symbols.iter().any(|s| s.name == "getValue" && s.scope.as_deref() == Some("MyClass")));
+    }
+
+    #[test]
+    fn test_analyze_interface() {
+        let source = r#"
+interface MyInterface {
+    void doSomething();
+}
+"#;
+        let (symbols, _) = analyze_java(source).unwrap();
+        assert!(symbols.iter().any(|s| s.name == "MyInterface" && matches!(s.kind, SymbolKind::Interface)));
+    }
+}
@@ -0,0 +1,292 @@
+//! Java symbol extraction
+//!
+//! Extracts classes, interfaces, annotation types, methods, constructors, fields, and imports from Java AST.
+
+use analyzer_core::{Symbol, SymbolKind, SymbolLocation};
+use anyhow::Result;
+use serde_json::json;
+use tree_sitter::{Node, Tree, TreeCursor};
+
+/// Extract symbols from a Java parse tree
+pub fn extract_symbols(tree: &Tree, source: &str) -> Result<Vec<Symbol>> {
+    let mut symbols = Vec::new();
+    let root = tree.root_node();
+    let mut cursor = root.walk();
+
+    extract_from_node(&mut cursor, source, &mut symbols, None)?;
+
+    Ok(symbols)
+}
+
+/// Recursively extract symbols from a node
+///
+/// Members and nested types are scoped to the enclosing type's name. Method
+/// and constructor bodies are not entered, so local and anonymous classes are
+/// skipped.
+fn extract_from_node(
+    cursor: &mut TreeCursor,
+    source: &str,
+    symbols: &mut Vec<Symbol>,
+    parent_scope: Option<String>,
+) -> Result<()> {
+    let node = cursor.node();
+
+    match node.kind() {
+        "class_declaration" | "interface_declaration" | "enum_declaration" | "record_declaration"
+        | "annotation_type_declaration" => {
+            let mut symbol = Symbol::try_from((node, source, symbol_kind(node)?, parent_scope.as_deref()))?;
+            if node.kind() == "annotation_type_declaration" {
+                symbol.metadata = Some(json!({ "annotation": true }).to_string());
+            }
+            let type_scope = Some(symbol.name.clone());
+            symbols.push(symbol);
+
+            if cursor.goto_first_child() {
+                loop {
+                    extract_from_node(cursor, source, symbols, type_scope.clone())?;
+                    if !cursor.goto_next_sibling() {
+                        break;
+                    }
+                }
+                cursor.goto_parent();
+            }
+        }
+        "method_declaration" => {
+            symbols.push(Symbol::try_from((node, source, symbol_kind(node)?, parent_scope.as_deref()))?);
+        }
+        "constructor_declaration" => {
+            symbols.push(Symbol {
+                metadata: Some(json!({ "constructor": true }).to_string()),
+                ..Symbol::try_from((node, source, symbol_kind(node)?, parent_scope.as_deref()))?
+            });
+        }
+        "field_declaration" => {
+            symbols.extend(extract_fields(node, source, parent_scope.as_deref())?);
+        }
+        "import_declaration" => {
+            if let Some(symbol) = extract_import(node, source)? {
+                symbols.push(symbol);
+            }
+        }
+        _ => {
+            // Recurse into children
+            if cursor.goto_first_child() {
+                loop {
+                    extract_from_node(cursor, source, symbols, parent_scope.clone())?;
+                    if !cursor.goto_next_sibling() {
+                        break;
+                    }
+                }
+                cursor.goto_parent();
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Extract every variable declared by a field declaration (`int a, b = 0;`)
+///
+/// The declared type is stored as `metadata["type"]`.
+fn extract_fields(node: Node, source: &str, scope: Option<&str>) -> Result<Vec<Symbol>> {
+    let kind = symbol_kind(node)?;
+    let metadata = node
+        .child_by_field_name("type")
+        .map(|ty| json!({ "type": node_text(ty, source) }).to_string());
+
+    let mut cursor = node.walk();
+    let symbols = node
+        .children_by_field_name("declarator", &mut cursor)
+        .filter_map(|declarator| declarator.child_by_field_name("name"))
+        .map(|name| Symbol {
+            metadata: metadata.clone(),
+            ..Symbol::with_location(node_text(name, source), kind, SymbolLocation::from_node(&node), scope)
+        })
+        .collect();
+    Ok(symbols)
+}
+
+/// Extract an import as a symbol named after the imported path
+///
+/// `import static` sets `metadata["static"]` and a trailing `.*` sets
+/// `metadata["wildcard"]`; the `.*` is not part of the name.
+fn extract_import(node: Node, source: &str) -> Result<Option<Symbol>> {
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.children(&mut cursor).collect();
+    let Some(path) = children
+        .iter()
+        .find(|child| matches!(child.kind(), "scoped_identifier" | "identifier"))
+    else {
+        return Ok(None);
+    };
+
+    let mut symbol = Symbol::with_location(
+        node_text(*path, source),
+        symbol_kind(node)?,
+        SymbolLocation::from_node(&node),
+        None,
+    );
+    let mut metadata = serde_json::Map::new();
+    if children.iter().any(|child| child.kind() == "static") {
+        metadata.insert("static".to_string(), json!(true));
+    }
+    if children.iter().any(|child| child.kind() == "asterisk") {
+        metadata.insert("wildcard".to_string(), json!(true));
+    }
+    if !metadata.is_empty() {
+        symbol.metadata = Some(serde_json::Value::Object(metadata).to_string());
+    }
+    Ok(Some(symbol))
+}
+
+/// Look up the symbol kind for a node in the shared mapping table
+fn symbol_kind(node: Node) -> Result<SymbolKind> {
+    SymbolKind::from_file_node_kind("java", node.kind())
+        .ok_or_else(|| anyhow::anyhow!("Node kind `{}` does not define a symbol", node.kind()))
+}
+
+/// Get text content of a node
+fn node_text(node: Node, source: &str) -> String {
+    source[node.byte_range()].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::JavaParser;
+    use serde_json::Value;
+
+    fn extract(source: &str) -> Vec<Symbol> {
+        let mut parser = JavaParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        extract_symbols(&tree, source).unwrap()
+    }
+
+    fn metadata(symbol: &Symbol) -> Option<Value> {
+        symbol.metadata.as_deref().map(|m| serde_json::from_str(m).unwrap())
+    }
+
+    #[test]
+    fn test_extract_class_members() {
+        let source = r#"
+public class Counter {
+    private int count, total = 0;
+
+    public Counter(int count) {
+        this.count = count;
+    }
+
+    public void increment() {
+        count++;
+    }
+}
+"#;
+        let symbols = extract(source);
+
+        let names: Vec<(&str, SymbolKind, Option<&str>)> = symbols
+            .iter()
+            .map(|s| (s.name.as_str(), s.kind, s.scope.as_deref()))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                ("Counter", SymbolKind::Class, None),
+                ("count", SymbolKind::Field, Some("Counter")),
+                ("total", SymbolKind::Field, Some("Counter")),
+                ("Counter", SymbolKind::Function, Some("Counter")),
+                ("increment", SymbolKind::Function, Some("Counter")),
+            ]
+        );
+        assert_eq!(metadata(&symbols[1]), Some(json!({"type": "int"})));
+        assert_eq!(metadata(&symbols[3]), Some(json!({"constructor": true})));
+        assert_eq!(metadata(&symbols[4]), None);
+    }
+
+    #[test]
+    fn test_inner_classes_are_scoped_to_enclosing_class() {
+        let source = r#"
+class Outer {
+    static class Inner {
+        void innerMethod() {}
+
+        class Deep {
+            int depth;
+        }
+    }
+
+    interface Callback {
+        void call();
+    }
+
+    void run() {
+        new Runnable() { public void run() {} };
+        class Local {}
+    }
+}
+"#;
+        let symbols = extract(source);
+
+        let scope = |name: &str| -> Option<&str> {
+            symbols.iter().find(|s| s.name == name).unwrap().scope.as_deref()
+        };
+        assert_eq!(scope("Outer"), None);
+        assert_eq!(scope("Inner"), Some("Outer"));
+        assert_eq!(scope("innerMethod"), Some("Inner"));
+        assert_eq!(scope("Deep"), Some("Inner"));
+        assert_eq!(scope("depth"), Some("Deep"));
+        assert_eq!(scope("call"), Some("Callback"));
+        assert_eq!(scope("Callback"), Some("Outer"));
+
+        // Bodies are not entered
+        assert_eq!(symbols.iter().filter(|s| s.name == "run").count(), 1);
+        assert!(!symbols.iter().any(|s| s.name == "Local"));
+    }
+
+    #[test]
+    fn test_extract_type_declarations() {
+        let source = r#"
+@interface Marker {
+    String value() default "";
+}
+
+interface Shape {
+    double area();
+}
+
+enum Color { RED, GREEN }
+
+record Point(int x, int y) {
+    double norm() { return Math.hypot(x, y); }
+}
+"#;
+        let symbols = extract(source);
+
+        let kind = |name: &str| symbols.iter().find(|s| s.name == name).map(|s| s.kind);
+        assert_eq!(kind("Marker"), Some(SymbolKind::Interface));
+        assert_eq!(metadata(symbols.iter().find(|s| s.name == "Marker").unwrap()), Some(json!({"annotation": true})));
+        assert_eq!(kind("Shape"), Some(SymbolKind::Interface));
+        assert_eq!(kind("area"), Some(SymbolKind::Function));
+        assert_eq!(kind("Color"), Some(SymbolKind::Enum));
+        assert_eq!(kind("Point"), Some(SymbolKind::Class));
+        assert_eq!(symbols.iter().find(|s| s.name == "norm").unwrap().scope.as_deref(), Some("Point"));
+    }
+
+    #[test]
+    fn test_extract_imports() {
+        let source = r#"
+package com.example;
+
+import java.util.List;
+import java.util.concurrent.*;
+import static java.lang.Math.max;
+"#;
+        let symbols = extract(source);
+
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["java.util.List", "java.util.concurrent", "java.lang.Math.max"]);
+        assert!(symbols.iter().all(|s| s.kind == SymbolKind::Import));
+        assert_eq!(metadata(&symbols[0]), None);
+        assert_eq!(metadata(&symbols[1]), Some(json!({"wildcard": true})));
+        assert_eq!(metadata(&symbols[2]), Some(json!({"static": true})));
+    }
+}
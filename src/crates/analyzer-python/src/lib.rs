@@ -5,23 +5,63 @@
 pub mod parser;
 pub mod symbol_extract;
 
-pub use parser::PythonParser;
-pub use symbol_extract::extract_symbols;
+pub use parser::{PythonParser, PythonParserPool};
+pub use symbol_extract::{extract_symbols, extract_symbols_for_version, mark_package_metadata};
 
-use analyzer_core::Symbol;
-use anyhow::Result;
+use analyzer_core::registry::IncrementalAnalyzerOutput;
+use analyzer_core::{IndexingError, ParseError, Symbol};
+use tree_sitter::Tree;
 
 /// Analyze a Python source file and extract symbols
-pub fn analyze_python(source: &str) -> Result<Vec<Symbol>> {
-    let mut parser = PythonParser::new()?;
-    let tree = parser.parse(source)?;
-    let symbols = extract_symbols(&tree, source)?;
-    Ok(symbols)
+pub fn analyze_python(source: &str) -> Result<(Vec<Symbol>, Vec<ParseError>), IndexingError> {
+    analyze_python_with_version(source, None)
+}
+
+/// Analyze a Python source file targeting a specific Python version
+///
+/// See `extract_symbols_for_version` for how incompatible syntax is flagged.
+pub fn analyze_python_with_version(
+    source: &str,
+    version: Option<(u8, u8)>,
+) -> Result<(Vec<Symbol>, Vec<ParseError>), IndexingError> {
+    let mut parser = PythonParser::new().map_err(|e| IndexingError::parse_failed("python", e))?;
+    if let Some((major, minor)) = version {
+        parser
+            .set_language_version(major, minor)
+            .map_err(|_| IndexingError::UnsupportedLanguage(format!("python {}.{}", major, minor)))?;
+    }
+    let tree = parser
+        .parse(source)
+        .map_err(|e| IndexingError::parse_failed("python", e))?;
+    let (symbols, mut errors) = extract_symbols_for_version(&tree, source, parser.language_version())
+        .map_err(IndexingError::extraction_failed)?;
+    errors.splice(0..0, ParseError::from_tree(&tree, source));
+    Ok((symbols, errors))
+}
+
+/// Analyze a Python source file, re-parsing against `old_tree` when given
+///
+/// `old_tree` must already be edited to match `source`. The new tree is
+/// returned for the next incremental parse.
+pub fn analyze_python_incremental(
+    source: &str,
+    old_tree: Option<&Tree>,
+) -> Result<IncrementalAnalyzerOutput, IndexingError> {
+    let mut parser = PythonParser::new().map_err(|e| IndexingError::parse_failed("python", e))?;
+    let tree = match old_tree {
+        Some(old_tree) => parser.parse_with_old_tree(source, old_tree),
+        None => parser.parse(source),
+    }
+    .map_err(|e| IndexingError::parse_failed("python", e))?;
+    let (symbols, mut errors) = extract_symbols(&tree, source, None).map_err(IndexingError::extraction_failed)?;
+    errors.splice(0..0, ParseError::from_tree(&tree, source));
+    Ok((symbols, errors, tree))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use analyzer_core::SymbolKind;
 
     #[test]
     fn test_analyze_simple_function() {
@@ -29,7 +69,7 @@ mod tests {
 def hello_world():
     print("Hello, world!")
 "#;
-        let symbols = analyze_python(source).unwrap();
+        let (symbols, _) = analyze_python(source).unwrap();
         assert!(symbols.iter().any(|s| s.name == "hello_world" && matches!(s.kind, SymbolKind::Function)));
     }
 
@@ -43,9 +83,32 @@ class MyClass:
     def get_value(self):
         return self.value
 "#;
-        let symbols = analyze_python(source).unwrap();
+        let (symbols, _) = analyze_python(source).unwrap();
         assert!(symbols.iter().any(|s| s.name == "MyClass" && matches!(s.kind, SymbolKind::Class)));
         assert!(symbols.iter().any(|s| s.name == "__init__" && matches!(s.kind, SymbolKind::Function)));
         assert!(symbols.iter().any(|s| s.name == "get_value" && matches!(s.kind, SymbolKind::Function)));
     }
+
+    #[test]
+    fn test_analyze_unsupported_version() {
+        let err = analyze_python_with_version("x = 1\n", Some((4, 0))).unwrap_err();
+        assert!(matches!(err, IndexingError::UnsupportedLanguage(ref v) if v == "python 4.0"));
+    }
+
+    #[test]
+    fn test_analyze_reports_parse_errors() {
+        let source = r#"
+def ok():
+    pass
+
+def broken(:
+    pass
+"#;
+        let (symbols, errors) = analyze_python(source).unwrap();
+        assert!(symbols.iter().any(|s| s.name == "broken"));
+        assert!(!errors.is_empty());
+        assert!(errors.iter().all(|e| e.line == 4));
+        assert!(errors.iter().all(|e| e.file_path.is_empty()));
+        assert!(analyze_python("x = 1\n").unwrap().1.is_empty());
+    }
 }
@@ -2,12 +2,14 @@
 //!
 //! Wraps the tree-sitter-python parser for use in the analyzer.
 
+use analyzer_core::pool::{ParserPool, ResettableParser};
 use anyhow::{Context, Result};
 use tree_sitter::{Parser, Tree};
 
 /// Python language parser
 pub struct PythonParser {
     parser: Parser,
+    version: Option<(u8, u8)>,
 }
 
 impl PythonParser {
@@ -18,7 +20,24 @@ impl PythonParser {
             .set_language(&tree_sitter_python::LANGUAGE.into())
             .context("Failed to set Python language for parser")?;
 
-        Ok(Self { parser })
+        Ok(Self { parser, version: None })
+    }
+
+    /// Target a Python version (2.x or 3.x)
+    ///
+    /// The grammar accepts both dialects; the version is used to flag
+    /// symbols using syntax the target does not support.
+    pub fn set_language_version(&mut self, major: u8, minor: u8) -> Result<()> {
+        if !matches!(major, 2 | 3) {
+            anyhow::bail!("Unsupported Python version {}.{}", major, minor);
+        }
+        self.version = Some((major, minor));
+        Ok(())
+    }
+
+    /// The targeted Python version, if one was set
+    pub fn language_version(&self) -> Option<(u8, u8)> {
+        self.version
     }
 
     /// Parse Python source code
@@ -34,6 +53,11 @@ impl PythonParser {
             .parse(source, Some(old_tree))
             .context("Failed to incrementally parse Python source")
     }
+
+    /// Clear per-file parse state, keeping the loaded grammar and target version
+    pub fn reset(&mut self) {
+        self.parser.reset();
+    }
 }
 
 impl Default for PythonParser {
@@ -42,6 +66,15 @@ impl Default for PythonParser {
     }
 }
 
+impl ResettableParser for PythonParser {
+    fn reset(&mut self) {
+        PythonParser::reset(self);
+    }
+}
+
+/// Pool of reusable Python parsers (`PythonParserPool::new(size)`)
+pub type PythonParserPool = ParserPool<PythonParser>;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -67,6 +100,23 @@ mod tests {
         assert!(root.to_sexp().contains("class_definition"));
     }
 
+    #[test]
+    fn test_set_language_version() {
+        let mut parser = PythonParser::new().unwrap();
+        assert_eq!(parser.language_version(), None);
+
+        parser.set_language_version(2, 7).unwrap();
+        assert_eq!(parser.language_version(), Some((2, 7)));
+
+        assert!(parser.set_language_version(4, 0).is_err());
+        assert_eq!(parser.language_version(), Some((2, 7)));
+
+        // Python 2 print statements parse regardless of the target version
+        let tree = parser.parse("print 'hello'\n").unwrap();
+        assert!(!tree.root_node().has_error());
+        assert!(tree.root_node().to_sexp().contains("print_statement"));
+    }
+
     #[test]
     fn test_parse_invalid_syntax() {
         let mut parser = PythonParser::new().unwrap();
@@ -76,4 +126,14 @@ mod tests {
         // Tree-sitter should still produce a tree even with errors
         assert!(tree.root_node().has_error());
     }
+
+    #[test]
+    fn test_pool_keeps_language_version() {
+        let pool = PythonParserPool::new(1);
+        pool.acquire().set_language_version(2, 7).unwrap();
+
+        let mut parser = pool.acquire();
+        assert_eq!(parser.language_version(), Some((2, 7)));
+        assert!(!parser.parse("def f():\n    pass\n").unwrap().root_node().has_error());
+    }
 }
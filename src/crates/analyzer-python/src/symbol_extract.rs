@@ -2,19 +2,94 @@
 //!
 //! Extracts functions, classes, and imports from Python AST.
 
-use analyzer_core::{Symbol, SymbolKind};
+use analyzer_core::{ExtractConfig, FunctionParam, ParseError, Symbol, SymbolKind, SymbolLocation};
 use anyhow::Result;
+use serde_json::{Map, Value};
 use tree_sitter::{Node, Tree, TreeCursor};
 
 /// Extract symbols from a Python parse tree
-pub fn extract_symbols(tree: &Tree, source: &str) -> Result<Vec<Symbol>> {
+///
+/// Subtrees nested deeper than `config.max_depth` (default
+/// [`ExtractConfig::default`]) are skipped and reported as parse errors.
+pub fn extract_symbols(
+    tree: &Tree,
+    source: &str,
+    config: Option<ExtractConfig>,
+) -> Result<(Vec<Symbol>, Vec<ParseError>)> {
+    let config = config.unwrap_or_default();
     let mut symbols = Vec::new();
+    let mut errors = Vec::new();
     let root = tree.root_node();
     let mut cursor = root.walk();
 
-    extract_from_node(&mut cursor, source, &mut symbols, None, 0)?;
+    extract_from_node(&mut cursor, source, &mut symbols, None, 0, &config, &mut errors)?;
+
+    Ok((symbols, errors))
+}
 
-    Ok(symbols)
+/// Extract symbols, flagging those whose syntax `version` does not support
+///
+/// Functions marked `python2` (print/exec statements) are incompatible with
+/// Python 3, and functions with a `min_python` above `version` are too new;
+/// both get `metadata["version_mismatch"] = true`.
+pub fn extract_symbols_for_version(
+    tree: &Tree,
+    source: &str,
+    version: Option<(u8, u8)>,
+) -> Result<(Vec<Symbol>, Vec<ParseError>)> {
+    let (mut symbols, errors) = extract_symbols(tree, source, None)?;
+    if let Some(version) = version {
+        symbols.iter_mut().for_each(|symbol| mark_version_mismatch(symbol, version));
+    }
+    Ok((symbols, errors))
+}
+
+/// Set `version_mismatch` when the symbol's syntax flags conflict with `version`
+fn mark_version_mismatch(symbol: &mut Symbol, version: (u8, u8)) {
+    let Some(Value::Object(mut metadata)) = symbol
+        .metadata
+        .as_deref()
+        .and_then(|m| serde_json::from_str::<Value>(m).ok())
+    else {
+        return;
+    };
+
+    let python2_on_3 = version.0 >= 3 && metadata.get("python2") == Some(&Value::Bool(true));
+    let too_new = metadata
+        .get("min_python")
+        .and_then(Value::as_str)
+        .and_then(parse_version)
+        .is_some_and(|min| version < min);
+
+    if python2_on_3 || too_new {
+        metadata.insert("version_mismatch".to_string(), true.into());
+        symbol.metadata = Some(Value::Object(metadata).to_string());
+    }
+}
+
+/// Parse `"3.8"` into `(3, 8)`
+fn parse_version(version: &str) -> Option<(u8, u8)> {
+    let (major, minor) = version.split_once('.')?;
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+/// Dunder assignments that describe a package when found in `__init__.py`
+const PACKAGE_METADATA_NAMES: &[&str] = &["__version__", "__author__"];
+
+/// Flag module-level `__version__` and `__author__` with `metadata["package_metadata"] = true`
+///
+/// Meant for symbols extracted from a package's `__init__.py`.
+pub fn mark_package_metadata(symbols: &mut [Symbol]) {
+    for symbol in symbols.iter_mut().filter(|s| {
+        s.kind == SymbolKind::Variable && s.scope.is_none() && PACKAGE_METADATA_NAMES.contains(&s.name.as_str())
+    }) {
+        let mut metadata = match symbol.metadata.as_deref().and_then(|m| serde_json::from_str::<Value>(m).ok()) {
+            Some(Value::Object(map)) => map,
+            _ => Map::new(),
+        };
+        metadata.insert("package_metadata".to_string(), true.into());
+        symbol.metadata = Some(Value::Object(metadata).to_string());
+    }
 }
 
 /// Recursively extract symbols from a node
@@ -23,9 +98,18 @@ fn extract_from_node(
     source: &str,
     symbols: &mut Vec<Symbol>,
     parent_scope: Option<String>,
-    _file_id: i64,
+    depth: usize,
+    config: &ExtractConfig,
+    errors: &mut Vec<ParseError>,
 ) -> Result<()> {
     let node = cursor.node();
+    if depth >= config.max_depth {
+        // Leaf tokens cannot hold symbols, so only skipped subtrees are reported
+        if node.named_child_count() > 0 {
+            errors.push(ParseError::nesting_too_deep(&node, config.max_depth));
+        }
+        return Ok(());
+    }
 
     match node.kind() {
         "function_definition" => {
@@ -36,7 +120,7 @@ fn extract_from_node(
 
                 if cursor.goto_first_child() {
                     loop {
-                        extract_from_node(cursor, source, symbols, function_scope.clone(), _file_id)?;
+                        extract_from_node(cursor, source, symbols, function_scope.clone(), depth + 1, config, errors)?;
                         if !cursor.goto_next_sibling() {
                             break;
                         }
@@ -49,11 +133,17 @@ fn extract_from_node(
             if let Some(symbol) = extract_class(node, source, parent_scope.as_deref())? {
                 // Extract nested symbols from class body
                 let class_scope = Some(symbol.name.clone());
-                symbols.push(symbol);
+                if symbol.kind == SymbolKind::Struct {
+                    let fields = extract_named_tuple_class_fields(node, source, &symbol.name);
+                    symbols.push(symbol);
+                    symbols.extend(fields);
+                } else {
+                    symbols.push(symbol);
+                }
 
                 if cursor.goto_first_child() {
                     loop {
-                        extract_from_node(cursor, source, symbols, class_scope.clone(), _file_id)?;
+                        extract_from_node(cursor, source, symbols, class_scope.clone(), depth + 1, config, errors)?;
                         if !cursor.goto_next_sibling() {
                             break;
                         }
@@ -70,7 +160,9 @@ fn extract_from_node(
         "assignment" => {
             // Extract variable assignments (module-level only for now)
             if parent_scope.is_none() {
-                if let Some(symbol) = extract_variable(node, source)? {
+                if let Some(mut named_tuple) = extract_named_tuple_call(node, source) {
+                    symbols.append(&mut named_tuple);
+                } else if let Some(symbol) = extract_variable(node, source)? {
                     symbols.push(symbol);
                 }
             }
@@ -79,7 +171,7 @@ fn extract_from_node(
             // Recurse into children
             if cursor.goto_first_child() {
                 loop {
-                    extract_from_node(cursor, source, symbols, parent_scope.clone(), _file_id)?;
+                    extract_from_node(cursor, source, symbols, parent_scope.clone(), depth + 1, config, errors)?;
                     if !cursor.goto_next_sibling() {
                         break;
                     }
@@ -94,45 +186,137 @@ fn extract_from_node(
 
 /// Extract a function definition
 fn extract_function(node: Node, source: &str, scope: Option<&str>) -> Result<Option<Symbol>> {
-    let name_node = node
-        .child_by_field_name("name")
-        .ok_or_else(|| anyhow::anyhow!("Function has no name"))?;
-
-    let name = node_text(name_node, source);
-    let line_start = node.start_position().row;
-    let line_end = node.end_position().row;
-
     Ok(Some(Symbol {
-        id: None,
-        file_id: 0, // Will be set by caller
-        name,
-        kind: SymbolKind::Function,
-        line_start,
-        line_end,
-        scope: scope.map(|s| s.to_string()),
-        metadata: None,
+        metadata: function_metadata(node, source),
+        ..Symbol::try_from((node, source, symbol_kind(node)?, scope))?
     }))
 }
 
+/// Build function metadata: parameters, return type, decorators and version-specific syntax flags
+///
+/// Annotations are stored as written, so `Optional[T]`, `List[T]` and
+/// `X | Y` are opaque strings.
+///
+/// `@property`, `@classmethod` and `@staticmethod` set `is_property`,
+/// `is_classmethod` and `is_staticmethod`.
+fn function_metadata(node: Node, source: &str) -> Option<String> {
+    let mut metadata = function_syntax_flags(node);
+
+    let params = function_params(node, source);
+    if !params.is_empty() {
+        metadata.insert("params".to_string(), serde_json::json!(params));
+    }
+    if let Some(return_type) = node.child_by_field_name("return_type") {
+        metadata.insert("return_type".to_string(), node_text(return_type, source).into());
+    }
+
+    let decorators = decorator_names(node, source);
+    for (decorator, flag) in [
+        ("property", "is_property"),
+        ("classmethod", "is_classmethod"),
+        ("staticmethod", "is_staticmethod"),
+    ] {
+        if decorators.iter().any(|name| last_dotted_segment(name) == decorator) {
+            metadata.insert(flag.to_string(), true.into());
+        }
+    }
+    if !decorators.is_empty() {
+        metadata.insert("decorators".to_string(), serde_json::json!(decorators));
+    }
+
+    (!metadata.is_empty()).then(|| Value::Object(metadata).to_string())
+}
+
+/// Parameters of a function definition
+///
+/// Splat parameters keep their stars (`*args`, `**kwargs`); the bare `*` and
+/// `/` separators are skipped.
+fn function_params(node: Node, source: &str) -> Vec<FunctionParam> {
+    let Some(parameters) = node.child_by_field_name("parameters") else {
+        return Vec::new();
+    };
+    let type_name = |param: Node| param.child_by_field_name("type").map(|ty| node_text(ty, source));
+    let mut cursor = parameters.walk();
+    parameters
+        .named_children(&mut cursor)
+        .filter_map(|param| {
+            let name = match param.kind() {
+                "identifier" | "list_splat_pattern" | "dictionary_splat_pattern" => param,
+                "typed_parameter" => param.named_child(0)?,
+                "default_parameter" | "typed_default_parameter" => param.child_by_field_name("name")?,
+                _ => return None,
+            };
+            Some(FunctionParam { name: node_text(name, source), type_name: type_name(param) })
+        })
+        .collect()
+}
+
+/// Flag version-specific syntax in a function's own body (nested definitions
+/// are flagged on their own symbols)
+///
+/// `print x` / `exec code` statements set `python2`, `:=` sets `min_python: "3.8"`.
+fn function_syntax_flags(node: Node) -> Map<String, Value> {
+    let mut metadata = Map::new();
+    let Some(body) = node.child_by_field_name("body") else {
+        return metadata;
+    };
+
+    let mut stack = vec![body];
+    while let Some(current) = stack.pop() {
+        match current.kind() {
+            "function_definition" | "class_definition" => continue,
+            "print_statement" | "exec_statement" => {
+                metadata.insert("python2".to_string(), true.into());
+            }
+            "named_expression" => {
+                metadata.insert("min_python".to_string(), "3.8".into());
+            }
+            _ => {}
+        }
+
+        let mut cursor = current.walk();
+        stack.extend(current.children(&mut cursor));
+    }
+
+    metadata
+}
+
 /// Extract a class definition
+///
+/// Superclasses are stored as `metadata["extends"]` and decorators as
+/// `metadata["decorators"]`; `@dataclass` sets `is_dataclass`.
 fn extract_class(node: Node, source: &str, scope: Option<&str>) -> Result<Option<Symbol>> {
-    let name_node = node
-        .child_by_field_name("name")
-        .ok_or_else(|| anyhow::anyhow!("Class has no name"))?;
+    // `class Point(NamedTuple)` defines a structural type,
+    // `class Drawable(Protocol)` a structural interface
+    let mut metadata = Map::new();
+    let kind = if has_base_class(node, source, "NamedTuple") {
+        SymbolKind::Struct
+    } else if has_base_class(node, source, "Protocol") {
+        metadata.insert("protocol".to_string(), true.into());
+        if has_decorator(node, source, "runtime_checkable") {
+            metadata.insert("runtime_checkable".to_string(), true.into());
+        }
+        SymbolKind::Interface
+    } else {
+        symbol_kind(node)?
+    };
 
-    let name = node_text(name_node, source);
-    let line_start = node.start_position().row;
-    let line_end = node.end_position().row;
+    let bases = base_classes(node, source);
+    if !bases.is_empty() {
+        metadata.insert("extends".to_string(), serde_json::json!(bases));
+    }
+
+    let decorators = decorator_names(node, source);
+    if has_decorator(node, source, "dataclass") {
+        metadata.insert("is_dataclass".to_string(), true.into());
+    }
+    if !decorators.is_empty() {
+        metadata.insert("decorators".to_string(), serde_json::json!(decorators));
+    }
 
     Ok(Some(Symbol {
-        id: None,
-        file_id: 0,
-        name,
-        kind: SymbolKind::Class,
-        line_start,
-        line_end,
-        scope: scope.map(|s| s.to_string()),
-        metadata: None,
+        metadata: (!metadata.is_empty()).then(|| Value::Object(metadata).to_string()),
+        ..Symbol::try_from((node, source, kind, scope))?
     }))
 }
 
@@ -150,25 +334,20 @@ fn extract_import(node: Node, source: &str, scope: Option<&str>) -> Result<Optio
     };
 
     if let Some(name) = name {
-        let line_start = node.start_position().row;
-        let line_end = node.end_position().row;
-
-        Ok(Some(Symbol {
-            id: None,
-            file_id: 0,
+        Ok(Some(Symbol::with_location(
             name,
-            kind: SymbolKind::Import,
-            line_start,
-            line_end,
-            scope: scope.map(|s| s.to_string()),
-            metadata: None,
-        }))
+            symbol_kind(node)?,
+            SymbolLocation::from_node(&node),
+            scope,
+        )))
     } else {
         Ok(None)
     }
 }
 
 /// Extract a variable assignment
+///
+/// `ALL_CAPS` names are treated as constants by convention.
 fn extract_variable(node: Node, source: &str) -> Result<Option<Symbol>> {
     // Get the left side of the assignment
     let left = node.child_by_field_name("left");
@@ -176,25 +355,219 @@ fn extract_variable(node: Node, source: &str) -> Result<Option<Symbol>> {
     if let Some(left_node) = left {
         if left_node.kind() == "identifier" {
             let name = node_text(left_node, source);
-            let line_start = node.start_position().row;
-            let line_end = node.end_position().row;
-
-            return Ok(Some(Symbol {
-                id: None,
-                file_id: 0,
+            let kind = if is_constant_name(&name) {
+                SymbolKind::Constant
+            } else {
+                symbol_kind(node)?
+            };
+            return Ok(Some(Symbol::with_location(
                 name,
-                kind: SymbolKind::Variable,
-                line_start,
-                line_end,
-                scope: None,
-                metadata: None,
-            }));
+                kind,
+                SymbolLocation::from_node(&node),
+                None,
+            )));
         }
     }
 
     Ok(None)
 }
 
+/// Whether a name follows the `UPPER_SNAKE_CASE` constant convention
+fn is_constant_name(name: &str) -> bool {
+    name.chars().any(|c| c.is_ascii_uppercase()) && !name.chars().any(|c| c.is_lowercase())
+}
+
+/// Check whether a class lists `base` (optionally module-qualified) among its superclasses
+fn has_base_class(node: Node, source: &str, base: &str) -> bool {
+    base_classes(node, source)
+        .iter()
+        .any(|name| last_dotted_segment(name) == base)
+}
+
+/// Names of a class's superclasses, in declaration order
+///
+/// Type arguments are dropped (`Generic[T]` -> `Generic`) and keyword
+/// arguments such as `metaclass=ABCMeta` are skipped.
+fn base_classes(node: Node, source: &str) -> Vec<String> {
+    let Some(superclasses) = node.child_by_field_name("superclasses") else {
+        return Vec::new();
+    };
+
+    let mut cursor = superclasses.walk();
+    let names = superclasses
+        .named_children(&mut cursor)
+        .filter_map(|arg| match arg.kind() {
+            "identifier" | "attribute" => Some(node_text(arg, source)),
+            "subscript" => arg.child_by_field_name("value").map(|value| node_text(value, source)),
+            _ => None,
+        })
+        .collect();
+    names
+}
+
+/// Check whether a definition is decorated with `name` (optionally module-qualified)
+fn has_decorator(node: Node, source: &str, name: &str) -> bool {
+    decorator_names(node, source)
+        .iter()
+        .any(|decorator| last_dotted_segment(decorator) == name)
+}
+
+/// Names of the decorators applied to a definition, top to bottom
+///
+/// Arguments are dropped: `@app.route("/")` -> `app.route`.
+fn decorator_names(node: Node, source: &str) -> Vec<String> {
+    let Some(parent) = node.parent().filter(|p| p.kind() == "decorated_definition") else {
+        return Vec::new();
+    };
+
+    let mut cursor = parent.walk();
+    let names = parent
+        .named_children(&mut cursor)
+        .filter(|child| child.kind() == "decorator")
+        .map(|decorator| {
+            let text = node_text(decorator, source);
+            let text = text.trim_start_matches('@').trim();
+            text.split('(').next().unwrap_or(text).trim().to_string()
+        })
+        .collect();
+    names
+}
+
+/// Extract annotated fields (`x: int`) from a `NamedTuple` class body
+fn extract_named_tuple_class_fields(node: Node, source: &str, class_name: &str) -> Vec<Symbol> {
+    let mut fields = Vec::new();
+    let Some(body) = node.child_by_field_name("body") else {
+        return fields;
+    };
+
+    let mut cursor = body.walk();
+    for statement in body.named_children(&mut cursor) {
+        if statement.kind() != "expression_statement" {
+            continue;
+        }
+        let Some(assignment) = statement.named_child(0) else {
+            continue;
+        };
+        if assignment.kind() != "assignment" {
+            continue;
+        }
+        let (Some(left), Some(annotation)) = (
+            assignment.child_by_field_name("left"),
+            assignment.child_by_field_name("type"),
+        ) else {
+            continue;
+        };
+        if left.kind() != "identifier" {
+            continue;
+        }
+
+        fields.push(field_symbol(
+            node_text(left, source),
+            assignment,
+            class_name,
+            Some(node_text(annotation, source)),
+        ));
+    }
+
+    fields
+}
+
+/// Extract `Point = namedtuple("Point", ["x", "y"])` (or `NamedTuple(...)`) as a struct
+/// followed by its fields
+fn extract_named_tuple_call(node: Node, source: &str) -> Option<Vec<Symbol>> {
+    let left = node.child_by_field_name("left")?;
+    let right = node.child_by_field_name("right")?;
+    if left.kind() != "identifier" || right.kind() != "call" {
+        return None;
+    }
+
+    let callee = node_text(right.child_by_field_name("function")?, source);
+    if !matches!(last_dotted_segment(&callee), "namedtuple" | "NamedTuple") {
+        return None;
+    }
+
+    let name = node_text(left, source);
+    let mut symbols = vec![Symbol::with_location(
+        name.clone(),
+        SymbolKind::Struct,
+        SymbolLocation::from_node(&node),
+        None,
+    )];
+
+    // Second positional argument holds the field names
+    let arguments = right.child_by_field_name("arguments")?;
+    let mut cursor = arguments.walk();
+    let Some(field_spec) = arguments.named_children(&mut cursor).nth(1) else {
+        return Some(symbols);
+    };
+
+    match field_spec.kind() {
+        // "x y" or "x, y"
+        "string" => {
+            let text = string_content(field_spec, source);
+            for field in text.split(|c: char| c == ',' || c.is_whitespace()) {
+                if !field.is_empty() {
+                    symbols.push(field_symbol(field.to_string(), field_spec, &name, None));
+                }
+            }
+        }
+        // ["x", "y"] or [("x", int), ("y", int)]
+        "list" | "tuple" => {
+            let mut cursor = field_spec.walk();
+            for element in field_spec.named_children(&mut cursor) {
+                let (field_node, annotation) = match element.kind() {
+                    "string" => (element, None),
+                    "tuple" => {
+                        let Some(first) = element.named_child(0) else {
+                            continue;
+                        };
+                        let annotation = element.named_child(1).map(|t| node_text(t, source));
+                        (first, annotation)
+                    }
+                    _ => continue,
+                };
+                if field_node.kind() == "string" {
+                    let field = string_content(field_node, source);
+                    symbols.push(field_symbol(field, element, &name, annotation));
+                }
+            }
+        }
+        _ => {}
+    }
+
+    Some(symbols)
+}
+
+/// Build a field symbol scoped to its owning type
+fn field_symbol(name: String, node: Node, owner: &str, annotation: Option<String>) -> Symbol {
+    Symbol {
+        metadata: annotation.map(|a| serde_json::json!({ "type": a }).to_string()),
+        ..Symbol::with_location(name, SymbolKind::Field, SymbolLocation::from_node(&node), Some(owner))
+    }
+}
+
+/// Text of a string literal without its quotes/prefix
+fn string_content(node: Node, source: &str) -> String {
+    let mut cursor = node.walk();
+    let content = node
+        .named_children(&mut cursor)
+        .filter(|child| child.kind() == "string_content")
+        .map(|child| node_text(child, source))
+        .collect::<String>();
+    content
+}
+
+/// `collections.namedtuple` -> `namedtuple`
+fn last_dotted_segment(name: &str) -> &str {
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+/// Look up the symbol kind for a node in the shared mapping table
+fn symbol_kind(node: Node) -> Result<SymbolKind> {
+    SymbolKind::from_file_node_kind("python", node.kind())
+        .ok_or_else(|| anyhow::anyhow!("Node kind `{}` does not define a symbol", node.kind()))
+}
+
 /// Get text content of a node
 fn node_text(node: Node, source: &str) -> String {
     source[node.byte_range()].to_string()
@@ -213,7 +586,7 @@ def my_function():
 "#;
         let mut parser = PythonParser::new().unwrap();
         let tree = parser.parse(source).unwrap();
-        let symbols = extract_symbols(&tree, source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
 
         assert_eq!(symbols.len(), 1);
         assert_eq!(symbols[0].name, "my_function");
@@ -232,7 +605,7 @@ class MyClass:
 "#;
         let mut parser = PythonParser::new().unwrap();
         let tree = parser.parse(source).unwrap();
-        let symbols = extract_symbols(&tree, source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
 
         // Should find class + 2 methods
         assert!(symbols.len() >= 3);
@@ -249,9 +622,353 @@ from pathlib import Path
 "#;
         let mut parser = PythonParser::new().unwrap();
         let tree = parser.parse(source).unwrap();
-        let symbols = extract_symbols(&tree, source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
 
         assert!(symbols.iter().any(|s| s.name == "os" && matches!(s.kind, SymbolKind::Import)));
         assert!(symbols.iter().any(|s| s.name == "pathlib" && matches!(s.kind, SymbolKind::Import)));
     }
+
+    #[test]
+    fn test_extract_namedtuple_call() {
+        let source = r#"
+from collections import namedtuple
+Point = namedtuple("Point", ["x", "y"])
+Pair = collections.namedtuple('Pair', 'left right')
+"#;
+        let mut parser = PythonParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+
+        assert!(symbols.iter().any(|s| s.name == "Point" && s.kind == SymbolKind::Struct));
+        for field in ["x", "y"] {
+            assert!(symbols.iter().any(|s| s.name == field
+                && s.kind == SymbolKind::Field
+                && s.scope.as_deref() == Some("Point")));
+        }
+        assert!(symbols.iter().any(|s| s.name == "Pair" && s.kind == SymbolKind::Struct));
+        assert!(symbols.iter().any(|s| s.name == "right" && s.scope.as_deref() == Some("Pair")));
+        assert!(!symbols.iter().any(|s| s.kind == SymbolKind::Variable));
+    }
+
+    #[test]
+    fn test_extract_namedtuple_class() {
+        let source = r#"
+class Point(NamedTuple):
+    x: int
+    y: int = 0
+
+class Plain(Base):
+    z: int
+"#;
+        let mut parser = PythonParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+
+        assert!(symbols.iter().any(|s| s.name == "Point" && s.kind == SymbolKind::Struct));
+        let x = symbols.iter().find(|s| s.name == "x").unwrap();
+        assert_eq!(x.kind, SymbolKind::Field);
+        assert_eq!(x.scope.as_deref(), Some("Point"));
+        assert_eq!(x.metadata.as_deref(), Some(r#"{"type":"int"}"#));
+        assert!(symbols.iter().any(|s| s.name == "y" && s.kind == SymbolKind::Field));
+
+        assert!(symbols.iter().any(|s| s.name == "Plain" && s.kind == SymbolKind::Class));
+        assert!(!symbols.iter().any(|s| s.name == "z"));
+    }
+
+    #[test]
+    fn test_extract_protocol_class() {
+        let source = r#"
+from typing import Protocol, runtime_checkable
+import typing
+
+class Drawable(Protocol):
+    def draw(self) -> None: ...
+
+@runtime_checkable
+class Closeable(typing.Protocol):
+    def close(self) -> None: ...
+
+class Container(Protocol[T]):
+    pass
+"#;
+        let mut parser = PythonParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+
+        let drawable = symbols.iter().find(|s| s.name == "Drawable").unwrap();
+        assert_eq!(drawable.kind, SymbolKind::Interface);
+        assert_eq!(drawable.metadata.as_deref(), Some(r#"{"extends":["Protocol"],"protocol":true}"#));
+        assert!(symbols.iter().any(|s| s.name == "draw" && s.scope.as_deref() == Some("Drawable")));
+
+        let closeable = symbols.iter().find(|s| s.name == "Closeable").unwrap();
+        assert_eq!(closeable.kind, SymbolKind::Interface);
+        assert_eq!(
+            closeable.metadata.as_deref(),
+            Some(r#"{"decorators":["runtime_checkable"],"extends":["typing.Protocol"],"protocol":true,"runtime_checkable":true}"#)
+        );
+
+        let container = symbols.iter().find(|s| s.name == "Container").unwrap();
+        assert_eq!(container.kind, SymbolKind::Interface);
+    }
+
+    #[test]
+    fn test_extract_base_classes() {
+        let source = r#"
+class Animal:
+    pass
+
+class Dog(Animal, mixins.Serializable, Generic[T], metaclass=ABCMeta):
+    pass
+"#;
+        let mut parser = PythonParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+
+        assert!(symbols.iter().find(|s| s.name == "Animal").unwrap().metadata.is_none());
+        let dog = symbols.iter().find(|s| s.name == "Dog").unwrap();
+        assert_eq!(
+            metadata(dog)["extends"],
+            serde_json::json!(["Animal", "mixins.Serializable", "Generic"])
+        );
+    }
+
+    fn metadata(symbol: &Symbol) -> Value {
+        symbol
+            .metadata
+            .as_deref()
+            .map(|m| serde_json::from_str(m).unwrap())
+            .unwrap_or(Value::Null)
+    }
+
+    #[test]
+    fn test_extract_function_params() {
+        let source = r#"
+def f(self, a, b: int, c=1, d: str = "x", *args, e, **kwargs): pass
+def g(a, /, *, b, **kw: Any) -> None: pass
+def h(): pass
+"#;
+        let mut parser = PythonParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+
+        let params = |name: &str| metadata(symbols.iter().find(|s| s.name == name).unwrap())["params"].clone();
+        assert_eq!(
+            params("f"),
+            serde_json::json!([
+                {"name": "self", "type": null},
+                {"name": "a", "type": null},
+                {"name": "b", "type": "int"},
+                {"name": "c", "type": null},
+                {"name": "d", "type": "str"},
+                {"name": "*args", "type": null},
+                {"name": "e", "type": null},
+                {"name": "**kwargs", "type": null},
+            ])
+        );
+        assert_eq!(
+            params("g"),
+            serde_json::json!([
+                {"name": "a", "type": null},
+                {"name": "b", "type": null},
+                {"name": "**kw", "type": "Any"},
+            ])
+        );
+        assert!(symbols.iter().find(|s| s.name == "h").unwrap().metadata.is_none());
+    }
+
+    #[test]
+    fn test_extract_type_hints() {
+        let source = r#"
+async def fetch(url: str, timeout: Optional[float] = None) -> List[bytes]: pass
+def parse(raw, strict: bool = False) -> dict[str, int] | None: pass
+def plain(a, b): pass
+"#;
+        let mut parser = PythonParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+        let function = |name: &str| metadata(symbols.iter().find(|s| s.name == name).unwrap());
+
+        // Fully annotated
+        let fetch = function("fetch");
+        assert_eq!(
+            fetch["params"],
+            serde_json::json!([
+                {"name": "url", "type": "str"},
+                {"name": "timeout", "type": "Optional[float]"},
+            ])
+        );
+        assert_eq!(fetch["return_type"], "List[bytes]");
+
+        // Partially annotated, union return type kept as written
+        let parse = function("parse");
+        assert_eq!(parse["params"][0], serde_json::json!({"name": "raw", "type": null}));
+        assert_eq!(parse["params"][1], serde_json::json!({"name": "strict", "type": "bool"}));
+        assert_eq!(parse["return_type"], "dict[str, int] | None");
+
+        // Unannotated
+        let plain = function("plain");
+        assert_eq!(plain["params"][1], serde_json::json!({"name": "b", "type": null}));
+        assert!(plain.get("return_type").is_none());
+    }
+
+    #[test]
+    fn test_extract_decorators() {
+        let source = r#"
+@dataclasses.dataclass(frozen=True)
+@total_ordering
+class Point:
+    @property
+    def norm(self): pass
+
+    @classmethod
+    def origin(cls): pass
+
+    @staticmethod
+    @functools.lru_cache(maxsize=None)
+    def parse(text): pass
+
+@click.command()
+@click.option("--name", default="world")
+def hello(name): pass
+
+def plain(): pass
+"#;
+        let mut parser = PythonParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+        let find = |name: &str| metadata(symbols.iter().find(|s| s.name == name).unwrap());
+
+        let point = find("Point");
+        assert_eq!(point["decorators"], serde_json::json!(["dataclasses.dataclass", "total_ordering"]));
+        assert_eq!(point["is_dataclass"], true);
+
+        assert_eq!(find("norm")["is_property"], true);
+        assert_eq!(find("origin")["is_classmethod"], true);
+        let parse = find("parse");
+        assert_eq!(parse["is_staticmethod"], true);
+        assert_eq!(parse["decorators"], serde_json::json!(["staticmethod", "functools.lru_cache"]));
+        assert!(parse.get("is_property").is_none());
+
+        let hello = find("hello");
+        assert_eq!(hello["decorators"], serde_json::json!(["click.command", "click.option"]));
+        assert!(hello.get("is_staticmethod").is_none());
+        assert!(symbols.iter().find(|s| s.name == "plain").unwrap().metadata.is_none());
+    }
+
+    #[test]
+    fn test_python2_print_statement() {
+        let source = r#"
+def greet(name):
+    print "Hello, %s" % name
+
+def modern(name):
+    print("Hello", name)
+"#;
+        let mut parser = PythonParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+
+        let greet = symbols.iter().find(|s| s.name == "greet").unwrap();
+        assert_eq!(metadata(greet)["python2"], true);
+        let modern = symbols.iter().find(|s| s.name == "modern").unwrap();
+        assert!(metadata(modern).get("python2").is_none());
+
+        // Targeting Python 3 flags the statement as incompatible
+        let (symbols, _) = extract_symbols_for_version(&tree, source, Some((3, 12))).unwrap();
+        let greet = symbols.iter().find(|s| s.name == "greet").unwrap();
+        assert_eq!(metadata(greet)["version_mismatch"], true);
+
+        let (symbols, _) = extract_symbols_for_version(&tree, source, Some((2, 7))).unwrap();
+        let greet = symbols.iter().find(|s| s.name == "greet").unwrap();
+        assert!(metadata(greet).get("version_mismatch").is_none());
+    }
+
+    #[test]
+    fn test_python3_walrus_operator() {
+        let source = r#"
+def first_long(items):
+    if (n := len(items)) > 10:
+        return n
+
+    def inner():
+        exec "pass"
+"#;
+        let mut parser = PythonParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        assert!(!tree.root_node().has_error());
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+
+        let outer = symbols.iter().find(|s| s.name == "first_long").unwrap();
+        assert_eq!(metadata(outer)["min_python"], "3.8");
+        // The nested function's exec statement is not attributed to the outer one
+        assert!(metadata(outer).get("python2").is_none());
+        let inner = symbols.iter().find(|s| s.name == "inner").unwrap();
+        assert_eq!(metadata(inner)["python2"], true);
+
+        for (version, mismatch) in [((3, 8), false), ((3, 7), true), ((2, 7), true)] {
+            let (symbols, _) = extract_symbols_for_version(&tree, source, Some(version)).unwrap();
+            let outer = symbols.iter().find(|s| s.name == "first_long").unwrap();
+            assert_eq!(metadata(outer).get("version_mismatch").is_some(), mismatch, "{:?}", version);
+        }
+    }
+
+    #[test]
+    fn test_mark_package_metadata() {
+        let source = r#"
+__version__ = "1.2.0"
+__author__ = "Jane"
+__all__ = ["load"]
+
+def load():
+    __version__ = "local"
+"#;
+        let mut parser = PythonParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (mut symbols, _) = extract_symbols(&tree, source, None).unwrap();
+        mark_package_metadata(&mut symbols);
+
+        let flagged: Vec<&str> = symbols
+            .iter()
+            .filter(|s| metadata(s)["package_metadata"] == true)
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(flagged, vec!["__version__", "__author__"]);
+    }
+
+    #[test]
+    fn test_all_caps_assignments_are_constants() {
+        let source = "MAX_RETRIES = 3\n_DEFAULT_TIMEOUT_2 = 1.5\nretries = 0\nHttpClient = None\n\ndef f():\n    LOCAL = 1\n";
+        let mut parser = PythonParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+
+        let kind = |name: &str| symbols.iter().find(|s| s.name == name).map(|s| s.kind);
+        assert_eq!(kind("MAX_RETRIES"), Some(SymbolKind::Constant));
+        assert_eq!(kind("_DEFAULT_TIMEOUT_2"), Some(SymbolKind::Constant));
+        assert_eq!(kind("retries"), Some(SymbolKind::Variable));
+        assert_eq!(kind("HttpClient"), Some(SymbolKind::Variable));
+        assert_eq!(kind("LOCAL"), None);
+    }
+
+    #[test]
+    fn test_max_depth_skips_deep_nesting() {
+        let source: String = (0..6)
+            .map(|i| format!("{}def f{}():\n", "    ".repeat(i), i))
+            .chain(std::iter::once(format!("{}pass\n", "    ".repeat(6))))
+            .collect();
+        let mut parser = PythonParser::new().unwrap();
+        let tree = parser.parse(&source).unwrap();
+
+        let (symbols, errors) = extract_symbols(&tree, &source, None).unwrap();
+        assert_eq!(symbols.len(), 6);
+        assert!(errors.is_empty());
+
+        // Each nested def adds a definition and a block level
+        let (symbols, errors) = extract_symbols(&tree, &source, Some(ExtractConfig { max_depth: 4 })).unwrap();
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["f0", "f1"]);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].message.contains("4 levels"));
+        assert_eq!(errors[0].line, 2);
+    }
 }
@@ -5,23 +5,42 @@
 pub mod parser;
 pub mod symbol_extract;
 
-pub use parser::RustParser;
+pub use parser::{RustParser, RustParserPool};
 pub use symbol_extract::extract_symbols;
 
-use analyzer_core::Symbol;
-use anyhow::Result;
+use analyzer_core::registry::IncrementalAnalyzerOutput;
+use analyzer_core::{IndexingError, ParseError, Symbol};
+use tree_sitter::Tree;
 
 /// Analyze a Rust source file and extract symbols
-pub fn analyze_rust(source: &str) -> Result<Vec<Symbol>> {
-    let mut parser = RustParser::new()?;
-    let tree = parser.parse(source)?;
-    let symbols = extract_symbols(&tree, source)?;
-    Ok(symbols)
+pub fn analyze_rust(source: &str) -> Result<(Vec<Symbol>, Vec<ParseError>), IndexingError> {
+    let (symbols, errors, _) = analyze_rust_incremental(source, None)?;
+    Ok((symbols, errors))
+}
+
+/// Analyze a Rust source file, re-parsing against `old_tree` when given
+///
+/// `old_tree` must already be edited to match `source`. The new tree is
+/// returned for the next incremental parse.
+pub fn analyze_rust_incremental(
+    source: &str,
+    old_tree: Option<&Tree>,
+) -> Result<IncrementalAnalyzerOutput, IndexingError> {
+    let mut parser = RustParser::new().map_err(|e| IndexingError::parse_failed("rust", e))?;
+    let tree = match old_tree {
+        Some(old_tree) => parser.parse_with_old_tree(source, old_tree),
+        None => parser.parse(source),
+    }
+    .map_err(|e| IndexingError::parse_failed("rust", e))?;
+    let (symbols, mut errors) = extract_symbols(&tree, source, None).map_err(IndexingError::extraction_failed)?;
+    errors.splice(0..0, ParseError::from_tree(&tree, source));
+    Ok((symbols, errors, tree))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use analyzer_core::SymbolKind;
 
     #[test]
     fn test_analyze_simple_function() {
@@ -30,7 +49,7 @@ fn hello_world() {
     println!("Hello, world!");
 }
 "#;
-        let symbols = analyze_rust(source).unwrap();
+        let (symbols, _) = analyze_rust(source).unwrap();
         assert!(symbols.iter().any(|s| s.name == "hello_world" && matches!(s.kind, SymbolKind::Function)));
     }
 
@@ -51,8 +70,8 @@ impl MyStruct {
     }
 }
 "#;
-        let symbols = analyze_rust(source).unwrap();
-        assert!(symbols.iter().any(|s| s.name == "MyStruct" && matches!(s.kind, SymbolKind::Class)));
+        let (symbols, _) = analyze_rust(source).unwrap();
+        assert!(symbols.iter().any(|s| s.name == "MyStruct" && matches!(s.kind, SymbolKind::Struct)));
         assert!(symbols.iter().any(|s| s.name == "new" && matches!(s.kind, SymbolKind::Function)));
         assert!(symbols.iter().any(|s| s.name == "get_value" && matches!(s.kind, SymbolKind::Function)));
     }
@@ -64,7 +83,34 @@ trait MyTrait {
     fn do_something(&self);
 }
 "#;
-        let symbols = analyze_rust(source).unwrap();
-        assert!(symbols.iter().any(|s| s.name == "MyTrait" && matches!(s.kind, SymbolKind::Type)));
+        let (symbols, _) = analyze_rust(source).unwrap();
+        assert!(symbols.iter().any(|s| s.name == "MyTrait" && matches!(s.kind, SymbolKind::Trait)));
+    }
+
+    #[test]
+    fn test_analyze_incremental_matches_full_parse() {
+        let old_source = "fn first() {}\n\nfn third() {}\n";
+        let (_, _, mut tree) = analyze_rust_incremental(old_source, None).unwrap();
+
+        let new_source = "fn first() {}\n\nfn second() {}\n\nfn third() {}\n";
+        tree.edit(&analyzer_core::incremental::input_edit(old_source, new_source));
+        let (symbols, errors, _) = analyze_rust_incremental(new_source, Some(&tree)).unwrap();
+
+        assert_eq!((symbols, errors), analyze_rust(new_source).unwrap());
+    }
+
+    #[test]
+    fn test_analyze_reports_parse_errors() {
+        let source = r#"
+fn ok() {}
+
+fn broken( {
+}
+"#;
+        let (symbols, errors) = analyze_rust(source).unwrap();
+        assert!(symbols.iter().any(|s| s.name == "ok"));
+        assert!(!errors.is_empty());
+        assert!(errors.iter().all(|e| e.line == 3));
+        assert!(analyze_rust("fn f() {}\n").unwrap().1.is_empty());
     }
 }
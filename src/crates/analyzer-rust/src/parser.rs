@@ -2,6 +2,7 @@
 //!
 //! Wraps the tree-sitter-rust parser for use in the analyzer.
 
+use analyzer_core::pool::{ParserPool, ResettableParser};
 use anyhow::{Context, Result};
 use tree_sitter::{Parser, Tree};
 
@@ -34,6 +35,11 @@ impl RustParser {
             .parse(source, Some(old_tree))
             .context("Failed to incrementally parse Rust source")
     }
+
+    /// Clear per-file parse state, keeping the loaded grammar
+    pub fn reset(&mut self) {
+        self.parser.reset();
+    }
 }
 
 impl Default for RustParser {
@@ -42,6 +48,15 @@ impl Default for RustParser {
     }
 }
 
+impl ResettableParser for RustParser {
+    fn reset(&mut self) {
+        RustParser::reset(self);
+    }
+}
+
+/// Pool of reusable Rust parsers (`RustParserPool::new(size)`)
+pub type RustParserPool = ParserPool<RustParser>;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -76,4 +91,19 @@ mod tests {
         // Tree-sitter should still produce a tree even with errors
         assert!(tree.root_node().has_error());
     }
+
+    #[test]
+    fn test_reset_and_pool_reuse() {
+        let mut parser = RustParser::new().unwrap();
+        parser.parse("fn broken(").unwrap();
+        parser.reset();
+        assert!(!parser.parse("fn ok() {}").unwrap().root_node().has_error());
+
+        let pool = RustParserPool::new(2);
+        for source in ["struct A;", "fn b() {}", "enum C { D }"] {
+            let tree = pool.acquire().parse(source).unwrap();
+            assert!(!tree.root_node().has_error());
+        }
+        assert_eq!(pool.available(), 2);
+    }
 }
@@ -1,20 +1,31 @@
 //! Rust symbol extraction
 //!
-//! Extracts functions, structs, enums, traits, and imports from Rust AST.
+//! Extracts functions, structs, enums, traits, macros, modules, imports, and FFI declarations from Rust AST.
 
-use analyzer_core::{Symbol, SymbolKind};
+use analyzer_core::{ExtractConfig, FunctionParam, ParseError, Symbol, SymbolKind, SymbolLocation};
 use anyhow::Result;
+use serde_json::{json, Map, Value};
 use tree_sitter::{Node, Tree, TreeCursor};
 
 /// Extract symbols from a Rust parse tree
-pub fn extract_symbols(tree: &Tree, source: &str) -> Result<Vec<Symbol>> {
+///
+/// Subtrees nested deeper than `config.max_depth` (default
+/// [`ExtractConfig::default`]) are skipped and reported as parse errors.
+pub fn extract_symbols(
+    tree: &Tree,
+    source: &str,
+    config: Option<ExtractConfig>,
+) -> Result<(Vec<Symbol>, Vec<ParseError>)> {
+    let config = config.unwrap_or_default();
     let mut symbols = Vec::new();
+    let mut errors = Vec::new();
     let root = tree.root_node();
     let mut cursor = root.walk();
 
-    extract_from_node(&mut cursor, source, &mut symbols, None, 0)?;
+    extract_from_node(&mut cursor, source, &mut symbols, None, 0, &config, &mut errors)?;
+    attach_trait_impls(root, source, &mut symbols);
 
-    Ok(symbols)
+    Ok((symbols, errors))
 }
 
 /// Recursively extract symbols from a node
@@ -23,19 +34,33 @@ fn extract_from_node(
     source: &str,
     symbols: &mut Vec<Symbol>,
     parent_scope: Option<String>,
-    _file_id: i64,
+    depth: usize,
+    config: &ExtractConfig,
+    errors: &mut Vec<ParseError>,
 ) -> Result<()> {
     let node = cursor.node();
+    if depth >= config.max_depth {
+        // Leaf tokens cannot hold symbols, so only skipped subtrees are reported
+        if node.named_child_count() > 0 {
+            errors.push(ParseError::nesting_too_deep(&node, config.max_depth));
+        }
+        return Ok(());
+    }
 
     match node.kind() {
         "function_item" => {
-            if let Some(symbol) = extract_function(node, source, parent_scope.as_deref())? {
+            if let Some(mut symbol) = extract_named_item(node, source, parent_scope.as_deref())? {
+                symbol.metadata = extract_function_metadata(node, source);
+                attach_visibility(node, source, &mut symbol);
+                if proc_macro_attribute(node, source).is_some() {
+                    symbol.kind = SymbolKind::Macro;
+                }
                 let function_scope = Some(symbol.name.clone());
                 symbols.push(symbol);
 
                 if cursor.goto_first_child() {
                     loop {
-                        extract_from_node(cursor, source, symbols, function_scope.clone(), _file_id)?;
+                        extract_from_node(cursor, source, symbols, function_scope.clone(), depth + 1, config, errors)?;
                         if !cursor.goto_next_sibling() {
                             break;
                         }
@@ -44,23 +69,25 @@ fn extract_from_node(
                 }
             }
         }
-        "struct_item" => {
-            if let Some(symbol) = extract_struct(node, source, parent_scope.as_deref())? {
-                symbols.push(symbol);
-            }
-        }
         "enum_item" => {
-            if let Some(symbol) = extract_enum(node, source, parent_scope.as_deref())? {
+            if let Some(mut symbol) = extract_named_item(node, source, parent_scope.as_deref())? {
+                attach_visibility(node, source, &mut symbol);
+                let variants = extract_enum_variants(node, source, &symbol.name)?;
                 symbols.push(symbol);
+                symbols.extend(variants);
             }
         }
-        "trait_item" => {
-            if let Some(symbol) = extract_trait(node, source, parent_scope.as_deref())? {
+        "struct_item" => {
+            if let Some(mut symbol) = extract_named_item(node, source, parent_scope.as_deref())? {
+                attach_visibility(node, source, &mut symbol);
+                let fields = extract_struct_fields(node, source, &symbol.name);
                 symbols.push(symbol);
+                symbols.extend(fields);
             }
         }
-        "type_item" => {
-            if let Some(symbol) = extract_type_alias(node, source, parent_scope.as_deref())? {
+        "trait_item" | "type_item" => {
+            if let Some(mut symbol) = extract_named_item(node, source, parent_scope.as_deref())? {
+                attach_visibility(node, source, &mut symbol);
                 symbols.push(symbol);
             }
         }
@@ -69,7 +96,7 @@ fn extract_from_node(
             if let Some(impl_scope) = extract_impl_scope(node, source)? {
                 if cursor.goto_first_child() {
                     loop {
-                        extract_from_node(cursor, source, symbols, Some(impl_scope.clone()), _file_id)?;
+                        extract_from_node(cursor, source, symbols, Some(impl_scope.clone()), depth + 1, config, errors)?;
                         if !cursor.goto_next_sibling() {
                             break;
                         }
@@ -78,15 +105,41 @@ fn extract_from_node(
                 }
             }
         }
+        "mod_item" => {
+            let symbol = extract_module(node, source, parent_scope.as_deref())?;
+            let module_scope = Some(symbol.name.clone());
+            symbols.push(symbol);
+
+            // Inline modules: items inside are scoped to the module
+            if cursor.goto_first_child() {
+                loop {
+                    extract_from_node(cursor, source, symbols, module_scope.clone(), depth + 1, config, errors)?;
+                    if !cursor.goto_next_sibling() {
+                        break;
+                    }
+                }
+                cursor.goto_parent();
+            }
+        }
+        "macro_definition" => {
+            symbols.push(extract_macro_definition(node, source, parent_scope.as_deref())?);
+        }
         "use_declaration" => {
             if let Some(symbol) = extract_use(node, source, parent_scope.as_deref())? {
                 symbols.push(symbol);
             }
         }
+        "extern_crate_declaration" => {
+            symbols.push(extract_extern_crate(node, source, parent_scope.as_deref())?);
+        }
+        "foreign_mod_item" => {
+            symbols.extend(extract_foreign_items(node, source, parent_scope.as_deref())?);
+        }
         "const_item" | "static_item" => {
             // Extract constants and static variables (module-level only for now)
-            if parent_scope.is_none() {
-                if let Some(symbol) = extract_constant(node, source)? {
+            if parent_scope.is_none() || declared_in_module(node) {
+                if let Some(mut symbol) = extract_constant(node, source, parent_scope.as_deref())? {
+                    attach_visibility(node, source, &mut symbol);
                     symbols.push(symbol);
                 }
             }
@@ -95,7 +148,7 @@ fn extract_from_node(
             // Recurse into children
             if cursor.goto_first_child() {
                 loop {
-                    extract_from_node(cursor, source, symbols, parent_scope.clone(), _file_id)?;
+                    extract_from_node(cursor, source, symbols, parent_scope.clone(), depth + 1, config, errors)?;
                     if !cursor.goto_next_sibling() {
                         break;
                     }
@@ -108,114 +161,343 @@ fn extract_from_node(
     Ok(())
 }
 
-/// Extract a function definition
-fn extract_function(node: Node, source: &str, scope: Option<&str>) -> Result<Option<Symbol>> {
-    let name_node = node
-        .child_by_field_name("name")
-        .ok_or_else(|| anyhow::anyhow!("Function has no name"))?;
-
-    let name = node_text(name_node, source);
-    let line_start = node.start_position().row;
-    let line_end = node.end_position().row;
-
-    Ok(Some(Symbol {
-        id: None,
-        file_id: 0,
-        name,
-        kind: SymbolKind::Function,
-        line_start,
-        line_end,
-        scope: scope.map(|s| s.to_string()),
-        metadata: None,
-    }))
-}
-
-/// Extract a struct definition
-fn extract_struct(node: Node, source: &str, scope: Option<&str>) -> Result<Option<Symbol>> {
-    let name_node = node
-        .child_by_field_name("name")
-        .ok_or_else(|| anyhow::anyhow!("Struct has no name"))?;
-
-    let name = node_text(name_node, source);
-    let line_start = node.start_position().row;
-    let line_end = node.end_position().row;
-
-    Ok(Some(Symbol {
-        id: None,
-        file_id: 0,
-        name,
-        kind: SymbolKind::Class,
-        line_start,
-        line_end,
-        scope: scope.map(|s| s.to_string()),
-        metadata: None,
-    }))
-}
-
-/// Extract an enum definition
-fn extract_enum(node: Node, source: &str, scope: Option<&str>) -> Result<Option<Symbol>> {
-    let name_node = node
-        .child_by_field_name("name")
-        .ok_or_else(|| anyhow::anyhow!("Enum has no name"))?;
-
-    let name = node_text(name_node, source);
-    let line_start = node.start_position().row;
-    let line_end = node.end_position().row;
-
-    Ok(Some(Symbol {
-        id: None,
-        file_id: 0,
-        name,
-        kind: SymbolKind::Type,
-        line_start,
-        line_end,
-        scope: scope.map(|s| s.to_string()),
-        metadata: None,
-    }))
-}
-
-/// Extract a trait definition
-fn extract_trait(node: Node, source: &str, scope: Option<&str>) -> Result<Option<Symbol>> {
-    let name_node = node
-        .child_by_field_name("name")
-        .ok_or_else(|| anyhow::anyhow!("Trait has no name"))?;
-
-    let name = node_text(name_node, source);
-    let line_start = node.start_position().row;
-    let line_end = node.end_position().row;
-
-    Ok(Some(Symbol {
-        id: None,
-        file_id: 0,
-        name,
-        kind: SymbolKind::Type,
-        line_start,
-        line_end,
-        scope: scope.map(|s| s.to_string()),
-        metadata: None,
-    }))
-}
-
-/// Extract a type alias
-fn extract_type_alias(node: Node, source: &str, scope: Option<&str>) -> Result<Option<Symbol>> {
-    let name_node = node
-        .child_by_field_name("name")
-        .ok_or_else(|| anyhow::anyhow!("Type alias has no name"))?;
-
-    let name = node_text(name_node, source);
-    let line_start = node.start_position().row;
-    let line_end = node.end_position().row;
-
-    Ok(Some(Symbol {
-        id: None,
-        file_id: 0,
-        name,
-        kind: SymbolKind::Type,
-        line_start,
-        line_end,
-        scope: scope.map(|s| s.to_string()),
-        metadata: None,
-    }))
+/// Extract a named item (function, struct, enum, trait or type alias)
+fn extract_named_item(node: Node, source: &str, scope: Option<&str>) -> Result<Option<Symbol>> {
+    Ok(Some(Symbol::try_from((node, source, symbol_kind(node)?, scope))?))
+}
+
+/// Merge the item's `pub`, `pub(crate)`, `pub(super)` or `pub(in path)`
+/// modifier into its metadata as `"visibility"`; private items are unchanged
+fn attach_visibility(node: Node, source: &str, symbol: &mut Symbol) {
+    let mut cursor = node.walk();
+    let Some(modifier) = node
+        .children(&mut cursor)
+        .find(|child| child.kind() == "visibility_modifier")
+    else {
+        return;
+    };
+
+    let mut metadata = symbol
+        .metadata
+        .as_deref()
+        .and_then(|m| serde_json::from_str::<Value>(m).ok())
+        .unwrap_or_else(|| json!({}));
+    metadata["visibility"] = json!(node_text(modifier, source));
+    symbol.metadata = Some(metadata.to_string());
+}
+
+/// Extract the fields of a struct, scoped to the struct name
+///
+/// Tuple struct fields get positional names (`_0`, `_1`, ...). Each field's
+/// type annotation is stored as `metadata["type"]`.
+fn extract_struct_fields(node: Node, source: &str, struct_name: &str) -> Vec<Symbol> {
+    let Some(body) = node.child_by_field_name("body") else {
+        return Vec::new();
+    };
+    let mut cursor = body.walk();
+    let fields: Vec<(String, Node, Node)> = match body.kind() {
+        "field_declaration_list" => body
+            .named_children(&mut cursor)
+            .filter(|field| field.kind() == "field_declaration")
+            .filter_map(|field| {
+                let name = field.child_by_field_name("name")?;
+                Some((node_text(name, source), field, field.child_by_field_name("type")?))
+            })
+            .collect(),
+        "ordered_field_declaration_list" => body
+            .children_by_field_name("type", &mut cursor)
+            .enumerate()
+            .map(|(i, ty)| (format!("_{}", i), ty, ty))
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    fields
+        .into_iter()
+        .map(|(name, field, ty)| Symbol {
+            metadata: Some(json!({ "type": node_text(ty, source) }).to_string()),
+            ..Symbol::with_location(name, SymbolKind::Field, SymbolLocation::from_node(&field), Some(struct_name))
+        })
+        .collect()
+}
+
+/// Extract each variant of an enum, scoped to the enum name
+///
+/// Metadata records the variant shape (`"unit"`, `"tuple"` or `"struct"`),
+/// its field types (struct fields as `{"name", "type"}`), and any explicit
+/// discriminant.
+fn extract_enum_variants(node: Node, source: &str, enum_name: &str) -> Result<Vec<Symbol>> {
+    let Some(body) = node.child_by_field_name("body") else {
+        return Ok(Vec::new());
+    };
+    let mut cursor = body.walk();
+    body.named_children(&mut cursor)
+        .filter(|variant| variant.kind() == "enum_variant")
+        .map(|variant| {
+            Ok(Symbol {
+                metadata: Some(enum_variant_metadata(variant, source).to_string()),
+                ..Symbol::try_from((variant, source, symbol_kind(variant)?, Some(enum_name)))?
+            })
+        })
+        .collect()
+}
+
+/// Shape, fields and discriminant of a single `enum_variant` node
+fn enum_variant_metadata(variant: Node, source: &str) -> Value {
+    let mut metadata = Map::new();
+    let mut cursor = variant.walk();
+    match variant.child_by_field_name("body") {
+        Some(body) if body.kind() == "ordered_field_declaration_list" => {
+            let fields: Vec<String> = body
+                .children_by_field_name("type", &mut cursor)
+                .map(|ty| node_text(ty, source))
+                .collect();
+            metadata.insert("variant".to_string(), json!("tuple"));
+            metadata.insert("fields".to_string(), json!(fields));
+        }
+        Some(body) if body.kind() == "field_declaration_list" => {
+            let fields: Vec<Value> = body
+                .named_children(&mut cursor)
+                .filter(|field| field.kind() == "field_declaration")
+                .filter_map(|field| {
+                    let name = field.child_by_field_name("name")?;
+                    let ty = field.child_by_field_name("type")?;
+                    Some(json!({ "name": node_text(name, source), "type": node_text(ty, source) }))
+                })
+                .collect();
+            metadata.insert("variant".to_string(), json!("struct"));
+            metadata.insert("fields".to_string(), json!(fields));
+        }
+        _ => {
+            metadata.insert("variant".to_string(), json!("unit"));
+        }
+    }
+    if let Some(value) = variant.child_by_field_name("value") {
+        metadata.insert("discriminant".to_string(), json!(node_text(value, source)));
+    }
+    Value::Object(metadata)
+}
+
+/// Build function metadata: parameters, `impl Trait` return types, test and proc macro attributes
+///
+/// `async fn` is treated as returning `impl Future<Output = T>`. Functions
+/// marked `#[test]` (or `#[cfg(test)]`, or inside a `#[cfg(test)]` module) get
+/// `"test": true`; `#[bench]` and `#[ignore]` are recorded as well.
+/// Procedural macros get `"macro_kind": "procedural"` and their `attribute`
+/// (`proc_macro`, `proc_macro_derive` or `proc_macro_attribute`); derive
+/// macros also record the `derive` name.
+fn extract_function_metadata(node: Node, source: &str) -> Option<String> {
+    let mut metadata = Map::new();
+    let return_type = node.child_by_field_name("return_type");
+
+    let params = function_params(node, source);
+    if !params.is_empty() {
+        metadata.insert("params".to_string(), json!(params));
+    }
+
+    if is_async(node, source) {
+        let output = return_type.map_or_else(|| "()".to_string(), |ty| node_text(ty, source));
+        metadata.insert("async".to_string(), json!(true));
+        metadata.insert(
+            "return_impl_trait".to_string(),
+            json!([format!("Future<Output = {}>", output)]),
+        );
+    } else if let Some(return_type) = return_type {
+        let traits = impl_trait_bounds(return_type, source);
+        if !traits.is_empty() {
+            metadata.insert("return_impl_trait".to_string(), json!(traits));
+        }
+    }
+
+    let attributes = preceding_attributes(node, source);
+    if attributes.iter().any(|a| is_test_attribute(a)) || in_test_module(node, source) {
+        metadata.insert("test".to_string(), json!(true));
+    }
+    for flag in ["bench", "ignore"] {
+        if attributes.iter().any(|a| attribute_path(a) == flag) {
+            metadata.insert(flag.to_string(), json!(true));
+        }
+    }
+
+    if let Some(attribute) = attributes.iter().find(|a| is_proc_macro_attribute(a)) {
+        let path = attribute_path(attribute);
+        metadata.insert("macro_kind".to_string(), json!("procedural"));
+        metadata.insert("attribute".to_string(), json!(path));
+        // `proc_macro_derive(Builder, attributes(builder))` -> `Builder`
+        let derive = attribute
+            .split_once('(')
+            .and_then(|(_, args)| args.split([',', ')']).next())
+            .map(str::trim)
+            .filter(|name| !name.is_empty());
+        if let (Some(derive), "proc_macro_derive") = (derive, path) {
+            metadata.insert("derive".to_string(), json!(derive));
+        }
+    }
+
+    if metadata.is_empty() {
+        None
+    } else {
+        Some(Value::Object(metadata).to_string())
+    }
+}
+
+/// Parameters of a function or function signature
+///
+/// Receivers are named `self`, typed `&Self`/`&mut Self` when borrowed;
+/// parameters are named after their pattern (`_` when there is none) and a
+/// C variadic is `...`.
+fn function_params(node: Node, source: &str) -> Vec<FunctionParam> {
+    let Some(parameters) = node.child_by_field_name("parameters") else {
+        return Vec::new();
+    };
+    let mut cursor = parameters.walk();
+    parameters
+        .named_children(&mut cursor)
+        .filter_map(|param| match param.kind() {
+            "self_parameter" => {
+                let receiver = node_text(param, source);
+                let borrow = receiver.strip_suffix("self").unwrap_or_default();
+                Some(FunctionParam {
+                    name: "self".to_string(),
+                    type_name: borrow.starts_with('&').then(|| format!("{}Self", borrow)),
+                })
+            }
+            "parameter" => Some(FunctionParam {
+                name: param
+                    .child_by_field_name("pattern")
+                    .map_or_else(|| "_".to_string(), |pattern| node_text(pattern, source)),
+                type_name: param.child_by_field_name("type").map(|ty| node_text(ty, source)),
+            }),
+            "variadic_parameter" => Some(FunctionParam { name: "...".to_string(), type_name: None }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Text of the attributes directly above an item, e.g. `["ignore", "test"]`
+fn preceding_attributes(node: Node, source: &str) -> Vec<String> {
+    let mut attributes = Vec::new();
+    let mut sibling = node.prev_sibling();
+
+    while let Some(prev) = sibling {
+        match prev.kind() {
+            "attribute_item" => {
+                let mut cursor = prev.walk();
+                attributes.extend(
+                    prev.named_children(&mut cursor)
+                        .filter(|child| child.kind() == "attribute")
+                        .map(|attr| node_text(attr, source)),
+                );
+            }
+            "line_comment" | "block_comment" => {}
+            _ => break,
+        }
+        sibling = prev.prev_sibling();
+    }
+
+    attributes
+}
+
+/// Attribute path without arguments: `cfg(test)` -> `cfg`
+fn attribute_path(attribute: &str) -> &str {
+    attribute.split('(').next().unwrap_or(attribute).trim()
+}
+
+/// `#[test]`, `#[tokio::test]`-style test macros and `#[cfg(test)]`
+fn is_test_attribute(attribute: &str) -> bool {
+    let path = attribute_path(attribute);
+    path == "test"
+        || path.ends_with("::test")
+        || attribute.split_whitespace().collect::<String>() == "cfg(test)"
+}
+
+/// `#[proc_macro]`, `#[proc_macro_derive(...)]` and `#[proc_macro_attribute]`
+fn is_proc_macro_attribute(attribute: &str) -> bool {
+    matches!(
+        attribute_path(attribute),
+        "proc_macro" | "proc_macro_derive" | "proc_macro_attribute"
+    )
+}
+
+/// The proc macro attribute of a function, if it defines a procedural macro
+fn proc_macro_attribute(node: Node, source: &str) -> Option<String> {
+    preceding_attributes(node, source)
+        .into_iter()
+        .find(|a| is_proc_macro_attribute(a))
+}
+
+/// Extract a `macro_rules!` definition
+///
+/// Metadata records `"macro_kind": "declarative"`, the number of `arms`, and
+/// `"exported": true` for `#[macro_export]` macros.
+fn extract_macro_definition(node: Node, source: &str, scope: Option<&str>) -> Result<Symbol> {
+    let mut cursor = node.walk();
+    let arms = node
+        .named_children(&mut cursor)
+        .filter(|child| child.kind() == "macro_rule")
+        .count();
+
+    let mut metadata = json!({ "macro_kind": "declarative", "arms": arms });
+    if preceding_attributes(node, source)
+        .iter()
+        .any(|a| attribute_path(a) == "macro_export")
+    {
+        metadata["exported"] = json!(true);
+    }
+
+    Ok(Symbol {
+        metadata: Some(metadata.to_string()),
+        ..Symbol::try_from((node, source, symbol_kind(node)?, scope))?
+    })
+}
+
+/// Whether any enclosing `mod` is compiled only for tests
+fn in_test_module(node: Node, source: &str) -> bool {
+    let mut ancestor = node.parent();
+    while let Some(current) = ancestor {
+        if current.kind() == "mod_item"
+            && preceding_attributes(current, source).iter().any(|a| is_test_attribute(a))
+        {
+            return true;
+        }
+        ancestor = current.parent();
+    }
+    false
+}
+
+/// Trait bounds of an `impl A + B` return type, empty for any other type
+fn impl_trait_bounds(return_type: Node, source: &str) -> Vec<String> {
+    match return_type.kind() {
+        "abstract_type" => return_type
+            .child_by_field_name("trait")
+            .map(|t| vec![node_text(t, source)])
+            .unwrap_or_default(),
+        "bounded_type" => {
+            let mut cursor = return_type.walk();
+            let bounds: Vec<Node> = return_type.named_children(&mut cursor).collect();
+            if bounds.first().map(|b| b.kind()) != Some("abstract_type") {
+                return Vec::new();
+            }
+            bounds
+                .into_iter()
+                .flat_map(|bound| match bound.kind() {
+                    "abstract_type" => impl_trait_bounds(bound, source),
+                    _ => vec![node_text(bound, source)],
+                })
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Whether a function item carries the `async` modifier
+fn is_async(node: Node, source: &str) -> bool {
+    let mut cursor = node.walk();
+    let found = node
+        .children(&mut cursor)
+        .filter(|child| child.kind() == "function_modifiers")
+        .any(|modifiers| node_text(modifiers, source).split_whitespace().any(|m| m == "async"));
+    found
 }
 
 /// Extract the scope name from an impl block
@@ -231,6 +513,70 @@ fn extract_impl_scope(node: Node, source: &str) -> Result<Option<String>> {
     }
 }
 
+/// Record `impl Trait for Type` blocks on the types they implement
+///
+/// Trait names go to `metadata["implements"]` of the struct, enum or type
+/// alias named `Type` in the same file, without generic arguments
+/// (`impl From<u8> for Id` -> `From`). Impls of types defined elsewhere are
+/// not tracked.
+fn attach_trait_impls(root: Node, source: &str, symbols: &mut [Symbol]) {
+    let mut impls = Vec::new();
+    collect_trait_impls(root, source, &mut impls);
+    if impls.is_empty() {
+        return;
+    }
+
+    for symbol in symbols
+        .iter_mut()
+        .filter(|s| matches!(s.kind, SymbolKind::Struct | SymbolKind::Enum | SymbolKind::Type))
+    {
+        let mut traits: Vec<&str> = Vec::new();
+        for (type_name, trait_name) in &impls {
+            if *type_name == symbol.name && !traits.contains(&trait_name.as_str()) {
+                traits.push(trait_name);
+            }
+        }
+        if traits.is_empty() {
+            continue;
+        }
+
+        let mut metadata = symbol
+            .metadata
+            .as_deref()
+            .and_then(|m| serde_json::from_str::<Value>(m).ok())
+            .unwrap_or_else(|| json!({}));
+        metadata["implements"] = json!(traits);
+        symbol.metadata = Some(metadata.to_string());
+    }
+}
+
+/// Collect `(type name, trait name)` pairs for every trait impl under `node`
+fn collect_trait_impls(node: Node, source: &str, impls: &mut Vec<(String, String)>) {
+    if node.kind() == "impl_item" {
+        if let (Some(trait_node), Some(type_node)) =
+            (node.child_by_field_name("trait"), node.child_by_field_name("type"))
+        {
+            impls.push((base_type_name(type_node, source), base_type_name(trait_node, source)));
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        collect_trait_impls(child, source, impls);
+    }
+}
+
+/// Name of a type without generic arguments (`Wrapper<T>` -> `Wrapper`)
+fn base_type_name(ty: Node, source: &str) -> String {
+    match ty.kind() {
+        "generic_type" => ty
+            .child_by_field_name("type")
+            .map(|inner| node_text(inner, source))
+            .unwrap_or_else(|| node_text(ty, source)),
+        _ => node_text(ty, source),
+    }
+}
+
 /// Extract a use declaration
 fn extract_use(node: Node, source: &str, scope: Option<&str>) -> Result<Option<Symbol>> {
     // Get the argument (what's being imported)
@@ -238,46 +584,100 @@ fn extract_use(node: Node, source: &str, scope: Option<&str>) -> Result<Option<S
 
     if let Some(arg_node) = arg {
         let name = node_text(arg_node, source);
-        let line_start = node.start_position().row;
-        let line_end = node.end_position().row;
-
-        Ok(Some(Symbol {
-            id: None,
-            file_id: 0,
+        Ok(Some(Symbol::with_location(
             name,
-            kind: SymbolKind::Import,
-            line_start,
-            line_end,
-            scope: scope.map(|s| s.to_string()),
-            metadata: None,
-        }))
+            symbol_kind(node)?,
+            SymbolLocation::from_node(&node),
+            scope,
+        )))
     } else {
         Ok(None)
     }
 }
 
 /// Extract a constant or static variable
-fn extract_constant(node: Node, source: &str) -> Result<Option<Symbol>> {
-    let name_node = node.child_by_field_name("name");
+fn extract_constant(node: Node, source: &str, scope: Option<&str>) -> Result<Option<Symbol>> {
+    if node.child_by_field_name("name").is_none() {
+        return Ok(None);
+    }
 
-    if let Some(name_node) = name_node {
-        let name = node_text(name_node, source);
-        let line_start = node.start_position().row;
-        let line_end = node.end_position().row;
+    Ok(Some(Symbol::try_from((node, source, symbol_kind(node)?, scope))?))
+}
 
-        Ok(Some(Symbol {
-            id: None,
-            file_id: 0,
-            name,
-            kind: SymbolKind::Variable,
-            line_start,
-            line_end,
-            scope: None,
-            metadata: None,
-        }))
-    } else {
-        Ok(None)
+/// Extract `mod name { ... }` or `mod name;` as a namespace
+///
+/// `metadata["inline"]` records whether the module body is declared in place
+/// or lives in another file.
+fn extract_module(node: Node, source: &str, scope: Option<&str>) -> Result<Symbol> {
+    let inline = node.child_by_field_name("body").is_some();
+    Ok(Symbol {
+        metadata: Some(json!({ "inline": inline }).to_string()),
+        ..Symbol::try_from((node, source, symbol_kind(node)?, scope))?
+    })
+}
+
+/// Whether an item sits directly in the body of an inline `mod`
+fn declared_in_module(node: Node) -> bool {
+    node.parent()
+        .filter(|body| body.kind() == "declaration_list")
+        .and_then(|body| body.parent())
+        .is_some_and(|parent| parent.kind() == "mod_item")
+}
+
+/// Extract `extern crate name [as alias];` as an import of `name`
+fn extract_extern_crate(node: Node, source: &str, scope: Option<&str>) -> Result<Symbol> {
+    let mut symbol = Symbol::try_from((node, source, symbol_kind(node)?, scope))?;
+    if let Some(alias) = node.child_by_field_name("alias") {
+        symbol.metadata = Some(json!({ "alias": node_text(alias, source) }).to_string());
     }
+    Ok(symbol)
+}
+
+/// Extract the functions and statics declared in an `extern "ABI" { ... }` block
+///
+/// Each gets `metadata["ffi"] = true` and `metadata["abi"]`; a bare `extern`
+/// block uses the default `"C"` ABI.
+fn extract_foreign_items(node: Node, source: &str, scope: Option<&str>) -> Result<Vec<Symbol>> {
+    let mut cursor = node.walk();
+    let abi = node
+        .children(&mut cursor)
+        .find(|child| child.kind() == "extern_modifier")
+        .and_then(|modifier| {
+            let mut cursor = modifier.walk();
+            let literal = modifier
+                .named_children(&mut cursor)
+                .find(|child| child.kind() == "string_literal");
+            literal
+        })
+        .map(|literal| node_text(literal, source).trim_matches('"').to_string())
+        .unwrap_or_else(|| "C".to_string());
+    let metadata = json!({ "ffi": true, "abi": abi });
+
+    let Some(body) = node.child_by_field_name("body") else {
+        return Ok(Vec::new());
+    };
+    let mut cursor = body.walk();
+    body.named_children(&mut cursor)
+        .filter(|item| matches!(item.kind(), "function_signature_item" | "static_item"))
+        .filter(|item| item.child_by_field_name("name").is_some())
+        .map(|item| {
+            let mut metadata = metadata.clone();
+            let params = function_params(item, source);
+            if !params.is_empty() {
+                metadata["params"] = json!(params);
+            }
+            Ok(Symbol {
+                metadata: Some(metadata.to_string()),
+                ..Symbol::try_from((item, source, symbol_kind(item)?, scope))?
+            })
+        })
+        .collect()
+}
+
+/// Look up the symbol kind for a node in the shared mapping table
+fn symbol_kind(node: Node) -> Result<SymbolKind> {
+    SymbolKind::from_file_node_kind("rust", node.kind())
+        .ok_or_else(|| anyhow::anyhow!("Node kind `{}` does not define a symbol", node.kind()))
 }
 
 /// Get text content of a node
@@ -299,7 +699,7 @@ fn my_function() {
 "#;
         let mut parser = RustParser::new().unwrap();
         let tree = parser.parse(source).unwrap();
-        let symbols = extract_symbols(&tree, source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
 
         assert_eq!(symbols.len(), 1);
         assert_eq!(symbols[0].name, "my_function");
@@ -325,13 +725,57 @@ impl MyStruct {
 "#;
         let mut parser = RustParser::new().unwrap();
         let tree = parser.parse(source).unwrap();
-        let symbols = extract_symbols(&tree, source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
 
-        assert!(symbols.iter().any(|s| s.name == "MyStruct" && matches!(s.kind, SymbolKind::Class)));
+        assert!(symbols.iter().any(|s| s.name == "MyStruct" && matches!(s.kind, SymbolKind::Struct)));
         assert!(symbols.iter().any(|s| s.name == "new" && matches!(s.kind, SymbolKind::Function)));
         assert!(symbols.iter().any(|s| s.name == "get_value" && matches!(s.kind, SymbolKind::Function)));
     }
 
+    #[test]
+    fn test_extract_trait_impls() {
+        let source = r#"
+struct Point { x: i32 }
+enum Shape { Circle }
+struct Plain;
+
+impl Point {
+    fn new() -> Self { Point { x: 0 } }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { Ok(()) }
+}
+
+impl From<i32> for Point {
+    fn from(x: i32) -> Self { Point { x } }
+}
+
+impl From<u8> for Point {
+    fn from(x: u8) -> Self { Point { x: x.into() } }
+}
+
+impl<T> Into<T> for Shape {}
+impl Clone for Remote {}
+"#;
+        let mut parser = RustParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+        let implements = |name: &str| {
+            let symbol = symbols.iter().find(|s| s.name == name).unwrap();
+            symbol
+                .metadata
+                .as_deref()
+                .map(|m| serde_json::from_str::<serde_json::Value>(m).unwrap()["implements"].clone())
+        };
+
+        assert_eq!(implements("Point"), Some(json!(["fmt::Display", "From"])));
+        assert_eq!(implements("Shape"), Some(json!(["Into"])));
+        assert_eq!(implements("Plain"), None);
+        // `fmt` is still scoped to the implementing type
+        assert!(symbols.iter().any(|s| s.name == "fmt" && s.scope.as_deref() == Some("Point")));
+    }
+
     #[test]
     fn test_extract_trait() {
         let source = r#"
@@ -341,9 +785,9 @@ trait MyTrait {
 "#;
         let mut parser = RustParser::new().unwrap();
         let tree = parser.parse(source).unwrap();
-        let symbols = extract_symbols(&tree, source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
 
-        assert!(symbols.iter().any(|s| s.name == "MyTrait" && matches!(s.kind, SymbolKind::Type)));
+        assert!(symbols.iter().any(|s| s.name == "MyTrait" && matches!(s.kind, SymbolKind::Trait)));
     }
 
     #[test]
@@ -356,9 +800,9 @@ enum MyEnum {
 "#;
         let mut parser = RustParser::new().unwrap();
         let tree = parser.parse(source).unwrap();
-        let symbols = extract_symbols(&tree, source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
 
-        assert!(symbols.iter().any(|s| s.name == "MyEnum" && matches!(s.kind, SymbolKind::Type)));
+        assert!(symbols.iter().any(|s| s.name == "MyEnum" && matches!(s.kind, SymbolKind::Enum)));
     }
 
     #[test]
@@ -369,9 +813,427 @@ use anyhow::Result;
 "#;
         let mut parser = RustParser::new().unwrap();
         let tree = parser.parse(source).unwrap();
-        let symbols = extract_symbols(&tree, source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
 
         assert!(symbols.iter().any(|s| s.name == "std::collections::HashMap" && matches!(s.kind, SymbolKind::Import)));
         assert!(symbols.iter().any(|s| s.name == "anyhow::Result" && matches!(s.kind, SymbolKind::Import)));
     }
+
+    fn function_metadata(source: &str, name: &str) -> Option<serde_json::Value> {
+        let mut parser = RustParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+        let symbol = symbols.iter().find(|s| s.name == name).unwrap();
+        symbol.metadata.as_deref().map(|m| serde_json::from_str(m).unwrap())
+    }
+
+    #[test]
+    fn test_extract_macro_rules() {
+        let source = r#"
+#[macro_export]
+macro_rules! hashmap {
+    () => { HashMap::new() };
+    ($($k:expr => $v:expr),+ $(,)?) => {{
+        let mut map = HashMap::new();
+        $(map.insert($k, $v);)+
+        map
+    }};
+}
+
+macro_rules! square {
+    ($x:expr) => { $x * $x };
+}
+"#;
+        let mut parser = RustParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+
+        let hashmap = symbols.iter().find(|s| s.name == "hashmap").unwrap();
+        assert_eq!(hashmap.kind, SymbolKind::Macro);
+        assert_eq!((hashmap.line_start, hashmap.line_end), (2, 9));
+        assert_eq!(
+            function_metadata(source, "hashmap"),
+            Some(json!({ "macro_kind": "declarative", "arms": 2, "exported": true }))
+        );
+        assert_eq!(
+            function_metadata(source, "square"),
+            Some(json!({ "macro_kind": "declarative", "arms": 1 }))
+        );
+    }
+
+    #[test]
+    fn test_extract_proc_macros() {
+        let source = r#"
+#[proc_macro_derive(Builder, attributes(builder))]
+pub fn derive_builder(input: TokenStream) -> TokenStream {
+    TokenStream::new()
+}
+
+#[proc_macro_attribute]
+pub fn route(attr: TokenStream, item: TokenStream) -> TokenStream { item }
+
+#[proc_macro]
+pub fn sql(input: TokenStream) -> TokenStream { input }
+
+pub fn helper() {}
+"#;
+        let mut parser = RustParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+        let kind = |name: &str| symbols.iter().find(|s| s.name == name).unwrap().kind;
+
+        assert_eq!(kind("derive_builder"), SymbolKind::Macro);
+        let derive = function_metadata(source, "derive_builder").unwrap();
+        assert_eq!(derive["macro_kind"], "procedural");
+        assert_eq!(derive["attribute"], "proc_macro_derive");
+        assert_eq!(derive["derive"], "Builder");
+        assert_eq!(derive["params"][0]["name"], "input");
+
+        assert_eq!(kind("route"), SymbolKind::Macro);
+        let route = function_metadata(source, "route").unwrap();
+        assert_eq!(route["attribute"], "proc_macro_attribute");
+        assert!(route.get("derive").is_none());
+        assert_eq!(function_metadata(source, "sql").unwrap()["attribute"], "proc_macro");
+
+        assert_eq!(kind("helper"), SymbolKind::Function);
+        assert_eq!(function_metadata(source, "helper"), Some(json!({"visibility": "pub"})));
+    }
+
+    #[test]
+    fn test_extract_return_impl_iterator() {
+        let metadata = function_metadata("fn iter() -> impl Iterator<Item = i32> { 0..3 }", "iter").unwrap();
+        assert_eq!(metadata["return_impl_trait"], json!(["Iterator<Item = i32>"]));
+    }
+
+    #[test]
+    fn test_extract_return_impl_closure_and_bounds() {
+        let source = r#"
+fn make_adder(x: i32) -> impl Fn(i32) -> i32 { move |y| x + y }
+fn add(a: i32) -> impl Add<i32> + Copy { a }
+fn plain() -> i32 { 0 }
+"#;
+        let metadata = function_metadata(source, "make_adder").unwrap();
+        assert_eq!(metadata["return_impl_trait"], json!(["Fn(i32) -> i32"]));
+
+        let metadata = function_metadata(source, "add").unwrap();
+        assert_eq!(metadata["return_impl_trait"], json!(["Add<i32>", "Copy"]));
+
+        assert!(function_metadata(source, "plain").is_none());
+    }
+
+    #[test]
+    fn test_extract_async_fn_implicit_future() {
+        let source = r#"
+async fn fetch() -> Result<()> { Ok(()) }
+pub async fn notify() {}
+"#;
+        let metadata = function_metadata(source, "fetch").unwrap();
+        assert_eq!(metadata["async"], true);
+        assert_eq!(metadata["return_impl_trait"], json!(["Future<Output = Result<()>>"]));
+
+        let metadata = function_metadata(source, "notify").unwrap();
+        assert_eq!(metadata["return_impl_trait"], json!(["Future<Output = ()>"]));
+    }
+
+    #[test]
+    fn test_extract_function_params() {
+        let source = r#"
+impl Buffer {
+    fn write(&mut self, mut data: &[u8], (x, y): (u8, u8), _: i32) {}
+    fn len(&self) -> usize { 0 }
+    fn into_inner(self) {}
+    fn boxed(self: Box<Self>) {}
+}
+extern "C" { fn printf(fmt: *const u8, ...) -> i32; }
+"#;
+        let params = |name: &str| function_metadata(source, name).map(|m| m["params"].clone());
+        assert_eq!(
+            params("write"),
+            Some(json!([
+                {"name": "self", "type": "&mut Self"},
+                {"name": "data", "type": "&[u8]"},
+                {"name": "(x, y)", "type": "(u8, u8)"},
+                {"name": "_", "type": "i32"},
+            ]))
+        );
+        assert_eq!(params("len"), Some(json!([{"name": "self", "type": "&Self"}])));
+        assert_eq!(params("into_inner"), Some(json!([{"name": "self", "type": null}])));
+        assert_eq!(params("boxed"), Some(json!([{"name": "self", "type": "Box<Self>"}])));
+        assert_eq!(
+            params("printf"),
+            Some(json!([{"name": "fmt", "type": "*const u8"}, {"name": "...", "type": null}]))
+        );
+    }
+
+    #[test]
+    fn test_extract_test_function() {
+        let source = r#"
+#[test]
+fn simple_test() {
+    assert!(true);
+}
+
+fn production() {}
+"#;
+        let metadata = function_metadata(source, "simple_test").unwrap();
+        assert_eq!(metadata["test"], true);
+        assert!(metadata.get("ignore").is_none());
+        assert!(function_metadata(source, "production").is_none());
+    }
+
+    #[test]
+    fn test_extract_cfg_test_module() {
+        let source = r#"
+#[cfg(test)]
+mod tests {}
+
+#[cfg(test)]
+mod more_tests {
+    // shared fixture
+    fn helper() {}
+
+    #[tokio::test]
+    async fn async_case() {}
+}
+"#;
+        assert_eq!(function_metadata(source, "helper").unwrap()["test"], true);
+
+        let metadata = function_metadata(source, "async_case").unwrap();
+        assert_eq!(metadata["test"], true);
+        assert_eq!(metadata["async"], true);
+    }
+
+    #[test]
+    fn test_extract_ignored_and_bench_functions() {
+        let source = r#"
+#[ignore]
+#[test]
+fn slow_test() {}
+
+#[bench]
+fn bench_parse(b: &mut Bencher) {}
+
+#[attested]
+fn not_a_test() {}
+"#;
+        let metadata = function_metadata(source, "slow_test").unwrap();
+        assert_eq!(metadata["test"], true);
+        assert_eq!(metadata["ignore"], true);
+
+        let metadata = function_metadata(source, "bench_parse").unwrap();
+        assert_eq!(metadata["bench"], true);
+        assert!(metadata.get("test").is_none());
+
+        assert!(function_metadata(source, "not_a_test").is_none());
+    }
+
+    #[test]
+    fn test_extract_extern_crate() {
+        let source = "extern crate serde;\nextern crate alloc as core_alloc;\n";
+        let mut parser = RustParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+
+        assert_eq!(symbols.len(), 2);
+        assert_eq!(symbols[0].name, "serde");
+        assert_eq!(symbols[0].kind, SymbolKind::Import);
+        assert!(symbols[0].metadata.is_none());
+        assert_eq!(symbols[1].name, "alloc");
+        assert_eq!(symbols[1].metadata.as_deref(), Some(r#"{"alias":"core_alloc"}"#));
+    }
+
+    #[test]
+    fn test_extract_extern_c_block() {
+        let source = "extern \"C\" {\n    fn malloc(size: usize) -> *mut u8;\n    static errno: i32;\n}\n";
+        let mut parser = RustParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+
+        assert_eq!(symbols.len(), 2);
+        assert_eq!(symbols[0].name, "malloc");
+        assert_eq!(symbols[0].kind, SymbolKind::Function);
+        assert_eq!(symbols[0].line_start, 1);
+        assert_eq!(symbols[1].name, "errno");
+        assert_eq!(symbols[1].kind, SymbolKind::Variable);
+        let metadata: Vec<Value> = symbols
+            .iter()
+            .map(|symbol| serde_json::from_str(symbol.metadata.as_deref().unwrap()).unwrap())
+            .collect();
+        assert_eq!(
+            metadata[0],
+            json!({"ffi": true, "abi": "C", "params": [{"name": "size", "type": "usize"}]})
+        );
+        assert_eq!(metadata[1], json!({"ffi": true, "abi": "C"}));
+    }
+
+    #[test]
+    fn test_extract_extern_rust_and_default_abi() {
+        let source = "extern \"Rust\" { fn internal_fn(); }\nextern { fn plain(); }\nfn normal() {}\n";
+        let mut parser = RustParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+
+        let abi = |name: &str| -> Option<Value> {
+            let symbol = symbols.iter().find(|s| s.name == name).unwrap();
+            let metadata: Value = serde_json::from_str(symbol.metadata.as_deref()?).unwrap();
+            assert_eq!(metadata["ffi"], true);
+            Some(metadata["abi"].clone())
+        };
+        assert_eq!(abi("internal_fn"), Some(json!("Rust")));
+        assert_eq!(abi("plain"), Some(json!("C")));
+        assert_eq!(abi("normal"), None);
+    }
+
+    #[test]
+    fn test_extract_enum_variants() {
+        let source = "enum Shape {\n    Empty,\n    Circle(f64),\n    Rect { w: f64, h: u32 },\n    Code = 3,\n}\n";
+        let mut parser = RustParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["Shape", "Empty", "Circle", "Rect", "Code"]);
+        assert_eq!(symbols[0].kind, SymbolKind::Enum);
+
+        let metadata = |name: &str| -> Value {
+            let symbol = symbols.iter().find(|s| s.name == name).unwrap();
+            assert_eq!(symbol.kind, SymbolKind::EnumVariant);
+            assert_eq!(symbol.scope.as_deref(), Some("Shape"));
+            serde_json::from_str(symbol.metadata.as_deref().unwrap()).unwrap()
+        };
+        assert_eq!(metadata("Empty"), json!({"variant": "unit"}));
+        assert_eq!(metadata("Circle"), json!({"variant": "tuple", "fields": ["f64"]}));
+        assert_eq!(
+            metadata("Rect"),
+            json!({"variant": "struct", "fields": [{"name": "w", "type": "f64"}, {"name": "h", "type": "u32"}]})
+        );
+        assert_eq!(metadata("Code"), json!({"variant": "unit", "discriminant": "3"}));
+
+        let rect = symbols.iter().find(|s| s.name == "Rect").unwrap();
+        assert_eq!((rect.line_start, rect.column_start), (3, 4));
+    }
+
+    #[test]
+    fn test_extract_struct_fields() {
+        let source = "struct Point(pub i32, String);\nstruct Config {\n    pub name: Vec<u8>,\n    verbose: bool,\n}\nstruct Marker;\n";
+        let mut parser = RustParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["Point", "_0", "_1", "Config", "name", "verbose", "Marker"]);
+
+        let field = |scope: &str, name: &str| -> (Value, usize) {
+            let symbol = symbols
+                .iter()
+                .find(|s| s.name == name && s.scope.as_deref() == Some(scope))
+                .unwrap();
+            assert_eq!(symbol.kind, SymbolKind::Field);
+            let metadata = serde_json::from_str(symbol.metadata.as_deref().unwrap()).unwrap();
+            (metadata, symbol.line_start)
+        };
+        assert_eq!(field("Point", "_0"), (json!({"type": "i32"}), 0));
+        assert_eq!(field("Point", "_1"), (json!({"type": "String"}), 0));
+        assert_eq!(field("Config", "name"), (json!({"type": "Vec<u8>"}), 2));
+        assert_eq!(field("Config", "verbose"), (json!({"type": "bool"}), 3));
+    }
+
+    #[test]
+    fn test_const_and_static_kinds() {
+        let source = "const MAX: usize = 8;\nstatic mut COUNTER: u32 = 0;\n";
+        let mut parser = RustParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+
+        assert_eq!(symbols.len(), 2);
+        assert_eq!((symbols[0].name.as_str(), symbols[0].kind), ("MAX", SymbolKind::Constant));
+        assert_eq!((symbols[1].name.as_str(), symbols[1].kind), ("COUNTER", SymbolKind::Variable));
+    }
+
+    #[test]
+    fn test_extract_modules() {
+        let source = r#"
+mod network;
+
+pub mod storage {
+    const PAGE_SIZE: usize = 4096;
+
+    pub fn open() {}
+
+    mod cache {
+        struct Entry;
+    }
+}
+"#;
+        let mut parser = RustParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+
+        let find = |name: &str| symbols.iter().find(|s| s.name == name).unwrap();
+        let inline = |name: &str| -> Value {
+            let metadata: Value = serde_json::from_str(find(name).metadata.as_deref().unwrap()).unwrap();
+            metadata["inline"].clone()
+        };
+
+        assert_eq!(find("network").kind, SymbolKind::Namespace);
+        assert_eq!(find("network").scope, None);
+        assert_eq!(inline("network"), json!(false));
+
+        assert_eq!(find("storage").kind, SymbolKind::Namespace);
+        assert_eq!(inline("storage"), json!(true));
+        assert_eq!((find("storage").line_start, find("storage").line_end), (3, 11));
+
+        assert_eq!(find("PAGE_SIZE").scope.as_deref(), Some("storage"));
+        assert_eq!(find("open").scope.as_deref(), Some("storage"));
+        assert_eq!(find("cache").scope.as_deref(), Some("storage"));
+        assert_eq!(find("Entry").scope.as_deref(), Some("cache"));
+    }
+
+    #[test]
+    fn test_extract_visibility() {
+        let source = r#"
+pub fn api() {}
+pub(crate) struct Internal;
+pub(super) enum Mode { A }
+pub(in crate::net) trait Transport {}
+pub type Id = u64;
+pub const LIMIT: usize = 4;
+fn private() {}
+
+impl Internal {
+    pub async fn start(&self) {}
+}
+"#;
+        let visibility = |name: &str| function_metadata(source, name).map(|m| m["visibility"].clone());
+
+        assert_eq!(visibility("api"), Some(json!("pub")));
+        assert_eq!(visibility("Internal"), Some(json!("pub(crate)")));
+        assert_eq!(visibility("Mode"), Some(json!("pub(super)")));
+        assert_eq!(visibility("Transport"), Some(json!("pub(in crate::net)")));
+        assert_eq!(visibility("Id"), Some(json!("pub")));
+        assert_eq!(visibility("LIMIT"), Some(json!("pub")));
+        assert_eq!(visibility("private"), None);
+
+        // Merged with the existing function metadata
+        let start = function_metadata(source, "start").unwrap();
+        assert_eq!(start["visibility"], "pub");
+        assert_eq!(start["async"], true);
+    }
+
+    #[test]
+    fn test_max_depth_skips_deep_nesting() {
+        let source = "mod a { mod b { mod c { fn deep() {} } } }\nfn top() {}\n";
+        let mut parser = RustParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+
+        let (symbols, errors) = extract_symbols(&tree, source, None).unwrap();
+        assert!(symbols.iter().any(|s| s.name == "deep"));
+        assert!(errors.is_empty());
+
+        let (symbols, errors) = extract_symbols(&tree, source, Some(ExtractConfig { max_depth: 4 })).unwrap();
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["a", "b", "top"]);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line, 0);
+    }
 }
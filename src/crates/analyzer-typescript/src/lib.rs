@@ -5,23 +5,53 @@
 pub mod parser;
 pub mod symbol_extract;
 
-pub use parser::TypeScriptParser;
-pub use symbol_extract::extract_symbols;
+pub use parser::{TypeScriptParser, TypeScriptParserPool};
+pub use symbol_extract::{extract_declaration_symbols, extract_symbols, get_enum_members};
 
-use analyzer_core::Symbol;
-use anyhow::Result;
+use analyzer_core::registry::IncrementalAnalyzerOutput;
+use analyzer_core::{IndexingError, ParseError, Symbol};
+use tree_sitter::Tree;
 
 /// Analyze a TypeScript source file and extract symbols
-pub fn analyze_typescript(source: &str) -> Result<Vec<Symbol>> {
-    let mut parser = TypeScriptParser::new()?;
-    let tree = parser.parse(source)?;
-    let symbols = extract_symbols(&tree, source)?;
-    Ok(symbols)
+pub fn analyze_typescript(source: &str) -> Result<(Vec<Symbol>, Vec<ParseError>), IndexingError> {
+    let (symbols, errors, _) = analyze_typescript_incremental(source, None)?;
+    Ok((symbols, errors))
+}
+
+/// Analyze a TypeScript source file, re-parsing against `old_tree` when given
+///
+/// `old_tree` must already be edited to match `source`. The new tree is
+/// returned for the next incremental parse.
+pub fn analyze_typescript_incremental(
+    source: &str,
+    old_tree: Option<&Tree>,
+) -> Result<IncrementalAnalyzerOutput, IndexingError> {
+    let mut parser = TypeScriptParser::new().map_err(|e| IndexingError::parse_failed("typescript", e))?;
+    let tree = match old_tree {
+        Some(old_tree) => parser.parse_with_old_tree(source, old_tree),
+        None => parser.parse(source),
+    }
+    .map_err(|e| IndexingError::parse_failed("typescript", e))?;
+    let (symbols, mut errors) = extract_symbols(&tree, source, None).map_err(IndexingError::extraction_failed)?;
+    errors.splice(0..0, ParseError::from_tree(&tree, source));
+    Ok((symbols, errors, tree))
+}
+
+/// Analyze a `.d.ts` declaration file and extract symbols
+pub fn analyze_typescript_declaration(source: &str) -> Result<(Vec<Symbol>, Vec<ParseError>), IndexingError> {
+    let mut parser = TypeScriptParser::new().map_err(|e| IndexingError::parse_failed("typescript", e))?;
+    let tree = parser
+        .parse_declaration(source)
+        .map_err(|e| IndexingError::parse_failed("typescript", e))?;
+    let (symbols, mut errors) = extract_declaration_symbols(&tree, source).map_err(IndexingError::extraction_failed)?;
+    errors.splice(0..0, ParseError::from_tree(&tree, source));
+    Ok((symbols, errors))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use analyzer_core::SymbolKind;
 
     #[test]
     fn test_analyze_simple_function() {
@@ -30,7 +60,7 @@ function helloWorld() {
     console.log("Hello, world!");
 }
 "#;
-        let symbols = analyze_typescript(source).unwrap();
+        let (symbols, _) = analyze_typescript(source).unwrap();
         assert!(symbols.iter().any(|s| s.name == "helloWorld" && matches!(s.kind, SymbolKind::Function)));
     }
 
@@ -49,7 +79,7 @@ class MyClass {
     }
 }
 "#;
-        let symbols = analyze_typescript(source).unwrap();
+        let (symbols, _) = analyze_typescript(source).unwrap();
         assert!(symbols.iter().any(|s| s.name == "MyClass" && matches!(s.kind, SymbolKind::Class)));
         assert!(symbols.iter().any(|s| s.name == "getValue" && matches!(s.kind, SymbolKind::Function)));
     }
@@ -62,7 +92,22 @@ interface User {
     age: number;
 }
 "#;
-        let symbols = analyze_typescript(source).unwrap();
-        assert!(symbols.iter().any(|s| s.name == "User" && matches!(s.kind, SymbolKind::Type)));
+        let (symbols, _) = analyze_typescript(source).unwrap();
+        assert!(symbols.iter().any(|s| s.name == "User" && matches!(s.kind, SymbolKind::Interface)));
+    }
+
+    #[test]
+    fn test_analyze_reports_parse_errors() {
+        let source = r#"
+function ok() {}
+
+function broken( {
+}
+"#;
+        let (symbols, errors) = analyze_typescript(source).unwrap();
+        assert!(symbols.iter().any(|s| s.name == "ok"));
+        assert!(!errors.is_empty());
+        assert!(errors.iter().all(|e| e.line == 3));
+        assert!(analyze_typescript("const x = 1;\n").unwrap().1.is_empty());
     }
 }
@@ -2,6 +2,7 @@
 //!
 //! Wraps the tree-sitter-typescript parser for use in the analyzer.
 
+use analyzer_core::pool::{ParserPool, ResettableParser};
 use anyhow::{Context, Result};
 use tree_sitter::{Parser, Tree};
 
@@ -28,12 +29,26 @@ impl TypeScriptParser {
             .context("Failed to parse TypeScript source")
     }
 
+    /// Parse a `.d.ts` declaration file
+    ///
+    /// Uses the TypeScript grammar; pass the tree to
+    /// `extract_declaration_symbols` to tag symbols as declarations.
+    pub fn parse_declaration(&mut self, source: &str) -> Result<Tree> {
+        self.parse(source)
+            .context("Failed to parse TypeScript declaration source")
+    }
+
     /// Parse with old tree for incremental parsing
     pub fn parse_with_old_tree(&mut self, source: &str, old_tree: &Tree) -> Result<Tree> {
         self.parser
             .parse(source, Some(old_tree))
             .context("Failed to incrementally parse TypeScript source")
     }
+
+    /// Clear per-file parse state, keeping the loaded grammar
+    pub fn reset(&mut self) {
+        self.parser.reset();
+    }
 }
 
 impl Default for TypeScriptParser {
@@ -42,6 +57,15 @@ impl Default for TypeScriptParser {
     }
 }
 
+impl ResettableParser for TypeScriptParser {
+    fn reset(&mut self) {
+        TypeScriptParser::reset(self);
+    }
+}
+
+/// Pool of reusable TypeScript parsers (`TypeScriptParserPool::new(size)`)
+pub type TypeScriptParserPool = ParserPool<TypeScriptParser>;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,20 +1,95 @@
 //! TypeScript symbol extraction
 //!
-//! Extracts functions, classes, interfaces, types, and imports from TypeScript AST.
+//! Extracts functions, classes, class fields, interfaces, types, enums, and imports from TypeScript AST.
 
-use analyzer_core::{Symbol, SymbolKind};
+use analyzer_core::{ExtractConfig, FunctionParam, ParseError, Symbol, SymbolKind, SymbolLocation};
 use anyhow::Result;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
 use tree_sitter::{Node, Tree, TreeCursor};
 
 /// Extract symbols from a TypeScript parse tree
-pub fn extract_symbols(tree: &Tree, source: &str) -> Result<Vec<Symbol>> {
+///
+/// Subtrees nested deeper than `config.max_depth` (default
+/// [`ExtractConfig::default`]) are skipped and reported as parse errors.
+pub fn extract_symbols(
+    tree: &Tree,
+    source: &str,
+    config: Option<ExtractConfig>,
+) -> Result<(Vec<Symbol>, Vec<ParseError>)> {
+    let config = config.unwrap_or_default();
     let mut symbols = Vec::new();
+    let mut errors = Vec::new();
     let root = tree.root_node();
     let mut cursor = root.walk();
 
-    extract_from_node(&mut cursor, source, &mut symbols, None, 0)?;
+    extract_from_node(&mut cursor, source, &mut symbols, None, 0, &config, &mut errors)?;
+    consolidate_overloads(&mut symbols);
+
+    Ok((symbols, errors))
+}
+
+/// Mark function implementations preceded by overload signatures
+///
+/// Signatures (`metadata["signature"] = true`) are counted per scope and name;
+/// the implementation that follows gets `metadata["overloaded"] = true` and
+/// `metadata["overload_count"] = n`. Signatures without an implementation
+/// (e.g. in `.d.ts` files) are left as they are.
+pub fn consolidate_overloads(symbols: &mut [Symbol]) {
+    let mut pending: HashMap<(Option<String>, String), usize> = HashMap::new();
+
+    for symbol in symbols.iter_mut().filter(|s| s.kind == SymbolKind::Function) {
+        let metadata = symbol
+            .metadata
+            .as_deref()
+            .and_then(|m| serde_json::from_str::<Value>(m).ok())
+            .filter(Value::is_object);
+        let key = (symbol.scope.clone(), symbol.name.clone());
+
+        if metadata.as_ref().is_some_and(|m| m["signature"] == true) {
+            *pending.entry(key).or_default() += 1;
+            continue;
+        }
+
+        if let Some(count) = pending.remove(&key) {
+            let mut metadata = metadata.unwrap_or_else(|| json!({}));
+            metadata["overloaded"] = true.into();
+            metadata["overload_count"] = count.into();
+            symbol.metadata = Some(metadata.to_string());
+        }
+    }
+}
+
+/// Extract symbols from a `.d.ts` declaration file parse tree
+///
+/// Every symbol is tagged with `metadata["declaration"] = true`.
+pub fn extract_declaration_symbols(tree: &Tree, source: &str) -> Result<(Vec<Symbol>, Vec<ParseError>)> {
+    extract_symbols_in_mode(tree, source, true)
+}
+
+/// Extract symbols, tagging them as declarations when `is_declaration` is set
+pub fn extract_symbols_in_mode(
+    tree: &Tree,
+    source: &str,
+    is_declaration: bool,
+) -> Result<(Vec<Symbol>, Vec<ParseError>)> {
+    let (mut symbols, errors) = extract_symbols(tree, source, None)?;
+    if is_declaration {
+        symbols.iter_mut().for_each(mark_declaration);
+    }
+    Ok((symbols, errors))
+}
 
-    Ok(symbols)
+/// Set `metadata["declaration"] = true`, keeping any existing metadata
+fn mark_declaration(symbol: &mut Symbol) {
+    let mut metadata = symbol
+        .metadata
+        .as_deref()
+        .and_then(|m| serde_json::from_str::<Value>(m).ok())
+        .filter(Value::is_object)
+        .unwrap_or_else(|| json!({}));
+    metadata["declaration"] = true.into();
+    symbol.metadata = Some(metadata.to_string());
 }
 
 /// Recursively extract symbols from a node
@@ -23,19 +98,29 @@ fn extract_from_node(
     source: &str,
     symbols: &mut Vec<Symbol>,
     parent_scope: Option<String>,
-    _file_id: i64,
+    depth: usize,
+    config: &ExtractConfig,
+    errors: &mut Vec<ParseError>,
 ) -> Result<()> {
     let node = cursor.node();
+    if depth >= config.max_depth {
+        // Leaf tokens cannot hold symbols, so only skipped subtrees are reported
+        if node.named_child_count() > 0 {
+            errors.push(ParseError::nesting_too_deep(&node, config.max_depth));
+        }
+        return Ok(());
+    }
 
     match node.kind() {
-        "function_declaration" | "function" | "arrow_function" | "method_definition" => {
+        "function_declaration" | "function" | "arrow_function" | "method_definition"
+        | "function_signature" | "method_signature" => {
             if let Some(symbol) = extract_function(node, source, parent_scope.as_deref())? {
                 let function_scope = Some(symbol.name.clone());
                 symbols.push(symbol);
 
                 if cursor.goto_first_child() {
                     loop {
-                        extract_from_node(cursor, source, symbols, function_scope.clone(), _file_id)?;
+                        extract_from_node(cursor, source, symbols, function_scope.clone(), depth + 1, config, errors)?;
                         if !cursor.goto_next_sibling() {
                             break;
                         }
@@ -51,7 +136,7 @@ fn extract_from_node(
 
                 if cursor.goto_first_child() {
                     loop {
-                        extract_from_node(cursor, source, symbols, class_scope.clone(), _file_id)?;
+                        extract_from_node(cursor, source, symbols, class_scope.clone(), depth + 1, config, errors)?;
                         if !cursor.goto_next_sibling() {
                             break;
                         }
@@ -60,6 +145,11 @@ fn extract_from_node(
                 }
             }
         }
+        "public_field_definition" => {
+            if let Some(symbol) = extract_field(node, source, parent_scope.as_deref())? {
+                symbols.push(symbol);
+            }
+        }
         "interface_declaration" => {
             if let Some(symbol) = extract_interface(node, source, parent_scope.as_deref())? {
                 symbols.push(symbol);
@@ -70,6 +160,9 @@ fn extract_from_node(
                 symbols.push(symbol);
             }
         }
+        "enum_declaration" => {
+            symbols.push(extract_enum(node, source, parent_scope.as_deref())?);
+        }
         "import_statement" => {
             if let Some(symbol) = extract_import(node, source, parent_scope.as_deref())? {
                 symbols.push(symbol);
@@ -79,6 +172,34 @@ fn extract_from_node(
             if let Some(symbol) = extract_export(node, source, parent_scope.as_deref())? {
                 symbols.push(symbol);
             }
+
+            // Extract the exported declaration itself
+            if cursor.goto_first_child() {
+                loop {
+                    extract_from_node(cursor, source, symbols, parent_scope.clone(), depth + 1, config, errors)?;
+                    if !cursor.goto_next_sibling() {
+                        break;
+                    }
+                }
+                cursor.goto_parent();
+            }
+        }
+        "module" | "internal_module" => {
+            // `declare module "pkg" { ... }` / `namespace Foo { ... }`
+            if let Some(symbol) = extract_module(node, source, parent_scope.as_deref())? {
+                let module_scope = Some(symbol.name.clone());
+                symbols.push(symbol);
+
+                if cursor.goto_first_child() {
+                    loop {
+                        extract_from_node(cursor, source, symbols, module_scope.clone(), depth + 1, config, errors)?;
+                        if !cursor.goto_next_sibling() {
+                            break;
+                        }
+                    }
+                    cursor.goto_parent();
+                }
+            }
         }
         "lexical_declaration" | "variable_declaration" => {
             // Extract const/let/var declarations (module-level only for now)
@@ -92,7 +213,7 @@ fn extract_from_node(
             // Recurse into children
             if cursor.goto_first_child() {
                 loop {
-                    extract_from_node(cursor, source, symbols, parent_scope.clone(), _file_id)?;
+                    extract_from_node(cursor, source, symbols, parent_scope.clone(), depth + 1, config, errors)?;
                     if !cursor.goto_next_sibling() {
                         break;
                     }
@@ -107,99 +228,316 @@ fn extract_from_node(
 
 /// Extract a function declaration
 fn extract_function(node: Node, source: &str, scope: Option<&str>) -> Result<Option<Symbol>> {
-    let name_node = node.child_by_field_name("name");
-
-    let name = if let Some(name_node) = name_node {
-        node_text(name_node, source)
-    } else {
-        // Anonymous function or arrow function
+    // Anonymous function or arrow function
+    if node.child_by_field_name("name").is_none() {
         return Ok(None);
-    };
-
-    let line_start = node.start_position().row;
-    let line_end = node.end_position().row;
+    }
 
     Ok(Some(Symbol {
-        id: None,
-        file_id: 0,
-        name,
-        kind: SymbolKind::Function,
-        line_start,
-        line_end,
-        scope: scope.map(|s| s.to_string()),
-        metadata: None,
+        metadata: extract_function_metadata(node, source),
+        ..Symbol::try_from((node, source, symbol_kind(node)?, scope))?
     }))
 }
 
+/// Build function metadata: parameters, type guard info from `x is T` return
+/// types, and `signature: true` for bodiless signatures (overloads and ambient
+/// declarations)
+fn extract_function_metadata(node: Node, source: &str) -> Option<String> {
+    let mut metadata = Map::new();
+
+    let params = function_params(node, source);
+    if !params.is_empty() {
+        metadata.insert("params".to_string(), json!(params));
+    }
+
+    if matches!(node.kind(), "function_signature" | "method_signature") {
+        metadata.insert("signature".to_string(), true.into());
+    }
+
+    if let Some(guard_type) = type_guard(node) {
+        metadata.insert("type_guard".to_string(), true.into());
+        metadata.insert("guard_type".to_string(), node_text(guard_type, source).into());
+    }
+
+    (!metadata.is_empty()).then(|| Value::Object(metadata).to_string())
+}
+
+/// Parameters from a function's `formal_parameters`
+///
+/// Names are the parameter pattern as written (`...rest`, `{ a, b }`), with a
+/// trailing `?` for optional parameters.
+fn function_params(node: Node, source: &str) -> Vec<FunctionParam> {
+    let Some(parameters) = node.child_by_field_name("parameters") else {
+        return Vec::new();
+    };
+    let mut cursor = parameters.walk();
+    parameters
+        .named_children(&mut cursor)
+        .filter(|param| matches!(param.kind(), "required_parameter" | "optional_parameter"))
+        .filter_map(|param| {
+            let mut name = node_text(param.child_by_field_name("pattern")?, source);
+            if param.kind() == "optional_parameter" {
+                name.push('?');
+            }
+            let type_name = param
+                .child_by_field_name("type")
+                .and_then(|annotation| annotation.named_child(0))
+                .map(|ty| node_text(ty, source));
+            Some(FunctionParam { name, type_name })
+        })
+        .collect()
+}
+
+/// The `T` of a `x is T` return type, if any
+fn type_guard(node: Node) -> Option<Node> {
+    let return_type = node.child_by_field_name("return_type")?;
+    if return_type.kind() != "type_predicate_annotation" {
+        return None;
+    }
+
+    let mut cursor = return_type.walk();
+    let predicate = return_type
+        .children(&mut cursor)
+        .find(|child| child.kind() == "type_predicate")?;
+    predicate.child_by_field_name("type")
+}
+
 /// Extract a class declaration
 fn extract_class(node: Node, source: &str, scope: Option<&str>) -> Result<Option<Symbol>> {
-    let name_node = node.child_by_field_name("name");
-
     // Skip anonymous classes
-    if name_node.is_none() {
+    if node.child_by_field_name("name").is_none() {
         return Ok(None);
     }
 
-    let name = node_text(name_node.unwrap(), source);
-    let line_start = node.start_position().row;
-    let line_end = node.end_position().row;
+    Ok(Some(Symbol {
+        metadata: heritage_metadata(node, source),
+        ..Symbol::try_from((node, source, symbol_kind(node)?, scope))?
+    }))
+}
+
+/// Extract a class property declaration (`private value: number = 0`)
+///
+/// Metadata records the annotated `type` (if any) and the effective `access`:
+/// the accessibility modifier, `private` for `#name` fields, else `public`.
+fn extract_field(node: Node, source: &str, scope: Option<&str>) -> Result<Option<Symbol>> {
+    if !node.parent().is_some_and(|parent| parent.kind() == "class_body") {
+        return Ok(None);
+    }
+    let Some(name) = node.child_by_field_name("name") else {
+        return Ok(None);
+    };
+
+    let mut cursor = node.walk();
+    let access = node
+        .children(&mut cursor)
+        .find(|child| child.kind() == "accessibility_modifier")
+        .map(|modifier| node_text(modifier, source))
+        .unwrap_or_else(|| {
+            let access = if name.kind() == "private_property_identifier" { "private" } else { "public" };
+            access.to_string()
+        });
+
+    let mut metadata = Map::new();
+    if let Some(ty) = node
+        .child_by_field_name("type")
+        .and_then(|annotation| annotation.named_child(0))
+    {
+        metadata.insert("type".to_string(), node_text(ty, source).into());
+    }
+    metadata.insert("access".to_string(), access.into());
 
     Ok(Some(Symbol {
-        id: None,
-        file_id: 0,
-        name,
-        kind: SymbolKind::Class,
-        line_start,
-        line_end,
-        scope: scope.map(|s| s.to_string()),
-        metadata: None,
+        metadata: Some(Value::Object(metadata).to_string()),
+        ..Symbol::try_from((node, source, symbol_kind(node)?, scope))?
     }))
 }
 
 /// Extract an interface declaration
 fn extract_interface(node: Node, source: &str, scope: Option<&str>) -> Result<Option<Symbol>> {
-    let name_node = node
-        .child_by_field_name("name")
-        .ok_or_else(|| anyhow::anyhow!("Interface has no name"))?;
-
-    let name = node_text(name_node, source);
-    let line_start = node.start_position().row;
-    let line_end = node.end_position().row;
-
     Ok(Some(Symbol {
-        id: None,
-        file_id: 0,
-        name,
-        kind: SymbolKind::Type,
-        line_start,
-        line_end,
-        scope: scope.map(|s| s.to_string()),
-        metadata: None,
+        metadata: heritage_metadata(node, source),
+        ..Symbol::try_from((node, source, symbol_kind(node)?, scope))?
     }))
 }
 
-/// Extract a type alias declaration
-fn extract_type_alias(node: Node, source: &str, scope: Option<&str>) -> Result<Option<Symbol>> {
-    let name_node = node
-        .child_by_field_name("name")
-        .ok_or_else(|| anyhow::anyhow!("Type alias has no name"))?;
+/// Build inheritance metadata for a class or interface
+///
+/// `extends` lists the parent class (or the interfaces an interface extends)
+/// and `implements` the interfaces a class implements. Type arguments are
+/// dropped: `Base<T>` -> `Base`.
+fn heritage_metadata(node: Node, source: &str) -> Option<String> {
+    let mut extends = Vec::new();
+    let mut implements = Vec::new();
 
-    let name = node_text(name_node, source);
-    let line_start = node.start_position().row;
-    let line_end = node.end_position().row;
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        match child.kind() {
+            "class_heritage" => {
+                let mut heritage_cursor = child.walk();
+                for clause in child.named_children(&mut heritage_cursor) {
+                    let mut clause_cursor = clause.walk();
+                    match clause.kind() {
+                        "extends_clause" => extends.extend(
+                            clause
+                                .children_by_field_name("value", &mut clause_cursor)
+                                .map(|value| node_text(value, source)),
+                        ),
+                        "implements_clause" => implements.extend(
+                            clause
+                                .named_children(&mut clause_cursor)
+                                .map(|ty| heritage_type_name(ty, source)),
+                        ),
+                        _ => {}
+                    }
+                }
+            }
+            "extends_type_clause" => {
+                let mut clause_cursor = child.walk();
+                extends.extend(
+                    child
+                        .children_by_field_name("type", &mut clause_cursor)
+                        .map(|ty| heritage_type_name(ty, source)),
+                );
+            }
+            _ => {}
+        }
+    }
 
+    let mut metadata = Map::new();
+    if !extends.is_empty() {
+        metadata.insert("extends".to_string(), json!(extends));
+    }
+    if !implements.is_empty() {
+        metadata.insert("implements".to_string(), json!(implements));
+    }
+    (!metadata.is_empty()).then(|| Value::Object(metadata).to_string())
+}
+
+/// Name of a heritage clause type without its type arguments
+fn heritage_type_name(ty: Node, source: &str) -> String {
+    match ty.kind() {
+        "generic_type" => ty
+            .child_by_field_name("name")
+            .map(|name| node_text(name, source))
+            .unwrap_or_else(|| node_text(ty, source)),
+        _ => node_text(ty, source),
+    }
+}
+
+/// Extract a type alias declaration
+fn extract_type_alias(node: Node, source: &str, scope: Option<&str>) -> Result<Option<Symbol>> {
     Ok(Some(Symbol {
-        id: None,
-        file_id: 0,
-        name,
-        kind: SymbolKind::Type,
-        line_start,
-        line_end,
-        scope: scope.map(|s| s.to_string()),
-        metadata: None,
+        metadata: type_alias_metadata(node, source),
+        ..Symbol::try_from((node, source, symbol_kind(node)?, scope))?
     }))
 }
 
+/// Build type alias metadata from the aliased type: `mapped_type: true` for
+/// `{ [K in keyof T]: ... }`, and `conditional_type: true` plus the
+/// `condition` (`"T extends U"`) for `T extends U ? X : Y`
+fn type_alias_metadata(node: Node, source: &str) -> Option<String> {
+    let value = node.child_by_field_name("value")?;
+    let mut metadata = Map::new();
+
+    match value.kind() {
+        "object_type" if is_mapped_type(value) => {
+            metadata.insert("mapped_type".to_string(), true.into());
+        }
+        "conditional_type" => {
+            let left = value.child_by_field_name("left")?;
+            let right = value.child_by_field_name("right")?;
+            metadata.insert("conditional_type".to_string(), true.into());
+            metadata.insert(
+                "condition".to_string(),
+                format!("{} extends {}", node_text(left, source), node_text(right, source)).into(),
+            );
+        }
+        _ => {}
+    }
+
+    (!metadata.is_empty()).then(|| Value::Object(metadata).to_string())
+}
+
+/// Whether an object type is a mapped type (`[K in ...]` index signature)
+fn is_mapped_type(object_type: Node) -> bool {
+    let mut cursor = object_type.walk();
+    let found = object_type
+        .named_children(&mut cursor)
+        .filter(|member| member.kind() == "index_signature")
+        .any(|signature| {
+            let mut cursor = signature.walk();
+            let mapped = signature
+                .named_children(&mut cursor)
+                .any(|child| child.kind() == "mapped_type_clause");
+            mapped
+        });
+    found
+}
+
+/// Extract an enum declaration
+///
+/// Members go to `metadata["members"]` as `{"name", "value"}` objects, where
+/// `value` is the initializer's source text (`null` when implicit);
+/// `const enum` sets `metadata["const_enum"] = true`.
+fn extract_enum(node: Node, source: &str, scope: Option<&str>) -> Result<Symbol> {
+    let mut members = Vec::new();
+    if let Some(body) = node.child_by_field_name("body") {
+        let mut cursor = body.walk();
+        for member in body.named_children(&mut cursor) {
+            let (name_node, value) = match member.kind() {
+                "enum_assignment" => (
+                    member.child_by_field_name("name"),
+                    member.child_by_field_name("value").map(|v| node_text(v, source)),
+                ),
+                "property_identifier" | "string" => (Some(member), None),
+                _ => continue,
+            };
+            let Some(name_node) = name_node else {
+                continue;
+            };
+            let name = node_text(name_node, source)
+                .trim_matches(|c| c == '"' || c == '\'')
+                .to_string();
+            members.push(json!({ "name": name, "value": value }));
+        }
+    }
+
+    let mut metadata = Map::new();
+    metadata.insert("members".to_string(), Value::Array(members));
+    let mut cursor = node.walk();
+    if node.children(&mut cursor).any(|child| child.kind() == "const") {
+        metadata.insert("const_enum".to_string(), true.into());
+    }
+
+    Ok(Symbol {
+        metadata: Some(Value::Object(metadata).to_string()),
+        ..Symbol::try_from((node, source, symbol_kind(node)?, scope))?
+    })
+}
+
+/// Enum members as `(name, value)` pairs, from `metadata["members"]`
+///
+/// Returns an empty list for symbols without member metadata.
+pub fn get_enum_members(symbol: &Symbol) -> Vec<(String, Option<String>)> {
+    let Some(metadata) = symbol
+        .metadata
+        .as_deref()
+        .and_then(|m| serde_json::from_str::<Value>(m).ok())
+    else {
+        return Vec::new();
+    };
+
+    metadata["members"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|member| {
+            let name = member["name"].as_str()?.to_string();
+            Some((name, member["value"].as_str().map(str::to_string)))
+        })
+        .collect()
+}
+
 /// Extract an import statement
 fn extract_import(node: Node, source: &str, scope: Option<&str>) -> Result<Option<Symbol>> {
     // Try to get the module specifier
@@ -210,24 +548,35 @@ fn extract_import(node: Node, source: &str, scope: Option<&str>) -> Result<Optio
         // Remove quotes
         name = name.trim_matches(|c| c == '"' || c == '\'').to_string();
 
-        let line_start = node.start_position().row;
-        let line_end = node.end_position().row;
-
-        Ok(Some(Symbol {
-            id: None,
-            file_id: 0,
+        Ok(Some(Symbol::with_location(
             name,
-            kind: SymbolKind::Import,
-            line_start,
-            line_end,
-            scope: scope.map(|s| s.to_string()),
-            metadata: None,
-        }))
+            symbol_kind(node)?,
+            SymbolLocation::from_node(&node),
+            scope,
+        )))
     } else {
         Ok(None)
     }
 }
 
+/// Extract an ambient module or namespace declaration
+fn extract_module(node: Node, source: &str, scope: Option<&str>) -> Result<Option<Symbol>> {
+    let Some(name_node) = node.child_by_field_name("name") else {
+        return Ok(None);
+    };
+
+    // Ambient module names are string literals
+    let name = node_text(name_node, source)
+        .trim_matches(|c| c == '"' || c == '\'')
+        .to_string();
+    Ok(Some(Symbol::with_location(
+        name,
+        symbol_kind(node)?,
+        SymbolLocation::from_node(&node),
+        scope,
+    )))
+}
+
 /// Extract an export statement
 fn extract_export(node: Node, _source: &str, _scope: Option<&str>) -> Result<Option<Symbol>> {
     // Check if it's a named export
@@ -254,19 +603,12 @@ fn extract_variable(node: Node, source: &str) -> Result<Option<Symbol>> {
                 if let Some(name_node) = name_node {
                     if name_node.kind() == "identifier" {
                         let name = node_text(name_node, source);
-                        let line_start = node.start_position().row;
-                        let line_end = node.end_position().row;
-
-                        return Ok(Some(Symbol {
-                            id: None,
-                            file_id: 0,
+                        return Ok(Some(Symbol::with_location(
                             name,
-                            kind: SymbolKind::Variable,
-                            line_start,
-                            line_end,
-                            scope: None,
-                            metadata: None,
-                        }));
+                            symbol_kind(node)?,
+                            SymbolLocation::from_node(&node),
+                            None,
+                        )));
                     }
                 }
             }
@@ -279,6 +621,12 @@ fn extract_variable(node: Node, source: &str) -> Result<Option<Symbol>> {
     Ok(None)
 }
 
+/// Look up the symbol kind for a node in the shared mapping table
+fn symbol_kind(node: Node) -> Result<SymbolKind> {
+    SymbolKind::from_file_node_kind("typescript", node.kind())
+        .ok_or_else(|| anyhow::anyhow!("Node kind `{}` does not define a symbol", node.kind()))
+}
+
 /// Get text content of a node
 fn node_text(node: Node, source: &str) -> String {
     source[node.byte_range()].to_string()
@@ -298,7 +646,7 @@ function myFunction() {
 "#;
         let mut parser = TypeScriptParser::new().unwrap();
         let tree = parser.parse(source).unwrap();
-        let symbols = extract_symbols(&tree, source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
 
         assert_eq!(symbols.len(), 1);
         assert_eq!(symbols[0].name, "myFunction");
@@ -320,7 +668,7 @@ class MyClass {
 "#;
         let mut parser = TypeScriptParser::new().unwrap();
         let tree = parser.parse(source).unwrap();
-        let symbols = extract_symbols(&tree, source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
 
         assert!(symbols.iter().any(|s| s.name == "MyClass" && matches!(s.kind, SymbolKind::Class)));
         assert!(symbols.iter().any(|s| s.name == "getValue" && matches!(s.kind, SymbolKind::Function)));
@@ -336,9 +684,63 @@ interface User {
 "#;
         let mut parser = TypeScriptParser::new().unwrap();
         let tree = parser.parse(source).unwrap();
-        let symbols = extract_symbols(&tree, source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+
+        assert!(symbols.iter().any(|s| s.name == "User" && matches!(s.kind, SymbolKind::Interface)));
+    }
+
+    #[test]
+    fn test_extract_heritage() {
+        let source = r#"
+interface Shape extends Named, Comparable<Shape> {}
+class Base {}
+class Circle extends Base implements Shape, Serializable<Circle> {}
+class Mixed extends mixin(Base) {}
+"#;
+        let mut parser = TypeScriptParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+        let metadata = |name: &str| -> Value {
+            let symbol = symbols.iter().find(|s| s.name == name).unwrap();
+            serde_json::from_str(symbol.metadata.as_deref().unwrap()).unwrap()
+        };
+
+        assert_eq!(metadata("Shape"), json!({ "extends": ["Named", "Comparable"] }));
+        assert!(symbols.iter().find(|s| s.name == "Base").unwrap().metadata.is_none());
+        assert_eq!(
+            metadata("Circle"),
+            json!({ "extends": ["Base"], "implements": ["Shape", "Serializable"] })
+        );
+        assert_eq!(metadata("Mixed")["extends"], json!(["mixin(Base)"]));
+    }
+
+    #[test]
+    fn test_extract_class_fields() {
+        let source = r#"
+class Counter {
+    private value: number = 0;
+    protected readonly label: string;
+    static instances = 0;
+    #secret: Map<string, number>;
+    increment() {}
+}
+"#;
+        let mut parser = TypeScriptParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+        let field = |name: &str| -> Value {
+            let symbol = symbols.iter().find(|s| s.name == name).unwrap();
+            assert_eq!(symbol.kind, SymbolKind::Field);
+            assert_eq!(symbol.scope.as_deref(), Some("Counter"));
+            serde_json::from_str(symbol.metadata.as_deref().unwrap()).unwrap()
+        };
 
-        assert!(symbols.iter().any(|s| s.name == "User" && matches!(s.kind, SymbolKind::Type)));
+        assert_eq!(field("value"), json!({ "type": "number", "access": "private" }));
+        assert_eq!(field("label"), json!({ "type": "string", "access": "protected" }));
+        assert_eq!(field("instances"), json!({ "access": "public" }));
+        assert_eq!(field("#secret"), json!({ "type": "Map<string, number>", "access": "private" }));
+        assert!(symbols.iter().any(|s| s.name == "increment" && s.kind == SymbolKind::Function));
+        assert_eq!(symbols.iter().filter(|s| s.kind == SymbolKind::Field).count(), 4);
     }
 
     #[test]
@@ -348,11 +750,48 @@ type UserId = string | number;
 "#;
         let mut parser = TypeScriptParser::new().unwrap();
         let tree = parser.parse(source).unwrap();
-        let symbols = extract_symbols(&tree, source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
 
         assert!(symbols.iter().any(|s| s.name == "UserId" && matches!(s.kind, SymbolKind::Type)));
     }
 
+    #[test]
+    fn test_extract_mapped_type() {
+        let source = r#"
+type Readonly<T> = { readonly [K in keyof T]: T[K] };
+type Dict = { [key: string]: number };
+"#;
+        let mut parser = TypeScriptParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+
+        let readonly = symbols.iter().find(|s| s.name == "Readonly").unwrap();
+        assert_eq!(readonly.kind, SymbolKind::Type);
+        let metadata: Value = serde_json::from_str(readonly.metadata.as_deref().unwrap()).unwrap();
+        assert_eq!(metadata["mapped_type"], true);
+
+        // A plain index signature is not a mapped type
+        let dict = symbols.iter().find(|s| s.name == "Dict").unwrap();
+        assert!(dict.metadata.is_none());
+    }
+
+    #[test]
+    fn test_extract_conditional_type() {
+        let source = r#"
+type NonNullable<T> = T extends null | undefined ? never : T;
+"#;
+        let mut parser = TypeScriptParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+
+        let non_nullable = symbols.iter().find(|s| s.name == "NonNullable").unwrap();
+        assert_eq!(non_nullable.kind, SymbolKind::Type);
+        let metadata: Value = serde_json::from_str(non_nullable.metadata.as_deref().unwrap()).unwrap();
+        assert_eq!(metadata["conditional_type"], true);
+        assert_eq!(metadata["condition"], "T extends null | undefined");
+        assert!(metadata.get("mapped_type").is_none());
+    }
+
     #[test]
     fn test_extract_imports() {
         let source = r#"
@@ -361,9 +800,283 @@ import axios from 'axios';
 "#;
         let mut parser = TypeScriptParser::new().unwrap();
         let tree = parser.parse(source).unwrap();
-        let symbols = extract_symbols(&tree, source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
 
         assert!(symbols.iter().any(|s| s.name == "react" && matches!(s.kind, SymbolKind::Import)));
         assert!(symbols.iter().any(|s| s.name == "axios" && matches!(s.kind, SymbolKind::Import)));
     }
+
+    #[test]
+    fn test_extract_type_guard() {
+        let source = r#"
+function isString(x: unknown): x is string { return typeof x === "string"; }
+function plain(x: unknown): boolean { return true; }
+"#;
+        let mut parser = TypeScriptParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+
+        let guard = symbols.iter().find(|s| s.name == "isString").unwrap();
+        let metadata: serde_json::Value =
+            serde_json::from_str(guard.metadata.as_deref().unwrap()).unwrap();
+        assert_eq!(metadata["type_guard"], true);
+        assert_eq!(metadata["guard_type"], "string");
+
+        let plain = symbols.iter().find(|s| s.name == "plain").unwrap();
+        let metadata: serde_json::Value =
+            serde_json::from_str(plain.metadata.as_deref().unwrap()).unwrap();
+        assert!(metadata.get("type_guard").is_none());
+    }
+
+    #[test]
+    fn test_extract_function_params() {
+        let source = r#"
+function f(this: Window, a: number, b?: string, c = 1, ...rest: any[]) {}
+class C {
+    constructor(private x: number, { y }: Opts) {}
+    m(a) {}
+    n() {}
+}
+"#;
+        let mut parser = TypeScriptParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+
+        let params = |name: &str| metadata(symbols.iter().find(|s| s.name == name).unwrap())["params"].clone();
+        assert_eq!(
+            params("f"),
+            json!([
+                {"name": "this", "type": "Window"},
+                {"name": "a", "type": "number"},
+                {"name": "b?", "type": "string"},
+                {"name": "c", "type": null},
+                {"name": "...rest", "type": "any[]"},
+            ])
+        );
+        assert_eq!(
+            params("constructor"),
+            json!([{"name": "x", "type": "number"}, {"name": "{ y }", "type": "Opts"}])
+        );
+        assert_eq!(params("m"), json!([{"name": "a", "type": null}]));
+        assert_eq!(params("n"), Value::Null);
+    }
+
+    #[test]
+    fn test_extract_declaration_file() {
+        let source = r#"
+declare module "express" {
+    export function json(): void;
+    interface Request { body: any; }
+}
+declare namespace NodeJS {
+    interface Global {}
+}
+export declare function greet(name: string): string;
+declare class Foo {
+    bar(): void;
+}
+export type Id = string;
+"#;
+        let mut parser = TypeScriptParser::new().unwrap();
+        let tree = parser.parse_declaration(source).unwrap();
+        let (symbols, _) = extract_declaration_symbols(&tree, source).unwrap();
+
+        let express = symbols.iter().find(|s| s.name == "express").unwrap();
+        assert_eq!(express.kind, SymbolKind::Module);
+        assert!(symbols.iter().any(|s| s.name == "json"
+            && s.kind == SymbolKind::Function
+            && s.scope.as_deref() == Some("express")));
+        assert!(symbols.iter().any(|s| s.name == "Request" && s.kind == SymbolKind::Interface));
+        assert!(symbols.iter().any(|s| s.name == "NodeJS" && s.kind == SymbolKind::Module));
+        assert!(symbols.iter().any(|s| s.name == "greet" && s.kind == SymbolKind::Function));
+        assert!(symbols.iter().any(|s| s.name == "Foo" && s.kind == SymbolKind::Class));
+        assert!(symbols.iter().any(|s| s.name == "bar" && s.scope.as_deref() == Some("Foo")));
+        assert!(symbols.iter().any(|s| s.name == "Id" && s.kind == SymbolKind::Type));
+
+        for symbol in &symbols {
+            let metadata: Value = serde_json::from_str(symbol.metadata.as_deref().unwrap()).unwrap();
+            assert_eq!(metadata["declaration"], true, "{}", symbol.name);
+        }
+    }
+
+    #[test]
+    fn test_declaration_keeps_existing_metadata() {
+        let source = "declare function isString(x: unknown): x is string;\n";
+        let mut parser = TypeScriptParser::new().unwrap();
+        let tree = parser.parse_declaration(source).unwrap();
+        let (symbols, _) = extract_declaration_symbols(&tree, source).unwrap();
+
+        let metadata: Value = serde_json::from_str(symbols[0].metadata.as_deref().unwrap()).unwrap();
+        assert_eq!(metadata["declaration"], true);
+        assert_eq!(metadata["type_guard"], true);
+    }
+
+    #[test]
+    fn test_regular_source_not_marked_declaration() {
+        let source = "export function main() {}\n";
+        let mut parser = TypeScriptParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols_in_mode(&tree, source, false).unwrap();
+
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(symbols[0].name, "main");
+        assert!(symbols[0].metadata.is_none());
+    }
+
+    fn metadata(symbol: &Symbol) -> Value {
+        symbol
+            .metadata
+            .as_deref()
+            .map(|m| serde_json::from_str(m).unwrap())
+            .unwrap_or(Value::Null)
+    }
+
+    #[test]
+    fn test_function_overloads() {
+        let source = r#"function format(x: number): string;
+function format(x: string): string;
+function format(x: any): string { return "" }
+function plain() {}
+"#;
+        let mut parser = TypeScriptParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+
+        let formats: Vec<&Symbol> = symbols.iter().filter(|s| s.name == "format").collect();
+        assert_eq!(formats.len(), 3);
+        assert_eq!(metadata(formats[0])["signature"], true);
+        assert_eq!(metadata(formats[1])["signature"], true);
+
+        let implementation = metadata(formats[2]);
+        assert_eq!(implementation["overloaded"], true);
+        assert_eq!(implementation["overload_count"], 2);
+        assert!(implementation.get("signature").is_none());
+
+        let plain = symbols.iter().find(|s| s.name == "plain").unwrap();
+        assert!(plain.metadata.is_none());
+    }
+
+    #[test]
+    fn test_method_overloads_are_scoped_to_class() {
+        let source = r#"class A {
+    run(x: number): void;
+    run(x: any) {}
+}
+class B {
+    run() {}
+}
+"#;
+        let mut parser = TypeScriptParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+
+        let a_run = symbols
+            .iter()
+            .find(|s| s.scope.as_deref() == Some("A") && s.name == "run" && metadata(s)["signature"] != true)
+            .unwrap();
+        assert_eq!(metadata(a_run)["overload_count"], 1);
+
+        let b_run = symbols.iter().find(|s| s.scope.as_deref() == Some("B")).unwrap();
+        assert!(b_run.metadata.is_none());
+    }
+
+    #[test]
+    fn test_declaration_signatures_without_implementation() {
+        let source = "declare function parse(x: string): number;\ndeclare function parse(x: Buffer): number;\n";
+        let mut parser = TypeScriptParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_declaration_symbols(&tree, source).unwrap();
+
+        assert_eq!(symbols.len(), 2);
+        for symbol in &symbols {
+            assert_eq!(metadata(symbol)["signature"], true);
+            assert!(metadata(symbol).get("overloaded").is_none());
+        }
+    }
+
+    fn enum_symbol<'a>(symbols: &'a [Symbol], name: &str) -> &'a Symbol {
+        symbols
+            .iter()
+            .find(|s| s.name == name && s.kind == SymbolKind::Enum)
+            .unwrap()
+    }
+
+    fn member(name: &str, value: Option<&str>) -> (String, Option<String>) {
+        (name.to_string(), value.map(str::to_string))
+    }
+
+    #[test]
+    fn test_enum_numeric_values() {
+        let source = "export enum Status { Active = 1, Inactive = 0, Pending }\n";
+        let mut parser = TypeScriptParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+
+        let status = enum_symbol(&symbols, "Status");
+        assert_eq!(
+            get_enum_members(status),
+            vec![member("Active", Some("1")), member("Inactive", Some("0")), member("Pending", None)]
+        );
+        assert_eq!(metadata(status)["members"][0], json!({"name": "Active", "value": "1"}));
+        assert!(metadata(status).get("const_enum").is_none());
+    }
+
+    #[test]
+    fn test_enum_string_values() {
+        let source = "const enum Direction { Up = \"UP\", 'Down' = 'DOWN' }\n";
+        let mut parser = TypeScriptParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+
+        let direction = enum_symbol(&symbols, "Direction");
+        assert_eq!(
+            get_enum_members(direction),
+            vec![member("Up", Some("\"UP\"")), member("Down", Some("'DOWN'"))]
+        );
+        assert_eq!(metadata(direction)["const_enum"], true);
+    }
+
+    #[test]
+    fn test_enum_computed_values() {
+        let source = r#"
+namespace Flags {
+    export enum Mask { Read = 1 << 0, Write = 1 << 1, All = Read | Write, Len = "abc".length }
+}
+"#;
+        let mut parser = TypeScriptParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+        let (symbols, _) = extract_symbols(&tree, source, None).unwrap();
+
+        let mask = enum_symbol(&symbols, "Mask");
+        assert_eq!(mask.scope.as_deref(), Some("Flags"));
+        assert_eq!(
+            get_enum_members(mask),
+            vec![
+                member("Read", Some("1 << 0")),
+                member("Write", Some("1 << 1")),
+                member("All", Some("Read | Write")),
+                member("Len", Some("\"abc\".length")),
+            ]
+        );
+        // Non-enum symbols have no members
+        assert_eq!(symbols[0].kind, SymbolKind::Module);
+        assert!(get_enum_members(&symbols[0]).is_empty());
+    }
+
+    #[test]
+    fn test_max_depth_skips_deep_nesting() {
+        let source = "function outer() {\n  function middle() {\n    function inner() {}\n  }\n}\n";
+        let mut parser = TypeScriptParser::new().unwrap();
+        let tree = parser.parse(source).unwrap();
+
+        let (symbols, errors) = extract_symbols(&tree, source, None).unwrap();
+        assert_eq!(symbols.len(), 3);
+        assert!(errors.is_empty());
+
+        let (symbols, errors) = extract_symbols(&tree, source, Some(ExtractConfig { max_depth: 4 })).unwrap();
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["outer", "middle"]);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line, 1);
+    }
 }
@@ -1,32 +1,106 @@
 // PyO3 bridge module - Exposes Rust analyzer functions to Python
 // Implements async bridge with error propagation
 
-use pyo3::exceptions::PyRuntimeError;
+use anyhow::Context;
+use pyo3::exceptions::{PyRuntimeError, PyStopAsyncIteration, PyValueError};
 use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyType};
 use pyo3_async_runtimes::tokio::future_into_py;
+use rusqlite::{Transaction, TransactionBehavior};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::{broadcast, Mutex};
 
 use analyzer_core::{
-    indexer::{discover_files, IndexerConfig},
+    embedding::populate_embedding_hints,
+    export::export_index_to_json_async,
+    incremental::{
+        content_hash, reindex_changed_files, reindex_file as reindex_single_file, FileChangeEvent,
+        FileWatcher, IncrementalIndex, IncrementalIndexHandle, IncrementalIndexer, IndexStats,
+    },
+    indexer::{analyze_directory_parallel, discover_files, truncate_symbols, IndexerConfig},
+    lsp::{export_lsp_workspace_symbols, import_lsp_workspace_symbols},
+    package_entry_point_metadata,
     query::{
-        find_exports_by_file, find_imports_by_file, find_symbols_by_file_path,
-        find_symbols_by_name, get_file_path_by_id, get_language_stats as query_language_stats,
-        list_files as query_list_files,
+        count_symbols_by_file, count_symbols_by_kind, explain_query, find_duplicate_symbol_names,
+        find_duplicate_symbols_in_file, find_exports_by_file, find_files_with_errors,
+        find_imports_by_file, find_package_entry_points, find_parse_errors_by_file,
+        find_slowest_files, find_symbols_by_file_and_kind, find_symbols_by_file_path,
+        find_symbols_by_file_path_paged, find_symbols_by_file_path_pattern, find_symbols_by_kind,
+        find_symbols_by_name, find_symbols_by_name_paged, find_symbols_by_name_prefix,
+        find_symbols_by_name_prefix_and_kind, find_symbols_by_name_regex,
+        find_symbols_grouped_by_file, find_test_symbols, get_file_path_by_id,
+        get_language_stats as query_language_stats,
+        get_language_stats_extended as query_language_stats_extended, get_symbol_context,
+        list_files as query_list_files, prune_change_log, query_change_log,
+        search_symbols_across_languages, ChangeLogEntry, QueryBuilder, QueryError, SymbolContext,
+    },
+    registry::{analyze_file_only, AnalyzerOutput, AnalyzerRegistry, FILE_ONLY_LANGUAGES},
+    snapshot::{create_snapshot, restore_snapshot},
+    storage::{
+        checkpoint_wal, compact_metadata, delete_file_by_path, delete_file_symbols,
+        get_file_by_path, get_index_size_bytes, get_or_create_file, get_symbol_by_id,
+        get_table_size_breakdown, init_schema, insert_symbols_batch, insert_symbols_in_transaction,
+        relocate_symbols_after_line, replace_parse_errors, set_file_content_hash, upsert_file,
+        validate_metadata_json, WalCheckpointMode,
     },
-    storage::{delete_file_symbols, get_file_by_path, init_schema, insert_symbol, upsert_file},
-    FileMetadata, Symbol,
+    FileMetadata, IndexingError, ParseError, Symbol, SymbolKind,
 };
-use analyzer_python::analyze_python;
-use analyzer_rust::analyze_rust;
-use analyzer_typescript::analyze_typescript;
+use analyzer_python::{
+    analyze_python, analyze_python_incremental, analyze_python_with_version, mark_package_metadata,
+};
+use analyzer_c::analyze_c;
+use analyzer_cpp::analyze_cpp;
+use analyzer_go::analyze_go;
+use analyzer_java::analyze_java;
+use analyzer_rust::{analyze_rust, analyze_rust_incremental};
+use analyzer_typescript::{analyze_typescript, analyze_typescript_declaration, analyze_typescript_incremental};
+
+/// Registry with every analyzer compiled into the bindings
+fn default_registry() -> AnalyzerRegistry {
+    let mut registry = AnalyzerRegistry::new();
+    registry
+        .register("python", analyze_python)
+        .register("typescript", analyze_typescript)
+        .register("typescript_declaration", analyze_typescript_declaration)
+        .register("javascript", analyze_typescript)
+        .register("rust", analyze_rust)
+        .register("go", analyze_go)
+        .register("java", analyze_java)
+        .register("c", analyze_c)
+        .register("cpp", analyze_cpp)
+        .register_file_only_languages();
+    registry
+}
+
+/// Incremental re-parsers used by the watch loop for languages that have one
+fn default_incremental_indexer() -> IncrementalIndexer {
+    let mut indexer = IncrementalIndexer::new();
+    indexer
+        .register("python", analyze_python_incremental)
+        .register("typescript", analyze_typescript_incremental)
+        .register("javascript", analyze_typescript_incremental)
+        .register("rust", analyze_rust_incremental);
+    indexer
+}
+
+/// Root directories accepted by `PyIndexerConfig(...)`: one path or a list
+#[derive(FromPyObject)]
+enum PyRootDirs {
+    One(String),
+    Many(Vec<String>),
+}
 
 /// Python wrapper for IndexerConfig
 #[pyclass]
 #[derive(Clone)]
 pub struct PyIndexerConfig {
     #[pyo3(get, set)]
-    pub root_dir: String,
+    pub root_dirs: Vec<String>,
 
     #[pyo3(get, set)]
     pub extensions: Vec<String>,
@@ -34,16 +108,32 @@ pub struct PyIndexerConfig {
     #[pyo3(get, set)]
     pub exclude_dirs: Vec<String>,
 
+    #[pyo3(get, set)]
+    pub gitignore: bool,
+
+    #[pyo3(get, set)]
+    pub use_contextaignore: bool,
+
     #[pyo3(get, set)]
     pub max_file_size: u64,
+
+    #[pyo3(get, set)]
+    pub max_symbols_per_file: Option<usize>,
+
+    #[pyo3(get, set)]
+    pub python_version: Option<(u8, u8)>,
 }
 
 #[pymethods]
 impl PyIndexerConfig {
     #[new]
-    fn new(root_dir: String) -> Self {
+    fn new(root_dirs: PyRootDirs) -> Self {
+        let root_dirs = match root_dirs {
+            PyRootDirs::One(root) => vec![root],
+            PyRootDirs::Many(roots) => roots,
+        };
         Self {
-            root_dir,
+            root_dirs,
             extensions: vec![],
             exclude_dirs: vec![
                 ".git".to_string(),
@@ -56,14 +146,38 @@ impl PyIndexerConfig {
                 "build".to_string(),
                 ".next".to_string(),
             ],
+            gitignore: true,
+            use_contextaignore: true,
             max_file_size: 10 * 1024 * 1024, // 10 MB
+            max_symbols_per_file: None,
+            python_version: None,
         }
     }
 
+    /// First root directory (kept for single-root callers)
+    #[getter]
+    fn root_dir(&self) -> Option<String> {
+        self.root_dirs.first().cloned()
+    }
+
+    /// Replace all root directories with a single one
+    #[setter]
+    fn set_root_dir(&mut self, root_dir: String) {
+        self.root_dirs = vec![root_dir];
+    }
+
+    /// Raise ValueError listing every invalid field
+    fn validate(&self) -> PyResult<()> {
+        IndexerConfig::from(self).validate().map_err(|errors| {
+            let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+            PyValueError::new_err(format!("Invalid indexer config: {}", messages.join("; ")))
+        })
+    }
+
     fn __repr__(&self) -> String {
         format!(
-            "PyIndexerConfig(root_dir='{}', extensions={:?}, exclude_dirs={:?})",
-            self.root_dir, self.extensions, self.exclude_dirs
+            "PyIndexerConfig(root_dirs={:?}, extensions={:?}, exclude_dirs={:?})",
+            self.root_dirs, self.extensions, self.exclude_dirs
         )
     }
 }
@@ -71,10 +185,14 @@ impl PyIndexerConfig {
 impl From<&PyIndexerConfig> for IndexerConfig {
     fn from(py_config: &PyIndexerConfig) -> Self {
         IndexerConfig {
-            root_dir: PathBuf::from(&py_config.root_dir),
+            root_dirs: py_config.root_dirs.iter().map(PathBuf::from).collect(),
             extensions: py_config.extensions.clone(),
             exclude_dirs: py_config.exclude_dirs.clone(),
+            gitignore: py_config.gitignore,
+            use_contextaignore: py_config.use_contextaignore,
             max_file_size: py_config.max_file_size,
+            max_symbols_per_file: py_config.max_symbols_per_file,
+            python_version: py_config.python_version,
         }
     }
 }
@@ -101,8 +219,18 @@ pub struct PySymbol {
     #[pyo3(get)]
     pub line_end: usize,
 
+    #[pyo3(get)]
+    pub column_start: usize,
+
+    #[pyo3(get)]
+    pub column_end: usize,
+
     #[pyo3(get)]
     pub scope: Option<String>,
+
+    /// Language-specific metadata as a JSON string
+    #[pyo3(get)]
+    pub metadata: Option<String>,
 }
 
 #[pymethods]
@@ -124,11 +252,85 @@ impl PySymbol {
         map.insert("kind".to_string(), self.kind.clone());
         map.insert("line_start".to_string(), self.line_start.to_string());
         map.insert("line_end".to_string(), self.line_end.to_string());
+        map.insert("column_start".to_string(), self.column_start.to_string());
+        map.insert("column_end".to_string(), self.column_end.to_string());
         if let Some(ref scope) = self.scope {
             map.insert("scope".to_string(), scope.clone());
         }
+        if let Some(ref metadata) = self.metadata {
+            map.insert("metadata".to_string(), metadata.clone());
+        }
         Ok(map)
     }
+
+    /// Rebuild a symbol from a `to_dict()` result
+    ///
+    /// Numbers may be given as ints or as the strings `to_dict()` produces;
+    /// `id`, `scope` and `metadata` are optional.
+    #[classmethod]
+    fn from_dict(_cls: &Bound<'_, PyType>, data: &Bound<'_, PyDict>) -> PyResult<Self> {
+        let required = |key: &str| -> PyResult<Bound<'_, PyAny>> {
+            data.get_item(key)?
+                .ok_or_else(|| PyValueError::new_err(format!("Missing symbol field: {}", key)))
+        };
+        let text = |key: &str| -> PyResult<String> { required(key)?.str()?.extract() };
+        let optional = |key: &str| -> PyResult<Option<String>> {
+            match data.get_item(key)? {
+                Some(value) if !value.is_none() => Ok(Some(value.str()?.extract()?)),
+                _ => Ok(None),
+            }
+        };
+
+        let kind = text("kind")?;
+        kind.parse::<SymbolKind>().map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+        Ok(Self {
+            id: optional("id")?.map(|id| parse_dict_field("id", id)).transpose()?,
+            file_id: parse_dict_field("file_id", text("file_id")?)?,
+            name: text("name")?,
+            kind,
+            line_start: parse_dict_field("line_start", text("line_start")?)?,
+            line_end: parse_dict_field("line_end", text("line_end")?)?,
+            column_start: parse_dict_field("column_start", text("column_start")?)?,
+            column_end: parse_dict_field("column_end", text("column_end")?)?,
+            scope: optional("scope")?,
+            metadata: optional("metadata")?,
+        })
+    }
+
+    /// Symbols are identified by `(file_id, name, kind, line_start)`
+    fn __hash__(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.identity().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self.identity() == other.identity()
+    }
+
+    fn __ne__(&self, other: &Self) -> bool {
+        !self.__eq__(other)
+    }
+
+    /// Order by position so `sorted(symbols)` follows source order
+    fn __lt__(&self, other: &Self) -> bool {
+        (self.file_id, self.line_start) < (other.file_id, other.line_start)
+    }
+}
+
+/// Parse the string form of a `PySymbol.from_dict` field
+fn parse_dict_field<T: std::str::FromStr>(key: &str, value: String) -> PyResult<T> {
+    value
+        .parse()
+        .map_err(|_| PyValueError::new_err(format!("Invalid {}: {}", key, value)))
+}
+
+impl PySymbol {
+    /// Fields compared by `__eq__` and `__hash__`
+    fn identity(&self) -> (i64, &str, &str, usize) {
+        (self.file_id, &self.name, &self.kind, self.line_start)
+    }
 }
 
 impl From<Symbol> for PySymbol {
@@ -140,7 +342,160 @@ impl From<Symbol> for PySymbol {
             kind: symbol.kind.to_string(),
             line_start: symbol.line_start,
             line_end: symbol.line_end,
+            column_start: symbol.column_start,
+            column_end: symbol.column_end,
             scope: symbol.scope,
+            metadata: symbol.metadata,
+        }
+    }
+}
+
+/// Python mirror of SymbolKind
+#[pyclass(eq, eq_int)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PySymbolKind {
+    Function,
+    Class,
+    Variable,
+    Constant,
+    Import,
+    Export,
+    Module,
+    Struct,
+    Enum,
+    Trait,
+    Interface,
+    Type,
+    Field,
+    EnumVariant,
+    Macro,
+    Namespace,
+}
+
+#[pymethods]
+impl PySymbolKind {
+    fn __str__(&self) -> String {
+        SymbolKind::from(*self).to_string()
+    }
+}
+
+impl From<PySymbolKind> for SymbolKind {
+    fn from(kind: PySymbolKind) -> Self {
+        match kind {
+            PySymbolKind::Function => SymbolKind::Function,
+            PySymbolKind::Class => SymbolKind::Class,
+            PySymbolKind::Variable => SymbolKind::Variable,
+            PySymbolKind::Constant => SymbolKind::Constant,
+            PySymbolKind::Import => SymbolKind::Import,
+            PySymbolKind::Export => SymbolKind::Export,
+            PySymbolKind::Module => SymbolKind::Module,
+            PySymbolKind::Struct => SymbolKind::Struct,
+            PySymbolKind::Enum => SymbolKind::Enum,
+            PySymbolKind::Trait => SymbolKind::Trait,
+            PySymbolKind::Interface => SymbolKind::Interface,
+            PySymbolKind::Type => SymbolKind::Type,
+            PySymbolKind::Field => SymbolKind::Field,
+            PySymbolKind::EnumVariant => SymbolKind::EnumVariant,
+            PySymbolKind::Macro => SymbolKind::Macro,
+            PySymbolKind::Namespace => SymbolKind::Namespace,
+        }
+    }
+}
+
+/// Accept either a `PySymbolKind` or its string name (case-insensitive)
+fn extract_symbol_kind(kind: &Bound<'_, PyAny>) -> PyResult<SymbolKind> {
+    if let Ok(kind) = kind.extract::<PySymbolKind>() {
+        return Ok(kind.into());
+    }
+
+    let name: String = kind
+        .extract()
+        .map_err(|_| PyValueError::new_err("kind must be a PySymbolKind or str"))?;
+    name.parse().map_err(|e| PyValueError::new_err(format!("{}", e)))
+}
+
+/// Python wrapper for SymbolContext
+#[pyclass]
+#[derive(Clone)]
+pub struct PySymbolContext {
+    #[pyo3(get)]
+    pub symbol: PySymbol,
+
+    #[pyo3(get)]
+    pub file_path: String,
+
+    #[pyo3(get)]
+    pub preceding_symbols: Vec<PySymbol>,
+
+    #[pyo3(get)]
+    pub following_symbols: Vec<PySymbol>,
+}
+
+#[pymethods]
+impl PySymbolContext {
+    fn __repr__(&self) -> String {
+        format!(
+            "PySymbolContext(symbol='{}', preceding={}, following={})",
+            self.symbol.name,
+            self.preceding_symbols.len(),
+            self.following_symbols.len()
+        )
+    }
+}
+
+impl From<SymbolContext> for PySymbolContext {
+    fn from(context: SymbolContext) -> Self {
+        Self {
+            symbol: PySymbol::from(context.symbol),
+            file_path: context.file_path,
+            preceding_symbols: context.preceding_symbols.into_iter().map(PySymbol::from).collect(),
+            following_symbols: context.following_symbols.into_iter().map(PySymbol::from).collect(),
+        }
+    }
+}
+
+/// Python wrapper for ChangeLogEntry
+#[pyclass]
+#[derive(Clone)]
+pub struct PyChangeLogEntry {
+    #[pyo3(get)]
+    pub id: i64,
+
+    #[pyo3(get)]
+    pub event_kind: String,
+
+    #[pyo3(get)]
+    pub file_path: String,
+
+    #[pyo3(get)]
+    pub timestamp: String,
+
+    #[pyo3(get)]
+    pub symbols_added: usize,
+
+    #[pyo3(get)]
+    pub symbols_removed: usize,
+}
+
+#[pymethods]
+impl PyChangeLogEntry {
+    fn __repr__(&self) -> String {
+        format!(
+            "PyChangeLogEntry(event_kind='{}', file_path='{}', timestamp='{}')",
+            self.event_kind, self.file_path, self.timestamp
+        )
+    }
+}
+
+impl From<ChangeLogEntry> for PyChangeLogEntry {
+    fn from(entry: ChangeLogEntry) -> Self {
+        Self {
+            id: entry.id,
+            event_kind: entry.event_kind,
+            file_path: entry.file_path,
+            timestamp: entry.timestamp,
+            symbols_added: entry.symbols_added,
+            symbols_removed: entry.symbols_removed,
         }
     }
 }
@@ -158,11 +513,17 @@ pub struct PyFileMetadata {
     #[pyo3(get)]
     pub size: u64,
 
-    #[pyo3(get)]
-    pub parse_errors: i32,
+    /// Exposed to Python as a list of dicts by the `parse_errors` getter
+    pub parse_errors: Vec<ParseError>,
 
     #[pyo3(get)]
     pub last_indexed: Option<String>,
+
+    #[pyo3(get)]
+    pub index_duration_ms: Option<u64>,
+
+    #[pyo3(get)]
+    pub metadata: Option<String>,
 }
 
 #[pymethods]
@@ -174,36 +535,348 @@ impl PyFileMetadata {
         )
     }
 
+    /// Syntax errors as dicts with `file_path`, `line`, `column` and `message`
+    #[getter]
+    fn parse_errors<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        self.parse_errors
+            .iter()
+            .map(|error| {
+                let dict = PyDict::new(py);
+                dict.set_item("file_path", &error.file_path)?;
+                dict.set_item("line", error.line)?;
+                dict.set_item("column", error.column)?;
+                dict.set_item("message", &error.message)?;
+                Ok(dict)
+            })
+            .collect()
+    }
+
+    /// `last_indexed` as a timezone-aware UTC `datetime`
+    #[getter]
+    fn last_indexed_datetime(&self, py: Python<'_>) -> PyResult<Option<Py<PyAny>>> {
+        let Some(ref last_indexed) = self.last_indexed else {
+            return Ok(None);
+        };
+
+        let datetime = py.import("datetime")?;
+        let parsed = datetime
+            .getattr("datetime")?
+            .call_method1("fromisoformat", (last_indexed,))?;
+
+        // Timestamps without an offset are UTC
+        if !parsed.getattr("tzinfo")?.is_none() {
+            return Ok(Some(parsed.unbind()));
+        }
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("tzinfo", datetime.getattr("timezone")?.getattr("utc")?)?;
+        Ok(Some(parsed.call_method("replace", (), Some(&kwargs))?.unbind()))
+    }
+
+    /// `last_indexed` as Unix epoch seconds
+    #[getter]
+    fn last_indexed_timestamp(&self, py: Python<'_>) -> PyResult<Option<u64>> {
+        let Some(datetime) = self.last_indexed_datetime(py)? else {
+            return Ok(None);
+        };
+        let seconds: f64 = datetime.bind(py).call_method0("timestamp")?.extract()?;
+        Ok(Some(seconds as u64))
+    }
+
     fn to_dict(&self) -> PyResult<std::collections::HashMap<String, String>> {
         let mut map = std::collections::HashMap::new();
         map.insert("path".to_string(), self.path.clone());
         map.insert("language".to_string(), self.language.clone());
         map.insert("size".to_string(), self.size.to_string());
-        map.insert("parse_errors".to_string(), self.parse_errors.to_string());
+        map.insert(
+            "parse_errors".to_string(),
+            serde_json::to_string(&self.parse_errors)
+                .map_err(|e| PyValueError::new_err(format!("Failed to serialize parse errors: {}", e)))?,
+        );
         if let Some(ref last_indexed) = self.last_indexed {
             map.insert("last_indexed".to_string(), last_indexed.clone());
         }
+        if let Some(duration_ms) = self.index_duration_ms {
+            map.insert("index_duration_ms".to_string(), duration_ms.to_string());
+        }
+        if let Some(ref metadata) = self.metadata {
+            map.insert("metadata".to_string(), metadata.clone());
+        }
         Ok(map)
     }
 }
 
+impl PyFileMetadata {
+    /// Wrap a stored file, loading its parse errors from the index
+    fn load(conn: &rusqlite::Connection, metadata: FileMetadata) -> anyhow::Result<Self> {
+        let parse_errors = match metadata.id {
+            Some(file_id) if metadata.parse_errors > 0 => find_parse_errors_by_file(conn, file_id)?,
+            _ => Vec::new(),
+        };
+        Ok(Self {
+            parse_errors,
+            ..Self::from(metadata)
+        })
+    }
+}
+
+/// Parse errors are not part of `FileMetadata`; see `PyFileMetadata::load`
 impl From<FileMetadata> for PyFileMetadata {
     fn from(metadata: FileMetadata) -> Self {
         Self {
             path: metadata.path,
             language: metadata.language,
             size: metadata.size,
-            parse_errors: metadata.parse_errors,
-            last_indexed: metadata.last_indexed,
+            parse_errors: Vec::new(),
+            last_indexed: metadata.last_indexed.map(|t| t.to_rfc3339()),
+            index_duration_ms: metadata.index_duration_ms,
+            metadata: metadata.metadata,
         }
     }
 }
 
+/// Async iterator over file change events, yielding dicts with `kind` and `paths`
+#[pyclass]
+pub struct PyFileChangeStream {
+    // Kept alive so the underlying watcher keeps emitting events
+    _watcher: std::sync::Mutex<FileWatcher>,
+    receiver: Arc<Mutex<broadcast::Receiver<FileChangeEvent>>>,
+}
+
+#[pymethods]
+impl PyFileChangeStream {
+    fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __anext__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let receiver = self.receiver.clone();
+
+        future_into_py(py, async move {
+            let mut receiver = receiver.lock().await;
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => {
+                        return Python::attach(|py| {
+                            let dict = PyDict::new(py);
+                            dict.set_item("kind", event.kind.to_string())?;
+                            dict.set_item(
+                                "paths",
+                                event
+                                    .paths
+                                    .iter()
+                                    .map(|p| p.to_string_lossy().to_string())
+                                    .collect::<Vec<_>>(),
+                            )?;
+                            Ok(dict.into_any().unbind())
+                        });
+                    }
+                    // Slow consumer: skip dropped events and keep going
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => {
+                        return Err(PyStopAsyncIteration::new_err("File watcher closed"));
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// Run the analyzer for `language`, or `None` if the language has no analyzer
+fn analyze_source(
+    path: &str,
+    language: &str,
+    source: &str,
+    python_version: Option<(u8, u8)>,
+) -> Option<Result<AnalyzerOutput, IndexingError>> {
+    let analysis = match language {
+        "python" => analyze_python_with_version(source, python_version).map(|(mut symbols, errors)| {
+            if path.ends_with("__init__.py") {
+                mark_package_metadata(&mut symbols);
+            }
+            (symbols, errors)
+        }),
+        "typescript" | "javascript" => analyze_typescript(source),
+        "typescript_declaration" => analyze_typescript_declaration(source),
+        "rust" => analyze_rust(source),
+        "go" => analyze_go(source),
+        "java" => analyze_java(source),
+        "c" => analyze_c(source),
+        "cpp" => analyze_cpp(source),
+        language if FILE_ONLY_LANGUAGES.contains(&language) => analyze_file_only(source),
+        _ => return None,
+    };
+    Some(analysis)
+}
+
+/// Replace the stored symbols and parse errors of `py_file` and refresh its row
+///
+/// Symbols beyond `max_symbols` are dropped and recorded as a parse error so
+/// callers can tell the index is incomplete. The content hash is only
+/// recorded when `source` is known. All writes share one transaction, so a
+/// failure leaves the file's previous symbols, errors and hash in place.
+fn store_file_analysis(
+    conn: &rusqlite::Connection,
+    py_file: &mut PyFileMetadata,
+    source: Option<&str>,
+    (mut extracted, mut parse_errors): (Vec<Symbol>, Vec<ParseError>),
+    max_symbols: Option<usize>,
+    start: Instant,
+) -> anyhow::Result<()> {
+    // Take the write lock up front, as `insert_symbols_batch` does
+    let tx = Transaction::new_unchecked(conn, TransactionBehavior::Immediate)
+        .context("Failed to start transaction")?;
+    let (file_id, _) = get_or_create_file(&tx, &py_file.path, &py_file.language, py_file.size)?;
+
+    // Clear old symbols for re-indexing
+    delete_file_symbols(&tx, file_id)?;
+
+    let total = extracted.len();
+    if truncate_symbols(&mut extracted, max_symbols) {
+        eprintln!(
+            "Truncated {} to {} of {} symbols",
+            py_file.path,
+            extracted.len(),
+            total
+        );
+        parse_errors.push(ParseError::new(
+            0,
+            0,
+            format!("symbols truncated to {} of {}", extracted.len(), total),
+        ));
+    }
+    py_file.parse_errors = parse_errors
+        .into_iter()
+        .map(|error| error.with_file_path(py_file.path.as_str()))
+        .collect();
+
+    // Persist extracted symbols
+    for sym in &mut extracted {
+        sym.file_id = file_id;
+    }
+    insert_symbols_in_transaction(&tx, &extracted)?;
+
+    // Refresh size/last_indexed/parse_errors and record how long the file took
+    py_file.index_duration_ms = Some(start.elapsed().as_millis() as u64);
+    upsert_file(
+        &tx,
+        &FileMetadata {
+            id: None,
+            path: py_file.path.clone(),
+            language: py_file.language.clone(),
+            size: py_file.size,
+            // Stamped with the current time by `upsert_file`
+            last_indexed: None,
+            parse_errors: py_file.parse_errors.len() as i32,
+            index_duration_ms: py_file.index_duration_ms,
+            metadata: py_file.metadata.clone(),
+        },
+    )?;
+    replace_parse_errors(&tx, file_id, &py_file.parse_errors)?;
+    if let Some(source) = source {
+        set_file_content_hash(&tx, &py_file.path, &content_hash(source.as_bytes()))?;
+    }
+
+    tx.commit().context("Failed to commit file analysis")?;
+    Ok(())
+}
+
+fn index_stats_dict(stats: IndexStats) -> HashMap<String, usize> {
+    HashMap::from([
+        ("events".to_string(), stats.events),
+        ("batches".to_string(), stats.batches),
+        ("reindexed".to_string(), stats.reindexed),
+        ("deleted".to_string(), stats.deleted),
+        ("errors".to_string(), stats.errors),
+    ])
+}
+
+/// Python wrapper for QueryBuilder; filter methods return the builder for chaining
+#[pyclass]
+#[derive(Clone, Default)]
+pub struct PyQueryBuilder {
+    inner: QueryBuilder,
+}
+
+#[pymethods]
+impl PyQueryBuilder {
+    #[new]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn filter_name(mut slf: PyRefMut<'_, Self>, name: String) -> PyRefMut<'_, Self> {
+        slf.inner = std::mem::take(&mut slf.inner).filter_name(&name);
+        slf
+    }
+
+    fn filter_kind(mut slf: PyRefMut<'_, Self>, kind: String) -> PyResult<PyRefMut<'_, Self>> {
+        let kind = kind.parse().map_err(|e| PyValueError::new_err(format!("{}", e)))?;
+        slf.inner = std::mem::take(&mut slf.inner).filter_kind(kind);
+        Ok(slf)
+    }
+
+    fn filter_file(mut slf: PyRefMut<'_, Self>, path: String) -> PyRefMut<'_, Self> {
+        slf.inner = std::mem::take(&mut slf.inner).filter_file(&path);
+        slf
+    }
+
+    fn filter_scope(mut slf: PyRefMut<'_, Self>, scope: String) -> PyRefMut<'_, Self> {
+        slf.inner = std::mem::take(&mut slf.inner).filter_scope(&scope);
+        slf
+    }
+
+    fn filter_line_range(mut slf: PyRefMut<'_, Self>, start: usize, end: usize) -> PyRefMut<'_, Self> {
+        slf.inner = std::mem::take(&mut slf.inner).filter_line_range(start, end);
+        slf
+    }
+
+    #[pyo3(signature = (field, desc=false))]
+    fn order_by(mut slf: PyRefMut<'_, Self>, field: String, desc: bool) -> PyResult<PyRefMut<'_, Self>> {
+        let field = field.parse().map_err(|e| PyValueError::new_err(format!("{}", e)))?;
+        slf.inner = std::mem::take(&mut slf.inner).order_by(field, desc);
+        Ok(slf)
+    }
+
+    fn limit(mut slf: PyRefMut<'_, Self>, n: usize) -> PyRefMut<'_, Self> {
+        slf.inner = std::mem::take(&mut slf.inner).limit(n);
+        slf
+    }
+
+    fn offset(mut slf: PyRefMut<'_, Self>, n: usize) -> PyRefMut<'_, Self> {
+        slf.inner = std::mem::take(&mut slf.inner).offset(n);
+        slf
+    }
+
+    /// Generated SQL (values are bound separately)
+    fn sql(&self) -> String {
+        self.inner.to_sql().0
+    }
+
+    /// Run the query against an indexer's database
+    fn execute(&self, indexer: PyRef<'_, PyIndexer>) -> PyResult<Vec<PySymbol>> {
+        let conn = init_schema(&indexer.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        let symbols = self
+            .inner
+            .execute(&conn)
+            .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))?;
+
+        Ok(symbols.into_iter().map(PySymbol::from).collect())
+    }
+
+    fn __repr__(&self) -> String {
+        format!("PyQueryBuilder(sql='{}')", self.sql())
+    }
+}
+
 /// Main Indexer class for Python
 #[pyclass]
 pub struct PyIndexer {
     db_path: PathBuf,
     runtime: Arc<tokio::runtime::Runtime>,
+    incremental: std::sync::Mutex<Option<IncrementalIndexHandle>>,
+    closed: std::sync::atomic::AtomicBool,
 }
 
 #[pymethods]
@@ -217,6 +890,8 @@ impl PyIndexer {
         Ok(Self {
             db_path: PathBuf::from(db_path),
             runtime: Arc::new(runtime),
+            incremental: std::sync::Mutex::new(None),
+            closed: std::sync::atomic::AtomicBool::new(false),
         })
     }
 
@@ -228,6 +903,44 @@ impl PyIndexer {
         Ok(())
     }
 
+    /// Index a single file synchronously and return its stored metadata
+    ///
+    /// Raises ValueError for files without an analyzer and RuntimeError if
+    /// the file cannot be read, parsed or stored.
+    fn index_file(&self, file_path: String) -> PyResult<PyFileMetadata> {
+        let start = Instant::now();
+        let language = analyzer_core::detect_language(&file_path)
+            .filter(|language| default_registry().supports(language))
+            .ok_or_else(|| PyValueError::new_err(format!("Unsupported file type: {}", file_path)))?;
+
+        let read_error = |e: std::io::Error| PyRuntimeError::new_err(format!("Failed to read {}: {}", file_path, e));
+        let size = std::fs::metadata(&file_path).map_err(read_error)?.len();
+        let source = std::fs::read_to_string(&file_path).map_err(read_error)?;
+        let analysis = analyze_source(&file_path, language, &source, None)
+            .ok_or_else(|| PyValueError::new_err(format!("Unsupported file type: {}", file_path)))?
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to parse {}: {}", file_path, e)))?;
+
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+        let mut py_file = PyFileMetadata::from(FileMetadata {
+            id: None,
+            path: file_path.clone(),
+            language: language.to_string(),
+            size,
+            last_indexed: None,
+            parse_errors: 0,
+            index_duration_ms: None,
+            metadata: package_entry_point_metadata(&file_path),
+        });
+        store_file_analysis(&conn, &mut py_file, Some(&source), analysis, None, start)
+            .map_err(|e| PyRuntimeError::new_err(format!("Database error: {}", e)))?;
+
+        let stored = get_file_by_path(&conn, &file_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))?
+            .ok_or_else(|| PyRuntimeError::new_err(format!("File not indexed: {}", file_path)))?;
+        PyFileMetadata::load(&conn, stored).map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))
+    }
+
     /// Discover files in a directory (synchronous)
     fn discover_files(&self, config: &PyIndexerConfig) -> PyResult<Vec<String>> {
         let rust_config: IndexerConfig = config.into();
@@ -241,6 +954,26 @@ impl PyIndexer {
             .collect())
     }
 
+    /// Re-index one file and report how its symbols changed
+    ///
+    /// Returns `{"added": [...], "removed": [...], "unchanged_count": int}`,
+    /// comparing symbols by name, kind and start line. Raises ValueError for
+    /// files without an analyzer.
+    fn reindex_file<'py>(&self, py: Python<'py>, file_path: String) -> PyResult<Bound<'py, PyDict>> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        let diff = reindex_single_file(&conn, &file_path, &default_registry(), None)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to re-index {}: {:#}", file_path, e)))?
+            .ok_or_else(|| PyValueError::new_err(format!("Unsupported file type: {}", file_path)))?;
+
+        let dict = PyDict::new(py);
+        dict.set_item("added", diff.added.into_iter().map(PySymbol::from).collect::<Vec<_>>())?;
+        dict.set_item("removed", diff.removed.into_iter().map(PySymbol::from).collect::<Vec<_>>())?;
+        dict.set_item("unchanged_count", diff.unchanged.len())?;
+        Ok(dict)
+    }
+
     /// Index files with progress reporting (async)
     fn index_files<'py>(
         &self,
@@ -289,6 +1022,8 @@ impl PyIndexer {
                             size,
                             last_indexed: None,
                             parse_errors: 0,
+                            index_duration_ms: None,
+                            metadata: package_entry_point_metadata(&file_path.to_string_lossy()),
                         })
                     }
                 })
@@ -302,65 +1037,31 @@ impl PyIndexer {
             }
 
             // Store in database and populate symbols via Tree-sitter analyzers
-            tokio::task::spawn_blocking({
+            let indexed_files = tokio::task::spawn_blocking({
                 let db_path = db_path.clone();
-                let files_to_store = indexed_files.clone();
+                let max_symbols = rust_config.max_symbols_per_file;
+                let python_version = rust_config.python_version;
+                let mut files_to_store = indexed_files;
                 move || {
                     let conn = init_schema(&db_path)?;
 
-                    for py_file in &files_to_store {
-                        let file_metadata = FileMetadata {
-                            id: None,
-                            path: py_file.path.clone(),
-                            language: py_file.language.clone(),
-                            size: py_file.size,
-                            last_indexed: py_file.last_indexed.clone(),
-                            parse_errors: py_file.parse_errors,
-                        };
-                        upsert_file(&conn, &file_metadata)?;
-
-                        // Resolve file_id reliably and refresh symbols
-                        if let Some(db_file) = get_file_by_path(&conn, &py_file.path)? {
-                            let file_id = db_file.id.unwrap_or(0);
-                            if file_id > 0 {
-                                // Clear old symbols for re-indexing
-                                let _ = delete_file_symbols(&conn, file_id);
-
-                                // Read file content
-                                let source = std::fs::read_to_string(&py_file.path)
-                                    .unwrap_or_else(|_| String::new());
-
-                                // Select analyzer by language
-                                let mut extracted: Vec<Symbol> = Vec::new();
-                                match py_file.language.as_str() {
-                                    "python" => {
-                                        if let Ok(mut syms) = analyze_python(&source) {
-                                            extracted.append(&mut syms);
-                                        }
-                                    }
-                                    "typescript" | "javascript" => {
-                                        if let Ok(mut syms) = analyze_typescript(&source) {
-                                            extracted.append(&mut syms);
-                                        }
-                                    }
-                                    "rust" => {
-                                        if let Ok(mut syms) = analyze_rust(&source) {
-                                            extracted.append(&mut syms);
-                                        }
-                                    }
-                                    _ => {}
-                                }
-
-                                // Persist extracted symbols
-                                for mut sym in extracted {
-                                    sym.file_id = file_id;
-                                    let _ = insert_symbol(&conn, &sym);
-                                }
-                            }
-                        }
+                    for py_file in &mut files_to_store {
+                        let start = Instant::now();
+
+                        // Unreadable files and analyzer failures are stored without symbols
+                        let source = std::fs::read_to_string(&py_file.path).ok();
+                        let analysis = source
+                            .as_deref()
+                            .and_then(|source| {
+                                analyze_source(&py_file.path, &py_file.language, source, python_version)
+                            })
+                            .and_then(Result::ok)
+                            .unwrap_or_default();
+
+                        store_file_analysis(&conn, py_file, source.as_deref(), analysis, max_symbols, start)?;
                     }
 
-                    Ok::<(), anyhow::Error>(())
+                    Ok::<_, anyhow::Error>(files_to_store)
                 }
             })
             .await
@@ -373,6 +1074,87 @@ impl PyIndexer {
         })
     }
 
+    /// Analyze a directory without touching the database (async)
+    ///
+    /// Resolves to a dict mapping file paths to lists of symbols.
+    fn analyze_directory_no_persist<'py>(
+        &self,
+        py: Python<'py>,
+        config: &PyIndexerConfig,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let rust_config: IndexerConfig = config.into();
+
+        future_into_py(py, async move {
+            let results = tokio::task::spawn_blocking(move || {
+                let registry = default_registry();
+                let mut results = HashMap::new();
+                for root in &rust_config.root_dirs {
+                    results.extend(analyze_directory_parallel(root, &rust_config, &registry)?);
+                }
+                Ok::<_, anyhow::Error>(results)
+            })
+            .await
+            .map_err(|e| PyRuntimeError::new_err(format!("Task join error: {}", e)))?
+            .map_err(|e| PyRuntimeError::new_err(format!("Analysis failed: {}", e)))?;
+
+            Ok(results
+                .into_iter()
+                .map(|(path, result)| {
+                    let symbols: Vec<PySymbol> =
+                        result.symbols.into_iter().map(PySymbol::from).collect();
+                    (path.to_string_lossy().to_string(), symbols)
+                })
+                .collect::<HashMap<String, Vec<PySymbol>>>())
+        })
+    }
+
+    /// Re-index only files changed since the last index run (async)
+    ///
+    /// Returns `{"checked": int, "reindexed": int, "deleted": int}`.
+    fn reindex_changed_files<'py>(
+        &self,
+        py: Python<'py>,
+        config: &PyIndexerConfig,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let rust_config: IndexerConfig = config.into();
+        let db_path = self.db_path.clone();
+
+        future_into_py(py, async move {
+            let stats = tokio::task::spawn_blocking(move || {
+                let conn = init_schema(&db_path)?;
+                reindex_changed_files(&conn, &rust_config, &default_registry())
+            })
+            .await
+            .map_err(|e| PyRuntimeError::new_err(format!("Task join error: {}", e)))?
+            .map_err(|e| PyRuntimeError::new_err(format!("Re-index failed: {}", e)))?;
+
+            Ok(HashMap::from([
+                ("checked".to_string(), stats.checked),
+                ("reindexed".to_string(), stats.reindexed),
+                ("deleted".to_string(), stats.deleted),
+            ]))
+        })
+    }
+
+    /// Analyze in-memory sources given as `(language, source)` tuples
+    fn analyze_text_batch(&self, sources: Vec<(String, String)>) -> PyResult<Vec<Vec<PySymbol>>> {
+        let registry = default_registry();
+
+        sources
+            .iter()
+            .map(|(language, source)| {
+                let (symbols, _) = registry
+                    .analyze(language, source)
+                    .ok_or_else(|| {
+                        PyValueError::new_err(format!("Unsupported language: {}", language))
+                    })?
+                    .map_err(|e| PyRuntimeError::new_err(format!("Analysis failed: {}", e)))?;
+
+                Ok(symbols.into_iter().map(PySymbol::from).collect())
+            })
+            .collect()
+    }
+
     /// List all indexed files
     fn list_files(&self) -> PyResult<Vec<PyFileMetadata>> {
         let conn = init_schema(&self.db_path)
@@ -381,7 +1163,11 @@ impl PyIndexer {
         let files = query_list_files(&conn)
             .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))?;
 
-        Ok(files.into_iter().map(PyFileMetadata::from).collect())
+        files
+            .into_iter()
+            .map(|file| PyFileMetadata::load(&conn, file))
+            .collect::<anyhow::Result<_>>()
+            .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))
     }
 
     /// Get language statistics as JSON string
@@ -395,19 +1181,339 @@ impl PyIndexer {
         Ok(stats.to_string())
     }
 
-    /// Find symbols by name
-    fn find_symbols(&self, name: String) -> PyResult<Vec<PySymbol>> {
+    /// Get language statistics with symbol and parse error counts as JSON string
+    fn get_language_stats_extended(&self) -> PyResult<String> {
         let conn = init_schema(&self.db_path)
             .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
 
-        let symbols = find_symbols_by_name(&conn, &name)
+        let stats = query_language_stats_extended(&conn)
             .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))?;
 
-        Ok(symbols.into_iter().map(PySymbol::from).collect())
+        Ok(stats.to_string())
     }
 
-    /// List all symbols in a specific file
-    fn list_symbols_in_file(&self, file_path: String) -> PyResult<Vec<PySymbol>> {
+    /// Number of indexed symbols per kind, e.g. `{"function": 12, "class": 3}`
+    fn get_symbol_counts(&self) -> PyResult<HashMap<String, usize>> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        let counts = count_symbols_by_kind(&conn)
+            .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))?;
+
+        Ok(counts.into_iter().map(|(kind, count)| (kind.to_string(), count)).collect())
+    }
+
+    /// Number of symbols stored for an indexed file
+    fn get_file_symbol_count(&self, file_path: String) -> PyResult<usize> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        let file_id = get_file_by_path(&conn, &file_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))?
+            .and_then(|file| file.id)
+            .ok_or_else(|| PyRuntimeError::new_err(format!("File not found in database: {}", file_path)))?;
+
+        count_symbols_by_file(&conn, file_id)
+            .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))
+    }
+
+    /// `(name, count)` pairs for names of `kind` declared more than once, most frequent first
+    ///
+    /// `kind` is a `PySymbolKind` or its name, e.g. `"function"`.
+    fn find_duplicate_symbol_names(&self, kind: &Bound<'_, PyAny>) -> PyResult<Vec<(String, usize)>> {
+        let kind = extract_symbol_kind(kind)?;
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        find_duplicate_symbol_names(&conn, kind)
+            .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))
+    }
+
+    /// `(name, count)` pairs for names declared more than once within an indexed file
+    fn find_duplicate_symbols_in_file(&self, file_path: String) -> PyResult<Vec<(String, usize)>> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        let file_id = get_file_by_path(&conn, &file_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))?
+            .and_then(|file| file.id)
+            .ok_or_else(|| PyRuntimeError::new_err(format!("File not found in database: {}", file_path)))?;
+
+        find_duplicate_symbols_in_file(&conn, file_id)
+            .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))
+    }
+
+    /// Find symbols by name
+    fn find_symbols(&self, name: String) -> PyResult<Vec<PySymbol>> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        let symbols = find_symbols_by_name(&conn, &name)
+            .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))?;
+
+        Ok(symbols.into_iter().map(PySymbol::from).collect())
+    }
+
+    /// Find every symbol named `name`, as a dict keyed by file path
+    fn find_symbols_grouped(&self, name: String) -> PyResult<HashMap<String, Vec<PySymbol>>> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        let grouped = find_symbols_grouped_by_file(&conn, &name)
+            .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))?;
+
+        Ok(grouped
+            .into_iter()
+            .map(|(path, symbols)| (path, symbols.into_iter().map(PySymbol::from).collect()))
+            .collect())
+    }
+
+    /// Look up a symbol by id; returns `None` if it does not exist
+    fn get_symbol(&self, id: i64) -> PyResult<Option<PySymbol>> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        let symbol = get_symbol_by_id(&conn, id)
+            .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))?;
+
+        Ok(symbol.map(PySymbol::from))
+    }
+
+    /// Find one page of the symbols named `name`, ordered by file then line
+    #[pyo3(signature = (name, limit, offset=0))]
+    fn find_symbols_paged(&self, name: String, limit: usize, offset: usize) -> PyResult<Vec<PySymbol>> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        let symbols = find_symbols_by_name_paged(&conn, &name, limit, offset)
+            .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))?;
+
+        Ok(symbols.into_iter().map(PySymbol::from).collect())
+    }
+
+    /// Find symbols of a kind, optionally restricted to one file
+    ///
+    /// `kind` is a `PySymbolKind` or its name, e.g. `"function"`.
+    #[pyo3(signature = (kind, file_path=None))]
+    fn find_symbols_by_kind(
+        &self,
+        kind: &Bound<'_, PyAny>,
+        file_path: Option<String>,
+    ) -> PyResult<Vec<PySymbol>> {
+        let kind = extract_symbol_kind(kind)?;
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        let symbols = match file_path {
+            Some(path) => {
+                let file_id = get_file_by_path(&conn, &path)
+                    .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))?
+                    .and_then(|file| file.id)
+                    .ok_or_else(|| PyRuntimeError::new_err(format!("File not found in database: {}", path)))?;
+                find_symbols_by_file_and_kind(&conn, file_id, kind)
+            }
+            None => find_symbols_by_kind(&conn, kind),
+        }
+        .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))?;
+
+        Ok(symbols.into_iter().map(PySymbol::from).collect())
+    }
+
+    /// Find test functions (e.g. Rust `#[test]`), optionally restricted to one file
+    #[pyo3(signature = (file_path=None))]
+    fn find_test_functions(&self, file_path: Option<String>) -> PyResult<Vec<PySymbol>> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        let file_id = match file_path {
+            Some(path) => Some(
+                get_file_by_path(&conn, &path)
+                    .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))?
+                    .and_then(|file| file.id)
+                    .ok_or_else(|| PyRuntimeError::new_err(format!("File not found in database: {}", path)))?,
+            ),
+            None => None,
+        };
+
+        let symbols = find_test_symbols(&conn, file_id)
+            .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))?;
+
+        Ok(symbols.into_iter().map(PySymbol::from).collect())
+    }
+
+    /// Find up to `limit` symbols whose name starts with `prefix`, ordered by name
+    ///
+    /// Matching is case-sensitive. `kind` is an optional `PySymbolKind` or its name.
+    #[pyo3(signature = (prefix, limit, kind=None))]
+    fn find_symbols_prefix(
+        &self,
+        prefix: String,
+        limit: usize,
+        kind: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<Vec<PySymbol>> {
+        let kind = kind.map(extract_symbol_kind).transpose()?;
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        let symbols = match kind {
+            Some(kind) => find_symbols_by_name_prefix_and_kind(&conn, &prefix, kind, limit),
+            None => find_symbols_by_name_prefix(&conn, &prefix, limit),
+        }
+        .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))?;
+
+        Ok(symbols.into_iter().map(PySymbol::from).collect())
+    }
+
+    /// Find symbols whose name matches a regular expression
+    fn find_symbols_regex(&self, pattern: String, limit: usize) -> PyResult<Vec<PySymbol>> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        let symbols = find_symbols_by_name_regex(&conn, &pattern, limit).map_err(|e| {
+            if e.downcast_ref::<QueryError>().is_some() {
+                PyValueError::new_err(e.to_string())
+            } else {
+                PyRuntimeError::new_err(format!("Query failed: {}", e))
+            }
+        })?;
+
+        Ok(symbols.into_iter().map(PySymbol::from).collect())
+    }
+
+    /// Search symbol names in every language (or only `languages`)
+    ///
+    /// Returns `(symbol, file_path)` tuples, best matches first.
+    #[pyo3(signature = (query, languages=None))]
+    fn search_all(&self, query: String, languages: Option<Vec<String>>) -> PyResult<Vec<(PySymbol, String)>> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        let languages = languages.unwrap_or_default();
+        let languages: Vec<&str> = languages.iter().map(String::as_str).collect();
+        let results = search_symbols_across_languages(&conn, &query, &languages)
+            .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))?;
+
+        Ok(results
+            .into_iter()
+            .map(|(symbol, path)| (PySymbol::from(symbol), path))
+            .collect())
+    }
+
+    /// Find all symbols in files under `dir_prefix` (e.g. "/src/api"), including subdirectories
+    fn find_symbols_in_directory(&self, dir_prefix: String) -> PyResult<Vec<PySymbol>> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        let pattern = format!("{}/*", dir_prefix.trim_end_matches('/'));
+        let symbols = find_symbols_by_file_path_pattern(&conn, &pattern)
+            .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))?;
+
+        Ok(symbols.into_iter().map(PySymbol::from).collect())
+    }
+
+    /// List indexed Python package entry points (`__init__.py` and `__main__.py`)
+    fn find_package_init_files(&self) -> PyResult<Vec<PyFileMetadata>> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        let files = find_package_entry_points(&conn)
+            .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))?;
+
+        files
+            .into_iter()
+            .map(|file| PyFileMetadata::load(&conn, file))
+            .collect::<anyhow::Result<_>>()
+            .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))
+    }
+
+    /// List indexed files with at least one parse error, ordered by path
+    fn find_files_with_errors(&self) -> PyResult<Vec<PyFileMetadata>> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        let files = find_files_with_errors(&conn)
+            .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))?;
+
+        files
+            .into_iter()
+            .map(|file| PyFileMetadata::load(&conn, file))
+            .collect::<anyhow::Result<_>>()
+            .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))
+    }
+
+    /// Write every indexed symbol to `output_path` as LSP `SymbolInformation` ndjson
+    fn export_lsp_workspace_symbols(&self, output_path: String) -> PyResult<()> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        export_lsp_workspace_symbols(&conn, &PathBuf::from(output_path))
+            .map_err(|e| PyRuntimeError::new_err(format!("Export failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Stream the whole index to `output_path` as `{"files": [...], "symbols": [...]}` (async)
+    ///
+    /// Resolves to the number of symbols written.
+    fn export_json_async<'py>(&self, py: Python<'py>, output_path: String) -> PyResult<Bound<'py, PyAny>> {
+        let db_path = self.db_path.clone();
+
+        future_into_py(py, async move {
+            let file = tokio::fs::File::create(&output_path)
+                .await
+                .map_err(|e| PyRuntimeError::new_err(format!("Failed to create {}: {}", output_path, e)))?;
+
+            export_index_to_json_async(&db_path, tokio::io::BufWriter::new(file))
+                .await
+                .map_err(|e| PyRuntimeError::new_err(format!("Export failed: {}", e)))
+        })
+    }
+
+    /// Repopulate the index from an ndjson file written by `export_lsp_workspace_symbols`
+    fn import_lsp_workspace_symbols(&self, input_path: String) -> PyResult<HashMap<String, usize>> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        let stats = import_lsp_workspace_symbols(&conn, &PathBuf::from(input_path))
+            .map_err(|e| PyRuntimeError::new_err(format!("Import failed: {}", e)))?;
+
+        Ok(HashMap::from([
+            ("files".to_string(), stats.files),
+            ("symbols".to_string(), stats.symbols),
+        ]))
+    }
+
+    /// Write a point-in-time snapshot of the whole index to `path` as ndjson
+    fn export_snapshot(&self, path: String) -> PyResult<()> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        let file = std::fs::File::create(&path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create {}: {}", path, e)))?;
+
+        create_snapshot(&conn, &mut std::io::BufWriter::new(file))
+            .map_err(|e| PyRuntimeError::new_err(format!("Export failed: {}", e)))
+    }
+
+    /// Replace the index with a snapshot written by `export_snapshot`
+    fn import_snapshot(&self, path: String) -> PyResult<HashMap<String, usize>> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        let file = std::fs::File::open(&path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open {}: {}", path, e)))?;
+
+        let counts = restore_snapshot(&conn, &mut std::io::BufReader::new(file))
+            .map_err(|e| PyRuntimeError::new_err(format!("Import failed: {}", e)))?;
+
+        Ok(HashMap::from([
+            ("files".to_string(), counts.files),
+            ("symbols".to_string(), counts.symbols),
+        ]))
+    }
+
+    /// List all symbols in a specific file
+    fn list_symbols_in_file(&self, file_path: String) -> PyResult<Vec<PySymbol>> {
         let conn = init_schema(&self.db_path)
             .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
 
@@ -417,6 +1523,18 @@ impl PyIndexer {
         Ok(symbols.into_iter().map(PySymbol::from).collect())
     }
 
+    /// List one page of the symbols in a file, ordered by line
+    #[pyo3(signature = (file_path, limit, offset=0))]
+    fn list_symbols_in_file_paged(&self, file_path: String, limit: usize, offset: usize) -> PyResult<Vec<PySymbol>> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        let symbols = find_symbols_by_file_path_paged(&conn, &file_path, limit, offset)
+            .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))?;
+
+        Ok(symbols.into_iter().map(PySymbol::from).collect())
+    }
+
     /// Find import symbols for a file
     fn find_imports(&self, file_path: String) -> PyResult<Vec<PySymbol>> {
         let conn = init_schema(&self.db_path)
@@ -439,6 +1557,47 @@ impl PyIndexer {
         Ok(symbols.into_iter().map(PySymbol::from).collect())
     }
 
+    /// Get a symbol with the symbols within `context_lines` lines above and below it
+    fn get_symbol_context(&self, symbol_id: i64, context_lines: usize) -> PyResult<PySymbolContext> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        let context = get_symbol_context(&conn, symbol_id, context_lines)
+            .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))?;
+
+        Ok(PySymbolContext::from(context))
+    }
+
+    /// Get change log entries recorded at or after `since_iso` (ISO 8601)
+    fn get_change_log(&self, since_iso: String) -> PyResult<Vec<PyChangeLogEntry>> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        let entries = query_change_log(&conn, &since_iso).map_err(|e| {
+            if e.downcast_ref::<QueryError>().is_some() {
+                PyValueError::new_err(e.to_string())
+            } else {
+                PyRuntimeError::new_err(format!("Query failed: {}", e))
+            }
+        })?;
+
+        Ok(entries.into_iter().map(PyChangeLogEntry::from).collect())
+    }
+
+    /// Delete change log entries recorded before `older_than_iso`; returns the number removed
+    fn prune_change_log(&self, older_than_iso: String) -> PyResult<usize> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        prune_change_log(&conn, &older_than_iso).map_err(|e| {
+            if e.downcast_ref::<QueryError>().is_some() {
+                PyValueError::new_err(e.to_string())
+            } else {
+                PyRuntimeError::new_err(format!("Prune failed: {}", e))
+            }
+        })
+    }
+
     /// Get file path by file_id
     fn get_file_path(&self, file_id: i64) -> PyResult<String> {
         let conn = init_schema(&self.db_path)
@@ -450,7 +1609,274 @@ impl PyIndexer {
         Ok(path)
     }
 
+    /// Remove a file and its symbols from the index
+    ///
+    /// Returns `False` if the path was not indexed.
+    fn remove_file(&self, path: String) -> PyResult<bool> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        delete_file_by_path(&conn, &path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Delete failed: {}", e)))
+    }
+
+    /// Shift symbols below `after_line` in a file after lines were inserted/removed
+    ///
+    /// Returns the number of symbols updated.
+    fn update_symbol_positions(
+        &self,
+        file_path: String,
+        after_line: usize,
+        line_delta: i64,
+    ) -> PyResult<usize> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        let file = get_file_by_path(&conn, &file_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))?
+            .ok_or_else(|| PyValueError::new_err(format!("File not indexed: {}", file_path)))?;
+
+        relocate_symbols_after_line(&conn, file.id.unwrap_or(0), after_line, line_delta)
+            .map_err(|e| PyRuntimeError::new_err(format!("Update failed: {}", e)))
+    }
+
+    /// Compute embedding hints for every symbol in a file and store them in metadata
+    fn compute_hints(&self, file_path: String) -> PyResult<()> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        let mut symbols = find_symbols_by_file_path(&conn, &file_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))?;
+
+        let source = std::fs::read_to_string(&file_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to read {}: {}", file_path, e)))?;
+
+        populate_embedding_hints(&mut symbols, &source);
+
+        insert_symbols_batch(&conn, &symbols)
+            .map_err(|e| PyRuntimeError::new_err(format!("Update failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Checkpoint the WAL ("passive", "full", "restart" or "truncate")
+    ///
+    /// Returns `(pages_in_wal, checkpointed_pages)`.
+    fn checkpoint_wal(&self, mode: String) -> PyResult<(i64, i64)> {
+        let mode = mode.parse().map_err(|e| PyValueError::new_err(format!("{}", e)))?;
+
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        let stats = checkpoint_wal(&conn, mode)
+            .map_err(|e| PyRuntimeError::new_err(format!("Checkpoint failed: {}", e)))?;
+
+        Ok((stats.pages_in_wal, stats.checkpointed_pages))
+    }
+
+    /// Files that took longest to index, as `(file, duration_ms)` tuples
+    fn get_slowest_files(&self, limit: usize) -> PyResult<Vec<(PyFileMetadata, u64)>> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        let files = find_slowest_files(&conn, limit)
+            .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))?;
+
+        files
+            .into_iter()
+            .map(|(file, duration_ms)| Ok((PyFileMetadata::load(&conn, file)?, duration_ms)))
+            .collect::<anyhow::Result<_>>()
+            .map_err(|e| PyRuntimeError::new_err(format!("Query failed: {}", e)))
+    }
+
+    /// Size of the index database in bytes
+    fn get_database_size_bytes(&self) -> PyResult<u64> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        get_index_size_bytes(&conn)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to read database size: {}", e)))
+    }
+
+    /// Bytes used per table and index, e.g. `{"symbols": 4096, ...}`
+    fn get_table_sizes(&self) -> PyResult<HashMap<String, u64>> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        get_table_size_breakdown(&conn)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to read table sizes: {}", e)))
+    }
+
+    /// Drop default-valued keys from symbol metadata; returns bytes saved
+    fn compact_metadata(&self) -> PyResult<u64> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        compact_metadata(&conn)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to compact metadata: {}", e)))
+    }
+
+    /// IDs of symbols whose metadata is not valid JSON
+    fn validate_metadata(&self) -> PyResult<Vec<i64>> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        validate_metadata_json(&conn)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to validate metadata: {}", e)))
+    }
+
+    /// Export a Deep Mode compliance report ("json", "csv" or "markdown") for a date range
+    #[cfg_attr(not(feature = "deep-mode"), allow(unused_variables))]
+    fn export_compliance_report(&self, format: String, from_date: String, to_date: String) -> PyResult<String> {
+        #[cfg(feature = "deep-mode")]
+        {
+            use analyzer_core::analysis::deep::{DeepMode, ReportFormat};
+
+            let format: ReportFormat = format.parse().map_err(|e| PyValueError::new_err(format!("{}", e)))?;
+            let conn = init_schema(&self.db_path)
+                .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+            DeepMode::new()
+                .export_compliance_report(&conn, format, &from_date, &to_date)
+                .map_err(|e| {
+                    if e.downcast_ref::<QueryError>().is_some() {
+                        PyValueError::new_err(format!("{}", e))
+                    } else {
+                        PyRuntimeError::new_err(format!("Failed to export compliance report: {}", e))
+                    }
+                })
+        }
+
+        #[cfg(not(feature = "deep-mode"))]
+        Err(PyRuntimeError::new_err("Compliance reports require the deep-mode feature"))
+    }
+
+    /// Get the SQLite query plan for a query function (e.g. "find_symbols_by_name")
+    fn explain_query(&self, name: String) -> PyResult<String> {
+        let conn = init_schema(&self.db_path)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+
+        explain_query(&conn, &name).map_err(|e| PyValueError::new_err(format!("{}", e)))
+    }
+
+    /// Watch a directory and return an async iterator of change event dicts
+    fn subscribe_file_changes<'py>(
+        &self,
+        py: Python<'py>,
+        root_dir: String,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let root = PathBuf::from(root_dir);
+        let mut watcher = FileWatcher::new(&root)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create watcher: {}", e)))?;
+        watcher
+            .watch(&root)
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to watch directory: {}", e)))?;
+
+        let receiver = watcher.subscribe();
+        let stream = PyFileChangeStream {
+            _watcher: std::sync::Mutex::new(watcher),
+            receiver: Arc::new(Mutex::new(receiver)),
+        };
+
+        Ok(Bound::new(py, stream)?.into_any())
+    }
+
+    /// Start re-indexing files in the background as they change on disk
+    fn start_incremental(&self, config: &PyIndexerConfig) -> PyResult<()> {
+        let mut incremental = self.incremental.lock().unwrap();
+        if incremental.is_some() {
+            return Err(PyRuntimeError::new_err("Incremental indexing is already running"));
+        }
+
+        let rust_config: IndexerConfig = config.into();
+        let _guard = self.runtime.enter();
+        let handle = IncrementalIndex::new(rust_config, &self.db_path)
+            .and_then(|index| {
+                index
+                    .with_registry(default_registry())
+                    .with_incremental_parsing(default_incremental_indexer())
+                    .start()
+            })
+            .map_err(|e| {
+                PyRuntimeError::new_err(format!("Failed to start incremental indexing: {}", e))
+            })?;
+
+        *incremental = Some(handle);
+        Ok(())
+    }
+
+    /// Stop background re-indexing and return its final statistics
+    fn stop_incremental(&self, py: Python<'_>) -> PyResult<HashMap<String, usize>> {
+        let handle = self
+            .incremental
+            .lock()
+            .unwrap()
+            .take()
+            .ok_or_else(|| PyRuntimeError::new_err("Incremental indexing is not running"))?;
+
+        handle.stop();
+        let runtime = self.runtime.clone();
+        let stats = py
+            .detach(|| runtime.block_on(handle.join()))
+            .map_err(|e| PyRuntimeError::new_err(format!("Incremental indexing failed: {}", e)))?;
+
+        Ok(index_stats_dict(stats))
+    }
+
+    /// Statistics of the running incremental indexer
+    fn incremental_stats(&self) -> PyResult<HashMap<String, usize>> {
+        self.incremental
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|handle| index_stats_dict(handle.stats()))
+            .ok_or_else(|| PyRuntimeError::new_err("Incremental indexing is not running"))
+    }
+
+    /// Stop incremental indexing and truncate the WAL into the database file
+    ///
+    /// Safe to call more than once; later calls do nothing.
+    fn close(&self, py: Python<'_>) -> PyResult<()> {
+        if self.closed.swap(true, std::sync::atomic::Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        if let Some(handle) = self.incremental.lock().unwrap().take() {
+            handle.stop();
+            let runtime = self.runtime.clone();
+            py.detach(|| runtime.block_on(handle.join()))
+                .map_err(|e| PyRuntimeError::new_err(format!("Incremental indexing failed: {}", e)))?;
+        }
+
+        // Don't create a database just to close it
+        if self.db_path.exists() {
+            let conn = init_schema(&self.db_path)
+                .map_err(|e| PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+            checkpoint_wal(&conn, WalCheckpointMode::Truncate)
+                .map_err(|e| PyRuntimeError::new_err(format!("Checkpoint failed: {}", e)))?;
+        }
+
+        Ok(())
+    }
+
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Close the indexer; exceptions raised in the `with` block propagate
+    fn __exit__(
+        &self,
+        py: Python<'_>,
+        _exc_type: Option<&Bound<'_, PyAny>>,
+        _exc_val: Option<&Bound<'_, PyAny>>,
+        _exc_tb: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<bool> {
+        self.close(py)?;
+        Ok(false)
+    }
+
     fn __repr__(&self) -> String {
-        format!("PyIndexer(db_path='{}')", self.db_path.display())
+        let closed = if self.closed.load(std::sync::atomic::Ordering::SeqCst) { " (closed)" } else { "" };
+        format!("PyIndexer(db_path='{}'){}", self.db_path.display(), closed)
     }
 }
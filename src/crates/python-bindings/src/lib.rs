@@ -1,12 +1,16 @@
 // PyO3 Python bindings for Contexta analyzer-core
 // Exposes Rust indexing functionality to Python with async support
 
+use pyo3::exceptions::PyDeprecationWarning;
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
 
 mod bridge;
 
-use bridge::{PyFileMetadata, PyIndexer, PyIndexerConfig};
+use bridge::{
+    PyChangeLogEntry, PyFileChangeStream, PyFileMetadata, PyIndexer, PyIndexerConfig, PyQueryBuilder, PySymbol,
+    PySymbolContext, PySymbolKind,
+};
 
 /// Placeholder analyze function - returns empty result for now.
 ///
@@ -25,23 +29,67 @@ fn analyze(
     Ok(result)
 }
 
+/// Languages with a bundled analyzer
+const LANGUAGES: &[&str] = &["python", "typescript", "javascript", "rust", "go", "java", "c", "cpp"];
+
+/// Capabilities that are always compiled in
+const BASE_FEATURES: &[&str] = &[
+    "incremental",
+    "fts5",
+    "regex_search",
+    "query_builder",
+    "lsp_export",
+    "change_log",
+    "constant_kind",
+];
+
+/// Capabilities enabled by this build, including optional Cargo features
+fn enabled_features() -> Vec<&'static str> {
+    #[cfg_attr(not(feature = "deep-mode"), allow(unused_mut))]
+    let mut features = BASE_FEATURES.to_vec();
+
+    #[cfg(feature = "deep-mode")]
+    features.extend(["deep-mode", "cross_file_analysis"]);
+
+    features
+}
+
 /// Return list of available analyzer capabilities
+///
+/// Deprecated: use `capabilities_v2()`, which returns a structured dict.
 #[pyfunction]
-fn capabilities() -> PyResult<Vec<String>> {
-    let mut caps = vec![
-        "analyze".to_string(),
-        "python".to_string(),
-        "typescript".to_string(),
-        "javascript".to_string(),
-        "rust".to_string(),
-    ];
+fn capabilities(py: Python<'_>) -> PyResult<Vec<String>> {
+    PyErr::warn(
+        py,
+        &py.get_type::<PyDeprecationWarning>(),
+        c"capabilities() is deprecated, use capabilities_v2()",
+        1,
+    )?;
 
-    #[cfg(feature = "deep-mode")]
-    caps.push("deep-mode".to_string());
+    let mut caps = vec!["analyze".to_string()];
+    caps.extend(LANGUAGES.iter().map(|lang| lang.to_string()));
+    caps.push("constant_kind".to_string());
+    if cfg!(feature = "deep-mode") {
+        caps.push("deep-mode".to_string());
+    }
 
     Ok(caps)
 }
 
+/// Return analyzer capabilities as a dict:
+/// `{"languages": [...], "features": [...], "version": str, "deep_mode": bool}`
+///
+/// `version` is the crate version, which is also the package `__version__`.
+#[pyfunction]
+fn capabilities_v2(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+    let caps = PyDict::new(py);
+    caps.set_item("languages", LANGUAGES.to_vec())?;
+    caps.set_item("features", enabled_features())?;
+    caps.set_item("version", env!("CARGO_PKG_VERSION"))?;
+    caps.set_item("deep_mode", cfg!(feature = "deep-mode"))?;
+    Ok(caps)
+}
+
 /// Check if a client version is compatible with this core version
 #[pyfunction]
 fn check_compatibility(client_version: String) -> PyResult<bool> {
@@ -57,10 +105,20 @@ fn _bindings(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyIndexer>()?;
     m.add_class::<PyIndexerConfig>()?;
     m.add_class::<PyFileMetadata>()?;
+    m.add_class::<PySymbol>()?;
+    m.add_class::<PySymbolKind>()?;
+    m.add_class::<PySymbolContext>()?;
+    m.add_class::<PyChangeLogEntry>()?;
+    m.add_class::<PyFileChangeStream>()?;
+    m.add_class::<PyQueryBuilder>()?;
+
+    // Package version, taken from the Cargo manifest
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
 
     // Add functions
     m.add_function(wrap_pyfunction!(analyze, m)?)?;
     m.add_function(wrap_pyfunction!(capabilities, m)?)?;
+    m.add_function(wrap_pyfunction!(capabilities_v2, m)?)?;
     m.add_function(wrap_pyfunction!(check_compatibility, m)?)?;
 
     Ok(())